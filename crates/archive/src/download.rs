@@ -0,0 +1,609 @@
+//! Provides a reader for remote archives, which retries transient failures with exponential
+//! backoff and resumes interrupted downloads with HTTP `Range` requests.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read};
+use std::iter::Take;
+use std::path::{Path, PathBuf};
+
+use super::{ArchiveError, content_length, ensure_containing_dir_exists};
+use attohttpc::header::{ETAG, HeaderMap, HeaderValue, IF_RANGE, LAST_MODIFIED, RANGE};
+use attohttpc::{ErrorKind, ResponseReader, Session, StatusCode};
+use headers::{AcceptRanges, ContentRange, HeaderMapExt};
+use log::debug;
+use retry::delay::Exponential;
+use retry::{OperationResult, retry};
+use tee::TeeReader;
+
+/// The number of times a failed request is retried before giving up
+const MAX_RETRIES: usize = 5;
+
+/// The delay before the first retry, which doubles with every subsequent retry
+#[cfg(not(test))]
+const INITIAL_BACKOFF_MILLIS: u64 = 500;
+#[cfg(test)]
+const INITIAL_BACKOFF_MILLIS: u64 = 1;
+
+fn backoff() -> Take<Exponential> {
+    Exponential::from_millis(INITIAL_BACKOFF_MILLIS).take(MAX_RETRIES)
+}
+
+/// The part of an archive that is requested when resuming a download
+struct Resume<'a> {
+    start: u64,
+    validator: Option<&'a HeaderValue>,
+}
+
+/// A download of a remote archive, which can be read like the response body
+///
+/// If the connection fails partway through, the download is transparently resumed from the
+/// number of bytes read so far: with a `Range` request if the server advertises support for
+/// them, or otherwise by requesting the whole archive again and skipping what was already read.
+pub struct Download {
+    client: Session,
+    url: String,
+    response: ResponseReader,
+    /// The total size of the archive in bytes
+    length: u64,
+    /// The number of bytes read so far
+    position: u64,
+    /// Whether the server accepts `Range` requests for the archive
+    accepts_ranges: bool,
+    /// The `ETag` or `Last-Modified` header of the archive, so that a resumed download falls
+    /// back to the complete archive if it has changed in the meantime
+    validator: Option<HeaderValue>,
+    /// The number of times the download was interrupted without any progress in between
+    interruptions: usize,
+}
+
+impl Download {
+    /// Start downloading the archive at the given URL, retrying transient failures
+    pub fn start(client: &Session, url: &str) -> Result<Self, ArchiveError> {
+        let (headers, response) =
+            retry(backoff(), || attempt(send(client, url, None))).map_err(exhausted)?;
+
+        Self::from_start(client, url, &headers, response)
+    }
+
+    /// Resume downloading the archive at the given URL after the first `position` bytes, which
+    /// were downloaded by an earlier attempt
+    ///
+    /// The `Range` request is conditional on the `validator` of the archive, so if the archive
+    /// has changed since, the server sends all of it and the download starts from the beginning.
+    pub fn start_after(
+        client: &Session,
+        url: &str,
+        position: u64,
+        validator: HeaderValue,
+    ) -> Result<Self, ArchiveError> {
+        let range = Resume {
+            start: position,
+            validator: Some(&validator),
+        };
+        let (headers, response) =
+            retry(backoff(), || attempt(send(client, url, Some(&range)))).map_err(exhausted)?;
+
+        let Some(content_range) = headers.typed_get::<ContentRange>() else {
+            debug!("Server sent the complete archive, restarting the download of {url}");
+            return Self::from_start(client, url, &headers, response);
+        };
+        match (content_range.bytes_range(), content_range.bytes_len()) {
+            (Some((start, _)), Some(length)) if start == position => Ok(Self {
+                client: client.clone(),
+                url: url.to_owned(),
+                response,
+                length,
+                position,
+                accepts_ranges: true,
+                validator: Some(validator),
+                interruptions: 0,
+            }),
+            (range, _) => Err(ArchiveError::UnexpectedContentRangeError(range)),
+        }
+    }
+
+    /// A download from the beginning of the archive, with the response to the first request
+    fn from_start(
+        client: &Session,
+        url: &str,
+        headers: &HeaderMap,
+        response: ResponseReader,
+    ) -> Result<Self, ArchiveError> {
+        let length = content_length(headers)?;
+        let accepts_ranges = headers
+            .typed_get::<AcceptRanges>()
+            .is_some_and(|accept_ranges| accept_ranges.is_bytes());
+        let validator = headers
+            .get(ETAG)
+            .or_else(|| headers.get(LAST_MODIFIED))
+            .cloned();
+
+        Ok(Self {
+            client: client.clone(),
+            url: url.to_owned(),
+            response,
+            length,
+            position: 0,
+            accepts_ranges,
+            validator,
+            interruptions: 0,
+        })
+    }
+
+    /// The total size of the archive in bytes
+    pub const fn length(&self) -> u64 {
+        self.length
+    }
+
+    /// The number of bytes of the archive that were downloaded by an earlier attempt
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// The validator that a later attempt can resume the download with, if the server accepts
+    /// `Range` requests for the archive
+    fn resumable_validator(&self) -> Option<&HeaderValue> {
+        self.validator.as_ref().filter(|_| self.accepts_ranges)
+    }
+
+    /// Reconnect after the download was interrupted, continuing from the current position
+    fn resume(&mut self, cause: io::Error) -> Result<(), ArchiveError> {
+        let interrupted = ArchiveError::InterruptedError {
+            received: self.position,
+            expected: self.length,
+            source: cause,
+        };
+        if self.interruptions == MAX_RETRIES {
+            return Err(ArchiveError::RetriesExhaustedError {
+                tries: MAX_RETRIES as u64 + 1,
+                error: Box::new(interrupted),
+            });
+        }
+        self.interruptions += 1;
+        debug!("{interrupted}, resuming download of {}", self.url);
+
+        let range = self.accepts_ranges.then_some(Resume {
+            start: self.position,
+            validator: self.validator.as_ref(),
+        });
+        let (client, url) = (&self.client, &self.url);
+        let (position, length) = (self.position, self.length);
+        let response = retry(backoff(), || {
+            attempt(
+                send(client, url, range.as_ref())
+                    .and_then(|(headers, response)| skip_to(position, length, &headers, response)),
+            )
+        })
+        .map_err(exhausted)?;
+
+        self.response = response;
+        Ok(())
+    }
+}
+
+impl Read for Download {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let cause = match self.response.read(buf) {
+                // The response body ends early if the connection is closed before it is complete
+                Ok(0) if !buf.is_empty() && self.position < self.length => {
+                    io::Error::from(io::ErrorKind::UnexpectedEof)
+                }
+                Ok(read) => {
+                    if read > 0 {
+                        self.interruptions = 0;
+                    }
+                    self.position += read as u64;
+                    return Ok(read);
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => return Err(error),
+                Err(error) => error,
+            };
+
+            self.resume(cause).map_err(io::Error::other)?;
+        }
+    }
+}
+
+/// Download the archive at the given URL into `cache_file`, returning its total size and a reader
+/// over all of its data
+///
+/// If `cache_file` holds part of the archive from an earlier attempt, along with its validator,
+/// the download resumes after that part, which is read from the file first. Otherwise, the
+/// download starts from the beginning, and the validator is recorded next to the file if the
+/// server accepts `Range` requests, so that a later attempt can resume it in turn.
+pub fn fetch_to_file(
+    client: &Session,
+    url: &str,
+    cache_file: &Path,
+) -> Result<(u64, Box<dyn Read>), ArchiveError> {
+    ensure_containing_dir_exists(&cache_file)?;
+    let validator_file = validator_file(cache_file);
+
+    if let Some((position, validator)) = partial_download(cache_file, &validator_file) {
+        debug!("Resuming the download of {url} after {position} bytes");
+        match Download::start_after(client, url, position, validator) {
+            Ok(download) if download.position() > 0 => {
+                let length = download.length();
+                let downloaded = File::open(cache_file)?.take(download.position());
+                let file = OpenOptions::new().append(true).open(cache_file)?;
+                let data = downloaded.chain(TeeReader::new(download, file));
+                return Ok((length, Box::new(data)));
+            }
+            Ok(download) => return start_file(download, cache_file, &validator_file),
+            Err(error) => debug!("Could not resume the download ({error}), starting over"),
+        }
+    }
+
+    start_file(Download::start(client, url)?, cache_file, &validator_file)
+}
+
+/// The file next to a partial download that records the validator of the archive, which is the
+/// `ETag` or `Last-Modified` header, so that the download is only resumed if it is unchanged
+#[must_use]
+pub fn validator_file(cache_file: &Path) -> PathBuf {
+    let mut file = cache_file.as_os_str().to_owned();
+    file.push(".validator");
+    PathBuf::from(file)
+}
+
+/// Finds the size and validator of the part of the archive left in `cache_file` by an earlier
+/// attempt, if it can be resumed
+fn partial_download(cache_file: &Path, validator_file: &Path) -> Option<(u64, HeaderValue)> {
+    let position = fs::metadata(cache_file).ok()?.len();
+    let validator = HeaderValue::from_bytes(&fs::read(validator_file).ok()?).ok()?;
+    (position > 0).then_some((position, validator))
+}
+
+/// Write a download from the beginning of the archive to `cache_file`
+fn start_file(
+    download: Download,
+    cache_file: &Path,
+    validator_file: &Path,
+) -> Result<(u64, Box<dyn Read>), ArchiveError> {
+    match download.resumable_validator() {
+        Some(validator) => fs::write(validator_file, validator.as_bytes())?,
+        None => remove_if_exists(validator_file)?,
+    }
+
+    let length = download.length();
+    let file = File::create(cache_file)?;
+    Ok((length, Box::new(TeeReader::new(download, file))))
+}
+
+fn remove_if_exists(file: &Path) -> io::Result<()> {
+    match fs::remove_file(file) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+/// Send a request for an archive, optionally for the part of it starting at a given position
+fn send(
+    client: &Session,
+    url: &str,
+    range: Option<&Resume>,
+) -> Result<(HeaderMap, ResponseReader), ArchiveError> {
+    let mut request = client.get(url);
+    if let Some(Resume { start, validator }) = range {
+        request = request.header(RANGE, format!("bytes={start}-"));
+        if let Some(validator) = validator {
+            request = request.header(IF_RANGE, (*validator).clone());
+        }
+    }
+
+    let (status, headers, response) = request
+        .send()
+        .map_err(|error| match error.kind() {
+            ErrorKind::Io(_) | ErrorKind::InvalidResponse(_) => {
+                ArchiveError::ConnectionError(error)
+            }
+            _ => ArchiveError::AttohttpcError(error),
+        })?
+        .split();
+
+    if status.is_success() {
+        Ok((headers, response))
+    } else if is_transient(status) {
+        Err(ArchiveError::TransientHttpError(status))
+    } else {
+        Err(ArchiveError::HttpError(status))
+    }
+}
+
+/// Position a resumed response at the given number of bytes into the archive
+///
+/// A partial response must start exactly there, while the bytes before it are skipped if the
+/// server sent the complete archive instead.
+fn skip_to(
+    position: u64,
+    length: u64,
+    headers: &HeaderMap,
+    mut response: ResponseReader,
+) -> Result<ResponseReader, ArchiveError> {
+    if let Some(content_range) = headers.typed_get::<ContentRange>() {
+        return match content_range.bytes_range() {
+            Some((start, _)) if start == position => Ok(response),
+            range => Err(ArchiveError::UnexpectedContentRangeError(range)),
+        };
+    }
+
+    let resumed_length = content_length(headers)?;
+    if resumed_length != length {
+        return Err(ArchiveError::UnexpectedContentLengthError(resumed_length));
+    }
+
+    debug!("Server sent the complete archive, skipping the first {position} bytes");
+    let skipped =
+        io::copy(&mut (&mut response).take(position), &mut io::sink()).and_then(|skipped| {
+            if skipped < position {
+                Err(io::Error::from(io::ErrorKind::UnexpectedEof))
+            } else {
+                Ok(skipped)
+            }
+        });
+
+    match skipped {
+        Ok(_) => Ok(response),
+        Err(source) => Err(ArchiveError::InterruptedError {
+            received: position,
+            expected: length,
+            source,
+        }),
+    }
+}
+
+/// Determines whether an HTTP failure status may succeed if the request is retried later
+fn is_transient(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+fn attempt<T>(result: Result<T, ArchiveError>) -> OperationResult<T, ArchiveError> {
+    match result {
+        Ok(value) => OperationResult::Ok(value),
+        Err(error) if error.is_retryable() => {
+            debug!("Download failed ({error}), retrying");
+            OperationResult::Retry(error)
+        }
+        Err(error) => OperationResult::Err(error),
+    }
+}
+
+fn exhausted(error: retry::Error<ArchiveError>) -> ArchiveError {
+    if error.error.is_retryable() {
+        ArchiveError::RetriesExhaustedError {
+            tries: error.tries,
+            error: Box::new(error.error),
+        }
+    } else {
+        error.error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    const ARCHIVE: &[u8] = b"0123456789";
+
+    /// Serve one scripted response per connection, returning the URL of the server and a handle
+    /// that resolves to the (lowercased) request headers that were received
+    fn serve(responses: Vec<Vec<u8>>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/archive.tar.gz", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|response| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut request = String::new();
+                    while reader.read_line(&mut request).unwrap() > 2 {}
+
+                    stream.write_all(&response).unwrap();
+                    request.to_lowercase()
+                })
+                .collect()
+        });
+
+        (url, handle)
+    }
+
+    fn response(status: &str, headers: &[&str], body: &[u8]) -> Vec<u8> {
+        let mut response = format!("HTTP/1.1 {status}\r\n");
+        for header in headers {
+            response.push_str(header);
+            response.push_str("\r\n");
+        }
+        response.push_str("\r\n");
+
+        let mut response = response.into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+
+    fn read_all(download: &mut dyn Read) -> Vec<u8> {
+        let mut data = Vec::new();
+        download.read_to_end(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_resume_with_range() {
+        let (url, server) = serve(vec![
+            response(
+                "200 OK",
+                &["Content-Length: 10", "Accept-Ranges: bytes", "ETag: \"v1\""],
+                &ARCHIVE[..4],
+            ),
+            response(
+                "206 Partial Content",
+                &["Content-Length: 6", "Content-Range: bytes 4-9/10"],
+                &ARCHIVE[4..],
+            ),
+        ]);
+
+        let mut download = Download::start(&Session::new(), &url).unwrap();
+        assert_eq!(download.length(), 10);
+        assert_eq!(read_all(&mut download), ARCHIVE);
+
+        let requests = server.join().unwrap();
+        assert!(!requests[0].contains("range:"));
+        assert!(requests[1].contains("range: bytes=4-\r\n"));
+        assert!(requests[1].contains("if-range: \"v1\"\r\n"));
+    }
+
+    #[test]
+    fn test_resume_without_range() {
+        let (url, server) = serve(vec![
+            response("200 OK", &["Content-Length: 10"], &ARCHIVE[..4]),
+            response("200 OK", &["Content-Length: 10"], ARCHIVE),
+        ]);
+
+        let mut download = Download::start(&Session::new(), &url).unwrap();
+        assert_eq!(read_all(&mut download), ARCHIVE);
+
+        let requests = server.join().unwrap();
+        assert!(!requests[1].contains("range:"));
+    }
+
+    #[test]
+    fn test_retry_transient_status() {
+        let (url, server) = serve(vec![
+            response("503 Service Unavailable", &["Content-Length: 0"], b""),
+            response("200 OK", &["Content-Length: 10"], ARCHIVE),
+        ]);
+
+        let mut download = Download::start(&Session::new(), &url).unwrap();
+        assert_eq!(read_all(&mut download), ARCHIVE);
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_permanent_status_is_not_retried() {
+        let (url, server) = serve(vec![response("404 Not Found", &["Content-Length: 0"], b"")]);
+
+        let error = Download::start(&Session::new(), &url).err().unwrap();
+        assert!(matches!(
+            error,
+            ArchiveError::HttpError(StatusCode::NOT_FOUND)
+        ));
+        assert!(!error.is_retryable());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_retries_exhausted() {
+        let unavailable = response("503 Service Unavailable", &["Content-Length: 0"], b"");
+        let (url, server) = serve(vec![unavailable; MAX_RETRIES + 1]);
+
+        let error = Download::start(&Session::new(), &url).err().unwrap();
+        match error {
+            ArchiveError::RetriesExhaustedError { tries, error } => {
+                assert_eq!(tries, MAX_RETRIES as u64 + 1);
+                assert!(matches!(
+                    *error,
+                    ArchiveError::TransientHttpError(StatusCode::SERVICE_UNAVAILABLE)
+                ));
+            }
+            error => panic!("unexpected error: {error}"),
+        }
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_unexpected_content_range() {
+        let (url, server) = serve(vec![
+            response(
+                "200 OK",
+                &["Content-Length: 10", "Accept-Ranges: bytes"],
+                &ARCHIVE[..4],
+            ),
+            response(
+                "206 Partial Content",
+                &["Content-Length: 10", "Content-Range: bytes 0-9/10"],
+                ARCHIVE,
+            ),
+        ]);
+
+        let mut download = Download::start(&Session::new(), &url).unwrap();
+        let mut data = Vec::new();
+        let error = download.read_to_end(&mut data).unwrap_err();
+        assert!(error.to_string().contains("unexpected content range"));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_fetch_to_file_keeps_partial_download() {
+        let (url, server) = serve(vec![response(
+            "200 OK",
+            &["Content-Length: 10", "Accept-Ranges: bytes", "ETag: \"v1\""],
+            &ARCHIVE[..4],
+        )]);
+        let dir = tempfile::tempdir().unwrap();
+        let cache_file = dir.path().join("archive.tar.gz");
+
+        let (length, mut data) = fetch_to_file(&Session::new(), &url, &cache_file).unwrap();
+        assert_eq!(length, 10);
+        assert!(data.read_to_end(&mut Vec::new()).is_err());
+        server.join().unwrap();
+
+        // The part that was downloaded is kept, along with the validator to resume it with
+        assert_eq!(fs::read(&cache_file).unwrap(), &ARCHIVE[..4]);
+        assert_eq!(fs::read(validator_file(&cache_file)).unwrap(), b"\"v1\"");
+    }
+
+    #[test]
+    fn test_fetch_to_file_resumes_partial_download() {
+        let (url, server) = serve(vec![response(
+            "206 Partial Content",
+            &["Content-Length: 6", "Content-Range: bytes 4-9/10"],
+            &ARCHIVE[4..],
+        )]);
+        let dir = tempfile::tempdir().unwrap();
+        let cache_file = dir.path().join("archive.tar.gz");
+        fs::write(&cache_file, &ARCHIVE[..4]).unwrap();
+        fs::write(validator_file(&cache_file), "\"v1\"").unwrap();
+
+        let (length, mut data) = fetch_to_file(&Session::new(), &url, &cache_file).unwrap();
+        assert_eq!(length, 10);
+        assert_eq!(read_all(&mut data), ARCHIVE);
+        assert_eq!(fs::read(&cache_file).unwrap(), ARCHIVE);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].contains("range: bytes=4-\r\n"));
+        assert!(requests[0].contains("if-range: \"v1\"\r\n"));
+    }
+
+    #[test]
+    fn test_fetch_to_file_restarts_changed_archive() {
+        // The archive changed since the partial download, so the server sends all of it
+        let (url, server) = serve(vec![response(
+            "200 OK",
+            &["Content-Length: 10", "Accept-Ranges: bytes", "ETag: \"v2\""],
+            ARCHIVE,
+        )]);
+        let dir = tempfile::tempdir().unwrap();
+        let cache_file = dir.path().join("archive.tar.gz");
+        fs::write(&cache_file, b"abcd").unwrap();
+        fs::write(validator_file(&cache_file), "\"v1\"").unwrap();
+
+        let (_, mut data) = fetch_to_file(&Session::new(), &url, &cache_file).unwrap();
+        assert_eq!(read_all(&mut data), ARCHIVE);
+        assert_eq!(fs::read(&cache_file).unwrap(), ARCHIVE);
+        assert_eq!(fs::read(validator_file(&cache_file)).unwrap(), b"\"v2\"");
+        server.join().unwrap();
+    }
+}
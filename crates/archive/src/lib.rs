@@ -1,13 +1,15 @@
 //! This crate provides types for fetching and unpacking compressed
 //! archives in tarball or zip format.
 use std::fs::{self, File};
-use std::io;
+use std::io::{self, Read, Write};
 use std::path::Path;
 
+use attohttpc::Session;
 use attohttpc::header::HeaderMap;
 use headers::{ContentLength, Header, HeaderMapExt};
 use thiserror::Error;
 
+mod download;
 mod tarball;
 mod zipfile;
 
@@ -31,21 +33,48 @@ fn ensure_containing_dir_exists<P: AsRef<Path>>(path: &P) -> io::Result<()> {
         .and_then(fs::create_dir_all)
 }
 
+pub use crate::download::validator_file;
 pub use crate::tarball::Tarball;
 pub use crate::zipfile::Zip;
 
 /// Error type for this crate
+///
+/// Failures that may succeed if the download is retried later are distinguished from permanent
+/// ones by [`ArchiveError::is_retryable`].
 #[derive(Error, Debug)]
 pub enum ArchiveError {
     #[error("HTTP failure ({0})")]
     HttpError(attohttpc::StatusCode),
 
+    #[error("transient HTTP failure ({0})")]
+    TransientHttpError(attohttpc::StatusCode),
+
+    #[error("connection failure: {0}")]
+    ConnectionError(#[source] attohttpc::Error),
+
+    #[error("download interrupted after {received} of {expected} bytes: {source}")]
+    InterruptedError {
+        received: u64,
+        expected: u64,
+        source: std::io::Error,
+    },
+
+    #[error("{error} (gave up after {tries} attempts)")]
+    RetriesExhaustedError {
+        tries: u64,
+        #[source]
+        error: Box<Self>,
+    },
+
     #[error("HTTP header '{0}' not found")]
     MissingHeaderError(&'static attohttpc::header::HeaderName),
 
     #[error("unexpected content length in HTTP response: {0}")]
     UnexpectedContentLengthError(u64),
 
+    #[error("unexpected content range in HTTP response: {0:?}")]
+    UnexpectedContentRangeError(Option<(u64, u64)>),
+
     #[error("{0}")]
     IoError(#[from] std::io::Error),
 
@@ -56,6 +85,17 @@ pub enum ArchiveError {
     ZipError(#[from] zip::result::ZipError),
 }
 
+impl ArchiveError {
+    /// Determines whether the failed operation may succeed if it is retried
+    #[must_use]
+    pub const fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::TransientHttpError(_) | Self::ConnectionError(_) | Self::InterruptedError { .. }
+        )
+    }
+}
+
 /// Metadata describing whether an archive comes from a local or remote origin.
 #[derive(Copy, Clone)]
 pub enum Origin {
@@ -66,7 +106,11 @@ pub enum Origin {
 pub trait Archive {
     fn compressed_size(&self) -> u64;
 
-    /// Unpacks the zip archive to the specified destination folder.
+    /// Unpacks the archive to the specified destination folder.
+    ///
+    /// Every byte of the compressed archive is also written to `digest`, including any trailing
+    /// data after the end of the archive contents, so that the caller can verify the integrity
+    /// of the complete archive once it has been unpacked.
     ///
     /// # Errors
     ///
@@ -74,6 +118,7 @@ pub trait Archive {
     fn unpack(
         self: Box<Self>,
         dest: &Path,
+        digest: &mut dyn Write,
         progress: &mut dyn FnMut(&(), usize),
     ) -> Result<(), ArchiveError>;
 
@@ -95,7 +140,7 @@ cfg_if::cfg_if! {
         }
 
         /// Fetch a remote archive in the native OS-preferred format from the specified
-        /// URL and store its results at the specified file path.
+        /// URL with the given HTTP client, and store its results at the specified file path.
         ///
         /// On Windows, the preferred format is zip. On Unixes, the preferred format
         /// is tarball.
@@ -103,8 +148,12 @@ cfg_if::cfg_if! {
         /// # Errors
         ///
         /// Returns an error if the archive cannot be fetched.
-        pub fn fetch_native(url: &str, cache_file: &Path) -> Result<Box<dyn Archive>, ArchiveError> {
-            Tarball::fetch(url, cache_file)
+        pub fn fetch_native(
+            client: &Session,
+            url: &str,
+            cache_file: &Path,
+        ) -> Result<Box<dyn Archive>, ArchiveError> {
+            Tarball::fetch(client, url, cache_file)
         }
     } else if #[cfg(windows)] {
         /// Load an archive in the native OS-preferred format from the specified file.
@@ -120,7 +169,7 @@ cfg_if::cfg_if! {
         }
 
         /// Fetch a remote archive in the native OS-preferred format from the specified
-        /// URL and store its results at the specified file path.
+        /// URL with the given HTTP client, and store its results at the specified file path.
         ///
         /// On Windows, the preferred format is zip. On Unixes, the preferred format
         /// is tarball.
@@ -128,14 +177,27 @@ cfg_if::cfg_if! {
         /// # Errors
         ///
         /// Returns an error if the archive cannot be fetched.
-        pub fn fetch_native(url: &str, cache_file: &Path) -> Result<Box<dyn Archive>, ArchiveError> {
-            Zip::fetch(url, cache_file)
+        pub fn fetch_native(
+            client: &Session,
+            url: &str,
+            cache_file: &Path,
+        ) -> Result<Box<dyn Archive>, ArchiveError> {
+            Zip::fetch(client, url, cache_file)
         }
     } else {
         compile_error!("Unsupported OS (expected 'unix' or 'windows').");
     }
 }
 
+/// Reads any data remaining in the source after the archive contents have been unpacked.
+///
+/// Decoders may stop reading before the end of the source (e.g. at the end-of-archive marker), so
+/// this ensures that the complete archive is written to the inventory cache and digest.
+fn drain<R: Read>(mut source: R) -> Result<(), ArchiveError> {
+    io::copy(&mut source, &mut io::sink())?;
+    Ok(())
+}
+
 /// Determines the length of an HTTP response's content in bytes, using
 /// the HTTP `"Content-Length"` header.
 fn content_length(headers: &HeaderMap) -> Result<u64, ArchiveError> {
@@ -2,10 +2,12 @@
 //! tarball in Unix operating systems.
 
 use std::fs::File;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::path::Path;
 
-use super::{Archive, ArchiveError, Origin, content_length, ensure_containing_dir_exists};
+use super::download::fetch_to_file;
+use super::{Archive, ArchiveError, Origin, drain};
+use attohttpc::Session;
 use flate2::read::GzDecoder;
 use progress_read::ProgressRead;
 use tee::TeeReader;
@@ -36,21 +38,19 @@ impl Tarball {
     /// tarball that can be streamed (and that tees its data to a local
     /// file as it streams).
     ///
+    /// If the file holds part of the tarball from an earlier attempt, the
+    /// download resumes after it where the server allows.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the HTTP request fails or the cache file cannot be created.
-    pub fn fetch(url: &str, cache_file: &Path) -> Result<Box<dyn Archive>, ArchiveError> {
-        let (status, headers, response) = attohttpc::get(url).send()?.split();
-
-        if !status.is_success() {
-            return Err(ArchiveError::HttpError(status));
-        }
-
-        let compressed_size = content_length(&headers)?;
-
-        ensure_containing_dir_exists(&cache_file)?;
-        let file = File::create(cache_file)?;
-        let data = Box::new(TeeReader::new(response, file));
+    /// Returns an error if the HTTP request fails after retrying any transient failures, or if the
+    /// cache file cannot be created.
+    pub fn fetch(
+        client: &Session,
+        url: &str,
+        cache_file: &Path,
+    ) -> Result<Box<dyn Archive>, ArchiveError> {
+        let (compressed_size, data) = fetch_to_file(client, url, cache_file)?;
 
         Ok(Box::new(Self {
             compressed_size,
@@ -67,12 +67,14 @@ impl Archive for Tarball {
     fn unpack(
         self: Box<Self>,
         dest: &Path,
+        digest: &mut dyn Write,
         progress: &mut dyn FnMut(&(), usize),
     ) -> Result<(), ArchiveError> {
-        let decoded = GzDecoder::new(ProgressRead::new(self.data, (), progress));
+        let mut source = ProgressRead::new(TeeReader::new(self.data, digest), (), progress);
+        let decoded = GzDecoder::new(&mut source);
         let mut tarball = tar::Archive::new(decoded);
         tarball.unpack(dest)?;
-        Ok(())
+        drain(source)
     }
     fn origin(&self) -> Origin {
         self.origin
@@ -83,7 +85,7 @@ impl Archive for Tarball {
 pub mod tests {
 
     use crate::tarball::Tarball;
-    use std::fs::File;
+    use std::fs::{File, read};
     use std::path::PathBuf;
 
     fn fixture_path(fixture_dir: &str) -> PathBuf {
@@ -102,4 +104,21 @@ pub mod tests {
 
         assert_eq!(tarball.compressed_size(), 402);
     }
+
+    #[test]
+    fn test_unpack_digest() {
+        let mut test_file_path = fixture_path("tarballs");
+        test_file_path.push("test-file.tar.gz");
+        let test_file = File::open(&test_file_path).expect("Couldn't open test file");
+        let archive = Tarball::load(test_file).expect("Failed to load archive");
+        let dest = tempfile::tempdir().expect("Couldn't create temp dir");
+
+        // Every byte of the archive is written to the digest, not just what extraction needs
+        let mut digest = Vec::new();
+        archive
+            .unpack(dest.path(), &mut digest, &mut |(), _| {})
+            .expect("Failed to unpack archive");
+
+        assert_eq!(digest, read(test_file_path).unwrap());
+    }
 }
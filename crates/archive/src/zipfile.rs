@@ -2,10 +2,12 @@
 //! zip file in Windows operating systems.
 
 use std::fs::File;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::path::Path;
 
-use super::{ArchiveError, content_length, ensure_containing_dir_exists};
+use super::download::fetch_to_file;
+use super::{ArchiveError, drain};
+use attohttpc::Session;
 use progress_read::ProgressRead;
 use tee::TeeReader;
 use verbatim::PathExt;
@@ -39,21 +41,19 @@ impl Zip {
     /// Initiate fetching of a Node zip archive from the given URL, returning
     /// a `Remote` data source.
     ///
+    /// If the file holds part of the archive from an earlier attempt, the
+    /// download resumes after it where the server allows.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the HTTP request fails or the cache file cannot be created.
-    pub fn fetch(url: &str, cache_file: &Path) -> Result<Box<dyn Archive>, ArchiveError> {
-        let (status, headers, response) = attohttpc::get(url).send()?.split();
-
-        if !status.is_success() {
-            return Err(ArchiveError::HttpError(status));
-        }
-
-        let compressed_size = content_length(&headers)?;
-
-        ensure_containing_dir_exists(&cache_file)?;
-        let file = File::create(cache_file)?;
-        let data = Box::new(TeeReader::new(response, file));
+    /// Returns an error if the HTTP request fails after retrying any transient failures, or if the
+    /// cache file cannot be created.
+    pub fn fetch(
+        client: &Session,
+        url: &str,
+        cache_file: &Path,
+    ) -> Result<Box<dyn Archive>, ArchiveError> {
+        let (compressed_size, data) = fetch_to_file(client, url, cache_file)?;
 
         Ok(Box::new(Self {
             compressed_size,
@@ -70,13 +70,15 @@ impl Archive for Zip {
     fn unpack(
         self: Box<Self>,
         dest: &Path,
+        digest: &mut dyn Write,
         progress: &mut dyn FnMut(&(), usize),
     ) -> Result<(), ArchiveError> {
         // Use a verbatim path to avoid the legacy Windows 260 byte path limit.
         let dest: &Path = &dest.to_verbatim();
-        let zip = ZipStreamReader::new(ProgressRead::new(self.data, (), progress));
+        let mut source = ProgressRead::new(TeeReader::new(self.data, digest), (), progress);
+        let zip = ZipStreamReader::new(&mut source);
         zip.extract(dest)?;
-        Ok(())
+        drain(source)
     }
     fn origin(&self) -> Origin {
         self.origin
@@ -87,7 +89,7 @@ impl Archive for Zip {
 pub mod tests {
 
     use crate::zipfile::Zip;
-    use std::fs::File;
+    use std::fs::{File, read};
     use std::path::PathBuf;
 
     fn fixture_path(fixture_dir: &str) -> PathBuf {
@@ -106,4 +108,21 @@ pub mod tests {
 
         assert_eq!(zip.compressed_size(), 214);
     }
+
+    #[test]
+    fn test_unpack_digest() {
+        let mut test_file_path = fixture_path("zips");
+        test_file_path.push("test-file.zip");
+        let test_file = File::open(&test_file_path).expect("Couldn't open test file");
+        let archive = Zip::load(test_file).expect("Failed to load archive");
+        let dest = tempfile::tempdir().expect("Couldn't create temp dir");
+
+        // Every byte of the archive is written to the digest, not just what extraction needs
+        let mut digest = Vec::new();
+        archive
+            .unpack(dest.path(), &mut digest, &mut |(), _| {})
+            .expect("Failed to unpack archive");
+
+        assert_eq!(digest, read(test_file_path).unwrap());
+    }
 }
@@ -122,7 +122,7 @@ impl PathExt for Path {
     fn rm_contents(&self) {
         for file in ok_or_panic! { fs::read_dir(self) } {
             let file = ok_or_panic! { file };
-            if file.file_type().map(|m| m.is_dir()).unwrap_or(false) {
+            if file.file_type().is_ok_and(|m| m.is_dir()) {
                 file.path().rm_rf();
             } else {
                 file.path().rm();
@@ -0,0 +1,41 @@
+//! Measures the cost of finding the project, which happens every time a shim runs.
+
+use std::env::set_current_dir;
+use std::fs::{create_dir_all, write};
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use tempfile::tempdir;
+use volta_core::project::LazyProject;
+
+fn find_project(c: &mut Criterion) {
+    let dir = tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let app = repo.join("packages").join("app");
+    let script = repo.join("tools").join("scripts").join("release");
+    create_dir_all(repo.join(".git")).unwrap();
+    create_dir_all(&app).unwrap();
+    create_dir_all(&script).unwrap();
+    write(
+        repo.join("package.json"),
+        r#"{ "workspaces": ["packages/*"], "volta": { "node": "20.19.2" } }"#,
+    )
+    .unwrap();
+    write(app.join("package.json"), r#"{ "name": "app" }"#).unwrap();
+    write(script.join("package.json"), r#"{ "name": "release" }"#).unwrap();
+
+    // A member package inheriting the pins of its workspace root
+    set_current_dir(&app).unwrap();
+    c.bench_function("find_project (workspace member)", |b| {
+        b.iter(|| black_box(LazyProject::init().get().unwrap().is_some()));
+    });
+
+    // A package outside the workspace, where the search goes up to the repository root
+    set_current_dir(&script).unwrap();
+    c.bench_function("find_project (not a member)", |b| {
+        b.iter(|| black_box(LazyProject::init().get().unwrap().is_some()));
+    });
+}
+
+criterion_group!(benches, find_project);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+//! Measures the cost of recording usage, which happens every time a shim runs.
+
+use std::fs::File;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use nodejs_semver::Version;
+use tempfile::tempdir;
+use volta_core::usage::Index;
+
+fn record_usage(c: &mut Criterion) {
+    let dir = tempdir().unwrap();
+    let index = Index::new(dir.path().to_owned());
+    let version = Version::parse("18.20.4").unwrap();
+    let project = Path::new("/path/to/project");
+
+    // The common case: the marker was already written recently, so nothing needs to change
+    index.record_tool("node", &version);
+    c.bench_function("record_tool (fresh)", |b| {
+        b.iter(|| index.record_tool(black_box("node"), black_box(&version)));
+    });
+
+    index.record_project(project);
+    c.bench_function("record_project (fresh)", |b| {
+        b.iter(|| index.record_project(black_box(project)));
+    });
+
+    // The first use of a version in a while: the marker's modification time is updated
+    let marker = dir.path().join("tools").join("yarn").join("1.22.22");
+    let yarn = Version::parse("1.22.22").unwrap();
+    index.record_tool("yarn", &yarn);
+    c.bench_function("record_tool (stale)", |b| {
+        b.iter(|| {
+            File::options()
+                .write(true)
+                .open(&marker)
+                .unwrap()
+                .set_modified(SystemTime::now() - Duration::from_hours(2))
+                .unwrap();
+            index.record_tool(black_box("yarn"), black_box(&yarn));
+        });
+    });
+}
+
+criterion_group!(benches, record_usage);
+criterion_main!(benches);
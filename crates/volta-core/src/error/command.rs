@@ -174,9 +174,9 @@ impl CommandError {
     pub const fn exit_code(&self) -> ExitCode {
         match self {
             // ConfigurationError
-            Self::NoPnpmSpecified
-            | Self::NoYarnSpecified
-            | Self::NoCurrentVersion { .. } => ExitCode::ConfigurationError,
+            Self::NoPnpmSpecified | Self::NoYarnSpecified | Self::NoCurrentVersion { .. } => {
+                ExitCode::ConfigurationError
+            }
 
             // ExecutableNotFound
             Self::NpxUnavailable { .. } => ExitCode::ExecutableNotFound,
@@ -4,6 +4,7 @@
 //! - Environment variable reading
 //! - Path manipulation (`VOLTA_HOME`, `PATH`)
 //! - Environment configuration
+//! - Network configuration (proxies and certificate authorities)
 
 use std::fmt;
 use std::path::PathBuf;
@@ -38,6 +39,15 @@ pub enum EnvironmentError {
 
     /// Thrown when unable to start the migration executable.
     MigrationStartFailed,
+
+    /// Thrown when a settings file could not be parsed.
+    ParseSettings { file: PathBuf },
+
+    /// Thrown when a configured proxy is not a valid HTTP or HTTPS URL.
+    InvalidProxy { url: String },
+
+    /// Thrown when a bundle of certificate authorities could not be loaded.
+    LoadCertificates { file: PathBuf },
 }
 
 impl fmt::Display for EnvironmentError {
@@ -82,6 +92,29 @@ Please create one of these and try again; or you can edit your profile manually
 
 Please ensure you have 'volta-migrate' on your PATH and run it directly."
             ),
+            Self::ParseSettings { file } => write!(
+                f,
+                "Could not parse settings file
+from {}
+
+Please ensure the file is correctly formatted.",
+                file.display()
+            ),
+            Self::InvalidProxy { url } => write!(
+                f,
+                "Invalid proxy URL: {url}
+
+Please ensure the proxies in your settings.json and in HTTP_PROXY, HTTPS_PROXY, and ALL_PROXY
+are valid http:// or https:// URLs."
+            ),
+            Self::LoadCertificates { file } => write!(
+                f,
+                "Could not load certificate authorities
+from {}
+
+Please ensure the file exists and contains PEM-encoded certificates.",
+                file.display()
+            ),
         }
     }
 }
@@ -98,6 +131,9 @@ impl EnvironmentError {
             | Self::NoShellProfile { .. }
             | Self::MigrationStartFailed => ExitCode::EnvironmentError,
             Self::LockAcquire => ExitCode::FileSystemError,
+            Self::ParseSettings { .. }
+            | Self::InvalidProxy { .. }
+            | Self::LoadCertificates { .. } => ExitCode::ConfigurationError,
         }
     }
 }
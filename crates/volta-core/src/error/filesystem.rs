@@ -13,11 +13,6 @@ use super::ExitCode;
 
 const PERMISSIONS_CTA: &str = "Please ensure you have correct permissions to the Volta directory.";
 
-const REPORT_BUG_CTA: &str =
-    "Please rerun the command that triggered this error with the environment
-variable `VOLTA_LOGLEVEL` set to `debug` and open an issue at
-https://github.com/volta-cli/volta/issues with the details!";
-
 /// Errors related to filesystem operations.
 #[derive(Debug)]
 #[cfg_attr(test, derive(PartialEq, Eq))]
@@ -51,11 +46,11 @@ pub enum FilesystemError {
     /// Could not read hooks file.
     ReadHooks { file: PathBuf },
 
-    /// Could not read Node index cache.
-    ReadNodeIndexCache { file: PathBuf },
+    /// Could not read settings file.
+    ReadSettings { file: PathBuf },
 
-    /// Could not read Node index cache expiration.
-    ReadNodeIndexExpiry { file: PathBuf },
+    /// Could not read registry metadata cache.
+    ReadMetadataCache { file: PathBuf },
 
     /// Could not read npm manifest file.
     ReadNpmManifest,
@@ -69,6 +64,12 @@ pub enum FilesystemError {
     /// Could not read default npm version file.
     ReadDefaultNpm { file: PathBuf },
 
+    /// Could not read a Node version file, such as `.nvmrc`.
+    ReadVersionFile { file: PathBuf },
+
+    /// Could not read a project lockfile.
+    ReadLockfile { file: PathBuf },
+
     /// Could not read user Path environment variable (Windows only).
     #[cfg(windows)]
     ReadUserPath,
@@ -83,11 +84,8 @@ pub enum FilesystemError {
     /// Could not write launcher.
     WriteLauncher { tool: String },
 
-    /// Could not write Node index cache.
-    WriteNodeIndexCache { file: PathBuf },
-
-    /// Could not write Node index cache expiration.
-    WriteNodeIndexExpiry { file: PathBuf },
+    /// Could not write registry metadata cache.
+    WriteMetadataCache { file: PathBuf },
 
     /// Could not write package configuration.
     WritePackageConfig { file: PathBuf },
@@ -102,6 +100,9 @@ pub enum FilesystemError {
     /// Could not write project manifest.
     WritePackage { file: PathBuf },
 
+    /// Could not write project lockfile.
+    WriteLockfile { file: PathBuf },
+
     // ==================== Delete Operations ====================
     /// Could not delete a directory.
     DeleteDir { dir: PathBuf },
@@ -111,11 +112,6 @@ pub enum FilesystemError {
 
     // ==================== Parse Operations ====================
     /// Could not parse Node index cache.
-    ParseNodeIndexCache,
-
-    /// Could not parse Node index cache expiration.
-    ParseNodeIndexExpiry,
-
     /// Could not parse npm manifest file from a Node install.
     ParseNpmManifest,
 }
@@ -191,17 +187,17 @@ from {}
 {PERMISSIONS_CTA}",
                 file.display()
             ),
-            Self::ReadNodeIndexCache { file } => write!(
+            Self::ReadSettings { file } => write!(
                 f,
-                "Could not read Node index cache
+                "Could not read settings file
 from {}
 
 {PERMISSIONS_CTA}",
                 file.display()
             ),
-            Self::ReadNodeIndexExpiry { file } => write!(
+            Self::ReadMetadataCache { file } => write!(
                 f,
-                "Could not read Node index cache expiration
+                "Could not read registry metadata cache
 from {}
 
 {PERMISSIONS_CTA}",
@@ -234,6 +230,22 @@ from {}
                 "Could not read default npm version
 from {}
 
+{PERMISSIONS_CTA}",
+                file.display()
+            ),
+            Self::ReadVersionFile { file } => write!(
+                f,
+                "Could not read Node version file
+from {}
+
+{PERMISSIONS_CTA}",
+                file.display()
+            ),
+            Self::ReadLockfile { file } => write!(
+                f,
+                "Could not read project lockfile
+from {}
+
 {PERMISSIONS_CTA}",
                 file.display()
             ),
@@ -268,17 +280,9 @@ to {}
 
 This is most likely an intermittent failure, please try again."
             ),
-            Self::WriteNodeIndexCache { file } => write!(
+            Self::WriteMetadataCache { file } => write!(
                 f,
-                "Could not write Node index cache
-to {}
-
-{PERMISSIONS_CTA}",
-                file.display()
-            ),
-            Self::WriteNodeIndexExpiry { file } => write!(
-                f,
-                "Could not write Node index cache expiration
+                "Could not write registry metadata cache
 to {}
 
 {PERMISSIONS_CTA}",
@@ -315,6 +319,14 @@ to {}
 Please ensure you have correct permissions.",
                 file.display()
             ),
+            Self::WriteLockfile { file } => write!(
+                f,
+                "Could not write project lockfile
+to {}
+
+{PERMISSIONS_CTA}",
+                file.display()
+            ),
 
             // Delete operations
             Self::DeleteDir { dir } => write!(
@@ -335,18 +347,6 @@ at {}
             ),
 
             // Parse operations
-            Self::ParseNodeIndexCache => write!(
-                f,
-                "Could not parse Node index cache file.
-
-{REPORT_BUG_CTA}"
-            ),
-            Self::ParseNodeIndexExpiry => write!(
-                f,
-                "Could not parse Node index cache expiration file.
-
-{REPORT_BUG_CTA}"
-            ),
             Self::ParseNpmManifest => write!(
                 f,
                 "Could not parse package.json file for bundled npm.
@@ -374,11 +374,13 @@ impl FilesystemError {
             Self::CurrentDir => ExitCode::EnvironmentError,
             Self::ReadDir { .. }
             | Self::ReadHooks { .. }
-            | Self::ReadNodeIndexCache { .. }
-            | Self::ReadNodeIndexExpiry { .. }
+            | Self::ReadSettings { .. }
+            | Self::ReadMetadataCache { .. }
             | Self::ReadPackageConfig { .. }
             | Self::ReadPlatform { .. }
-            | Self::ReadDefaultNpm { .. } => ExitCode::FileSystemError,
+            | Self::ReadDefaultNpm { .. }
+            | Self::ReadVersionFile { .. }
+            | Self::ReadLockfile { .. } => ExitCode::FileSystemError,
             Self::ReadNpmManifest => ExitCode::UnknownError,
             #[cfg(windows)]
             Self::ReadUserPath => ExitCode::EnvironmentError,
@@ -386,11 +388,11 @@ impl FilesystemError {
             // Write operations - all filesystem errors except WriteLauncher
             Self::WriteBinConfig { .. }
             | Self::WriteDefaultNpm { .. }
-            | Self::WriteNodeIndexCache { .. }
-            | Self::WriteNodeIndexExpiry { .. }
+            | Self::WriteMetadataCache { .. }
             | Self::WritePackageConfig { .. }
             | Self::WritePlatform { .. }
-            | Self::WritePackage { .. } => ExitCode::FileSystemError,
+            | Self::WritePackage { .. }
+            | Self::WriteLockfile { .. } => ExitCode::FileSystemError,
             Self::WriteLauncher { .. } => ExitCode::FileSystemError,
             #[cfg(windows)]
             Self::WriteUserPath => ExitCode::EnvironmentError,
@@ -399,9 +401,7 @@ impl FilesystemError {
             Self::DeleteDir { .. } | Self::DeleteFile { .. } => ExitCode::FileSystemError,
 
             // Parse operations
-            Self::ParseNodeIndexCache | Self::ParseNodeIndexExpiry | Self::ParseNpmManifest => {
-                ExitCode::UnknownError
-            }
+            Self::ParseNpmManifest => ExitCode::UnknownError,
         }
     }
 }
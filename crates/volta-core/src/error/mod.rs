@@ -207,6 +207,12 @@ pub enum ExitCode {
     /// The command or feature is not yet implemented.
     NotYetImplemented = 9,
 
+    /// Newer versions of some tools are available, reported by `volta list --outdated --exit-code`.
+    Outdated = 10,
+
+    /// The project toolchain failed some of the checks of `volta verify`.
+    Unverified = 11,
+
     /// The requested executable could not be run.
     ExecutionFailure = 126,
 
@@ -21,11 +21,23 @@ pub enum NetworkError {
     /// Thrown when the public registry for Node or Yarn could not be downloaded.
     RegistryFetch { tool: String, from_url: String },
 
+    /// Thrown when the published checksums for a tool could not be downloaded.
+    ChecksumFetch { tool: String, from_url: String },
+
     /// Thrown when there is an error fetching the latest version of Yarn.
     YarnLatestFetch { from_url: String },
 
     /// Thrown when unable to parse the node index.
     ParseNodeIndex { from_url: String },
+
+    /// Thrown when a tool needs to be downloaded while in offline mode.
+    Offline { tool: String },
+
+    /// Thrown when checking for newer versions while in offline mode.
+    OutdatedOffline,
+
+    /// Thrown when listing the versions available from the registry while in offline mode.
+    RemoteVersionsOffline { tool: String },
 }
 
 impl fmt::Display for NetworkError {
@@ -43,6 +55,13 @@ Please verify your internet connection and ensure the correct version is specifi
                 "Could not download {tool} version registry
 from {from_url}
 
+Please verify your internet connection."
+            ),
+            Self::ChecksumFetch { tool, from_url } => write!(
+                f,
+                "Could not download {tool} checksums
+from {from_url}
+
 Please verify your internet connection."
             ),
             Self::YarnLatestFetch { from_url } => write!(
@@ -59,6 +78,24 @@ from {from_url}
 
 Please verify your internet connection."
             ),
+            Self::Offline { tool } => write!(
+                f,
+                "Could not download {tool} while in offline mode.
+
+Please fetch it while online, or unset VOLTA_OFFLINE and remove the `--offline` flag."
+            ),
+            Self::OutdatedOffline => write!(
+                f,
+                "Could not check for newer versions while in offline mode.
+
+Please unset VOLTA_OFFLINE and remove the `--offline` flag."
+            ),
+            Self::RemoteVersionsOffline { tool } => write!(
+                f,
+                "Could not list the available versions of {tool} while in offline mode.
+
+Please unset VOLTA_OFFLINE and remove the `--offline` flag."
+            ),
         }
     }
 }
@@ -21,9 +21,32 @@ https://github.com/volta-cli/volta/issues with the details!";
 #[derive(Debug)]
 #[cfg_attr(test, derive(PartialEq, Eq))]
 pub enum PackageError {
+    /// Thrown when an executable can't be used as an alias.
+    AliasInvalid { alias: String },
+
+    /// Thrown when an alias could refer to more than one executable of a package.
+    AliasBinAmbiguous {
+        package: String,
+        alias: String,
+        bins: Vec<String>,
+    },
+
+    /// Thrown when an alias refers to an executable that the package doesn't have.
+    AliasBinNotFound {
+        package: String,
+        bin: String,
+        bins: Vec<String>,
+    },
+
+    /// Thrown when `--as` is used to install anything but a single package.
+    AliasUnsupported,
+
     /// Thrown when a user tries to `volta fetch` a package (not supported).
     FetchNotSupported { package: String },
 
+    /// Thrown when `volta install` is run without tools outside a project with `volta.tools`.
+    NoProjectTools,
+
     /// Thrown when a user tries to `volta pin` a package (not supported).
     PinNotSupported { package: String },
 
@@ -45,6 +68,16 @@ pub enum PackageError {
     /// Thrown when reading a project manifest (package.json) fails.
     ProjectManifestRead { file: PathBuf },
 
+    /// Thrown when parsing a project lockfile (volta.lock) fails.
+    LockfileParse { file: PathBuf },
+
+    /// Thrown when an entry in `volta.tools` isn't a package name and exact version.
+    ProjectToolInvalid {
+        file: PathBuf,
+        tool: String,
+        value: String,
+    },
+
     /// Thrown when a package has been unpacked but is not formed correctly.
     UnpackLayout,
 
@@ -86,6 +119,43 @@ impl fmt::Display for PackageError {
     #[allow(clippy::too_many_lines)]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Self::AliasInvalid { alias } => write!(
+                f,
+                "Invalid executable alias: '{alias}'
+
+Please use `--as <alias>`, or `--as <executable>=<alias>` to choose an executable of the package."
+            ),
+            Self::AliasBinAmbiguous {
+                package,
+                alias,
+                bins,
+            } => write!(
+                f,
+                "Could not determine which executable of {package} to install as '{alias}'
+
+Please use `--as <executable>={alias}`, with one of: {}",
+                bins.join(", ")
+            ),
+            Self::AliasBinNotFound { package, bin, bins } => write!(
+                f,
+                "Package {package} has no executable '{bin}'
+
+Please use `--as <executable>=<alias>`, with one of: {}",
+                bins.join(", ")
+            ),
+            Self::AliasUnsupported => write!(
+                f,
+                "Only a single global package can be installed with `--as`
+
+Please install Node, npm, pnpm, Yarn and any other packages separately."
+            ),
+            Self::NoProjectTools => write!(
+                f,
+                "No tools to install.
+
+Please specify the tools to install, like `volta install node`, or run `volta install` in a
+project with a `volta.tools` key in its package.json."
+            ),
             Self::FetchNotSupported { package } => write!(
                 f,
                 "Fetching packages without installing them is not supported.
@@ -138,6 +208,22 @@ from {}
 Please ensure that the file exists.",
                 file.display()
             ),
+            Self::LockfileParse { file } => write!(
+                f,
+                "Could not parse project lockfile
+at {}
+
+Please ensure that the file is correctly formatted, or remove it and run `volta pin --refresh`.",
+                file.display()
+            ),
+            Self::ProjectToolInvalid { file, tool, value } => write!(
+                f,
+                "Invalid version of tool '{tool}' in project manifest: '{value}'
+at {}
+
+Please use a package name and an exact version, such as \"typescript@5.4.5\".",
+                file.display()
+            ),
             Self::UnpackLayout => write!(
                 f,
                 "Could not determine package directory layout.
@@ -236,6 +322,8 @@ impl PackageError {
             // ConfigurationError
             Self::ManifestParse { .. }
             | Self::ProjectManifestParse { .. }
+            | Self::LockfileParse { .. }
+            | Self::ProjectToolInvalid { .. }
             | Self::UnpackLayout
             | Self::LinkMissing { .. }
             | Self::LinkWrongManager { .. }
@@ -249,7 +337,12 @@ impl PackageError {
             | Self::WorkspacePathInvalid { .. } => ExitCode::FileSystemError,
 
             // InvalidArguments
-            Self::FetchNotSupported { .. }
+            Self::AliasInvalid { .. }
+            | Self::AliasBinAmbiguous { .. }
+            | Self::AliasBinNotFound { .. }
+            | Self::AliasUnsupported
+            | Self::FetchNotSupported { .. }
+            | Self::NoProjectTools
             | Self::PinNotSupported { .. }
             | Self::NotFound { .. } => ExitCode::InvalidArguments,
 
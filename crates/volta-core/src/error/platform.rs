@@ -6,6 +6,7 @@
 //! - Project platform settings
 
 use std::fmt;
+use std::path::PathBuf;
 
 use super::ExitCode;
 
@@ -41,6 +42,9 @@ pub enum PlatformError {
     /// Thrown when unable to parse the platform.json file.
     ParsePlatform,
 
+    /// Thrown when a fallback source, such as `.nvmrc`, has a Node version that can't be parsed.
+    ParseFallbackVersion { file: PathBuf, version: String },
+
     /// Thrown when the user tries to pin Node or Yarn versions outside of a package.
     NotInPackage,
 }
@@ -106,6 +110,14 @@ variable `VOLTA_LOGLEVEL` set to `debug` and open an issue at
 https://github.com/volta-cli/volta/issues with the details!"
                 )
             }
+            Self::ParseFallbackVersion { file, version } => write!(
+                f,
+                "Could not parse Node version '{version}'
+from {}
+
+Please use a version like `20.19.2`, a range like `^20`, or an LTS line like `lts/iron`.",
+                file.display()
+            ),
             Self::NotInPackage => write!(
                 f,
                 "Not in a node package.
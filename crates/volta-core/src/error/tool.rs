@@ -41,6 +41,68 @@ pub enum ToolError {
     /// Failed to persist archive to inventory cache.
     PersistInventory { tool: String },
 
+    // ==================== Integrity Errors ====================
+    /// The checksum of a downloaded or cached archive did not match the published checksum.
+    ChecksumMismatch {
+        tool: String,
+        version: String,
+        expected: String,
+        actual: String,
+    },
+
+    /// The published checksums did not include an entry for the archive.
+    MissingChecksum { file: String, from_url: String },
+
+    /// The digest of a downloaded or cached archive did not match the registry integrity.
+    IntegrityMismatch {
+        tool: String,
+        version: String,
+        expected: String,
+        actual: String,
+    },
+
+    /// The digest of an archive did not match the hash in the `packageManager` field.
+    CorepackHashMismatch {
+        tool: String,
+        version: String,
+        expected: String,
+        actual: String,
+    },
+
+    /// The hash in the `packageManager` field used an unsupported format or algorithm.
+    UnsupportedCorepackHash { hash: String },
+
+    /// The checksum published for an archive did not match the one in the project lockfile.
+    LockChecksumMismatch {
+        tool: String,
+        version: String,
+        file: PathBuf,
+        expected: String,
+        actual: String,
+    },
+
+    /// The registry metadata did not include a supported integrity for the version.
+    MissingIntegrity {
+        tool: String,
+        version: String,
+        from_url: String,
+    },
+
+    /// The signature on the published checksums could not be parsed or did not verify.
+    BadSignature { from_url: String },
+
+    /// The signature on the published checksums could not be downloaded.
+    MissingSignature { from_url: String },
+
+    /// The published checksums were signed by a key that is not in the keyring.
+    UnknownSigningKey {
+        key_id: String,
+        keyring_dir: PathBuf,
+    },
+
+    /// A file in the keyring directory did not contain valid public keys.
+    InvalidSigningKey { file: PathBuf },
+
     // ==================== Setup Errors ====================
     /// Failed to set a tool to executable.
     SetExecutable { tool: String },
@@ -52,6 +114,17 @@ pub enum ToolError {
         dir: PathBuf,
     },
 
+    // ==================== Uninstall Errors ====================
+    /// Uninstalling Node or a package manager requires an exact version.
+    UninstallExactVersion { tool: String },
+
+    /// The version to uninstall is still used by the default platform or installed packages.
+    UninstallVersionInUse {
+        tool: String,
+        version: String,
+        references: Vec<String>,
+    },
+
     // ==================== Serialization Errors ====================
     /// Failed to serialize executable configuration.
     SerializeBinConfig,
@@ -64,6 +137,7 @@ pub enum ToolError {
 }
 
 impl fmt::Display for ToolError {
+    #[allow(clippy::too_many_lines)]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             // Determination errors
@@ -119,6 +193,123 @@ Please ensure the correct version is specified."
 {PERMISSIONS_CTA}"
             ),
 
+            // Integrity errors
+            Self::ChecksumMismatch {
+                tool,
+                version,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Checksum verification failed for {tool} v{version}
+
+Expected SHA-256: {expected}
+Actual SHA-256:   {actual}
+
+The archive may have been corrupted or tampered with, and has been removed. Please try again."
+            ),
+            Self::MissingChecksum { file, from_url } => write!(
+                f,
+                "Could not find a checksum for {file}
+in {from_url}
+
+Please ensure the correct version is specified."
+            ),
+            Self::IntegrityMismatch {
+                tool,
+                version,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Integrity verification failed for {tool} v{version}
+
+Expected: {expected}
+Actual:   {actual}
+
+The archive may have been corrupted or tampered with, and has been removed. Please try again."
+            ),
+            Self::CorepackHashMismatch {
+                tool,
+                version,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{tool} v{version} does not match the hash in the `packageManager` field of package.json
+
+Expected: {expected}
+Actual:   {actual}
+
+Please ensure the `packageManager` field is correct, or remove its hash to skip this check."
+            ),
+            Self::UnsupportedCorepackHash { hash } => write!(
+                f,
+                "Could not verify the hash '{hash}' in the `packageManager` field of package.json
+
+Please use a hash like `sha512.<hex digest>`, or remove it to skip this check."
+            ),
+            Self::LockChecksumMismatch {
+                tool,
+                version,
+                file,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "The published checksum of {tool} v{version} does not match the one in {}
+
+Expected: {expected}
+Actual:   {actual}
+
+Please ensure the lockfile is correct, or run `volta pin --refresh` to record the current checksums.",
+                file.display()
+            ),
+            Self::MissingIntegrity {
+                tool,
+                version,
+                from_url,
+            } => write!(
+                f,
+                "Could not find the integrity of {tool} v{version}
+in the registry metadata from {from_url}
+
+Please ensure the correct version is specified."
+            ),
+            Self::BadSignature { from_url } => write!(
+                f,
+                "Signature verification failed for the Node checksums
+using the signature from {from_url}
+
+The checksums may have been tampered with. Please try again."
+            ),
+            Self::MissingSignature { from_url } => write!(
+                f,
+                "Could not download the signature for the Node checksums
+from {from_url}
+
+Signature verification is enabled with `VOLTA_VERIFY_SIGNATURES`, so Node cannot
+be installed without a valid signature."
+            ),
+            Self::UnknownSigningKey {
+                key_id,
+                keyring_dir,
+            } => write!(
+                f,
+                "The Node checksums are signed by an unknown key: {key_id}
+
+If you trust this key, add it to the keyring directory at
+{}",
+                keyring_dir.display()
+            ),
+            Self::InvalidSigningKey { file } => write!(
+                f,
+                "Could not read the signing keys in {}
+
+Please ensure the file contains binary or ASCII-armored OpenPGP public keys.",
+                file.display()
+            ),
+
             // Setup errors
             Self::SetExecutable { tool } => write!(
                 f,
@@ -135,6 +326,29 @@ at {}
                 dir.display()
             ),
 
+            // Uninstall errors
+            Self::UninstallExactVersion { tool } => write!(
+                f,
+                "Could not determine which version of {tool} to uninstall
+
+Please supply an exact version, like `volta uninstall {tool}@<version>`."
+            ),
+            Self::UninstallVersionInUse {
+                tool,
+                version,
+                references,
+            } => {
+                let uses = indent(&references.join("\n"), "    ");
+                write!(
+                    f,
+                    "Could not uninstall {tool} v{version} because it is still used by:
+
+{uses}
+
+Please change those to another version first, or use `--force` to uninstall it anyway."
+                )
+            }
+
             // Serialization errors
             Self::SerializeBinConfig => write!(
                 f,
@@ -166,12 +380,30 @@ impl ToolError {
             // Internal/unknown errors
             Self::CouldNotDetermine
             | Self::UnpackArchive { .. }
+            | Self::ChecksumMismatch { .. }
+            | Self::MissingChecksum { .. }
+            | Self::IntegrityMismatch { .. }
+            | Self::MissingIntegrity { .. }
+            | Self::BadSignature { .. }
+            | Self::UnknownSigningKey { .. }
             | Self::SerializeBinConfig
             | Self::SerializePackageConfig
             | Self::SerializePlatform => ExitCode::UnknownError,
 
             // Invalid arguments
-            Self::ParseSpec { .. } | Self::InvalidName { .. } => ExitCode::InvalidArguments,
+            Self::ParseSpec { .. }
+            | Self::InvalidName { .. }
+            | Self::UninstallExactVersion { .. } => ExitCode::InvalidArguments,
+
+            // Network errors
+            Self::MissingSignature { .. } => ExitCode::NetworkError,
+
+            // Configuration errors
+            Self::InvalidSigningKey { .. }
+            | Self::UninstallVersionInUse { .. }
+            | Self::CorepackHashMismatch { .. }
+            | Self::UnsupportedCorepackHash { .. }
+            | Self::LockChecksumMismatch { .. } => ExitCode::ConfigurationError,
 
             // Filesystem errors
             Self::SetExecutable { .. }
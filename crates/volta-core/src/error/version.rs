@@ -25,6 +25,9 @@ pub enum VersionError {
     /// No matching Yarn version found.
     YarnNotFound { matching: String },
 
+    /// No matching version found in the local inventory while in offline mode.
+    NotFoundOffline { tool: String, matching: String },
+
     /// Failed to parse a version string.
     ParseFailed { version: String },
 
@@ -61,6 +64,12 @@ Please verify that the version is correct."#
                 r#"Could not find Yarn version matching "{matching}" in the version registry.
 
 Please verify that the version is correct."#
+            ),
+            Self::NotFoundOffline { tool, matching } => write!(
+                f,
+                r#"Could not find {tool} version matching "{matching}" in the local inventory.
+
+Only versions that have already been fetched can be used in offline mode. Please fetch a matching version while online."#
             ),
             Self::ParseFailed { version } => write!(
                 f,
@@ -93,6 +102,7 @@ impl VersionError {
             | Self::NpmNotFound { .. }
             | Self::PnpmNotFound { .. }
             | Self::YarnNotFound { .. }
+            | Self::NotFoundOffline { .. }
             | Self::ParseFailed { .. }
             | Self::Yarn2NotSupported => ExitCode::NoVersionMatch,
             Self::NoBundledNpm { .. } => ExitCode::ConfigurationError,
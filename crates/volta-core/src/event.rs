@@ -7,8 +7,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::{ExitCode, VoltaError};
 use crate::hook::Publish;
-use crate::monitor::send_events;
+use crate::monitor::{post_events, send_events};
 use crate::session::ActivityKind;
+use crate::settings::Settings;
 
 // the Event data that is serialized to JSON and sent the plugin
 #[derive(Deserialize, Serialize)]
@@ -146,10 +147,11 @@ impl Log {
         self.events.push(event);
     }
 
-    pub fn publish(&self, plugin: Option<&Publish>) {
+    pub fn publish(&self, plugin: Option<&Publish>, settings: &Settings) {
         match plugin {
-            // Note: This call to unimplemented is left in, as it's not a Fallible operation that can use ErrorKind::Unimplemented
-            Some(Publish::Url(_)) => unimplemented!(),
+            Some(Publish::Url(url)) => {
+                post_events(url, &self.events, settings);
+            }
             Some(Publish::Bin(command)) => {
                 send_events(command, &self.events);
             }
@@ -161,10 +163,54 @@ impl Log {
 #[cfg(test)]
 pub mod tests {
 
-    use super::{Kind, Log};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::{Event, Kind, Log};
     use crate::error::{BinaryError, ErrorKind, ExitCode};
+    use crate::hook::Publish;
     use crate::session::ActivityKind;
-    use regex::Regex;
+    use crate::settings::Settings;
+
+    /// Accepts a single HTTP request on a local port, returning the URL to send it to and a
+    /// handle that resolves to the request line and body
+    fn serve_once(status: &'static str) -> (String, thread::JoinHandle<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/events", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let mut stream = reader.into_inner();
+            write!(stream, "HTTP/1.1 {status}\r\nContent-Length: 0\r\n\r\n").unwrap();
+
+            (request_line, String::from_utf8(body).unwrap())
+        });
+
+        (url, handle)
+    }
 
     #[test]
     fn test_adding_events() {
@@ -197,8 +243,7 @@ pub mod tests {
         assert_eq!(event_log.events[4].name, "args");
         match event_log.events[4].event {
             Kind::Args { ref argv } => {
-                let re = Regex::new("volta_core").unwrap();
-                assert!(re.is_match(argv));
+                assert!(argv.contains("volta_core"));
             }
             _ => {
                 panic!(
@@ -208,4 +253,52 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_publish_url() {
+        let (url, server) = serve_once("200 OK");
+
+        let mut event_log = Log::init();
+        event_log.add_event_start(ActivityKind::Install);
+        event_log.add_event_end(ActivityKind::Install, ExitCode::Success);
+        event_log.publish(Some(&Publish::Url(url)), &Settings::default());
+
+        let (request_line, body) = server.join().unwrap();
+        assert!(request_line.starts_with("POST /events "));
+
+        let events: Vec<Event> = serde_json::from_str(&body).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].name, "install");
+        assert_eq!(events[0].event, Kind::Start);
+        assert_eq!(events[1].event, Kind::End { exit_code: 0 });
+    }
+
+    #[test]
+    fn test_publish_url_error_status() {
+        let (url, server) = serve_once("500 Internal Server Error");
+
+        let mut event_log = Log::init();
+        event_log.add_event_start(ActivityKind::Install);
+        // A failing events server must not cause a panic
+        event_log.publish(Some(&Publish::Url(url)), &Settings::default());
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_publish_url_unreachable() {
+        // Bind and immediately drop a listener to find a port with nothing listening on it
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+
+        let mut event_log = Log::init();
+        event_log.add_event_start(ActivityKind::Install);
+        // An unreachable events server must not cause a panic
+        event_log.publish(
+            Some(&Publish::Url(format!("http://{addr}/events"))),
+            &Settings::default(),
+        );
+    }
 }
@@ -11,6 +11,7 @@ use crate::layout::volta_home;
 use retry::delay::Fibonacci;
 use retry::{OperationResult, retry};
 use tempfile::{NamedTempFile, TempDir, tempdir_in};
+use walkdir::WalkDir;
 
 /// Opens a file, creating it if it doesn't exist
 ///
@@ -117,6 +118,21 @@ pub fn read_dir_eager(dir: &Path) -> io::Result<impl Iterator<Item = (DirEntry,
     Ok(vec.into_iter())
 }
 
+/// Computes the total size in bytes of the files at the given path
+///
+/// For a directory, this includes every file within it. Entries which cannot be read are skipped,
+/// since the size is only informational.
+#[must_use]
+pub fn disk_usage(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(Metadata::is_file)
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
 /// Reads the contents of a directory and returns a Vec of the matched results
 /// from the input function
 ///
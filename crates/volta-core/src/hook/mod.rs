@@ -1,6 +1,7 @@
 //! Provides types for working with Volta hooks.
 
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fs::File;
 use std::iter::once;
 use std::marker::PhantomData;
@@ -9,6 +10,7 @@ use std::path::Path;
 use crate::error::{Context, ErrorKind, Fallible, FilesystemError, HookError};
 use crate::layout::volta_home;
 use crate::project::Project;
+use crate::tool::node::NodeChannel;
 use crate::tool::{Node, Npm, Pnpm, Tool};
 use log::debug;
 use once_cell::unsync::OnceCell;
@@ -63,6 +65,14 @@ pub struct ToolHooks<T: Tool> {
     pub latest: Option<tool::MetadataHook>,
     /// The hook for resolving the Tool Index URL
     pub index: Option<tool::MetadataHook>,
+    /// The hook for resolving the URL for the checksums of a distro version (Node only)
+    ///
+    /// Without it, the checksums of a `distro` hook are expected in a `SHASUMS256.txt` file next
+    /// to the archive. If the mirror doesn't host that file, the archive is installed with a
+    /// warning, verified only against the project lockfile if there is one.
+    pub checksum: Option<tool::DistroHook>,
+    /// The hooks for each prerelease channel, such as `nightly` (Node only)
+    channels: HashMap<NodeChannel, Self>,
 
     phantom: PhantomData<T>,
 }
@@ -84,11 +94,35 @@ impl<T: Tool> ToolHooks<T> {
             distro: self.distro.or(other.distro),
             latest: self.latest.or(other.latest),
             index: self.index.or(other.index),
+            checksum: self.checksum.or(other.checksum),
+            channels: merge_channels(self.channels, other.channels),
             phantom: PhantomData,
         }
     }
 }
 
+impl ToolHooks<Node> {
+    /// Returns the hooks for a prerelease channel of Node, if any
+    pub fn channel(&self, channel: NodeChannel) -> Option<&Self> {
+        self.channels.get(&channel)
+    }
+}
+
+/// Merges the hooks for each prerelease channel, giving precedence to the current ones
+fn merge_channels<T: Tool>(
+    mut current: HashMap<NodeChannel, ToolHooks<T>>,
+    other: HashMap<NodeChannel, ToolHooks<T>>,
+) -> HashMap<NodeChannel, ToolHooks<T>> {
+    for (channel, hooks) in other {
+        let merged = match current.remove(&channel) {
+            Some(existing) => existing.merge(hooks),
+            None => hooks,
+        };
+        current.insert(channel, merged);
+    }
+    current
+}
+
 impl YarnHooks {
     /// Extends this `YarnHooks` with another, giving precendence to the current instance
     fn merge(self, other: Self) -> Self {
@@ -268,7 +302,7 @@ impl EventHooks {
 #[cfg(test)]
 pub mod tests {
 
-    use super::{HookConfig, Publish, RegistryFormat, tool};
+    use super::{HookConfig, NodeChannel, Project, Publish, RegistryFormat, tool};
     use std::path::PathBuf;
 
     fn fixture_path(fixture_dir: &str) -> PathBuf {
@@ -320,6 +354,13 @@ pub mod tests {
                 base_path: fixture_dir.clone(),
             })
         );
+        assert_eq!(
+            node.checksum,
+            Some(tool::DistroHook::Bin {
+                bin: "/some/bin/for/node/checksum".to_string(),
+                base_path: fixture_dir.clone(),
+            })
+        );
         // pnpm
         assert_eq!(
             pnpm.distro,
@@ -400,6 +441,12 @@ pub mod tests {
                 "http://localhost/node/index/".to_string()
             ))
         );
+        assert_eq!(
+            node.checksum,
+            Some(tool::DistroHook::Prefix(
+                "http://localhost/node/checksum/".to_string()
+            ))
+        );
         // pnpm
         assert_eq!(
             pnpm.distro,
@@ -441,6 +488,46 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_from_str_channels() {
+        let fixture_dir = fixture_path("hooks");
+        let channels_file = fixture_dir.join("channels.json");
+        let hooks = HookConfig::from_file(&channels_file).unwrap().unwrap();
+        let node = hooks.node.unwrap();
+        let nightly = node.channel(NodeChannel::Nightly).unwrap();
+        let v8_canary = node.channel(NodeChannel::V8Canary).unwrap();
+
+        assert_eq!(
+            node.index,
+            Some(tool::MetadataHook::Prefix(
+                "http://localhost/node/index/".to_string()
+            ))
+        );
+        assert_eq!(node.distro, None);
+        assert_eq!(
+            nightly.distro,
+            Some(tool::DistroHook::Prefix(
+                "http://localhost/node/nightly/".to_string()
+            ))
+        );
+        assert_eq!(
+            nightly.index,
+            Some(tool::MetadataHook::Prefix(
+                "http://localhost/node/nightly/".to_string()
+            ))
+        );
+        assert_eq!(nightly.checksum, None);
+        assert_eq!(
+            v8_canary.index,
+            Some(tool::MetadataHook::Template(
+                "http://localhost/node/v8-canary/{{filename}}".to_string()
+            ))
+        );
+        assert!(node.channel(NodeChannel::Rc).is_none());
+        assert!(NodeChannel::Rc.hooks(Some(&node)).is_none());
+        assert!(NodeChannel::Release.hooks(Some(&node)).is_some());
+    }
+
     #[test]
     fn test_from_str_templates() {
         let fixture_dir = fixture_path("hooks");
@@ -467,6 +554,12 @@ pub mod tests {
                 "http://localhost/node/index/{{version}}/".to_string()
             ))
         );
+        assert_eq!(
+            node.checksum,
+            Some(tool::DistroHook::Template(
+                "http://localhost/node/checksum/{{version}}/".to_string()
+            ))
+        );
         // pnpm
         assert_eq!(
             pnpm.distro,
@@ -623,6 +716,13 @@ pub mod tests {
                 base_path: project_hooks_dir,
             })
         );
+        // The project doesn't define a node checksum hook, so it is inherited from the default
+        assert_eq!(
+            node.checksum,
+            Some(tool::DistroHook::Template(
+                "http://localhost/node/checksum/{{version}}/".to_string()
+            ))
+        );
         // pnpm
         assert_eq!(
             pnpm.distro,
@@ -670,6 +770,24 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_workspace_root_hooks() {
+        // A workspace member without a `volta` key uses the hooks of the workspace root
+        let member_dir = fixture_path("workspaces/pnpm/libs/core");
+        let project = Project::for_root(&member_dir).unwrap().unwrap();
+        let paths = project
+            .workspace_roots()
+            .map(|root| root.join(".volta").join("hooks.json"));
+
+        let hooks = HookConfig::from_paths(paths).unwrap();
+        assert_eq!(
+            hooks.node.unwrap().index,
+            Some(tool::MetadataHook::Prefix(
+                "http://localhost/workspace/node/index/".to_string()
+            ))
+        );
+    }
+
     #[test]
     fn test_from_paths() {
         let project_hooks_dir = fixture_path("hooks/project/.volta");
@@ -703,6 +821,13 @@ pub mod tests {
                 base_path: project_hooks_dir,
             })
         );
+        // The project doesn't define a node checksum hook, so it is inherited from the default
+        assert_eq!(
+            node.checksum,
+            Some(tool::DistroHook::Template(
+                "http://localhost/node/checksum/{{version}}/".to_string()
+            ))
+        );
         // pnpm
         assert_eq!(
             pnpm.distro,
@@ -1,9 +1,11 @@
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::path::Path;
 
 use super::RegistryFormat;
 use super::tool;
 use crate::error::{ErrorKind, Fallible, HookError, VoltaError};
+use crate::tool::node::NodeChannel;
 use crate::tool::{Node, Npm, Pnpm, Tool};
 use serde::{Deserialize, Serialize};
 
@@ -158,11 +160,24 @@ pub struct RawToolHooks<T: Tool> {
     pub distro: Option<RawResolveHook>,
     pub latest: Option<RawResolveHook>,
     pub index: Option<RawResolveHook>,
+    pub checksum: Option<RawResolveHook>,
+    pub nightly: Option<RawChannelHooks>,
+    pub rc: Option<RawChannelHooks>,
+    #[serde(rename = "v8-canary")]
+    pub v8_canary: Option<RawChannelHooks>,
 
     #[serde(skip)]
     phantom: PhantomData<T>,
 }
 
+/// The hooks for a prerelease channel of Node, such as `node.nightly`
+#[derive(Serialize, Deserialize)]
+pub struct RawChannelHooks {
+    pub distro: Option<RawResolveHook>,
+    pub index: Option<RawResolveHook>,
+    pub checksum: Option<RawResolveHook>,
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename = "yarn")]
 pub struct RawYarnHooks {
@@ -205,16 +220,49 @@ impl<T: Tool> RawToolHooks<T> {
             .index
             .map(|d| d.into_metadata_hook(base_dir))
             .transpose()?;
+        let checksum = self
+            .checksum
+            .map(|d| d.into_distro_hook(base_dir))
+            .transpose()?;
+
+        let mut channels = HashMap::new();
+        for (channel, hooks) in [
+            (NodeChannel::Nightly, self.nightly),
+            (NodeChannel::Rc, self.rc),
+            (NodeChannel::V8Canary, self.v8_canary),
+        ] {
+            if let Some(hooks) = hooks {
+                channels.insert(channel, hooks.into_tool_hooks(base_dir)?);
+            }
+        }
 
         Ok(super::ToolHooks {
             distro,
             latest,
             index,
+            checksum,
+            channels,
             phantom: PhantomData,
         })
     }
 }
 
+impl RawChannelHooks {
+    fn into_tool_hooks<T: Tool>(self, base_dir: &Path) -> Fallible<super::ToolHooks<T>> {
+        RawToolHooks {
+            distro: self.distro,
+            latest: None,
+            index: self.index,
+            checksum: self.checksum,
+            nightly: None,
+            rc: None,
+            v8_canary: None,
+            phantom: PhantomData,
+        }
+        .into_tool_hooks(base_dir)
+    }
+}
+
 impl RawYarnHooks {
     pub fn into_yarn_hooks(self, base_dir: &Path) -> Fallible<super::YarnHooks> {
         let distro = self
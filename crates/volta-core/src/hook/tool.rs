@@ -196,7 +196,7 @@ pub mod tests {
         assert_eq!(
             hook.resolve(&version, filename)
                 .expect("Could not resolve URL"),
-            format!("{}{}", prefix, filename)
+            format!("{prefix}{filename}")
         );
     }
 
@@ -209,8 +209,7 @@ pub mod tests {
 
         // tar.gz format has extra handling, to support a multi-part extension
         let expected = format!(
-            "http://localhost/node/{}/{}/{}/tar.gz/node-v1.0.0.tar.gz",
-            NODE_DISTRO_OS, NODE_DISTRO_ARCH, version
+            "http://localhost/node/{NODE_DISTRO_OS}/{NODE_DISTRO_ARCH}/{version}/tar.gz/node-v1.0.0.tar.gz"
         );
         assert_eq!(
             hook.resolve(&version, "node-v1.0.0.tar.gz")
@@ -220,8 +219,7 @@ pub mod tests {
 
         // zip is a standard extension
         let expected = format!(
-            "http://localhost/node/{}/{}/{}/zip/node-v1.0.0.zip",
-            NODE_DISTRO_OS, NODE_DISTRO_ARCH, version
+            "http://localhost/node/{NODE_DISTRO_OS}/{NODE_DISTRO_ARCH}/{version}/zip/node-v1.0.0.zip"
         );
         assert_eq!(
             hook.resolve(&version, "node-v1.0.0.zip")
@@ -238,7 +236,7 @@ pub mod tests {
 
         assert_eq!(
             hook.resolve(filename).expect("Could not resolve URL"),
-            format!("{}{}", prefix, filename)
+            format!("{prefix}{filename}")
         );
     }
 
@@ -247,10 +245,8 @@ pub mod tests {
         let hook = MetadataHook::Template(
             "http://localhost/node/{{os}}/{{arch}}/{{filename}}".to_string(),
         );
-        let expected = format!(
-            "http://localhost/node/{}/{}/index.json",
-            NODE_DISTRO_OS, NODE_DISTRO_ARCH
-        );
+        let expected =
+            format!("http://localhost/node/{NODE_DISTRO_OS}/{NODE_DISTRO_ARCH}/index.json");
 
         assert_eq!(
             hook.resolve("index.json").expect("Could not resolve URL"),
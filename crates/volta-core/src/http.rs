@@ -0,0 +1,512 @@
+//! Provides the HTTP client used for all of Volta's network traffic.
+//!
+//! Proxies are read from the `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY`, and `NO_PROXY` environment
+//! variables, following the curl conventions. In addition to the system certificate store, the
+//! certificate authorities in the PEM bundles named by `NODE_EXTRA_CA_CERTS` and
+//! `npm_config_cafile` are trusted, as they would be by Node and npm. The corresponding entries in
+//! `settings.json` take precedence over the environment.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Context, EnvironmentError, ErrorKind, Fallible};
+use crate::settings::Settings;
+use attohttpc::{ProxySettings, Session};
+use log::debug;
+use once_cell::unsync::OnceCell;
+use rustls_pki_types::CertificateDer;
+use rustls_pki_types::pem::PemObject;
+use url::Url;
+
+/// Environment variables that name PEM bundles of extra certificate authorities
+const CA_FILE_VARS: [&str; 2] = ["NODE_EXTRA_CA_CERTS", "npm_config_cafile"];
+
+/// Returns the HTTP client configured with the proxies and certificate authorities from the
+/// settings and the environment
+///
+/// The client is created on first use and shared by every request made with the same settings,
+/// so the certificate authorities are only loaded once per session.
+///
+/// # Errors
+///
+/// Returns an error if a proxy URL is invalid or a certificate bundle cannot be loaded.
+pub fn client(settings: &Settings) -> Fallible<Session> {
+    settings
+        .lazy_client()
+        .client
+        .get_or_try_init(|| client_with_env(settings, |name| env::var(name).ok()))
+        .cloned()
+}
+
+/// The HTTP client for a set of settings, created when it is first used
+///
+/// The client is derived entirely from the settings and the environment, so it is ignored when
+/// comparing settings.
+#[derive(Debug, Default)]
+pub struct LazyClient {
+    client: OnceCell<Session>,
+}
+
+impl PartialEq for LazyClient {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for LazyClient {}
+
+fn client_with_env<E>(settings: &Settings, env: E) -> Fallible<Session>
+where
+    E: Fn(&str) -> Option<String>,
+{
+    let mut client = Session::new();
+    client.proxy_settings(proxy_settings(settings, &env)?);
+
+    for file in ca_files(settings, &env) {
+        for certificate in load_certificates(&file)? {
+            client.add_root_certificate(certificate);
+        }
+    }
+
+    Ok(client)
+}
+
+fn proxy_settings<E>(settings: &Settings, env: &E) -> Fallible<ProxySettings>
+where
+    E: Fn(&str) -> Option<String>,
+{
+    let no_proxy = settings
+        .no_proxy()
+        .map(str::to_owned)
+        .or_else(|| env_var(env, "no_proxy"))
+        .unwrap_or_default();
+
+    // As with curl, a `NO_PROXY` of "*" disables proxies entirely
+    if no_proxy.trim() == "*" {
+        return Ok(ProxySettings::builder().build());
+    }
+
+    let all_proxy = env_var(env, "all_proxy");
+    let plain_proxy = settings
+        .http_proxy()
+        .map(str::to_owned)
+        .or_else(|| env_var(env, "http_proxy"))
+        .or_else(|| all_proxy.clone());
+    let secure_proxy = settings
+        .https_proxy()
+        .map(str::to_owned)
+        .or_else(|| env_var(env, "https_proxy"))
+        .or(all_proxy);
+
+    let mut builder = ProxySettings::builder()
+        .http_proxy(plain_proxy.as_deref().map(parse_proxy).transpose()?)
+        .https_proxy(secure_proxy.as_deref().map(parse_proxy).transpose()?);
+
+    for host in no_proxy
+        .split(',')
+        .map(|host| host.trim().trim_start_matches('.'))
+        .filter(|host| !host.is_empty())
+    {
+        builder = builder.add_no_proxy_host(host);
+    }
+
+    Ok(builder.build())
+}
+
+fn parse_proxy(url: &str) -> Fallible<Url> {
+    match Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {
+            debug!("Using proxy {parsed}");
+            Ok(parsed)
+        }
+        _ => Err(ErrorKind::Environment(EnvironmentError::InvalidProxy {
+            url: url.to_owned(),
+        })
+        .into()),
+    }
+}
+
+/// Determines the PEM bundles of extra certificate authorities, from the settings followed by
+/// the environment
+fn ca_files<E>(settings: &Settings, env: &E) -> Vec<PathBuf>
+where
+    E: Fn(&str) -> Option<String>,
+{
+    settings
+        .ca_file()
+        .map(Path::to_path_buf)
+        .into_iter()
+        .chain(
+            CA_FILE_VARS
+                .iter()
+                .filter_map(|name| env_var(env, name))
+                .map(PathBuf::from),
+        )
+        .collect()
+}
+
+fn load_certificates(file: &Path) -> Fallible<Vec<CertificateDer<'static>>> {
+    let certificates = CertificateDer::pem_file_iter(file)
+        .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+        .with_context(|| EnvironmentError::LoadCertificates {
+            file: file.to_owned(),
+        })?;
+
+    if certificates.is_empty() {
+        return Err(ErrorKind::Environment(EnvironmentError::LoadCertificates {
+            file: file.to_owned(),
+        })
+        .into());
+    }
+
+    debug!(
+        "Loaded {} certificate authorities from {}",
+        certificates.len(),
+        file.display()
+    );
+    Ok(certificates)
+}
+
+/// Reads an environment variable in either lowercase or uppercase, ignoring empty values
+fn env_var<E>(env: &E, name: &str) -> Option<String>
+where
+    E: Fn(&str) -> Option<String>,
+{
+    env(&name.to_ascii_lowercase())
+        .or_else(|| env(&name.to_ascii_uppercase()))
+        .filter(|value| !value.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::thread::{self, JoinHandle};
+
+    use attohttpc::Response;
+    use rustls::{ServerConfig, ServerConnection, StreamOwned};
+    use rustls_pki_types::PrivateKeyDer;
+
+    fn fixture_path(fixture: &str) -> PathBuf {
+        let mut fixture_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        fixture_path.push("fixtures");
+        fixture_path.push("tls");
+        fixture_path.push(fixture);
+        fixture_path
+    }
+
+    fn settings(json: &str) -> Settings {
+        serde_json::from_str(json).expect("Could not parse settings")
+    }
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| ((*name).to_owned(), (*value).to_owned()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    fn proxy_for(proxies: &ProxySettings, url: &str) -> Option<String> {
+        proxies
+            .for_url(&Url::parse(url).unwrap())
+            .map(ToString::to_string)
+    }
+
+    fn tls_config() -> Arc<ServerConfig> {
+        let certificates = CertificateDer::pem_file_iter(fixture_path("server.pem"))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let key = PrivateKeyDer::from_pem_file(fixture_path("server.key")).unwrap();
+
+        Arc::new(
+            ServerConfig::builder()
+                .with_no_client_auth()
+                .with_single_cert(certificates, key)
+                .unwrap(),
+        )
+    }
+
+    /// Reads the head of an HTTP request, returning the request line
+    fn read_request<S: std::io::Read>(stream: S) -> String {
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 2 {
+            line.clear();
+        }
+        request_line.trim_end().to_owned()
+    }
+
+    fn respond<S: Write>(mut stream: S, body: &str) {
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        stream.flush().unwrap();
+    }
+
+    /// Serves a single request over TLS, returning the port and the request line
+    fn serve_tls() -> (u16, JoinHandle<Option<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut tls = StreamOwned::new(ServerConnection::new(tls_config()).unwrap(), stream);
+            // The handshake fails if the client does not trust the certificate
+            tls.conn.complete_io(&mut tls.sock).ok()?;
+
+            let request_line = read_request(&mut tls);
+            respond(&mut tls, "secure");
+            tls.conn.send_close_notify();
+            tls.conn.complete_io(&mut tls.sock).ok();
+            Some(request_line)
+        });
+
+        (port, handle)
+    }
+
+    /// Stands in for an intercepting corporate proxy: CONNECT tunnels are terminated by the proxy
+    /// itself with a certificate from the test CA, and plain requests are answered directly.
+    fn serve_proxy() -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let connect = read_request(&mut stream);
+            if !connect.starts_with("CONNECT ") {
+                respond(&mut stream, "proxied");
+                return vec![connect];
+            }
+
+            stream
+                .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+                .unwrap();
+            let mut tls = StreamOwned::new(ServerConnection::new(tls_config()).unwrap(), stream);
+            tls.conn.complete_io(&mut tls.sock).unwrap();
+
+            let request_line = read_request(&mut tls);
+            respond(&mut tls, "intercepted");
+            tls.conn.send_close_notify();
+            tls.conn.complete_io(&mut tls.sock).ok();
+            vec![connect, request_line]
+        });
+
+        (url, handle)
+    }
+
+    fn get(client: &Session, url: &str) -> attohttpc::Result<String> {
+        client
+            .get(url)
+            .send()
+            .and_then(Response::error_for_status)
+            .and_then(Response::text)
+    }
+
+    #[test]
+    fn test_proxy_settings_from_env() {
+        let proxies = proxy_settings(
+            &Settings::default(),
+            &env(&[
+                ("HTTPS_PROXY", "http://secure.example.com:8080"),
+                ("all_proxy", "http://all.example.com:3128"),
+                ("NO_PROXY", "localhost, .internal.example.com"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            proxy_for(&proxies, "https://registry.npmjs.org/npm"),
+            Some("http://secure.example.com:8080/".into())
+        );
+        assert_eq!(
+            proxy_for(&proxies, "http://nodejs.org/dist/index.json"),
+            Some("http://all.example.com:3128/".into())
+        );
+        assert_eq!(proxy_for(&proxies, "https://localhost:8443/npm"), None);
+        assert_eq!(
+            proxy_for(&proxies, "https://mirror.internal.example.com/npm"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_proxy_settings_take_precedence() {
+        let proxies = proxy_settings(
+            &settings(r#"{ "httpsProxy": "http://settings.example.com:8080", "noProxy": "" }"#),
+            &env(&[
+                ("HTTPS_PROXY", "http://env.example.com:8080"),
+                ("NO_PROXY", "registry.npmjs.org"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            proxy_for(&proxies, "https://registry.npmjs.org/npm"),
+            Some("http://settings.example.com:8080/".into())
+        );
+        assert_eq!(proxy_for(&proxies, "http://nodejs.org/dist"), None);
+    }
+
+    #[test]
+    fn test_proxy_settings_disabled() {
+        let proxies = proxy_settings(
+            &settings(r#"{ "httpsProxy": "http://settings.example.com:8080" }"#),
+            &env(&[("no_proxy", "*")]),
+        )
+        .unwrap();
+
+        assert_eq!(proxy_for(&proxies, "https://registry.npmjs.org/npm"), None);
+    }
+
+    #[test]
+    fn test_invalid_proxy() {
+        let error = proxy_settings(
+            &Settings::default(),
+            &env(&[("HTTPS_PROXY", "socks5://proxy.example.com")]),
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::Environment(EnvironmentError::InvalidProxy { url })
+                if url == "socks5://proxy.example.com"
+        ));
+    }
+
+    #[test]
+    fn test_ca_files() {
+        let files = ca_files(
+            &settings(r#"{ "caFile": "/etc/volta/ca.pem" }"#),
+            &env(&[
+                ("npm_config_cafile", "/etc/npm/ca.pem"),
+                ("NODE_EXTRA_CA_CERTS", "/etc/node/ca.pem"),
+            ]),
+        );
+
+        assert_eq!(
+            files,
+            [
+                PathBuf::from("/etc/volta/ca.pem"),
+                PathBuf::from("/etc/node/ca.pem"),
+                PathBuf::from("/etc/npm/ca.pem"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_certificates() {
+        assert_eq!(load_certificates(&fixture_path("ca.pem")).unwrap().len(), 1);
+
+        // Private keys are not certificates
+        assert!(load_certificates(&fixture_path("server.key")).is_err());
+        assert!(load_certificates(&fixture_path("missing.pem")).is_err());
+    }
+
+    #[test]
+    fn test_client_trusts_extra_ca() {
+        let (port, server) = serve_tls();
+        let ca_file = fixture_path("ca.pem");
+        let client = client_with_env(
+            &Settings::default(),
+            env(&[("NODE_EXTRA_CA_CERTS", ca_file.to_str().unwrap())]),
+        )
+        .unwrap();
+
+        let body = get(&client, &format!("https://localhost:{port}/npm")).unwrap();
+        assert_eq!(body, "secure");
+        assert_eq!(server.join().unwrap().unwrap(), "GET /npm HTTP/1.1");
+    }
+
+    #[test]
+    fn test_client_rejects_unknown_ca() {
+        let (port, server) = serve_tls();
+        let client = client_with_env(&Settings::default(), env(&[])).unwrap();
+
+        assert!(get(&client, &format!("https://localhost:{port}/npm")).is_err());
+        assert_eq!(server.join().unwrap(), None);
+    }
+
+    #[test]
+    fn test_client_uses_intercepting_proxy() {
+        let (proxy_url, proxy) = serve_proxy();
+        let client = client_with_env(
+            &settings(&format!(
+                r#"{{ "httpsProxy": "{proxy_url}", "caFile": "{}" }}"#,
+                fixture_path("ca.pem").display()
+            )),
+            env(&[]),
+        )
+        .unwrap();
+
+        // The registry host does not resolve, so the request must go through the proxy
+        let body = get(&client, "https://registry.test/npm").unwrap();
+        assert_eq!(body, "intercepted");
+        assert_eq!(
+            proxy.join().unwrap(),
+            ["CONNECT registry.test:443 HTTP/1.1", "GET /npm HTTP/1.1"]
+        );
+    }
+
+    #[test]
+    fn test_client_uses_http_proxy() {
+        let (proxy_url, proxy) = serve_proxy();
+        let client =
+            client_with_env(&Settings::default(), env(&[("HTTP_PROXY", &proxy_url)])).unwrap();
+
+        let body = get(&client, "http://registry.test/npm").unwrap();
+        assert_eq!(body, "proxied");
+        assert_eq!(
+            proxy.join().unwrap(),
+            ["GET http://registry.test/npm HTTP/1.1"]
+        );
+    }
+
+    #[test]
+    fn test_client_bypasses_proxy() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/npm", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let request_line = read_request(&stream);
+            respond(stream, "direct");
+            request_line
+        });
+        let client = client_with_env(
+            &Settings::default(),
+            env(&[
+                ("HTTP_PROXY", "http://unreachable.test:3128"),
+                ("NO_PROXY", "127.0.0.1"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(get(&client, &url).unwrap(), "direct");
+        assert_eq!(server.join().unwrap(), "GET /npm HTTP/1.1");
+    }
+
+    #[test]
+    fn test_client_created_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let ca_file = dir.path().join("ca.pem");
+        std::fs::copy(fixture_path("ca.pem"), &ca_file).unwrap();
+        let settings = settings(&format!(r#"{{ "caFile": "{}" }}"#, ca_file.display()));
+
+        assert!(client(&settings).is_ok());
+
+        // The certificate authorities are not loaded again
+        std::fs::remove_file(&ca_file).unwrap();
+        assert!(client(&settings).is_ok());
+        assert!(client_with_env(&settings, env(&[])).is_err());
+    }
+}
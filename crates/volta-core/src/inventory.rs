@@ -6,9 +6,10 @@ use std::ffi::OsStr;
 use std::path::Path;
 
 use crate::error::{Context, ErrorKind, Fallible, FilesystemError};
-use crate::fs::read_dir_eager;
+use crate::fs::{disk_usage, read_dir_eager};
 use crate::layout::volta_home;
-use crate::tool::PackageConfig;
+use crate::tool::package::package_image;
+use crate::tool::{ImageKind, PackageConfig};
 use crate::version::parse;
 use log::debug;
 use nodejs_semver::Version;
@@ -36,6 +37,32 @@ pub fn node_versions() -> Fallible<BTreeSet<Version>> {
     volta_home().and_then(|home| read_versions(home.node_image_root_dir()))
 }
 
+/// Determines the disk space used by a version of a tool, including both its image and the
+/// archives kept in the inventory
+///
+/// # Errors
+///
+/// Returns an error if the Volta home directory cannot be determined.
+pub fn tool_disk_usage(kind: ImageKind, version: &Version) -> Fallible<u64> {
+    let image = disk_usage(&kind.image_dir(&version.to_string())?);
+    let archives: u64 = kind
+        .inventory_files(version)?
+        .iter()
+        .map(|file| disk_usage(file))
+        .sum();
+
+    Ok(image + archives)
+}
+
+/// Determines the disk space used by the image of a globally installed version of a package
+///
+/// # Errors
+///
+/// Returns an error if the Volta home directory cannot be determined.
+pub fn package_disk_usage(name: &str, version: &Version) -> Fallible<u64> {
+    package_image(name, version).map(|image| disk_usage(&image))
+}
+
 /// Checks if a given npm version image is available on the local machine
 ///
 /// # Errors
@@ -1,10 +1,13 @@
 //! The main implementation crate for the core of Volta.
 
+use std::env;
+
 mod command;
 pub mod error;
 pub mod event;
 pub mod fs;
 mod hook;
+pub mod http;
 pub mod inventory;
 pub mod layout;
 pub mod log;
@@ -13,12 +16,41 @@ pub mod platform;
 pub mod project;
 pub mod run;
 pub mod session;
+pub mod settings;
 pub mod shim;
 pub mod signal;
 pub mod style;
 pub mod sync;
 pub mod tool;
 pub mod toolchain;
+pub mod usage;
 pub mod version;
 
 const VOLTA_FEATURE_PNPM: &str = "VOLTA_FEATURE_PNPM";
+const VOLTA_OFFLINE: &str = "VOLTA_OFFLINE";
+
+/// Determines whether a boolean environment variable is enabled
+///
+/// A variable is enabled when it is set to any value other than an empty string, `0` or `false`.
+pub(crate) fn env_flag(name: &str) -> bool {
+    env::var_os(name).is_some_and(|value| is_enabled(&value.to_string_lossy()))
+}
+
+fn is_enabled(value: &str) -> bool {
+    !(value.is_empty() || value == "0" || value.eq_ignore_ascii_case("false"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_enabled;
+
+    #[test]
+    fn test_enabled_values() {
+        for value in ["1", "true", "yes"] {
+            assert!(is_enabled(value), "{value} should enable the flag");
+        }
+        for value in ["", "0", "false", "FALSE"] {
+            assert!(!is_enabled(value), "{value} should not enable the flag");
+        }
+    }
+}
@@ -2,12 +2,16 @@ use std::env;
 use std::io::Write;
 use std::path::PathBuf;
 use std::process::{Child, Stdio};
+use std::time::Duration;
 
+use attohttpc::{RequestBuilder, Response};
 use log::debug;
 use tempfile::NamedTempFile;
 
 use crate::command::create_command;
 use crate::event::Event;
+use crate::http;
+use crate::settings::Settings;
 
 /// Send event to the spawned command process
 // if hook command is not configured, this is not called
@@ -29,6 +33,36 @@ pub fn send_events(command: &str, events: &[Event]) {
     }
 }
 
+/// Timeout for establishing a connection to the events URL
+const PUBLISH_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Timeout for the whole request to the events URL, once connected
+const PUBLISH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Send events to the configured URL as a JSON POST request
+// if hook url is not configured, this is not called
+pub fn post_events(url: &str, events: &[Event], settings: &Settings) {
+    let client = match http::client(settings) {
+        Ok(client) => client,
+        Err(error) => {
+            debug!("Could not publish events to '{url}': {error}");
+            return;
+        }
+    };
+
+    let result = client
+        .post(url)
+        .connect_timeout(PUBLISH_CONNECT_TIMEOUT)
+        .timeout(PUBLISH_TIMEOUT)
+        .json(&events)
+        .and_then(RequestBuilder::send)
+        .and_then(Response::error_for_status);
+
+    if let Err(error) = result {
+        debug!("Could not publish events to '{url}': {error:?}");
+    }
+}
+
 // Write the events JSON to a file in the temporary directory
 fn write_events_file(events_json: &str) -> Option<PathBuf> {
     match NamedTempFile::new() {
@@ -66,6 +66,9 @@ impl RuntimeImage {
             .with_context(build_path_error)
     }
 
+    /// # Errors
+    ///
+    /// Returns an error if the new `PATH` cannot be constructed.
     #[cfg(test)]
     pub fn path_from(&self, base_path: &str) -> Fallible<OsString> {
         self.path_with_base(&envoy::Var::from(base_path))
@@ -1,8 +1,11 @@
 use std::fmt;
 
 use crate::error::{EnvironmentError, ErrorKind, Fallible};
+use crate::project::{FallbackSource, PackageManagerField, Project};
 use crate::session::Session;
-use crate::tool::{Node, Npm, Pnpm, Yarn};
+use crate::tool::package::PackageManager;
+use crate::tool::{Node, Npm, Pnpm, Yarn, resolve_project_pins};
+use crate::usage;
 use nodejs_semver::Version;
 
 mod image;
@@ -30,6 +33,9 @@ pub enum Source {
 
     /// Represents a version from the command line (via `volta run`)
     CommandLine,
+
+    /// Represents a version from a fallback source of a project, such as `.nvmrc`
+    Fallback(FallbackSource),
 }
 
 impl fmt::Display for Source {
@@ -39,6 +45,7 @@ impl fmt::Display for Source {
             Self::Project => write!(f, "project"),
             Self::Binary => write!(f, "binary"),
             Self::CommandLine => write!(f, "command-line"),
+            Self::Fallback(source) => write!(f, "{source}"),
         }
     }
 }
@@ -76,6 +83,13 @@ impl<T> Sourced<T> {
             source: Source::CommandLine,
         }
     }
+
+    pub const fn with_fallback(value: T, source: FallbackSource) -> Self {
+        Self {
+            value,
+            source: Source::Fallback(source),
+        }
+    }
 }
 
 impl<T> Sourced<T> {
@@ -183,6 +197,18 @@ impl PlatformSpec {
         }
     }
 
+    /// Convert this `PlatformSpec` into a Platform with all sources set to the given `Fallback`
+    #[must_use]
+    pub fn as_fallback(&self, source: FallbackSource) -> Platform {
+        let sourced = |version: &Version| Sourced::with_fallback(version.clone(), source);
+        Platform {
+            node: sourced(&self.node),
+            npm: self.npm.as_ref().map(sourced),
+            pnpm: self.pnpm.as_ref().map(sourced),
+            yarn: self.yarn.as_ref().map(sourced),
+        }
+    }
+
     /// Convert this `PlatformSpec` into a Platform with all sources set to `Binary`
     pub fn as_binary(&self) -> Platform {
         Platform {
@@ -248,13 +274,37 @@ impl Platform {
     ///   - If there is no pnpm/Yarn version in the project platform, we pull
     ///     pnpm/Yarn from the default platform if available, and merge the two
     ///     platforms into a final one
+    /// - If the project doesn't pin a platform, but one of the fallback sources in the settings
+    ///   (such as `.nvmrc`) has a Node version, then we use that version in the same way
     /// - If there is no Project platform, then we use the user Default Platform
+    /// - In any case, if the project has a `packageManager` field, then its package manager
+    ///   version is used unless the `volta` key of the project pins one
+    ///
+    /// Using a Project platform records the project as recently used, so that `volta prune` keeps
+    /// the versions it pins. Ranges and tags in the project that aren't in its lockfile yet are
+    /// resolved and locked first.
     ///
     /// # Errors
     ///
     /// Returns an error if the platform cannot be determined.
     pub fn current(session: &mut Session) -> Fallible<Option<Self>> {
-        if let Some(mut platform) = session.project_platform()?.map(PlatformSpec::as_project) {
+        resolve_project_pins(session, false)?;
+
+        let project_platform = match session.project_platform()? {
+            Some(platform) => Some(platform.as_project()),
+            None => session
+                .fallback_platform()?
+                .map(|fallback| fallback.platform.as_fallback(fallback.source)),
+        };
+
+        let mut platform = if let Some(mut platform) = project_platform {
+            if let Some(root) = session
+                .project()?
+                .and_then(|project| project.manifest_file().parent())
+            {
+                usage::record_project(root);
+            }
+
             if platform.pnpm.is_none() {
                 platform.pnpm = session
                     .default_platform()?
@@ -269,24 +319,56 @@ impl Platform {
                     .map(Sourced::with_default);
             }
 
-            Ok(Some(platform))
+            Some(platform)
         } else {
-            Ok(session.default_platform()?.map(PlatformSpec::as_default))
+            session.default_platform()?.map(PlatformSpec::as_default)
+        };
+
+        if let (Some(platform), Some(field)) = (
+            platform.as_mut(),
+            session.project()?.and_then(Project::package_manager),
+        ) {
+            platform.use_package_manager(field);
+        }
+
+        Ok(platform)
+    }
+
+    /// Uses the package manager from the `packageManager` field of the project, unless the
+    /// platform already has a version of it from the project
+    fn use_package_manager(&mut self, field: &PackageManagerField) {
+        let current = match field.manager {
+            PackageManager::Npm => &mut self.npm,
+            PackageManager::Pnpm => &mut self.pnpm,
+            PackageManager::Yarn => &mut self.yarn,
+        };
+
+        if !matches!(
+            current,
+            Some(Sourced {
+                source: Source::Project,
+                ..
+            })
+        ) {
+            *current = Some(Sourced::with_project(field.version.clone()));
         }
     }
 
     /// Check out a `Platform` into a fully-realized `RuntimeImage`
     ///
-    /// This will ensure that all necessary tools are fetched and available for execution
+    /// This will ensure that all necessary tools are fetched and available for execution, and
+    /// record them as used in the last-used index
     ///
     /// # Errors
     ///
     /// Returns an error if any tools cannot be fetched.
     pub fn checkout(self, session: &mut Session) -> Fallible<RuntimeImage> {
         Node::new(self.node.value.clone()).ensure_fetched(session)?;
+        usage::record_tool("node", &self.node.value);
 
         if let Some(Sourced { value: version, .. }) = &self.npm {
             Npm::new(version.clone()).ensure_fetched(session)?;
+            usage::record_tool("npm", version);
         }
 
         // Only force download of the pnpm version if the pnpm feature flag is set. If it isn't,
@@ -296,10 +378,12 @@ impl Platform {
             && let Some(Sourced { value: version, .. }) = &self.pnpm
         {
             Pnpm::new(version.clone()).ensure_fetched(session)?;
+            usage::record_tool("pnpm", version);
         }
 
         if let Some(Sourced { value: version, .. }) = &self.yarn {
             Yarn::new(version.clone()).ensure_fetched(session)?;
+            usage::record_tool("yarn", version);
         }
 
         Ok(RuntimeImage {
@@ -32,6 +32,9 @@ impl System {
         new_path.join().with_context(build_path_error)
     }
 
+    /// # Errors
+    ///
+    /// Returns an error if the new `PATH` cannot be constructed.
     #[cfg(test)]
     pub fn path_from(base_path: &str) -> Fallible<OsString> {
         Self::path_with_base(&envoy::Var::from(base_path))
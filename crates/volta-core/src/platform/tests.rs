@@ -32,6 +32,7 @@ fn build_test_path() -> String {
 }
 
 #[test]
+#[allow(clippy::similar_names)]
 fn test_image_path() {
     #[cfg(unix)]
     let path_delimiter = ":";
@@ -0,0 +1,390 @@
+//! Provides the fallback sources of a Node version for projects that don't pin one, such as the
+//! `.nvmrc` file used by nvm or the `engines.node` field of `package.json`
+//!
+//! The sources are opt-in, with the `fallbackSources` setting. A range or tag is resolved to an
+//! exact version, and the resolution is reused for a few hours so that running a tool doesn't
+//! read the whole index of Node versions every time.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use super::Project;
+use crate::error::{Context, Fallible, FilesystemError, PlatformError};
+use crate::fs::{create_staging_file, ensure_containing_dir_exists, read_file};
+use crate::layout::volta_home;
+use crate::platform::PlatformSpec;
+use crate::session::Session;
+use crate::tool::node;
+use crate::version::{Tag, VersionSpec, parse};
+use log::debug;
+use nodejs_semver::Version;
+use serde::{Deserialize, Serialize};
+
+/// How long the resolution of a range or tag is reused before resolving it again
+const RESOLUTION_MAX_AGE: Duration = Duration::from_hours(4);
+
+/// A source of the Node version for projects that don't pin one
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum FallbackSource {
+    /// The `.nvmrc` file used by nvm
+    #[serde(rename = ".nvmrc")]
+    Nvmrc,
+
+    /// The `.node-version` file used by nodenv, fnm and others
+    #[serde(rename = ".node-version")]
+    NodeVersion,
+
+    /// The `engines.node` field of `package.json`
+    #[serde(rename = "engines.node")]
+    Engines,
+}
+
+impl fmt::Display for FallbackSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Nvmrc => ".nvmrc",
+            Self::NodeVersion => ".node-version",
+            Self::Engines => "engines.node",
+        })
+    }
+}
+
+/// The platform of a project that doesn't pin one, from the first fallback source with a version
+#[cfg_attr(test, derive(Debug))]
+pub struct FallbackPlatform {
+    /// The source of the Node version
+    pub source: FallbackSource,
+    /// The file containing the Node version, which is `package.json` for `engines.node`
+    pub file: PathBuf,
+    /// The platform, with the Node version resolved to an exact one
+    pub platform: PlatformSpec,
+}
+
+impl Project {
+    /// Returns the platform from the fallback sources, if it has been resolved
+    #[must_use]
+    pub fn fallback(&self) -> Option<&FallbackPlatform> {
+        self.fallback.get().and_then(Option::as_ref)
+    }
+
+    /// Resolves the platform from the fallback sources in the settings, unless the project pins
+    /// its own platform
+    pub(crate) fn resolve_fallback(
+        &self,
+        session: &Session,
+    ) -> Fallible<Option<&FallbackPlatform>> {
+        if self.platform.is_some() {
+            return Ok(None);
+        }
+
+        self.fallback
+            .get_or_try_init(|| resolve(self, session))
+            .map(Option::as_ref)
+    }
+
+    /// Finds the raw Node version in the first of the sources that has one, along with the file
+    /// it is from
+    fn fallback_version(
+        &self,
+        sources: &[FallbackSource],
+    ) -> Fallible<Option<(FallbackSource, PathBuf, String)>> {
+        let Some(root) = self.manifest_file.parent() else {
+            return Ok(None);
+        };
+
+        for &source in sources {
+            let (file, version) = match source {
+                FallbackSource::Engines => (self.manifest_file.clone(), self.engines_node.clone()),
+                FallbackSource::Nvmrc | FallbackSource::NodeVersion => {
+                    let file = root.join(source.to_string());
+                    let version = read_file(&file)
+                        .with_context(|| FilesystemError::ReadVersionFile { file: file.clone() })?;
+                    (file, version)
+                }
+            };
+
+            if let Some(version) = version.filter(|version| !version.trim().is_empty()) {
+                return Ok(Some((source, file, version)));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+fn resolve(project: &Project, session: &Session) -> Fallible<Option<FallbackPlatform>> {
+    let sources = session.settings()?.fallback_sources();
+    let Some((source, file, raw)) = project.fallback_version(sources)? else {
+        return Ok(None);
+    };
+
+    let matching = parse_version(&raw).ok_or_else(|| PlatformError::ParseFallbackVersion {
+        file: file.clone(),
+        version: raw.trim().to_owned(),
+    })?;
+    let node = resolve_cached(matching, session)?;
+    debug!("Using Node {node} from {}", file.display());
+
+    Ok(Some(FallbackPlatform {
+        source,
+        file,
+        platform: PlatformSpec {
+            node,
+            npm: None,
+            pnpm: None,
+            yarn: None,
+        },
+    }))
+}
+
+/// Parses a Node version in the forms supported by nvm
+///
+/// Only the first line is used, ignoring comments, and the `node` and `stable` aliases are the
+/// newest version.
+fn parse_version(raw: &str) -> Option<VersionSpec> {
+    let version = raw
+        .lines()
+        .map(|line| {
+            line.split_once('#')
+                .map_or(line, |(version, _)| version)
+                .trim()
+        })
+        .find(|line| !line.is_empty())?;
+
+    match version {
+        "node" | "stable" => Some(VersionSpec::Tag(Tag::Latest)),
+        _ => match version.parse() {
+            // Other aliases, such as `iojs` or the user's own nvm aliases, have no equivalent
+            Ok(VersionSpec::Tag(Tag::Custom(_))) | Err(_) => None,
+            Ok(matching) => Some(matching),
+        },
+    }
+}
+
+/// Resolves a Node version to an exact one, reusing a recent resolution of the same range or tag
+fn resolve_cached(matching: VersionSpec, session: &Session) -> Fallible<Version> {
+    if let VersionSpec::Exact(version) = matching {
+        return Ok(version);
+    }
+
+    let key = matching.to_string();
+    let mut cache = ResolutionCache::load();
+    if let Some(version) = cache.get(&key, SystemTime::now()) {
+        debug!("Using the recent resolution of Node {key} to {version}");
+        return Ok(version);
+    }
+
+    let offline = session.offline();
+    let version = node::resolve(matching, session)?;
+
+    // Offline resolution only considers the local inventory, so it isn't reused
+    if !offline {
+        cache.insert(key, &version, SystemTime::now());
+        cache.save();
+    }
+
+    Ok(version)
+}
+
+/// The recent resolutions of ranges and tags to exact Node versions, keyed by range or tag
+#[derive(Default, Deserialize, Serialize)]
+struct ResolutionCache(HashMap<String, Resolution>);
+
+#[derive(Deserialize, Serialize)]
+struct Resolution {
+    version: String,
+    expires: String,
+}
+
+impl Resolution {
+    fn is_fresh(&self, now: SystemTime) -> bool {
+        httpdate::parse_http_date(&self.expires).is_ok_and(|expires| now < expires)
+    }
+}
+
+impl ResolutionCache {
+    /// Reads the cache, which is only an optimization, so it is empty if it can't be read
+    fn load() -> Self {
+        volta_home()
+            .ok()
+            .and_then(|home| read_file(home.fallback_cache_file()).ok().flatten())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn get(&self, key: &str, now: SystemTime) -> Option<Version> {
+        self.0
+            .get(key)
+            .filter(|resolution| resolution.is_fresh(now))
+            .and_then(|resolution| parse(&resolution.version).ok())
+    }
+
+    /// Records a resolution, dropping any which have expired
+    fn insert(&mut self, key: String, version: &Version, now: SystemTime) {
+        self.0.retain(|_, resolution| resolution.is_fresh(now));
+        self.0.insert(
+            key,
+            Resolution {
+                version: version.to_string(),
+                expires: httpdate::fmt_http_date(now + RESOLUTION_MAX_AGE),
+            },
+        );
+    }
+
+    /// Writes the cache, ignoring any failure since the versions can always be resolved again
+    fn save(&self) {
+        if let Err(error) = self.write() {
+            debug!("Could not write the cache of Node version resolutions.\n{error}");
+        }
+    }
+
+    fn write(&self) -> Fallible<()> {
+        let cache_file = volta_home()?.fallback_cache_file();
+        let staging = create_staging_file()?;
+
+        serde_json::to_writer(staging.as_file(), self).with_context(|| {
+            FilesystemError::WriteMetadataCache {
+                file: staging.path().to_path_buf(),
+            }
+        })?;
+
+        ensure_containing_dir_exists(&cache_file).with_context(|| {
+            FilesystemError::ContainingDir {
+                path: cache_file.to_owned(),
+            }
+        })?;
+        staging
+            .persist(cache_file)
+            .with_context(|| FilesystemError::WriteMetadataCache {
+                file: cache_file.to_owned(),
+            })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::parse_requirements;
+
+    fn fixture_path(fixture_dir: &str) -> PathBuf {
+        let mut fixture_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        fixture_path.push("fixtures");
+        fixture_path.push(fixture_dir);
+        fixture_path
+    }
+
+    fn version(version: &str) -> Version {
+        Version::parse(version).unwrap()
+    }
+
+    #[test]
+    fn parses_nvm_versions() {
+        assert_eq!(
+            parse_version("v20.19.2\n"),
+            Some(VersionSpec::Exact(version("20.19.2")))
+        );
+        assert_eq!(
+            parse_version("# The version for CI\n\n22.16.0 # pinned\n"),
+            Some(VersionSpec::Exact(version("22.16.0")))
+        );
+        assert_eq!(
+            parse_version("20"),
+            Some(VersionSpec::Semver(parse_requirements("20").unwrap()))
+        );
+        assert_eq!(
+            parse_version(">=18 <21"),
+            Some(VersionSpec::Semver(parse_requirements(">=18 <21").unwrap()))
+        );
+        assert_eq!(parse_version("lts/*"), Some(VersionSpec::Tag(Tag::Lts)));
+        assert_eq!(
+            parse_version("lts/iron"),
+            Some(VersionSpec::Tag(Tag::LtsCodename("iron".into())))
+        );
+        assert_eq!(parse_version("node"), Some(VersionSpec::Tag(Tag::Latest)));
+        assert_eq!(parse_version("stable"), Some(VersionSpec::Tag(Tag::Latest)));
+    }
+
+    #[test]
+    fn rejects_unsupported_versions() {
+        assert_eq!(parse_version("iojs"), None);
+        assert_eq!(parse_version("my-alias"), None);
+        assert_eq!(parse_version("# only a comment"), None);
+    }
+
+    #[test]
+    fn finds_first_source_with_version() {
+        let root = fixture_path("fallback");
+        let project = Project::for_root(&root).unwrap().unwrap();
+        let found = |sources: &[FallbackSource]| {
+            project
+                .fallback_version(sources)
+                .unwrap()
+                .map(|(source, file, version)| (source, file, version.trim().to_owned()))
+        };
+
+        assert_eq!(
+            found(&[FallbackSource::Nvmrc, FallbackSource::Engines]),
+            Some((
+                FallbackSource::Nvmrc,
+                root.join(".nvmrc"),
+                "lts/iron".to_owned()
+            ))
+        );
+        assert_eq!(
+            found(&[FallbackSource::NodeVersion, FallbackSource::Nvmrc]),
+            Some((
+                FallbackSource::NodeVersion,
+                root.join(".node-version"),
+                "20.19.2".to_owned()
+            ))
+        );
+        assert_eq!(
+            found(&[FallbackSource::Engines]),
+            Some((
+                FallbackSource::Engines,
+                root.join("package.json"),
+                ">=20".to_owned()
+            ))
+        );
+        assert_eq!(found(&[]), None);
+    }
+
+    #[test]
+    fn skips_missing_sources() {
+        let root = fixture_path("basic");
+        let project = Project::for_root(&root).unwrap().unwrap();
+
+        assert!(
+            project
+                .fallback_version(&[FallbackSource::Nvmrc, FallbackSource::NodeVersion])
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn reuses_fresh_resolutions() {
+        let now = SystemTime::now();
+        let mut cache = ResolutionCache::default();
+        cache.insert("lts/iron".into(), &version("20.19.2"), now);
+
+        assert_eq!(cache.get("lts/iron", now), Some(version("20.19.2")));
+        assert_eq!(cache.get("lts/jod", now), None);
+        assert_eq!(cache.get("lts/iron", now + RESOLUTION_MAX_AGE), None);
+    }
+
+    #[test]
+    fn drops_expired_resolutions() {
+        let now = SystemTime::now();
+        let mut cache = ResolutionCache::default();
+        cache.insert("lts/iron".into(), &version("20.19.2"), now);
+        cache.insert("^22".into(), &version("22.16.0"), now + RESOLUTION_MAX_AGE);
+
+        assert!(!cache.0.contains_key("lts/iron"));
+        assert!(cache.0.contains_key("^22"));
+    }
+}
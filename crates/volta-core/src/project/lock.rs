@@ -0,0 +1,346 @@
+//! Provides the project lockfile, `volta.lock`, which records the exact versions that the ranges
+//! and tags in the `volta` key of a manifest resolved to, along with the checksums of their
+//! archives, so that every checkout of the project uses the same versions
+
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::io::{ErrorKind as IoErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use super::ManifestKey;
+use crate::error::{Context, ErrorKind, Fallible, FilesystemError, PackageError, VersionError};
+use crate::version::{VersionSpec, parse, version_serde};
+use nodejs_semver::Version;
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+
+/// The name of the lockfile, which sits beside the manifest with the `volta` key it resolves
+pub const LOCKFILE_NAME: &str = "volta.lock";
+
+/// A version of a tool in the `volta` key of a manifest
+pub(super) enum Pin {
+    Exact(Version),
+    Requested(RequestedPin),
+}
+
+impl Pin {
+    /// Parses a version in the `volta` key, which is either exact or a range or tag to resolve
+    pub(super) fn parse(key: ManifestKey, value: String, manifest: &Path) -> Fallible<Self> {
+        match parse(&value) {
+            Ok(version) => Ok(Self::Exact(version)),
+            // The bundled version of npm depends on Node, so it can't be locked
+            Err(error) if value == "bundled" => Err(error),
+            Err(error) => match value.parse() {
+                Ok(VersionSpec::Semver(_) | VersionSpec::Tag(_)) => {
+                    Ok(Self::Requested(RequestedPin {
+                        key,
+                        requested: value,
+                        lock_file: manifest.with_file_name(LOCKFILE_NAME),
+                        locked: None,
+                        checksums: BTreeMap::new(),
+                    }))
+                }
+                _ => Err(error),
+            },
+        }
+    }
+}
+
+/// A range or tag in the `volta` key of a manifest, such as `"node": "^20.11"`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestedPin {
+    pub key: ManifestKey,
+    /// The range or tag, as written in the manifest
+    pub requested: String,
+    /// The lockfile beside the manifest
+    pub lock_file: PathBuf,
+    /// The exact version in the lockfile, if this range or tag has been resolved
+    pub locked: Option<Version>,
+    /// The checksums in the lockfile for the archives of the locked version, by file name
+    pub checksums: BTreeMap<String, String>,
+}
+
+impl RequestedPin {
+    /// Parses the range or tag, to resolve it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range or tag is invalid.
+    pub fn matching(&self) -> Fallible<VersionSpec> {
+        self.requested.parse().with_context(|| {
+            ErrorKind::Version(VersionError::ParseFailed {
+                version: self.requested.clone(),
+            })
+        })
+    }
+}
+
+/// The contents of a project lockfile
+#[derive(Default, Serialize, Deserialize)]
+pub struct Lockfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    node: Option<LockEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    npm: Option<LockEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pnpm: Option<LockEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    yarn: Option<LockEntry>,
+}
+
+/// The version that a range or tag resolved to
+#[derive(Clone, Serialize, Deserialize)]
+struct LockEntry {
+    requested: String,
+    #[serde(with = "version_serde")]
+    version: Version,
+    /// The checksums of the archives, by file name, since Node has a different archive for each
+    /// platform that the project is used on
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    checksums: BTreeMap<String, String>,
+}
+
+impl Lockfile {
+    /// Reads a lockfile, if it exists
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lockfile cannot be read or parsed.
+    pub fn from_file(file: &Path) -> Fallible<Option<Self>> {
+        let contents = match read_to_string(file) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == IoErrorKind::NotFound => return Ok(None),
+            Err(error) => {
+                return Err(error).with_context(|| FilesystemError::ReadLockfile {
+                    file: file.to_owned(),
+                });
+            }
+        };
+
+        serde_json::from_str(&contents)
+            .map(Some)
+            .with_context(|| PackageError::LockfileParse {
+                file: file.to_owned(),
+            })
+    }
+
+    /// Writes the lockfile, formatted to be committed alongside the manifest
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lockfile cannot be written.
+    pub fn write(&self, file: &Path) -> Fallible<()> {
+        let mut contents =
+            serde_json::to_string_pretty(self).with_context(|| FilesystemError::WriteLockfile {
+                file: file.to_owned(),
+            })?;
+        contents.push('\n');
+
+        // Written to a staging file first, so that a shim never reads a partial lockfile. The
+        // staging file sits beside the lockfile rather than in the Volta tmp directory, which
+        // may be on another filesystem than the project.
+        let dir = file.parent().unwrap_or_else(|| Path::new("."));
+        let mut staging =
+            NamedTempFile::new_in(dir).with_context(|| FilesystemError::CreateTempFile {
+                in_dir: dir.to_owned(),
+            })?;
+        staging
+            .write_all(contents.as_bytes())
+            .with_context(|| FilesystemError::WriteLockfile {
+                file: staging.path().to_path_buf(),
+            })?;
+        staging
+            .persist(file)
+            .with_context(|| FilesystemError::WriteLockfile {
+                file: file.to_owned(),
+            })?;
+        Ok(())
+    }
+
+    /// Records the version that a range or tag resolved to, along with the checksum of its
+    /// archive for this platform, if known
+    ///
+    /// If the version is unchanged, the checksums for other platforms are kept.
+    pub fn lock(
+        &mut self,
+        pin: &RequestedPin,
+        version: Version,
+        checksum: Option<(String, String)>,
+    ) {
+        let entry = self.entry_mut(pin.key);
+        let mut checksums = match entry.take() {
+            Some(previous) if previous.version == version => previous.checksums,
+            _ => BTreeMap::new(),
+        };
+        checksums.extend(checksum);
+
+        *entry = Some(LockEntry {
+            requested: pin.requested.clone(),
+            version,
+            checksums,
+        });
+    }
+
+    /// Fills in the versions of the pins that this lockfile has resolved
+    ///
+    /// Entries for a different range or tag than the one in the manifest are out of date, so
+    /// those pins are left unresolved.
+    pub(super) fn apply(&self, pin: &mut RequestedPin) {
+        if let Some(entry) = self.entry(pin.key)
+            && entry.requested == pin.requested
+        {
+            pin.locked = Some(entry.version.clone());
+            pin.checksums.clone_from(&entry.checksums);
+        }
+    }
+
+    const fn entry(&self, key: ManifestKey) -> Option<&LockEntry> {
+        match key {
+            ManifestKey::Node => self.node.as_ref(),
+            ManifestKey::Npm => self.npm.as_ref(),
+            ManifestKey::Pnpm => self.pnpm.as_ref(),
+            ManifestKey::Yarn => self.yarn.as_ref(),
+        }
+    }
+
+    const fn entry_mut(&mut self, key: ManifestKey) -> &mut Option<LockEntry> {
+        match key {
+            ManifestKey::Node => &mut self.node,
+            ManifestKey::Npm => &mut self.npm,
+            ManifestKey::Pnpm => &mut self.pnpm,
+            ManifestKey::Yarn => &mut self.yarn,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requested(key: ManifestKey, value: &str) -> RequestedPin {
+        match Pin::parse(key, value.into(), Path::new("/project/package.json")).unwrap() {
+            Pin::Requested(pin) => pin,
+            Pin::Exact(version) => panic!("Expected a range or tag, got {version}"),
+        }
+    }
+
+    #[test]
+    fn parses_exact_versions_and_ranges() {
+        let pin = Pin::parse(
+            ManifestKey::Node,
+            "20.19.2".into(),
+            Path::new("package.json"),
+        );
+        assert!(matches!(pin, Ok(Pin::Exact(_))));
+
+        let pin = requested(ManifestKey::Node, "^20.11");
+        assert_eq!(pin.requested, "^20.11");
+        assert_eq!(pin.lock_file, Path::new("/project/volta.lock"));
+        assert_eq!(pin.locked, None);
+
+        requested(ManifestKey::Node, "20");
+        requested(ManifestKey::Node, "lts");
+        requested(ManifestKey::Yarn, "1.22.x");
+
+        assert!(
+            Pin::parse(
+                ManifestKey::Npm,
+                "bundled".into(),
+                Path::new("package.json")
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn applies_matching_entries() {
+        let mut lockfile = Lockfile::default();
+        let node = requested(ManifestKey::Node, "20");
+        lockfile.lock(
+            &node,
+            "20.19.2".parse().unwrap(),
+            Some(("node-v20.19.2-linux-x64.tar.gz".into(), "sha256-abc".into())),
+        );
+
+        let mut pin = node.clone();
+        lockfile.apply(&mut pin);
+        assert_eq!(pin.locked, Some("20.19.2".parse().unwrap()));
+        assert_eq!(
+            pin.checksums.get("node-v20.19.2-linux-x64.tar.gz").unwrap(),
+            "sha256-abc"
+        );
+
+        // An entry for a different range is out of date
+        let mut pin = requested(ManifestKey::Node, "22");
+        lockfile.apply(&mut pin);
+        assert_eq!(pin.locked, None);
+
+        let mut pin = requested(ManifestKey::Yarn, "1");
+        lockfile.apply(&mut pin);
+        assert_eq!(pin.locked, None);
+    }
+
+    #[test]
+    fn keeps_checksums_of_unchanged_versions() {
+        let mut lockfile = Lockfile::default();
+        let node = requested(ManifestKey::Node, "20");
+        lockfile.lock(
+            &node,
+            "20.19.2".parse().unwrap(),
+            Some(("node-v20.19.2-linux-x64.tar.gz".into(), "sha256-abc".into())),
+        );
+        lockfile.lock(
+            &node,
+            "20.19.2".parse().unwrap(),
+            Some((
+                "node-v20.19.2-darwin-arm64.tar.gz".into(),
+                "sha256-def".into(),
+            )),
+        );
+
+        let mut pin = node.clone();
+        lockfile.apply(&mut pin);
+        assert_eq!(pin.checksums.len(), 2);
+
+        lockfile.lock(&node, "20.19.3".parse().unwrap(), None);
+        let mut pin = node;
+        lockfile.apply(&mut pin);
+        assert_eq!(pin.locked, Some("20.19.3".parse().unwrap()));
+        assert!(pin.checksums.is_empty());
+    }
+
+    #[test]
+    fn round_trips_through_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(LOCKFILE_NAME);
+        assert!(Lockfile::from_file(&file).unwrap().is_none());
+
+        let mut lockfile = Lockfile::default();
+        lockfile.lock(
+            &requested(ManifestKey::Pnpm, "^9"),
+            "9.15.0".parse().unwrap(),
+            Some(("pnpm-9.15.0.tgz".into(), "sha512-abc".into())),
+        );
+        lockfile.write(&file).unwrap();
+
+        // The staging file is renamed to the lockfile
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+        assert_eq!(
+            read_to_string(&file).unwrap(),
+            r#"{
+  "pnpm": {
+    "requested": "^9",
+    "version": "9.15.0",
+    "checksums": {
+      "pnpm-9.15.0.tgz": "sha512-abc"
+    }
+  }
+}
+"#
+        );
+
+        let mut pin = requested(ManifestKey::Pnpm, "^9");
+        Lockfile::from_file(&file).unwrap().unwrap().apply(&mut pin);
+        assert_eq!(pin.locked, Some("9.15.0".parse().unwrap()));
+    }
+}
@@ -1,6 +1,7 @@
 //! Provides the `Project` type, which represents a Node project tree in
 //! the filesystem.
 
+use std::collections::BTreeMap;
 use std::env;
 use std::ffi::OsStr;
 use std::iter::once;
@@ -9,18 +10,31 @@ use std::path::{Path, PathBuf};
 use nodejs_semver::Version;
 use once_cell::unsync::OnceCell;
 
-use crate::error::{Context, Fallible, FilesystemError, PackageError, PlatformError, VoltaError};
+use crate::error::{Context, Fallible, FilesystemError, PackageError, PlatformError};
 use crate::layout::volta_home;
 use crate::platform::PlatformSpec;
 use crate::tool::BinConfig;
 use chain_map::ChainMap;
 use indexmap::IndexSet;
 
+mod fallback;
+mod lock;
+mod package_manager;
 mod serial;
 #[cfg(test)]
 mod tests;
-
-use serial::{Manifest, ManifestKey, update_manifest};
+mod tools;
+mod workspace;
+
+pub use fallback::{FallbackPlatform, FallbackSource};
+use lock::Pin;
+pub use lock::{LOCKFILE_NAME, Lockfile, RequestedPin};
+pub use package_manager::PackageManagerField;
+pub use serial::ManifestKey;
+use serial::{Manifest, update_extends, update_manifest};
+#[allow(clippy::module_name_repetitions)]
+pub use tools::ProjectTool;
+use workspace::{extends_path, find_workspace_root};
 
 /// A lazily loaded Project
 #[allow(clippy::module_name_repetitions)]
@@ -68,6 +82,16 @@ pub struct Project {
     workspace_manifests: IndexSet<PathBuf>,
     dependencies: ChainMap<String, String>,
     platform: Option<PlatformSpec>,
+    /// The ranges and tags in the `volta` key, which are resolved through the lockfile
+    requested: Vec<RequestedPin>,
+    engines_node: Option<String>,
+    package_manager: Option<PackageManagerField>,
+    /// The packages in `volta.tools`, by the names given to them there
+    tools: BTreeMap<String, ProjectTool>,
+    /// The manifest of the workspace root, if this is a member package inheriting its pins
+    /// without a `volta` key of its own
+    workspace_root: Option<PathBuf>,
+    fallback: OnceCell<Option<FallbackPlatform>>,
 }
 
 impl Project {
@@ -90,15 +114,32 @@ impl Project {
         )
     }
 
+    /// Creates an optional Project instance for the project rooted at the specified directory
+    ///
+    /// Unlike `for_dir`, this will not search the ancestors if there is no `package.json` there
+    pub(crate) fn for_root(root: &Path) -> Fallible<Option<Self>> {
+        let manifest_file = root.join("package.json");
+        if manifest_file.is_file() {
+            Self::from_file(manifest_file).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Creates a Project instance from the given package manifest file (`package.json`)
     fn from_file(manifest_file: PathBuf) -> Fallible<Self> {
         let manifest = Manifest::from_file(&manifest_file)?;
+        let mut extends = next_manifest(&manifest_file, &manifest);
+        let workspace_root = extends.clone().filter(|_| manifest.platform.is_none());
         let mut dependencies: ChainMap<String, String> = manifest.dependency_maps.collect();
         let mut workspace_manifests = IndexSet::new();
         let mut platform = manifest.platform;
-        let mut extends = manifest.extends;
+        let engines_node = manifest.engines_node;
+        let mut package_manager = manifest.package_manager;
+        let mut tools = manifest.tools;
 
-        // Iterate the `volta.extends` chain, parsing each file in turn
+        // Iterate the `volta.extends` chain (including implicit workspace roots), parsing each
+        // file in turn
         while let Some(path) = extends {
             // Detect cycles to prevent infinite looping
             if path == manifest_file || workspace_manifests.contains(&path) {
@@ -113,28 +154,55 @@ impl Project {
             }
 
             let manifest = Manifest::from_file(&path)?;
+            extends = next_manifest(&path, &manifest);
             workspace_manifests.insert(path);
             dependencies.extend(manifest.dependency_maps);
+            package_manager = package_manager.or(manifest.package_manager);
+            for (name, tool) in manifest.tools {
+                tools.entry(name).or_insert(tool);
+            }
 
             platform = match (platform, manifest.platform) {
                 (Some(base), Some(ext)) => Some(base.merge(ext)),
                 (Some(plat), None) | (None, Some(plat)) => Some(plat),
                 (None, None) => None,
             };
-
-            extends = manifest.extends;
         }
 
-        let platform = platform.map(TryInto::try_into).transpose()?;
+        let (mut platform, requested) = match platform {
+            Some(platform) => platform.into_platform()?,
+            None => (None, Vec::new()),
+        };
+        if let (Some(platform), Some(field)) = (platform.as_mut(), package_manager.as_ref()) {
+            field.apply(platform);
+        }
 
         Ok(Self {
             manifest_file,
             workspace_manifests,
             dependencies,
             platform,
+            requested,
+            engines_node,
+            package_manager,
+            tools,
+            workspace_root,
+            fallback: OnceCell::new(),
         })
     }
 
+    /// Records the implicit workspace root in `volta.extends` before the first pin in a member
+    /// package, so that adding a `volta` key doesn't stop it from inheriting the root's pins
+    fn persist_workspace_root(&mut self) -> Fallible<()> {
+        if let Some(root) = self.workspace_root.take()
+            && let Some(path) = extends_path(&self.manifest_file, &root)
+        {
+            update_extends(&self.manifest_file, &path)?;
+        }
+
+        Ok(())
+    }
+
     /// Returns a reference to the manifest file for the current project
     #[must_use]
     pub fn manifest_file(&self) -> &Path {
@@ -155,6 +223,31 @@ impl Project {
         self.platform.as_ref()
     }
 
+    /// Returns the `engines.node` range of the project manifest, if any
+    #[must_use]
+    pub fn engines_node(&self) -> Option<&str> {
+        self.engines_node.as_deref()
+    }
+
+    /// Returns the ranges and tags in the `volta` key, along with the versions they are locked to
+    #[must_use]
+    pub fn requested_pins(&self) -> &[RequestedPin] {
+        &self.requested
+    }
+
+    /// Returns the range or tag that is locked to the given version of a tool, if any
+    #[must_use]
+    pub fn locked_pin(&self, key: ManifestKey, version: &Version) -> Option<&RequestedPin> {
+        self.requested
+            .iter()
+            .find(|pin| pin.key == key && pin.locked.as_ref() == Some(version))
+    }
+
+    /// Returns the packages in `volta.tools`, including those inherited through `volta.extends`
+    pub fn tools(&self) -> impl Iterator<Item = &ProjectTool> {
+        self.tools.values()
+    }
+
     /// Returns true if the project dependency map contains the specified dependency
     #[must_use]
     pub fn has_direct_dependency(&self, dependency: &str) -> bool {
@@ -212,7 +305,9 @@ impl Project {
     ///
     /// Returns an error if the manifest cannot be updated.
     pub fn pin_node(&mut self, version: Version) -> Fallible<()> {
-        update_manifest(&self.manifest_file, &ManifestKey::Node, Some(&version))?;
+        self.persist_workspace_root()?;
+        self.requested.retain(|pin| pin.key != ManifestKey::Node);
+        update_manifest(&self.manifest_file, ManifestKey::Node, Some(&version))?;
 
         if let Some(platform) = self.platform.as_mut() {
             platform.node = version;
@@ -234,8 +329,10 @@ impl Project {
     ///
     /// Returns an error if the manifest cannot be updated or no Node is pinned.
     pub fn pin_npm(&mut self, version: Option<Version>) -> Fallible<()> {
+        self.persist_workspace_root()?;
         if let Some(platform) = self.platform.as_mut() {
-            update_manifest(&self.manifest_file, &ManifestKey::Npm, version.as_ref())?;
+            self.requested.retain(|pin| pin.key != ManifestKey::Npm);
+            update_manifest(&self.manifest_file, ManifestKey::Npm, version.as_ref())?;
 
             platform.npm = version;
 
@@ -251,8 +348,10 @@ impl Project {
     ///
     /// Returns an error if the manifest cannot be updated or no Node is pinned.
     pub fn pin_pnpm(&mut self, version: Option<Version>) -> Fallible<()> {
+        self.persist_workspace_root()?;
         if let Some(platform) = self.platform.as_mut() {
-            update_manifest(&self.manifest_file, &ManifestKey::Pnpm, version.as_ref())?;
+            self.requested.retain(|pin| pin.key != ManifestKey::Pnpm);
+            update_manifest(&self.manifest_file, ManifestKey::Pnpm, version.as_ref())?;
 
             platform.pnpm = version;
 
@@ -271,8 +370,10 @@ impl Project {
     ///
     /// Returns an error if the manifest cannot be updated or no Node is pinned.
     pub fn pin_yarn(&mut self, version: Option<Version>) -> Fallible<()> {
+        self.persist_workspace_root()?;
         if let Some(platform) = self.platform.as_mut() {
-            update_manifest(&self.manifest_file, &ManifestKey::Yarn, version.as_ref())?;
+            self.requested.retain(|pin| pin.key != ManifestKey::Yarn);
+            update_manifest(&self.manifest_file, ManifestKey::Yarn, version.as_ref())?;
 
             platform.yarn = version;
 
@@ -302,6 +403,16 @@ fn is_project_root(dir: &Path) -> bool {
     is_node_root(dir) && !is_dependency(dir)
 }
 
+/// The next manifest in the chain after the given one, from its `volta.extends` or, for a
+/// workspace member without a `volta` key, from the root of the workspace
+fn next_manifest(file: &Path, manifest: &Manifest) -> Option<PathBuf> {
+    if manifest.platform.is_some() {
+        return manifest.extends.clone();
+    }
+
+    file.parent().and_then(find_workspace_root)
+}
+
 /// Starts at `base_dir` and walks up the directory tree until a package.json file is found
 pub(crate) fn find_closest_root(mut dir: PathBuf) -> Option<PathBuf> {
     while !is_project_root(&dir) {
@@ -314,10 +425,10 @@ pub(crate) fn find_closest_root(mut dir: PathBuf) -> Option<PathBuf> {
 }
 
 struct PartialPlatform {
-    node: Option<Version>,
-    npm: Option<Version>,
-    pnpm: Option<Version>,
-    yarn: Option<Version>,
+    node: Option<Pin>,
+    npm: Option<Pin>,
+    pnpm: Option<Pin>,
+    yarn: Option<Pin>,
 }
 
 impl PartialPlatform {
@@ -329,19 +440,35 @@ impl PartialPlatform {
             yarn: self.yarn.or(other.yarn),
         }
     }
-}
 
-impl TryFrom<PartialPlatform> for PlatformSpec {
-    type Error = VoltaError;
+    /// Converts the pins into a `PlatformSpec`, along with the ranges and tags among them
+    ///
+    /// Ranges and tags which aren't in the lockfile yet are left out of the platform, so if the
+    /// Node version is one of those, there is no platform until it has been resolved.
+    fn into_platform(self) -> Fallible<(Option<PlatformSpec>, Vec<RequestedPin>)> {
+        let mut requested = Vec::new();
+        let mut version = |pin: Option<Pin>| match pin? {
+            Pin::Exact(version) => Some(version),
+            Pin::Requested(pin) => {
+                let locked = pin.locked.clone();
+                requested.push(pin);
+                locked
+            }
+        };
 
-    fn try_from(partial: PartialPlatform) -> Fallible<Self> {
-        let node = partial.node.ok_or(PlatformError::NoProjectNode)?;
+        let node = self.node.ok_or(PlatformError::NoProjectNode)?;
+        let node = version(Some(node));
+        let npm = version(self.npm);
+        let pnpm = version(self.pnpm);
+        let yarn = version(self.yarn);
 
-        Ok(Self {
+        let platform = node.map(|node| PlatformSpec {
             node,
-            npm: partial.npm,
-            pnpm: partial.pnpm,
-            yarn: partial.yarn,
-        })
+            npm,
+            pnpm,
+            yarn,
+        });
+
+        Ok((platform, requested))
     }
 }
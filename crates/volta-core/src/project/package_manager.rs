@@ -0,0 +1,217 @@
+//! Provides parsing of the Corepack `packageManager` field of a project manifest, which pins the
+//! version of npm, pnpm, or Yarn (and optionally its hash) for projects that use Corepack
+
+use std::fmt;
+use std::path::PathBuf;
+
+use super::Project;
+use super::serial::update_package_manager;
+use crate::error::Fallible;
+use crate::platform::PlatformSpec;
+use crate::style::tool_version;
+use crate::tool::package::PackageManager;
+use log::{debug, warn};
+use nodejs_semver::Version;
+
+/// The package manager pinned by the `packageManager` field of a project manifest
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PackageManagerField {
+    /// The manifest file containing the field
+    pub file: PathBuf,
+    pub manager: PackageManager,
+    pub version: Version,
+    /// The Corepack hash of the package manager archive, e.g. `sha512.<hex digest>`
+    pub hash: Option<String>,
+}
+
+impl PackageManagerField {
+    /// Parses the value of the `packageManager` field, e.g. `pnpm@9.1.0+sha512.<hex digest>`
+    ///
+    /// Corepack also accepts package managers and versions that Volta can't use, such as URLs, so
+    /// those are skipped rather than treated as errors.
+    pub(super) fn parse(file: PathBuf, value: &str) -> Option<Self> {
+        let parsed = value.split_once('@').and_then(|(name, rest)| {
+            let manager = match name {
+                "npm" => PackageManager::Npm,
+                "pnpm" => PackageManager::Pnpm,
+                "yarn" => PackageManager::Yarn,
+                _ => return None,
+            };
+            let (version, hash) = match rest.split_once('+') {
+                Some((version, hash)) => (version, Some(hash.to_owned())),
+                None => (rest, None),
+            };
+
+            Some((manager, Version::parse(version).ok()?, hash))
+        });
+
+        if let Some((manager, version, hash)) = parsed {
+            Some(Self {
+                file,
+                manager,
+                version,
+                hash,
+            })
+        } else {
+            debug!(
+                "Ignoring unsupported `packageManager` value '{value}' in {}",
+                file.display()
+            );
+            None
+        }
+    }
+
+    /// Pins the package manager in a platform which doesn't already have a version for it
+    ///
+    /// If the platform has a different version, it is kept and the conflict is reported.
+    pub(crate) fn apply(&self, platform: &mut PlatformSpec) {
+        let pinned = match self.manager {
+            PackageManager::Npm => &mut platform.npm,
+            PackageManager::Pnpm => &mut platform.pnpm,
+            PackageManager::Yarn => &mut platform.yarn,
+        };
+
+        match pinned {
+            None => *pinned = Some(self.version.clone()),
+            Some(version) if *version != self.version => warn!(
+                "The `packageManager` field in {} asks for {}, but the `volta` key pins {}, which takes precedence.",
+                self.file.display(),
+                tool_version(self.name(), &self.version),
+                tool_version(self.name(), &*version),
+            ),
+            Some(_) => {}
+        }
+    }
+
+    /// The name of the package manager, as used in the field
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self.manager {
+            PackageManager::Npm => "npm",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Yarn => "yarn",
+        }
+    }
+}
+
+impl fmt::Display for PackageManagerField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.name(), self.version)?;
+        if let Some(hash) = &self.hash {
+            write!(f, "+{hash}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Project {
+    /// Returns the package manager pinned by the `packageManager` field, if any
+    #[must_use]
+    pub const fn package_manager(&self) -> Option<&PackageManagerField> {
+        self.package_manager.as_ref()
+    }
+
+    /// Updates the version (and hash) of the `packageManager` field, in the manifest that has it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be updated.
+    pub fn update_package_manager(
+        &mut self,
+        version: Version,
+        hash: Option<String>,
+    ) -> Fallible<()> {
+        if let Some(field) = self.package_manager.as_mut() {
+            field.version = version;
+            field.hash = hash;
+            update_package_manager(&field.file, &field.to_string())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: &str) -> Option<PackageManagerField> {
+        PackageManagerField::parse(PathBuf::from("package.json"), value)
+    }
+
+    #[test]
+    fn parses_version_and_hash() {
+        let field = parse("pnpm@9.1.0+sha512.abc123").unwrap();
+        assert_eq!(field.manager, PackageManager::Pnpm);
+        assert_eq!(field.version, Version::parse("9.1.0").unwrap());
+        assert_eq!(field.hash.as_deref(), Some("sha512.abc123"));
+        assert_eq!(field.to_string(), "pnpm@9.1.0+sha512.abc123");
+
+        let field = parse("yarn@1.22.22").unwrap();
+        assert_eq!(field.manager, PackageManager::Yarn);
+        assert_eq!(field.hash, None);
+        assert_eq!(field.to_string(), "yarn@1.22.22");
+    }
+
+    #[test]
+    fn ignores_unsupported_values() {
+        assert_eq!(parse("bun@1.1.0"), None);
+        assert_eq!(parse("yarn@^1.22"), None);
+        assert_eq!(parse("pnpm@https://example.com/pnpm.tgz"), None);
+        assert_eq!(parse("npm"), None);
+    }
+
+    #[test]
+    fn fills_missing_version() {
+        let field = parse("yarn@1.22.22").unwrap();
+        let mut platform = PlatformSpec {
+            node: Version::parse("20.19.2").unwrap(),
+            npm: None,
+            pnpm: None,
+            yarn: None,
+        };
+
+        field.apply(&mut platform);
+        assert_eq!(platform.yarn, Some(Version::parse("1.22.22").unwrap()));
+        assert_eq!(platform.npm, None);
+    }
+
+    #[test]
+    fn keeps_volta_version_on_conflict() {
+        let field = parse("npm@10.8.1").unwrap();
+        let mut platform = PlatformSpec {
+            node: Version::parse("20.19.2").unwrap(),
+            npm: Some(Version::parse("10.5.0").unwrap()),
+            pnpm: None,
+            yarn: None,
+        };
+
+        field.apply(&mut platform);
+        assert_eq!(platform.npm, Some(Version::parse("10.5.0").unwrap()));
+    }
+
+    #[test]
+    fn updates_manifest_field() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("package.json");
+        std::fs::write(
+            &manifest,
+            "{\n  \"name\": \"corepack\",\n  \"packageManager\": \"yarn@1.22.19+sha1.abcd\"\n}\n",
+        )
+        .unwrap();
+
+        let mut project = Project::for_root(dir.path()).unwrap().unwrap();
+        project
+            .update_package_manager(Version::parse("1.22.22").unwrap(), None)
+            .unwrap();
+
+        assert_eq!(
+            project.package_manager().unwrap().to_string(),
+            "yarn@1.22.22"
+        );
+        assert_eq!(
+            std::fs::read_to_string(&manifest).unwrap(),
+            "{\n  \"name\": \"corepack\",\n  \"packageManager\": \"yarn@1.22.22\"\n}\n"
+        );
+    }
+}
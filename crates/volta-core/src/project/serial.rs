@@ -1,12 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::fs::{File, read_to_string};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use super::PartialPlatform;
+use super::lock::{Lockfile, Pin};
+use super::package_manager::PackageManagerField;
+use super::tools::{ProjectTool, parse_tools};
 use crate::error::{Context, Fallible, FilesystemError, PackageError};
-use crate::version::parse;
 use dunce::canonicalize;
 use nodejs_semver::Version;
 use serde::{Deserialize, Serialize};
@@ -21,17 +23,45 @@ pub(super) struct Manifest {
     pub dependency_maps: DependencyMapIterator,
     pub platform: Option<PartialPlatform>,
     pub extends: Option<PathBuf>,
+    pub engines_node: Option<String>,
+    pub package_manager: Option<PackageManagerField>,
+    pub tools: BTreeMap<String, ProjectTool>,
 }
 
 impl Manifest {
     pub fn from_file(file: &Path) -> Fallible<Self> {
         let raw = RawManifest::from_file(file)?;
 
+        // `engines` is only informational for npm, so anything other than a string is ignored
+        let engines_node = raw
+            .engines
+            .as_ref()
+            .and_then(|engines| engines.get("node"))
+            .and_then(Value::as_str)
+            .map(str::to_owned);
+
+        // Corepack only reads a string, so anything else is ignored in the same way
+        let package_manager = raw
+            .package_manager
+            .as_ref()
+            .and_then(Value::as_str)
+            .and_then(|value| PackageManagerField::parse(file.to_owned(), value));
+
         let dependency_maps = raw.dependencies.into_iter().chain(raw.dev_dependencies);
 
-        let (platform, extends) = match raw.volta {
-            Some(toolchain) => {
-                let (partial, extends) = toolchain.parse_split()?;
+        let mut toolchain = raw.volta;
+        let tools = toolchain
+            .as_mut()
+            .and_then(|toolchain| toolchain.tools.take())
+            .unwrap_or_default();
+        let tools = parse_tools(tools, file)?;
+
+        let (platform, extends) = match toolchain {
+            // A `volta` key with only tools in it doesn't pin a platform, so the project inherits
+            // one in the same way as a project without a `volta` key
+            Some(toolchain) if toolchain.has_pins() || tools.is_empty() => {
+                let (mut partial, extends) = toolchain.parse_split(file)?;
+                partial.apply_lockfile()?;
 
                 let next = extends
                     .map(|path| {
@@ -46,18 +76,23 @@ impl Manifest {
                     .transpose()?;
                 (Some(partial), next)
             }
-            None => (None, None),
+            _ => (None, None),
         };
 
         Ok(Self {
             dependency_maps,
             platform,
             extends,
+            engines_node,
+            package_manager,
+            tools,
         })
     }
 }
 
-pub(super) enum ManifestKey {
+/// A tool in the `volta` key of a manifest
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ManifestKey {
     Node,
     Npm,
     Pnpm,
@@ -82,9 +117,51 @@ impl fmt::Display for ManifestKey {
 /// If the value is `None`, will remove the key from the hash
 pub(super) fn update_manifest(
     file: &Path,
-    key: &ManifestKey,
+    key: ManifestKey,
     value: Option<&Version>,
 ) -> Fallible<()> {
+    let key = key.to_string();
+
+    edit_manifest(file, |root| {
+        match (value, root.get_mut("volta").and_then(|v| v.as_object_mut())) {
+            (Some(v), Some(hash)) => {
+                hash.insert(key, Value::String(v.to_string()));
+            }
+            (None, Some(hash)) => {
+                hash.remove(&key);
+            }
+            (Some(v), None) => {
+                let mut map = Map::new();
+                map.insert(key, Value::String(v.to_string()));
+                root.insert("volta".into(), Value::Object(map));
+            }
+            (None, None) => {}
+        }
+    })
+}
+
+/// Sets `volta.extends` in the specified manifest, creating the `volta` hash if needed
+pub(super) fn update_extends(file: &Path, extends: &str) -> Fallible<()> {
+    edit_manifest(file, |root| {
+        let volta = root
+            .entry("volta")
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Some(hash) = volta.as_object_mut() {
+            hash.insert("extends".into(), Value::String(extends.into()));
+        }
+    })
+}
+
+/// Updates the Corepack `packageManager` field in the specified manifest
+pub(super) fn update_package_manager(file: &Path, value: &str) -> Fallible<()> {
+    edit_manifest(file, |root| {
+        root.insert("packageManager".into(), Value::String(value.into()));
+    })
+}
+
+/// Applies an edit to the root object of the specified manifest, keeping its indentation and
+/// trailing newline
+fn edit_manifest(file: &Path, edit: impl FnOnce(&mut Map<String, Value>)) -> Fallible<()> {
     let contents = read_to_string(file).with_context(|| PackageError::ProjectManifestRead {
         file: file.to_owned(),
     })?;
@@ -100,22 +177,7 @@ pub(super) fn update_manifest(
             file: file.to_owned(),
         })?;
 
-    let key = key.to_string();
-
-    match (value, root.get_mut("volta").and_then(|v| v.as_object_mut())) {
-        (Some(v), Some(hash)) => {
-            hash.insert(key, Value::String(v.to_string()));
-        }
-        (None, Some(hash)) => {
-            hash.remove(&key);
-        }
-        (Some(v), None) => {
-            let mut map = Map::new();
-            map.insert(key, Value::String(v.to_string()));
-            root.insert("volta".into(), Value::Object(map));
-        }
-        (None, None) => {}
-    }
+    edit(root);
 
     let indent = detect_indent::detect_indent(&contents);
     let mut output = File::create(file).with_context(|| FilesystemError::WritePackage {
@@ -146,6 +208,11 @@ struct RawManifest {
     dev_dependencies: Option<HashMap<String, String>>,
 
     volta: Option<ToolchainSpec>,
+
+    engines: Option<Value>,
+
+    #[serde(rename = "packageManager")]
+    package_manager: Option<Value>,
 }
 
 impl RawManifest {
@@ -172,15 +239,27 @@ struct ToolchainSpec {
     yarn: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     extends: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<BTreeMap<String, String>>,
 }
 
 impl ToolchainSpec {
+    /// Whether the `volta` key pins any tools of the platform, or extends another manifest
+    const fn has_pins(&self) -> bool {
+        self.node.is_some()
+            || self.npm.is_some()
+            || self.pnpm.is_some()
+            || self.yarn.is_some()
+            || self.extends.is_some()
+    }
+
     /// Moves the tool versions into a `PartialPlatform` and returns that along with the `extends` value
-    fn parse_split(self) -> Fallible<(PartialPlatform, Option<PathBuf>)> {
-        let node = self.node.map(parse).transpose()?;
-        let npm = self.npm.map(parse).transpose()?;
-        let pnpm = self.pnpm.map(parse).transpose()?;
-        let yarn = self.yarn.map(parse).transpose()?;
+    fn parse_split(self, file: &Path) -> Fallible<(PartialPlatform, Option<PathBuf>)> {
+        let pin = |key, value| Pin::parse(key, value, file);
+        let node = self.node.map(|v| pin(ManifestKey::Node, v)).transpose()?;
+        let npm = self.npm.map(|v| pin(ManifestKey::Npm, v)).transpose()?;
+        let pnpm = self.pnpm.map(|v| pin(ManifestKey::Pnpm, v)).transpose()?;
+        let yarn = self.yarn.map(|v| pin(ManifestKey::Yarn, v)).transpose()?;
 
         let platform = PartialPlatform {
             node,
@@ -192,3 +271,33 @@ impl ToolchainSpec {
         Ok((platform, self.extends))
     }
 }
+
+impl PartialPlatform {
+    /// Fills in the versions of the ranges and tags from the lockfile beside the manifest, if any
+    fn apply_lockfile(&mut self) -> Fallible<()> {
+        let mut requested = [
+            &mut self.node,
+            &mut self.npm,
+            &mut self.pnpm,
+            &mut self.yarn,
+        ]
+        .into_iter()
+        .filter_map(|pin| match pin {
+            Some(Pin::Requested(requested)) => Some(requested),
+            _ => None,
+        })
+        .peekable();
+
+        let Some(first) = requested.peek() else {
+            return Ok(());
+        };
+
+        if let Some(lockfile) = Lockfile::from_file(&first.lock_file)? {
+            for pin in requested {
+                lockfile.apply(pin);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -7,7 +7,7 @@ fn fixture_path(fixture_dirs: &[&str]) -> PathBuf {
     let mut cargo_manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     cargo_manifest_dir.push("fixtures");
 
-    for fixture_dir in fixture_dirs.iter() {
+    for fixture_dir in fixture_dirs {
         cargo_manifest_dir.push(fixture_dir);
     }
 
@@ -197,7 +197,7 @@ mod project {
                 assert_eq!(&expected_paths, paths);
                 assert_eq!(&expected_paths[0], duplicate);
             }
-            kind => panic!("Wrong error kind: {:?}", kind),
+            kind => panic!("Wrong error kind: {kind:?}"),
         }
 
         // cycle-2 has a cycle with 2 separate extensions, not including the original package.json
@@ -214,11 +214,269 @@ mod project {
                 assert_eq!(&expected_paths, paths);
                 assert_eq!(&expected_paths[1], duplicate);
             }
-            kind => panic!("Wrong error kind: {:?}", kind),
+            kind => panic!("Wrong error kind: {kind:?}"),
         }
     }
 }
 
+mod workspaces {
+    use super::*;
+
+    fn member(fixture_dirs: &[&str]) -> Project {
+        let mut path = vec!["workspaces"];
+        path.extend_from_slice(fixture_dirs);
+        Project::for_dir(fixture_path(&path)).unwrap().unwrap()
+    }
+
+    #[test]
+    fn npm_member_inherits_root() {
+        let project = member(&["npm", "packages", "app"]);
+        let platform = project.platform().unwrap();
+
+        assert_eq!(platform.node, "20.19.2".parse().unwrap());
+        assert_eq!(platform.npm, Some("10.8.2".parse().unwrap()));
+        assert_eq!(
+            project.workspace_roots().collect::<Vec<_>>(),
+            vec![
+                &*fixture_path(&["workspaces", "npm", "packages", "app"]),
+                &*fixture_path(&["workspaces", "npm"]),
+            ]
+        );
+
+        // Dependencies of the root are shared with its members
+        assert!(project.has_direct_dependency("express"));
+        assert!(project.has_direct_dependency("eslint"));
+    }
+
+    #[test]
+    fn member_with_volta_key_does_not_inherit() {
+        let project = member(&["npm", "packages", "pinned"]);
+        let platform = project.platform().unwrap();
+
+        assert_eq!(platform.node, "22.16.0".parse().unwrap());
+        assert_eq!(platform.npm, None);
+        assert_eq!(project.workspace_roots().count(), 1);
+    }
+
+    #[test]
+    fn non_member_does_not_inherit() {
+        let project = member(&["npm", "tools", "script"]);
+
+        assert!(project.platform().is_none());
+        assert_eq!(project.workspace_roots().count(), 1);
+    }
+
+    #[test]
+    fn yarn_member_inherits_root() {
+        let project = member(&["yarn", "apps", "web", "client"]);
+        let platform = project.platform().unwrap();
+
+        assert_eq!(platform.node, "20.19.2".parse().unwrap());
+        assert_eq!(platform.yarn, Some("1.22.22".parse().unwrap()));
+    }
+
+    #[test]
+    fn pnpm_member_inherits_root() {
+        let project = member(&["pnpm", "libs", "core"]);
+        let platform = project.platform().unwrap();
+
+        assert_eq!(platform.node, "22.16.0".parse().unwrap());
+        assert_eq!(platform.pnpm, Some("9.15.0".parse().unwrap()));
+
+        // Packages excluded from the workspace are standalone
+        let project = member(&["pnpm", "libs", "private"]);
+        assert!(project.platform().is_none());
+    }
+
+    #[test]
+    fn lerna_member_inherits_root() {
+        let project = member(&["lerna", "modules", "utils"]);
+        let platform = project.platform().unwrap();
+
+        assert_eq!(platform.node, "18.20.8".parse().unwrap());
+    }
+
+    #[test]
+    fn nested_workspaces_inherit_each_root() {
+        let project = member(&["nested", "platform", "packages", "api"]);
+        let platform = project.platform().unwrap();
+
+        assert_eq!(platform.node, "18.20.8".parse().unwrap());
+        assert_eq!(platform.yarn, Some("1.22.22".parse().unwrap()));
+        assert_eq!(
+            project.workspace_roots().collect::<Vec<_>>(),
+            vec![
+                &*fixture_path(&["workspaces", "nested", "platform", "packages", "api"]),
+                &*fixture_path(&["workspaces", "nested", "platform"]),
+                &*fixture_path(&["workspaces", "nested"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn pin_in_member_keeps_workspace_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let app = root.join("packages").join("app");
+        std::fs::create_dir_all(&app).unwrap();
+        std::fs::write(
+            root.join("package.json"),
+            r#"{ "workspaces": ["packages/*"], "volta": { "node": "20.19.2", "yarn": "1.22.22" } }"#,
+        )
+        .unwrap();
+        std::fs::write(app.join("package.json"), "{\n  \"name\": \"app\"\n}\n").unwrap();
+
+        let mut project = Project::for_dir(app.clone()).unwrap().unwrap();
+        project.pin_node("22.16.0".parse().unwrap()).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(app.join("package.json")).unwrap(),
+            "{\n  \"name\": \"app\",\n  \"volta\": {\n    \"extends\": \"../../package.json\",\n    \"node\": \"22.16.0\"\n  }\n}\n"
+        );
+
+        let project = Project::for_dir(app).unwrap().unwrap();
+        let platform = project.platform().unwrap();
+        assert_eq!(platform.node, "22.16.0".parse().unwrap());
+        assert_eq!(platform.yarn, Some("1.22.22".parse().unwrap()));
+    }
+}
+
+mod lockfile {
+    use super::*;
+
+    fn project(fixture: &str) -> Project {
+        Project::for_dir(fixture_path(&["lock", fixture]))
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn locked_ranges_use_lockfile_versions() {
+        let project = project("locked");
+        let platform = project.platform().unwrap();
+
+        assert_eq!(platform.node, "20.19.2".parse().unwrap());
+        assert_eq!(platform.npm, Some("10.8.2".parse().unwrap()));
+        assert_eq!(platform.yarn, Some("1.22.22".parse().unwrap()));
+
+        let requested: Vec<_> = project
+            .requested_pins()
+            .iter()
+            .map(|pin| (pin.key, pin.requested.as_str()))
+            .collect();
+        assert_eq!(
+            requested,
+            vec![(ManifestKey::Node, "20"), (ManifestKey::Yarn, "^1.22")]
+        );
+
+        let node = project
+            .locked_pin(ManifestKey::Node, &"20.19.2".parse().unwrap())
+            .unwrap();
+        assert_eq!(
+            node.lock_file,
+            fixture_path(&["lock", "locked", LOCKFILE_NAME])
+        );
+        assert!(
+            node.checksums
+                .contains_key("node-v20.19.2-linux-x64.tar.gz")
+        );
+        assert!(
+            project
+                .locked_pin(ManifestKey::Node, &"20.19.3".parse().unwrap())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn unresolved_node_has_no_platform() {
+        let project = project("unlocked");
+
+        assert!(project.platform().is_none());
+        assert_eq!(project.requested_pins().len(), 1);
+        assert_eq!(project.requested_pins()[0].locked, None);
+    }
+
+    #[test]
+    fn stale_entries_are_unresolved() {
+        let project = project("stale");
+
+        assert!(project.platform().is_none());
+        let locked: Vec<_> = project
+            .requested_pins()
+            .iter()
+            .map(|pin| pin.locked.clone())
+            .collect();
+        assert_eq!(locked, vec![None, Some("1.22.22".parse().unwrap())]);
+    }
+
+    #[test]
+    fn pin_replaces_range() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{ "volta": { "node": "^20" } }"#,
+        )
+        .unwrap();
+
+        let mut project = Project::for_dir(dir.path().to_owned()).unwrap().unwrap();
+        project.pin_node("22.16.0".parse().unwrap()).unwrap();
+
+        assert!(project.requested_pins().is_empty());
+        assert_eq!(project.platform().unwrap().node, "22.16.0".parse().unwrap());
+    }
+}
+
+mod tools {
+    use super::*;
+
+    fn tool_versions(project: &Project) -> Vec<String> {
+        project
+            .tools()
+            .map(|tool| format!("{}@{}", tool.name, tool.version))
+            .collect()
+    }
+
+    #[test]
+    fn reads_project_tools() {
+        let project = Project::for_dir(fixture_path(&["tools"])).unwrap().unwrap();
+
+        assert_eq!(
+            tool_versions(&project),
+            vec!["typescript@5.4.5", "turbo@2.0.4"]
+        );
+    }
+
+    #[test]
+    fn member_overrides_root_tools() {
+        let project = Project::for_dir(fixture_path(&["tools", "packages", "legacy"]))
+            .unwrap()
+            .unwrap();
+
+        // A `volta` key with only tools still inherits the platform of the workspace root
+        assert_eq!(project.platform().unwrap().node, "20.19.2".parse().unwrap());
+        assert_eq!(
+            tool_versions(&project),
+            vec!["typescript@4.9.5", "turbo@2.0.4"]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_tools() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{ "volta": { "node": "20.19.2", "tools": { "tsc-tool": "typescript@^5" } } }"#,
+        )
+        .unwrap();
+
+        let error = Project::for_dir(dir.path().to_owned()).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::Package(PackageError::ProjectToolInvalid { tool, .. }) if tool == "tsc-tool"
+        ));
+    }
+}
+
 mod needs_yarn_run {
     use super::*;
 
@@ -0,0 +1,75 @@
+//! Provides the packages declared in the `volta.tools` map of a manifest, which are installed
+//! into versioned package images and used in place of the default version inside the project
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::error::{Fallible, PackageError};
+use crate::tool::ToolSpec;
+use crate::version::VersionSpec;
+use nodejs_semver::Version;
+
+/// A package in the `volta.tools` map of a manifest, such as `"tsc-tool": "typescript@5.4.5"`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProjectTool {
+    /// The package name
+    pub name: String,
+    /// The exact version of the package
+    pub version: Version,
+}
+
+impl ProjectTool {
+    /// Parses an entry of `volta.tools`, which must be a package name and an exact version
+    fn parse(tool: &str, value: &str, manifest: &Path) -> Fallible<Self> {
+        match ToolSpec::try_from_str(value) {
+            Ok(ToolSpec::Package(name, VersionSpec::Exact(version))) => Ok(Self { name, version }),
+            _ => Err(PackageError::ProjectToolInvalid {
+                file: manifest.to_owned(),
+                tool: tool.into(),
+                value: value.into(),
+            }
+            .into()),
+        }
+    }
+}
+
+/// Parses the `volta.tools` map of a manifest, keyed by the names given to the tools there
+pub(super) fn parse_tools(
+    tools: BTreeMap<String, String>,
+    manifest: &Path,
+) -> Fallible<BTreeMap<String, ProjectTool>> {
+    tools
+        .into_iter()
+        .map(|(tool, value)| {
+            let parsed = ProjectTool::parse(&tool, &value, manifest)?;
+            Ok((tool, parsed))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: &str) -> Fallible<ProjectTool> {
+        ProjectTool::parse("tool", value, Path::new("package.json"))
+    }
+
+    #[test]
+    fn parses_exact_versions() {
+        let tool = parse("typescript@5.4.5").unwrap();
+        assert_eq!(tool.name, "typescript");
+        assert_eq!(tool.version, "5.4.5".parse().unwrap());
+
+        let tool = parse("@angular/cli@17.3.0").unwrap();
+        assert_eq!(tool.name, "@angular/cli");
+    }
+
+    #[test]
+    fn rejects_ranges_and_platform_tools() {
+        assert!(parse("typescript").is_err());
+        assert!(parse("typescript@^5").is_err());
+        assert!(parse("typescript@latest").is_err());
+        assert!(parse("node@20.19.2").is_err());
+    }
+}
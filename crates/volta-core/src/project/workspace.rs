@@ -0,0 +1,250 @@
+//! Provides discovery of the monorepo workspace containing a project, so that member packages
+//! without a `volta` key inherit the pins of the workspace root without needing `volta.extends`
+
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use globset::{GlobBuilder, GlobSetBuilder};
+use log::debug;
+use serde_json::Value;
+use yaml_rust2::YamlLoader;
+
+/// Finds the manifest of the closest workspace root which declares the package in `package_dir`
+/// as one of its members
+///
+/// Workspaces can be declared with the `workspaces` field of `package.json` (used by npm and
+/// Yarn), the `packages` of `pnpm-workspace.yaml`, or the `packages` of `lerna.json`. The search
+/// stops at the first ancestor that declares a workspace, since a package can only belong to the
+/// closest one, and at the root of a repository, since a workspace never spans several.
+pub(super) fn find_workspace_root(package_dir: &Path) -> Option<PathBuf> {
+    for root in package_dir.ancestors() {
+        let manifest = root.join("package.json");
+        if root != package_dir && manifest.is_file() {
+            let patterns = workspace_patterns(root, &manifest);
+            if !patterns.is_empty() {
+                let member = package_dir.strip_prefix(root).ok()?;
+                if !is_member(&patterns, member) {
+                    debug!(
+                        "Closest workspace root at {} does not contain {}",
+                        root.display(),
+                        package_dir.display()
+                    );
+                    return None;
+                }
+
+                debug!(
+                    "Found workspace root at {} containing {}",
+                    root.display(),
+                    package_dir.display()
+                );
+                return Some(manifest);
+            }
+        }
+
+        if is_repository_root(root) {
+            return None;
+        }
+    }
+
+    None
+}
+
+/// Determines whether a directory is the root of a version control repository
+fn is_repository_root(dir: &Path) -> bool {
+    [".git", ".hg", ".svn"]
+        .iter()
+        .any(|name| dir.join(name).exists())
+}
+
+/// Collects the patterns matching the member packages of a workspace from all of its configs
+fn workspace_patterns(root: &Path, manifest: &Path) -> Vec<String> {
+    let mut patterns = Vec::new();
+
+    if let Some(manifest) = read_json(manifest) {
+        // npm and Yarn use a list, while Yarn also allows an object with `packages` and `nohoist`
+        let workspaces = manifest.get("workspaces");
+        let packages = workspaces.and_then(|workspaces| workspaces.get("packages"));
+        patterns.extend(string_list(packages.or(workspaces)));
+    }
+
+    if let Some(lerna) = read_json(&root.join("lerna.json")) {
+        patterns.extend(string_list(lerna.get("packages")));
+    }
+
+    patterns.extend(pnpm_patterns(&root.join("pnpm-workspace.yaml")));
+
+    patterns
+}
+
+/// Reads a JSON config, ignoring it if it's missing or invalid
+fn read_json(file: &Path) -> Option<Value> {
+    let contents = read_to_string(file).ok()?;
+    serde_json::from_str(&contents)
+        .inspect_err(|_| {
+            debug!(
+                "Ignoring invalid {} while searching for a workspace root",
+                file.display()
+            );
+        })
+        .ok()
+}
+
+/// The strings in a JSON list, skipping anything else
+fn string_list(value: Option<&Value>) -> impl Iterator<Item = String> + '_ {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_owned)
+}
+
+/// Reads the `packages` of a `pnpm-workspace.yaml` file, ignoring it if it's missing or invalid
+fn pnpm_patterns(file: &Path) -> Vec<String> {
+    let Ok(contents) = read_to_string(file) else {
+        return Vec::new();
+    };
+
+    YamlLoader::load_from_str(&contents).map_or_else(
+        |_| {
+            debug!(
+                "Ignoring invalid {} while searching for a workspace root",
+                file.display()
+            );
+            Vec::new()
+        },
+        |documents| {
+            documents
+                .first()
+                .and_then(|document| document["packages"].as_vec())
+                .into_iter()
+                .flatten()
+                .filter_map(|pattern| pattern.as_str().map(str::to_owned))
+                .collect()
+        },
+    )
+}
+
+/// Determines whether a package, relative to the workspace root, matches the workspace patterns
+///
+/// Patterns starting with `!` exclude the packages they match, as in pnpm.
+fn is_member(patterns: &[String], member: &Path) -> bool {
+    let mut include = GlobSetBuilder::new();
+    let mut exclude = GlobSetBuilder::new();
+
+    for pattern in patterns {
+        let (set, pattern) = pattern
+            .strip_prefix('!')
+            .map_or((&mut include, pattern.as_str()), |pattern| {
+                (&mut exclude, pattern)
+            });
+        let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+
+        match GlobBuilder::new(pattern).literal_separator(true).build() {
+            Ok(glob) => {
+                set.add(glob);
+            }
+            Err(_) => debug!("Ignoring invalid workspace pattern '{pattern}'"),
+        }
+    }
+
+    match (include.build(), exclude.build()) {
+        (Ok(include), Ok(exclude)) => include.is_match(member) && !exclude.is_match(member),
+        _ => false,
+    }
+}
+
+/// The path from a member package manifest to the workspace root manifest, for `volta.extends`
+pub(super) fn extends_path(member_manifest: &Path, root_manifest: &Path) -> Option<String> {
+    let member_dir = member_manifest.parent()?;
+    let root_dir = root_manifest.parent()?;
+    let depth = member_dir.strip_prefix(root_dir).ok()?.components().count();
+
+    Some(format!("{}package.json", "../".repeat(depth)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{create_dir_all, write};
+
+    fn member(patterns: &[&str], member: &str) -> bool {
+        let patterns: Vec<String> = patterns.iter().map(|&pattern| pattern.to_owned()).collect();
+        is_member(&patterns, Path::new(member))
+    }
+
+    #[test]
+    fn matches_workspace_patterns() {
+        assert!(member(&["packages/*"], "packages/app"));
+        assert!(!member(&["packages/*"], "packages/app/nested"));
+        assert!(member(&["packages/**"], "packages/app/nested"));
+        assert!(member(&["./apps/*/"], "apps/web"));
+        assert!(member(&["tools/cli"], "tools/cli"));
+        assert!(!member(&["packages/*"], "tools/cli"));
+        assert!(!member(&[], "packages/app"));
+    }
+
+    #[test]
+    fn excludes_negated_patterns() {
+        let patterns = ["libs/*", "!libs/private"];
+        assert!(member(&patterns, "libs/core"));
+        assert!(!member(&patterns, "libs/private"));
+    }
+
+    #[test]
+    fn relative_extends_path() {
+        assert_eq!(
+            extends_path(
+                Path::new("/repo/packages/app/package.json"),
+                Path::new("/repo/package.json")
+            )
+            .as_deref(),
+            Some("../../package.json")
+        );
+        assert_eq!(
+            extends_path(
+                Path::new("/repo/package.json"),
+                Path::new("/other/package.json")
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn stops_at_closest_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let tools = root.join("tools");
+        let script = tools.join("script");
+        create_dir_all(&script).unwrap();
+        write(
+            root.join("package.json"),
+            r#"{ "workspaces": ["tools", "tools/*"] }"#,
+        )
+        .unwrap();
+        write(tools.join("package.json"), r#"{ "workspaces": ["lib/*"] }"#).unwrap();
+
+        // The member of the outer workspace is not part of the closer one
+        assert_eq!(find_workspace_root(&script), None);
+        assert_eq!(find_workspace_root(&tools), Some(root.join("package.json")));
+    }
+
+    #[test]
+    fn stops_at_repository_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let repo = root.join("vendor").join("repo");
+        create_dir_all(repo.join(".git")).unwrap();
+        write(
+            root.join("package.json"),
+            r#"{ "workspaces": ["vendor/*"] }"#,
+        )
+        .unwrap();
+
+        assert_eq!(find_workspace_root(&repo), None);
+        assert_eq!(
+            find_workspace_root(&root.join("vendor").join("other")),
+            Some(root.join("package.json"))
+        );
+    }
+}
@@ -8,7 +8,7 @@ use crate::error::{BinaryError, Context, EnvironmentError, ErrorKind, Fallible,
 use crate::layout::volta_home;
 use crate::platform::{Platform, Sourced, System};
 use crate::session::Session;
-use crate::tool::package::BinConfig;
+use crate::tool::package::{BinConfig, package_image, project_tool_bin};
 use log::debug;
 
 /// Determine the correct command to run for a 3rd-party binary
@@ -46,6 +46,24 @@ pub(super) fn command(exe: &OsStr, args: &[OsString], session: &mut Session) ->
         }
     }
 
+    // Then the packages in `volta.tools`, which take the place of the default inside the project
+    if let Some(path_to_bin) = project_tool_bin(&bin, session)? {
+        debug!(
+            "Found {} in project tools at '{}'",
+            bin,
+            path_to_bin.display()
+        );
+
+        let platform = Platform::current(session)?;
+        return Ok(ToolCommand::new(
+            path_to_bin,
+            args,
+            platform,
+            ToolKind::ProjectLocalBinary(bin),
+        )
+        .into());
+    }
+
     // Try to use the default toolchain
     if let Some(default_tool) = DefaultBinary::from_name(exe, session)? {
         debug!(
@@ -131,9 +149,9 @@ impl DefaultBinary {
     ///
     /// Returns an error if the binary configuration cannot be loaded.
     pub fn from_config(bin_config: BinConfig, session: &mut Session) -> Fallible<Self> {
-        let package_dir = volta_home()?.package_image_dir(&bin_config.package);
+        let package_dir = package_image(&bin_config.package, &bin_config.version)?;
         let mut bin_path = bin_config.manager.binary_dir(package_dir);
-        bin_path.push(&bin_config.name);
+        bin_path.push(bin_config.bin_name());
 
         // If the user does not have yarn set in the platform for this binary, use the default
         // This is necessary because some tools (e.g. ember-cli with the `--yarn` option) invoke `yarn`
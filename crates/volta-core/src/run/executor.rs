@@ -16,7 +16,9 @@ use crate::signal::pass_control_to_shim;
 use crate::style::{note_prefix, tool_version};
 use crate::sync::VoltaLock;
 use crate::tool::ToolSpec;
-use crate::tool::package::{DirectInstall, InPlaceUpgrade, PackageConfig, PackageManager};
+use crate::tool::package::{
+    DirectInstall, InPlaceUpgrade, PackageConfig, PackageManager, package_image,
+};
 use log::{info, warn};
 
 pub enum Executor {
@@ -354,14 +356,14 @@ impl PackageLinkCommand {
     ///
     /// This will also check for some common failure cases and alert the user
     pub fn execute(mut self, session: &mut Session) -> Fallible<ExitStatus> {
-        self.check_linked_package(session)?;
+        let config = self.check_linked_package(session)?;
 
         let image = self.platform.checkout(session)?;
         let path = image.path()?;
 
         self.command.env(RECURSION_ENV_VAR, "1");
         self.command.env("PATH", path);
-        let package_root = volta_home()?.package_image_dir(&self.tool);
+        let package_root = package_image(&self.tool, &config.version)?;
         PackageManager::Npm.setup_global_command(&mut self.command, package_root);
 
         self.command
@@ -373,7 +375,9 @@ impl PackageLinkCommand {
     ///     - The package is not found as a global
     ///     - The package exists, but was linked using a different package manager
     ///     - The package is using a different version of Node than the current project (warning)
-    fn check_linked_package(&self, session: &Session) -> Fallible<()> {
+    ///
+    /// Returns the config of the package, which has the version to link
+    fn check_linked_package(&self, session: &Session) -> Fallible<PackageConfig> {
         let config =
             PackageConfig::from_file(volta_home()?.default_package_config_file(&self.tool))
                 .with_context(|| {
@@ -400,7 +404,7 @@ impl PackageLinkCommand {
             );
         }
 
-        Ok(())
+        Ok(config)
     }
 }
 
@@ -555,7 +559,7 @@ impl UninstallCommand {
             self.tool.name()
         );
 
-        self.tool.uninstall(session.pnpm_enabled())?;
+        self.tool.uninstall(session, false)?;
 
         Ok(ExitStatus::from_raw(0))
     }
@@ -528,7 +528,7 @@ mod tests {
                     assert_eq!(install.tools, vec!["typescript@3"]);
                 }
                 _ => panic!("Doesn't parse global install as a global"),
-            };
+            }
         }
 
         #[test]
@@ -536,7 +536,7 @@ mod tests {
             match CommandArg::for_npm(&arg_list(["install", "--save-dev", "typescript"])) {
                 CommandArg::Standard => (),
                 _ => panic!("Parses local install as global"),
-            };
+            }
         }
 
         #[test]
@@ -546,7 +546,7 @@ mod tests {
                     assert_eq!(uninstall.tools, vec!["typescript"]);
                 }
                 _ => panic!("Doesn't parse global uninstall as a global"),
-            };
+            }
         }
 
         #[test]
@@ -554,7 +554,7 @@ mod tests {
             match CommandArg::for_npm(&arg_list(["uninstall", "--save-dev", "typescript"])) {
                 CommandArg::Standard => (),
                 _ => panic!("Parses local uninstall as global"),
-            };
+            }
         }
 
         #[test]
@@ -575,7 +575,7 @@ mod tests {
                     );
                 }
                 _ => panic!("Doesn't parse global install as a global"),
-            };
+            }
         }
 
         #[test]
@@ -591,7 +591,7 @@ mod tests {
                     assert_eq!(uninstall.tools, vec!["typescript", "cowsay", "ember-cli"]);
                 }
                 _ => panic!("Doesn't parse global uninstall as a global"),
-            };
+            }
         }
 
         #[test]
@@ -599,7 +599,7 @@ mod tests {
             match CommandArg::for_npm(&arg_list(["link"])) {
                 CommandArg::Intercepted(InterceptedCommand::Link(_)) => (),
                 _ => panic!("Doesn't parse bare link command ('npm link' with no packages"),
-            };
+            }
         }
 
         #[test]
@@ -609,7 +609,7 @@ mod tests {
                     assert_eq!(link.tools, vec!["typescript", "react"]);
                 }
                 _ => panic!("Doesn't parse link command with packages"),
-            };
+            }
         }
 
         #[test]
@@ -617,7 +617,7 @@ mod tests {
             match CommandArg::for_npm(&arg_list(["unlink"])) {
                 CommandArg::Intercepted(InterceptedCommand::Unlink) => (),
                 _ => panic!("Doesn't parse bare unlink command ('npm unlink' with no packages"),
-            };
+            }
         }
 
         #[test]
@@ -633,12 +633,12 @@ mod tests {
             match CommandArg::for_npm(&arg_list(["install", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Install(_)) => (),
                 _ => panic!("Doesn't parse long form (--global)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["install", "-g", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Install(_)) => (),
                 _ => panic!("Doesn't parse short form (-g)"),
-            };
+            }
         }
 
         #[test]
@@ -646,62 +646,62 @@ mod tests {
             match CommandArg::for_npm(&arg_list(["i", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Install(_)) => (),
                 _ => panic!("Doesn't parse short form (i)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["in", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Install(_)) => (),
                 _ => panic!("Doesn't parse short form (in)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["ins", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Install(_)) => (),
                 _ => panic!("Doesn't parse short form (ins)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["inst", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Install(_)) => (),
                 _ => panic!("Doesn't parse short form (inst)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["insta", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Install(_)) => (),
                 _ => panic!("Doesn't parse short form (insta)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["instal", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Install(_)) => (),
                 _ => panic!("Doesn't parse short form (instal)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["install", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Install(_)) => (),
                 _ => panic!("Doesn't parse exact command (install)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["isnt", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Install(_)) => (),
                 _ => panic!("Doesn't parse short form misspelling (isnt)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["isnta", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Install(_)) => (),
                 _ => panic!("Doesn't parse short form misspelling (isnta)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["isntal", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Install(_)) => (),
                 _ => panic!("Doesn't parse short form misspelling (isntal)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["isntall", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Install(_)) => (),
                 _ => panic!("Doesn't parse misspelling (isntall)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["add", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Install(_)) => (),
                 _ => panic!("Doesn't parse 'add' alias"),
-            };
+            }
         }
 
         #[test]
@@ -709,32 +709,32 @@ mod tests {
             match CommandArg::for_npm(&arg_list(["uninstall", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Uninstall(_)) => (),
                 _ => panic!("Doesn't parse long form (uninstall)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["unlink", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Uninstall(_)) => (),
                 _ => panic!("Doesn't parse 'unlink'"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["remove", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Uninstall(_)) => (),
                 _ => panic!("Doesn't parse 'remove'"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["un", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Uninstall(_)) => (),
                 _ => panic!("Doesn't parse short form (un)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["rm", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Uninstall(_)) => (),
                 _ => panic!("Doesn't parse short form (rm)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["r", "--global", "typescript"])) {
                 CommandArg::Global(GlobalCommand::Uninstall(_)) => (),
                 _ => panic!("Doesn't parse short form (r)"),
-            };
+            }
         }
 
         #[test]
@@ -742,12 +742,12 @@ mod tests {
             match CommandArg::for_npm(&arg_list(["link"])) {
                 CommandArg::Intercepted(InterceptedCommand::Link(_)) => (),
                 _ => panic!("Doesn't parse long form (link)"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list(["ln"])) {
                 CommandArg::Intercepted(InterceptedCommand::Link(_)) => (),
                 _ => panic!("Doesn't parse short form (ln)"),
-            };
+            }
         }
 
         #[test]
@@ -769,7 +769,7 @@ mod tests {
                     assert_eq!(install.tools, vec!["typescript", "cowsay"]);
                 }
                 _ => panic!("Doesn't parse install with extra flags as a global"),
-            };
+            }
 
             match CommandArg::for_npm(&arg_list([
                 "uninstall",
@@ -842,7 +842,7 @@ mod tests {
                     assert_eq!(install.tools, vec!["typescript"]);
                 }
                 _ => panic!("Doesn't parse global add as a global"),
-            };
+            }
         }
 
         #[test]
@@ -850,12 +850,12 @@ mod tests {
             match CommandArg::for_yarn(&arg_list(["add", "typescript"])) {
                 CommandArg::Standard => (),
                 _ => panic!("Parses local add as a global"),
-            };
+            }
 
             match CommandArg::for_yarn(&arg_list(["add", "global"])) {
                 CommandArg::Standard => (),
                 _ => panic!("Incorrectly handles bad order"),
-            };
+            }
         }
 
         #[test]
@@ -865,7 +865,7 @@ mod tests {
                     assert_eq!(uninstall.tools, vec!["typescript"]);
                 }
                 _ => panic!("Doesn't parse global remove as a global"),
-            };
+            }
         }
 
         #[test]
@@ -873,12 +873,12 @@ mod tests {
             match CommandArg::for_yarn(&arg_list(["remove", "typescript"])) {
                 CommandArg::Standard => (),
                 _ => panic!("Parses local remove as a global"),
-            };
+            }
 
             match CommandArg::for_yarn(&arg_list(["remove", "global"])) {
                 CommandArg::Standard => (),
                 _ => panic!("Incorrectly handles bad order"),
-            };
+            }
         }
 
         #[test]
@@ -896,7 +896,7 @@ mod tests {
                     assert_eq!(install.tools, vec!["typescript", "cowsay", "ember-cli"]);
                 }
                 _ => panic!("Doesn't parse global add as a global"),
-            };
+            }
         }
 
         #[test]
@@ -912,7 +912,7 @@ mod tests {
                     assert_eq!(uninstall.tools, vec!["typescript", "cowsay", "ember-cli"]);
                 }
                 _ => panic!("Doesn't parse global remove as a global"),
-            };
+            }
         }
 
         #[test]
@@ -934,7 +934,7 @@ mod tests {
                     assert_eq!(install.tools, vec!["ember-cli", "typescript"]);
                 }
                 _ => panic!("Doesn't parse global add as a global"),
-            };
+            }
 
             match CommandArg::for_yarn(&arg_list([
                 "global",
@@ -948,7 +948,7 @@ mod tests {
                     assert_eq!(uninstall.tools, vec!["ember-cli", "typescript"]);
                 }
                 _ => panic!("Doesn't parse global add as a global"),
-            };
+            }
         }
     }
 }
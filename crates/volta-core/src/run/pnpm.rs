@@ -60,7 +60,8 @@ fn validate_platform_pnpm(platform: &Platform) -> Fallible<()> {
         Some(_) => Ok(()),
         None => match platform.node.source {
             Source::Project => Err(ErrorKind::Platform(PlatformError::NoProjectPnpm).into()),
-            Source::Default | Source::Binary => {
+            // Fallback sources only have a Node version, so this is missing from the default too
+            Source::Default | Source::Binary | Source::Fallback(_) => {
                 Err(ErrorKind::Platform(PlatformError::NoDefaultPnpm).into())
             }
             Source::CommandLine => Err(CommandError::NoPnpmSpecified.into()),
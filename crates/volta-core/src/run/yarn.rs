@@ -65,7 +65,8 @@ fn validate_platform_yarn(platform: &Platform) -> Fallible<()> {
         Some(_) => Ok(()),
         None => match platform.node.source {
             Source::Project => Err(ErrorKind::Platform(PlatformError::NoProjectYarn).into()),
-            Source::Default | Source::Binary => {
+            // Fallback sources only have a Node version, so this is missing from the default too
+            Source::Default | Source::Binary | Source::Fallback(_) => {
                 Err(ErrorKind::Platform(PlatformError::NoDefaultYarn).into())
             }
             Source::CommandLine => Err(CommandError::NoYarnSpecified.into()),
@@ -6,13 +6,14 @@ use std::env;
 use std::fmt::{self, Display, Formatter};
 use std::process::exit;
 
-use crate::VOLTA_FEATURE_PNPM;
 use crate::error::{ExitCode, Fallible, VoltaError};
 use crate::event::Log;
 use crate::hook::{HookConfig, LazyHookConfig};
 use crate::platform::PlatformSpec;
-use crate::project::{LazyProject, Project};
+use crate::project::{FallbackPlatform, LazyProject, Project};
+use crate::settings::{LazySettings, Settings};
 use crate::toolchain::{LazyToolchain, Toolchain};
+use crate::{VOLTA_FEATURE_PNPM, VOLTA_OFFLINE, env_flag};
 use log::debug;
 
 #[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
@@ -20,8 +21,10 @@ pub enum ActivityKind {
     Fetch,
     Install,
     Uninstall,
+    Prune,
     Update,
     List,
+    ListRemote,
     Current,
     Default,
     Pin,
@@ -38,6 +41,7 @@ pub enum ActivityKind {
     Shim,
     Completions,
     Which,
+    Verify,
     Setup,
     Run,
     Args,
@@ -49,8 +53,10 @@ impl Display for ActivityKind {
             Self::Fetch => "fetch",
             Self::Install => "install",
             Self::Uninstall => "uninstall",
+            Self::Prune => "prune",
             Self::Update => "update",
             Self::List => "list",
+            Self::ListRemote => "list-remote",
             Self::Current => "current",
             Self::Default => "default",
             Self::Pin => "pin",
@@ -68,6 +74,7 @@ impl Display for ActivityKind {
             Self::Shim => "shim",
             Self::Completions => "completions",
             Self::Which => "which",
+            Self::Verify => "verify",
             Self::Run => "run",
             Self::Args => "args",
         };
@@ -82,14 +89,18 @@ impl Display for ActivityKind {
 /// - the current directory
 /// - the Node project tree that contains the current directory (if any)
 /// - the Volta hook configuration
+/// - the Volta settings
 /// - the inventory of locally-fetched Volta tools
 pub struct Session {
     hooks: LazyHookConfig,
+    settings: LazySettings,
     toolchain: LazyToolchain,
     project: LazyProject,
     event_log: Log,
     /// Cached result of checking `VOLTA_FEATURE_PNPM` environment variable
     pnpm_enabled: bool,
+    /// Whether tools must be resolved from the local inventory, without network access
+    offline: bool,
 }
 
 impl Session {
@@ -98,10 +109,12 @@ impl Session {
     pub fn init() -> Self {
         Self {
             hooks: LazyHookConfig::init(),
+            settings: LazySettings::init(),
             toolchain: LazyToolchain::init(),
             project: LazyProject::init(),
             event_log: Log::init(),
             pnpm_enabled: env::var_os(VOLTA_FEATURE_PNPM).is_some(),
+            offline: env_flag(VOLTA_OFFLINE),
         }
     }
 
@@ -111,6 +124,17 @@ impl Session {
         self.pnpm_enabled
     }
 
+    /// Returns whether offline mode is enabled, either with `VOLTA_OFFLINE` or `--offline`.
+    #[must_use]
+    pub const fn offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Enables offline mode for the rest of the session.
+    pub const fn enable_offline(&mut self) {
+        self.offline = true;
+    }
+
     /// Produces a reference to the current Node project, if any.
     ///
     /// # Errors
@@ -129,6 +153,11 @@ impl Session {
         self.project.get_mut()
     }
 
+    /// Discards the loaded project, so that it is loaded again from the filesystem when next used
+    pub fn reload_project(&mut self) {
+        self.project = LazyProject::init();
+    }
+
     /// Returns the user's default platform, if any
     ///
     /// # Errors
@@ -150,6 +179,17 @@ impl Session {
         Ok(None)
     }
 
+    /// Returns the platform from the fallback sources in the settings, such as `.nvmrc`, if the
+    /// current project doesn't pin its own platform.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a fallback source cannot be read or its version cannot be resolved.
+    pub fn fallback_platform(&self) -> Fallible<Option<&FallbackPlatform>> {
+        self.project()?
+            .map_or_else(|| Ok(None), |project| project.resolve_fallback(self))
+    }
+
     /// Produces a reference to the current toolchain (default platform specification)
     ///
     /// # Errors
@@ -177,6 +217,15 @@ impl Session {
         self.hooks.get(self.project()?)
     }
 
+    /// Produces a reference to the Volta settings
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the settings cannot be loaded.
+    pub fn settings(&self) -> Fallible<&Settings> {
+        self.settings.get(self.project()?)
+    }
+
     pub fn add_event_start(&mut self, activity_kind: ActivityKind) {
         self.event_log.add_event_start(activity_kind);
     }
@@ -194,6 +243,7 @@ impl Session {
         let Self {
             project,
             hooks,
+            settings,
             mut event_log,
             ..
         } = self;
@@ -204,7 +254,15 @@ impl Session {
         match plugin_res {
             Ok(plugin) => {
                 event_log.add_event_args();
-                event_log.publish(plugin);
+                let default_settings = Settings::default();
+                let settings = project
+                    .get()
+                    .and_then(|p| settings.get(p))
+                    .unwrap_or_else(|e| {
+                        debug!("Unable to load settings for publishing the event log.\n{e}");
+                        &default_settings
+                    });
+                event_log.publish(plugin, settings);
             }
             Err(e) => {
                 debug!("Unable to publish event log.\n{e}");
@@ -0,0 +1,321 @@
+//! Provides types for working with Volta settings, which configure where tools are downloaded from
+//! and how Volta connects to the network.
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::iter::once;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Context, EnvironmentError, Fallible, FilesystemError};
+use crate::http::LazyClient;
+use crate::layout::volta_home;
+use crate::project::{FallbackSource, Project};
+use log::debug;
+use once_cell::unsync::OnceCell;
+use serde::Deserialize;
+
+const PUBLIC_NODE_SERVER_ROOT: &str = "https://nodejs.org/dist";
+const PUBLIC_NPM_REGISTRY: &str = "https://registry.npmjs.org";
+
+/// Lazily loaded Volta settings
+#[allow(clippy::module_name_repetitions)]
+pub struct LazySettings {
+    settings: OnceCell<Settings>,
+}
+
+impl LazySettings {
+    /// Constructs a new `LazySettings`
+    #[must_use]
+    pub const fn init() -> Self {
+        Self {
+            settings: OnceCell::new(),
+        }
+    }
+
+    /// Forces the loading of the settings from both project-local and user-default settings files
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a settings file cannot be read or parsed.
+    pub fn get(&self, project: Option<&Project>) -> Fallible<&Settings> {
+        self.settings.get_or_try_init(|| Settings::current(project))
+    }
+}
+
+/// Volta settings
+///
+/// Hooks for a specific tool take precedence over these settings.
+#[derive(Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    /// The root URL of a mirror of the Node distribution server, e.g. `https://nodejs.org/dist`
+    node_mirror: Option<String>,
+
+    /// The root URL of an npm registry, used for npm, pnpm, and Yarn
+    npm_registry: Option<String>,
+
+    /// The proxy for plain HTTP requests, taking precedence over `HTTP_PROXY`
+    http_proxy: Option<String>,
+
+    /// The proxy for HTTPS requests, taking precedence over `HTTPS_PROXY`
+    https_proxy: Option<String>,
+
+    /// A comma-separated list of hosts that are not proxied, taking precedence over `NO_PROXY`
+    no_proxy: Option<String>,
+
+    /// A PEM bundle of certificate authorities to trust in addition to the system store
+    ///
+    /// Relative paths are resolved against the directory containing the settings file.
+    ca_file: Option<PathBuf>,
+
+    /// The sources of a Node version to use in projects that don't pin one, in order of
+    /// precedence, e.g. `[".nvmrc", ".node-version", "engines.node"]`
+    fallback_sources: Option<Vec<FallbackSource>>,
+
+    /// The HTTP client configured from these settings, created when it is first used
+    #[serde(skip)]
+    client: LazyClient,
+}
+
+impl Settings {
+    /// The root URL of the Node distribution server
+    #[must_use]
+    pub fn node_server_root(&self) -> &str {
+        self.node_mirror
+            .as_deref()
+            .map_or(PUBLIC_NODE_SERVER_ROOT, |mirror| {
+                mirror.trim_end_matches('/')
+            })
+    }
+
+    /// The URL of the index of available Node versions
+    #[must_use]
+    pub fn node_version_index(&self) -> String {
+        format!("{}/index.json", self.node_server_root())
+    }
+
+    /// The root URL of the npm registry
+    #[must_use]
+    pub fn npm_registry(&self) -> &str {
+        self.npm_registry
+            .as_deref()
+            .map_or(PUBLIC_NPM_REGISTRY, |registry| {
+                registry.trim_end_matches('/')
+            })
+    }
+
+    /// The proxy for plain HTTP requests, if configured
+    #[must_use]
+    pub fn http_proxy(&self) -> Option<&str> {
+        self.http_proxy.as_deref()
+    }
+
+    /// The proxy for HTTPS requests, if configured
+    #[must_use]
+    pub fn https_proxy(&self) -> Option<&str> {
+        self.https_proxy.as_deref()
+    }
+
+    /// The comma-separated list of hosts that are not proxied, if configured
+    #[must_use]
+    pub fn no_proxy(&self) -> Option<&str> {
+        self.no_proxy.as_deref()
+    }
+
+    /// The PEM bundle of extra certificate authorities, if configured
+    #[must_use]
+    pub fn ca_file(&self) -> Option<&Path> {
+        self.ca_file.as_deref()
+    }
+
+    /// The HTTP client configured from these settings
+    pub(crate) const fn lazy_client(&self) -> &LazyClient {
+        &self.client
+    }
+
+    /// The sources of a Node version for projects that don't pin one, in order of precedence
+    #[must_use]
+    pub fn fallback_sources(&self) -> &[FallbackSource] {
+        self.fallback_sources.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the current settings, which are a merge between the user settings and the project
+    /// settings (if any).
+    fn current(project: Option<&Project>) -> Fallible<Self> {
+        let default_settings_file = volta_home()?.default_settings_file();
+
+        // Project settings take precedence over the user settings, following the same order as
+        // the hooks (workspace_roots is already sorted in descending precedence order)
+        let paths = project
+            .into_iter()
+            .flat_map(Project::workspace_roots)
+            .map(|root| {
+                let mut path = root.join(".volta");
+                path.push("settings.json");
+                Cow::Owned(path)
+            })
+            .chain(once(Cow::Borrowed(default_settings_file)));
+
+        Self::from_paths(paths)
+    }
+
+    /// Returns the merged settings loaded from an iterator of potential settings files
+    ///
+    /// `paths` should be sorted in order of descending precedence.
+    fn from_paths<P, I>(paths: I) -> Fallible<Self>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = P>,
+    {
+        paths
+            .into_iter()
+            .try_fold(Self::default(), |acc, settings_file| {
+                Ok(match Self::from_file(settings_file.as_ref())? {
+                    Some(settings) => {
+                        debug!("Loaded settings file: {}", settings_file.as_ref().display());
+                        acc.merge(settings)
+                    }
+                    None => acc,
+                })
+            })
+    }
+
+    fn from_file(file_path: &Path) -> Fallible<Option<Self>> {
+        if !file_path.is_file() {
+            return Ok(None);
+        }
+
+        let file = File::open(file_path).with_context(|| FilesystemError::ReadSettings {
+            file: file_path.to_path_buf(),
+        })?;
+
+        let mut settings: Self =
+            serde_json::de::from_reader(file).with_context(|| EnvironmentError::ParseSettings {
+                file: file_path.to_path_buf(),
+            })?;
+
+        if let (Some(ca_file), Some(dir)) = (settings.ca_file.as_mut(), file_path.parent()) {
+            *ca_file = dir.join(&*ca_file);
+        }
+
+        Ok(Some(settings))
+    }
+
+    /// Merges these `Settings` with another, giving precedence to the current instance
+    fn merge(self, other: Self) -> Self {
+        Self {
+            node_mirror: self.node_mirror.or(other.node_mirror),
+            npm_registry: self.npm_registry.or(other.npm_registry),
+            http_proxy: self.http_proxy.or(other.http_proxy),
+            https_proxy: self.https_proxy.or(other.https_proxy),
+            no_proxy: self.no_proxy.or(other.no_proxy),
+            ca_file: self.ca_file.or(other.ca_file),
+            fallback_sources: self.fallback_sources.or(other.fallback_sources),
+            client: LazyClient::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Settings;
+    use crate::project::FallbackSource;
+    use std::path::PathBuf;
+
+    fn fixture_path(fixture_dir: &str) -> PathBuf {
+        let mut fixture_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        fixture_path.push("fixtures");
+        fixture_path.push(fixture_dir);
+        fixture_path
+    }
+
+    #[test]
+    fn test_defaults() {
+        let settings = Settings::default();
+        assert_eq!(settings.node_server_root(), "https://nodejs.org/dist");
+        assert_eq!(
+            settings.node_version_index(),
+            "https://nodejs.org/dist/index.json"
+        );
+        assert_eq!(settings.npm_registry(), "https://registry.npmjs.org");
+        assert!(settings.fallback_sources().is_empty());
+    }
+
+    #[test]
+    fn test_from_file() {
+        let settings_file = fixture_path("settings/settings.json");
+        let settings = Settings::from_file(&settings_file)
+            .expect("Could not parse settings.json")
+            .expect("Could not find settings.json");
+
+        // Trailing slashes are removed
+        assert_eq!(
+            settings.node_server_root(),
+            "https://mirror.example.com/node/dist"
+        );
+        assert_eq!(
+            settings.node_version_index(),
+            "https://mirror.example.com/node/dist/index.json"
+        );
+        assert_eq!(
+            settings.npm_registry(),
+            "https://mirror.example.com/npm/registry"
+        );
+        assert_eq!(
+            settings.https_proxy(),
+            Some("http://proxy.example.com:8080")
+        );
+        assert_eq!(settings.http_proxy(), None);
+        assert_eq!(settings.no_proxy(), Some("localhost,.internal.example.com"));
+
+        // The CA bundle is relative to the settings file
+        assert_eq!(
+            settings.ca_file(),
+            Some(fixture_path("settings/certs/ca.pem").as_path())
+        );
+        assert_eq!(
+            settings.fallback_sources(),
+            [FallbackSource::Nvmrc, FallbackSource::Engines]
+        );
+    }
+
+    #[test]
+    fn test_from_paths() {
+        let project_settings_file = fixture_path("settings/project/.volta/settings.json");
+        let default_settings_file = fixture_path("settings/settings.json");
+        let missing_settings_file = fixture_path("settings/missing/settings.json");
+
+        let settings = Settings::from_paths([
+            missing_settings_file,
+            project_settings_file,
+            default_settings_file,
+        ])
+        .expect("Could not load settings");
+
+        // The project mirror takes precedence, while the registry is inherited
+        assert_eq!(
+            settings.node_server_root(),
+            "https://project.example.com/node/dist"
+        );
+        assert_eq!(
+            settings.npm_registry(),
+            "https://mirror.example.com/npm/registry"
+        );
+        assert_eq!(
+            settings.https_proxy(),
+            Some("http://project.example.com:3128")
+        );
+        assert_eq!(settings.no_proxy(), Some("localhost,.internal.example.com"));
+
+        // The list of fallback sources is replaced as a whole
+        assert_eq!(settings.fallback_sources(), [FallbackSource::NodeVersion]);
+    }
+
+    #[test]
+    fn test_from_paths_none() {
+        let settings = Settings::from_paths([fixture_path("settings/missing/settings.json")])
+            .expect("Could not load settings");
+
+        assert_eq!(settings, Settings::default());
+    }
+}
@@ -1,12 +1,13 @@
 //! The view layer of Volta, with utilities for styling command-line output.
 use std::borrow::Cow;
 use std::error::Error;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use archive::Origin;
 use cfg_if::cfg_if;
+use chrono::{DateTime, SecondsFormat, Utc};
 use console::{StyledObject, style};
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{HumanBytes, HumanDuration, ProgressBar, ProgressStyle};
 use terminal_size::{Width, terminal_size};
 
 pub const MAX_WIDTH: usize = 100;
@@ -50,6 +51,29 @@ where
     format!("{name:}@{version:}")
 }
 
+/// Format a size in bytes for display, like `12.50 MiB`
+#[must_use]
+pub fn file_size(bytes: u64) -> String {
+    HumanBytes(bytes).to_string()
+}
+
+/// Format how long ago a time was, like `3 days ago`
+#[must_use]
+pub fn time_ago(time: SystemTime) -> String {
+    match time.elapsed() {
+        Ok(elapsed) if elapsed >= Duration::from_secs(1) => {
+            format!("{} ago", HumanDuration(elapsed))
+        }
+        _ => String::from("just now"),
+    }
+}
+
+/// Format a time as an RFC 3339 timestamp in UTC, like `2024-07-08T12:30:00Z`
+#[must_use]
+pub fn timestamp(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
 /// Get the width of the terminal, limited to a maximum of `MAX_WIDTH`
 #[must_use]
 pub fn text_width() -> Option<usize> {
@@ -0,0 +1,357 @@
+//! Provides a cache of registry metadata fetched over HTTP, keyed by URL and `Accept` header
+//!
+//! Each entry is a single file in the HTTP cache directory, containing a line of JSON with the
+//! URL, `Accept` header and caching headers of the response, followed by the response body. An
+//! entry is used as-is until it expires according to the `Cache-Control` or `Expires` headers,
+//! after which it is revalidated with the `ETag` and `Last-Modified` headers.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::registry_fetch_error;
+use crate::error::{Context, Fallible, FilesystemError};
+use crate::fs::{
+    create_staging_file, ensure_containing_dir_exists, read_file, remove_file_if_exists,
+};
+use crate::layout::volta_home;
+use attohttpc::header::{
+    ACCEPT, ETAG, HeaderMap, HeaderName, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use attohttpc::{Response, Session, StatusCode};
+use headers::{CacheControl, Expires, HeaderMapExt};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// How long a response without any caching headers is considered fresh
+const DEFAULT_MAX_AGE: Duration = Duration::from_hours(4);
+
+/// The URL and caching headers of a cached response
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct CacheMetadata {
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    accept: Option<String>,
+    expires: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_modified: Option<String>,
+}
+
+impl CacheMetadata {
+    /// Build the metadata of a response, or `None` if it must not be stored
+    ///
+    /// A revalidated response may not repeat the validators, so those of the `previous` metadata
+    /// are kept in that case.
+    fn from_headers(
+        url: &str,
+        accept: Option<&str>,
+        headers: &HeaderMap,
+        previous: Option<Self>,
+    ) -> Option<Self> {
+        let expires = expires_at(headers, SystemTime::now())?;
+        let (etag, last_modified) = previous.map_or((None, None), |previous| {
+            (previous.etag, previous.last_modified)
+        });
+
+        Some(Self {
+            url: url.to_owned(),
+            accept: accept.map(str::to_owned),
+            expires: httpdate::fmt_http_date(expires),
+            etag: header_value(headers, &ETAG).or(etag),
+            last_modified: header_value(headers, &LAST_MODIFIED).or(last_modified),
+        })
+    }
+}
+
+struct CacheEntry {
+    metadata: CacheMetadata,
+    body: String,
+}
+
+impl CacheEntry {
+    /// Parse a cache file, ignoring it if it is invalid or belongs to a different request
+    fn parse(content: &str, url: &str, accept: Option<&str>) -> Option<Self> {
+        let (metadata, body) = content.split_once('\n')?;
+        let metadata: CacheMetadata = serde_json::from_str(metadata).ok()?;
+
+        (metadata.url == url && metadata.accept.as_deref() == accept).then(|| Self {
+            metadata,
+            body: body.to_owned(),
+        })
+    }
+
+    fn is_fresh(&self) -> bool {
+        httpdate::parse_http_date(&self.metadata.expires)
+            .is_ok_and(|expires| SystemTime::now() < expires)
+    }
+
+    /// Writes the entry, ignoring any failure since the URL can always be fetched again
+    fn save(&self, cache_file: &Path) {
+        if let Err(error) = self.write(cache_file) {
+            debug!(
+                "Could not write the cache of {}.\n{error}",
+                self.metadata.url
+            );
+        }
+    }
+
+    fn write(&self, cache_file: &Path) -> Fallible<()> {
+        let staging = create_staging_file()?;
+        let mut staging_file: &File = staging.as_file();
+
+        // The metadata is serialized without any whitespace, so it fits on a single line
+        serde_json::to_writer(staging_file, &self.metadata)
+            .map_err(std::io::Error::from)
+            .and_then(|()| writeln!(staging_file))
+            .and_then(|()| staging_file.write_all(self.body.as_bytes()))
+            .with_context(|| FilesystemError::WriteMetadataCache {
+                file: staging.path().to_path_buf(),
+            })?;
+
+        ensure_containing_dir_exists(&cache_file).with_context(|| {
+            FilesystemError::ContainingDir {
+                path: cache_file.to_owned(),
+            }
+        })?;
+        staging
+            .persist(cache_file)
+            .with_context(|| FilesystemError::WriteMetadataCache {
+                file: cache_file.to_owned(),
+            })?;
+
+        Ok(())
+    }
+}
+
+/// Fetch the body of a registry URL, using the cache when possible
+///
+/// Fresh cache entries are used without any network access, while expired entries are
+/// revalidated with the registry.
+pub fn fetch(client: &Session, tool: &str, url: &str, accept: Option<&str>) -> Fallible<String> {
+    let cache_file = cache_file(url, accept)?;
+    let cached = match read_entry(&cache_file, url, accept)? {
+        Some(entry) if entry.is_fresh() => {
+            debug!("Found valid cache of {url}");
+            return Ok(entry.body);
+        }
+        cached => cached,
+    };
+
+    let mut request = client.get(url);
+    if let Some(accept) = accept {
+        request = request.header(ACCEPT, accept);
+    }
+    if let Some(CacheEntry { metadata, .. }) = &cached {
+        debug!("Cache of {url} has expired, revalidating");
+        if let Some(etag) = &metadata.etag {
+            request = request.header(IF_NONE_MATCH, etag.as_str());
+        }
+        if let Some(last_modified) = &metadata.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified.as_str());
+        }
+    }
+
+    let response = request
+        .send()
+        .with_context(registry_fetch_error(tool, url))?;
+    let headers = response.headers().clone();
+
+    let (body, previous) = match cached {
+        Some(entry) if response.status() == StatusCode::NOT_MODIFIED => {
+            debug!("Cache of {url} is still valid");
+            (entry.body, Some(entry.metadata))
+        }
+        _ => {
+            let body = response
+                .error_for_status()
+                .and_then(Response::text)
+                .with_context(registry_fetch_error(tool, url))?;
+            (body, None)
+        }
+    };
+
+    if let Some(metadata) = CacheMetadata::from_headers(url, accept, &headers, previous) {
+        let entry = CacheEntry { metadata, body };
+        entry.save(&cache_file);
+        Ok(entry.body)
+    } else {
+        debug!("Not caching {url}, as the response must not be stored");
+        remove_file_if_exists(&cache_file)?;
+        Ok(body)
+    }
+}
+
+/// Read the cached body of a URL, even if it has expired
+pub fn read_stale(url: &str, accept: Option<&str>) -> Fallible<Option<String>> {
+    let cache_file = cache_file(url, accept)?;
+    Ok(read_entry(&cache_file, url, accept)?.map(|entry| entry.body))
+}
+
+/// Determine the file that caches a URL
+fn cache_file(url: &str, accept: Option<&str>) -> Fallible<PathBuf> {
+    Ok(volta_home()?.http_cache_file(&cache_key(url, accept)))
+}
+
+/// The SHA-256 digest of a URL and its `Accept` header, since registries send different
+/// documents depending on it
+fn cache_key(url: &str, accept: Option<&str>) -> String {
+    let mut hasher = Sha256::new_with_prefix(url.as_bytes());
+    if let Some(accept) = accept {
+        hasher.update(b"\n");
+        hasher.update(accept.as_bytes());
+    }
+
+    hasher
+        .finalize()
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+fn read_entry(cache_file: &Path, url: &str, accept: Option<&str>) -> Fallible<Option<CacheEntry>> {
+    let content = read_file(cache_file).with_context(|| FilesystemError::ReadMetadataCache {
+        file: cache_file.to_owned(),
+    })?;
+
+    Ok(content.and_then(|content| {
+        let entry = CacheEntry::parse(&content, url, accept);
+        if entry.is_none() {
+            debug!("Ignoring invalid cache of {url}");
+        }
+        entry
+    }))
+}
+
+/// Determine when a response expires, or `None` if it must not be stored
+///
+/// `Cache-Control` takes precedence over `Expires`, and responses without either are fresh for
+/// a few hours.
+fn expires_at(headers: &HeaderMap, now: SystemTime) -> Option<SystemTime> {
+    let cache_control = headers.typed_get::<CacheControl>();
+
+    match cache_control {
+        Some(cache_control) if cache_control.no_store() => None,
+        Some(cache_control) if cache_control.no_cache() => Some(now),
+        Some(cache_control) if cache_control.max_age().is_some() => {
+            cache_control.max_age().map(|max_age| now + max_age)
+        }
+        _ => Some(
+            headers
+                .typed_get::<Expires>()
+                .map_or(now + DEFAULT_MAX_AGE, SystemTime::from),
+        ),
+    }
+}
+
+fn header_value(headers: &HeaderMap, name: &HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use attohttpc::header::{CACHE_CONTROL, EXPIRES, HeaderValue};
+
+    const URL: &str = "https://registry.npmjs.org/npm";
+
+    fn headers(entries: &[(HeaderName, &str)]) -> HeaderMap {
+        entries
+            .iter()
+            .map(|(name, value)| (name.clone(), HeaderValue::from_str(value).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_expires_at() {
+        let now = httpdate::parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+
+        assert_eq!(expires_at(&headers(&[]), now), Some(now + DEFAULT_MAX_AGE));
+        assert_eq!(
+            expires_at(&headers(&[(CACHE_CONTROL, "public, max-age=300")]), now),
+            Some(now + Duration::from_mins(5))
+        );
+        assert_eq!(
+            expires_at(&headers(&[(CACHE_CONTROL, "no-cache")]), now),
+            Some(now)
+        );
+        assert_eq!(
+            expires_at(&headers(&[(CACHE_CONTROL, "no-store")]), now),
+            None
+        );
+        assert_eq!(
+            expires_at(&headers(&[(EXPIRES, "Sun, 06 Nov 1994 09:49:37 GMT")]), now),
+            Some(now + Duration::from_hours(1))
+        );
+        // Cache-Control takes precedence over Expires
+        assert_eq!(
+            expires_at(
+                &headers(&[
+                    (CACHE_CONTROL, "max-age=60"),
+                    (EXPIRES, "Sun, 06 Nov 1994 09:49:37 GMT")
+                ]),
+                now
+            ),
+            Some(now + Duration::from_mins(1))
+        );
+    }
+
+    #[test]
+    fn test_parse_entry() {
+        let content = format!(
+            "{{\"url\":\"{URL}\",\"expires\":\"Sun, 06 Nov 1994 08:49:37 GMT\",\"etag\":\"W/\\\"abc\\\"\"}}\n{{\"name\":\"npm\"}}\n"
+        );
+
+        let entry = CacheEntry::parse(&content, URL, None).expect("Could not parse entry");
+        assert_eq!(entry.metadata.etag.as_deref(), Some("W/\"abc\""));
+        assert_eq!(entry.metadata.last_modified, None);
+        assert_eq!(entry.body, "{\"name\":\"npm\"}\n");
+        assert!(!entry.is_fresh());
+
+        // Entries for a different URL or Accept header, or with invalid metadata are ignored
+        assert!(CacheEntry::parse(&content, "https://registry.npmjs.org/pnpm", None).is_none());
+        assert!(CacheEntry::parse(&content, URL, Some("application/json")).is_none());
+        assert!(CacheEntry::parse("{\"name\":\"npm\"}", URL, None).is_none());
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let accept = "application/vnd.npm.install-v1+json";
+
+        assert_eq!(cache_key(URL, None).len(), 64);
+        assert_eq!(cache_key(URL, Some(accept)), cache_key(URL, Some(accept)));
+        assert_ne!(cache_key(URL, Some(accept)), cache_key(URL, None));
+        assert_ne!(
+            cache_key(URL, Some(accept)),
+            cache_key(URL, Some("application/json"))
+        );
+    }
+
+    #[test]
+    fn test_serialize_metadata() {
+        let metadata = CacheMetadata {
+            url: URL.into(),
+            accept: Some("application/vnd.npm.install-v1+json".into()),
+            expires: "Sun, 06 Nov 1994 08:49:37 GMT".into(),
+            etag: Some("\"abc\"".into()),
+            last_modified: None,
+        };
+
+        let serialized = serde_json::to_string(&metadata).unwrap();
+        assert!(!serialized.contains('\n'));
+        assert_eq!(
+            serde_json::from_str::<CacheMetadata>(&serialized).unwrap(),
+            metadata
+        );
+    }
+}
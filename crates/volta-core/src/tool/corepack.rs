@@ -0,0 +1,156 @@
+//! Provides support for the Corepack `packageManager` field of a project, verifying its hash when
+//! a package manager is fetched and keeping it in sync when one is pinned
+
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+
+use super::integrity::Integrity;
+use super::package::PackageManager;
+use super::{Npm, Pnpm, Yarn};
+use crate::error::{Fallible, ToolError};
+use crate::layout::volta_home;
+use crate::project::Project;
+use crate::session::Session;
+use crate::style::{note_prefix, success_prefix, tool_version};
+use log::{debug, info, warn};
+use nodejs_semver::Version;
+
+/// Finds the hash to verify a package manager archive against, if the `packageManager` field of
+/// the project pins that exact version with a hash
+///
+/// Corepack hashes the single `yarn.js` bundle for Yarn 2 and later, rather than the registry
+/// tarball that Volta fetches, so those hashes can't be verified.
+pub(super) fn corepack_hash(
+    session: &Session,
+    manager: PackageManager,
+    version: &Version,
+) -> Fallible<Option<Integrity>> {
+    let Some(field) = session.project()?.and_then(Project::package_manager) else {
+        return Ok(None);
+    };
+    let Some(hash) = field.hash.as_deref() else {
+        return Ok(None);
+    };
+    if field.manager != manager || field.version != *version {
+        return Ok(None);
+    }
+
+    if manager == PackageManager::Yarn && version.major >= 2 {
+        debug!(
+            "Skipping `packageManager` hash check, Corepack hashes a different file for Yarn 2+"
+        );
+        return Ok(None);
+    }
+
+    Integrity::from_corepack(hash).map(Some).ok_or_else(|| {
+        ToolError::UnsupportedCorepackHash {
+            hash: hash.to_owned(),
+        }
+        .into()
+    })
+}
+
+/// Offers to update the `packageManager` field of the project after pinning a package manager
+/// version in the `volta` key, so that Corepack and Volta agree
+///
+/// The offer is only made in an interactive terminal. Otherwise, the field is left alone and the
+/// conflict is reported whenever the project is loaded. A hash in the field is recomputed for the
+/// new version, or dropped with a warning when it can't be, e.g. for Yarn 2 and later.
+pub(super) fn sync_package_manager(
+    session: &mut Session,
+    manager: PackageManager,
+    version: &Version,
+) -> Fallible<()> {
+    let Some(project) = session.project_mut()? else {
+        return Ok(());
+    };
+    let Some(field) = project.package_manager() else {
+        return Ok(());
+    };
+    if field.manager != manager || field.version == *version {
+        return Ok(());
+    }
+
+    let pinned = tool_version(field.name(), version);
+    let question = format!(
+        "Also update the `packageManager` field in {} from {} to {pinned}?",
+        field.file.display(),
+        tool_version(field.name(), &field.version),
+    );
+    if !confirm(&question) {
+        info!(
+            "{} the `packageManager` field in {} still asks for {}",
+            note_prefix(),
+            field.file.display(),
+            tool_version(field.name(), &field.version),
+        );
+        return Ok(());
+    }
+
+    // The hash is recomputed with the same algorithm, so that Corepack can still verify it
+    let hash = field.hash.as_deref().and_then(|previous| {
+        let hash = Integrity::from_corepack(previous)
+            .filter(|_| !(manager == PackageManager::Yarn && version.major >= 2))
+            .and_then(|previous| {
+                let archive = archive_file(manager, version).ok()?;
+                previous.digest_file(&archive).ok()
+            })
+            .map(|integrity| integrity.to_corepack());
+        if hash.is_none() {
+            warn!(
+                "Could not compute the hash of {pinned} for the `packageManager` field in {}, so \
+                 it was removed and Corepack will not verify it.",
+                field.file.display()
+            );
+        }
+        hash
+    });
+
+    project.update_package_manager(version.clone(), hash)?;
+    info!(
+        "{} updated the `packageManager` field to {pinned}",
+        success_prefix()
+    );
+
+    Ok(())
+}
+
+/// The archive of a package manager version in the inventory
+fn archive_file(manager: PackageManager, version: &Version) -> Fallible<PathBuf> {
+    let home = volta_home()?;
+    let version = version.to_string();
+
+    Ok(match manager {
+        PackageManager::Npm => home
+            .npm_inventory_dir()
+            .join(Npm::archive_filename(&version)),
+        PackageManager::Pnpm => home
+            .pnpm_inventory_dir()
+            .join(Pnpm::archive_filename(&version)),
+        PackageManager::Yarn => home
+            .yarn_inventory_dir()
+            .join(Yarn::archive_filename(&version)),
+    })
+}
+
+/// Asks a yes or no question in the terminal, defaulting to yes
+///
+/// Returns `false` without asking if either stdin or stderr isn't a terminal.
+fn confirm(question: &str) -> bool {
+    if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+        return false;
+    }
+
+    eprint!("{question} [Y/n] ");
+    let _ = io::stderr().flush();
+
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+
+    matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "" | "y" | "yes"
+    )
+}
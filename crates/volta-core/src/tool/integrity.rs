@@ -0,0 +1,401 @@
+//! Provides verification of registry tarballs against their published integrity, and against the
+//! Corepack hash of the project
+
+use std::fmt::{self, Write as _};
+use std::fs::{File, read_to_string, write};
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::error::{Fallible, ToolError};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use log::debug;
+use sha1::Sha1;
+use sha2::{Digest, Sha224, Sha256, Sha512};
+
+/// The hash algorithms supported for integrity checks, ordered from weakest to strongest
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Algorithm {
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha512,
+}
+
+impl Algorithm {
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "sha1" => Some(Self::Sha1),
+            "sha224" => Some(Self::Sha224),
+            "sha256" => Some(Self::Sha256),
+            "sha512" => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Sha1 => "sha1",
+            Self::Sha224 => "sha224",
+            Self::Sha256 => "sha256",
+            Self::Sha512 => "sha512",
+        })
+    }
+}
+
+/// The expected digest of a tarball, as published in the `dist` metadata of a registry
+///
+/// Displays as a Subresource Integrity string, e.g. `sha512-<base64 digest>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Integrity {
+    algorithm: Algorithm,
+    digest: Vec<u8>,
+}
+
+impl Integrity {
+    /// Determine the strongest integrity from the `dist.integrity` and `dist.shasum` fields
+    ///
+    /// The `integrity` field is a Subresource Integrity string, which may list several hashes
+    /// separated by whitespace. The legacy `shasum` field is a hex-encoded SHA-1 digest.
+    pub fn from_dist(integrity: Option<&str>, shasum: Option<&str>) -> Option<Self> {
+        integrity
+            .and_then(Self::parse_sri)
+            .or_else(|| shasum.and_then(Self::parse_shasum))
+    }
+
+    fn parse_sri(sri: &str) -> Option<Self> {
+        sri.split_whitespace()
+            .filter_map(|hash| {
+                let (prefix, digest) = hash.split_once('-')?;
+                let algorithm = Algorithm::from_prefix(prefix)?;
+                // Subresource Integrity allows options after the digest, separated by `?`
+                let digest = digest.split_once('?').map_or(digest, |(digest, _)| digest);
+                let digest = STANDARD.decode(digest).ok()?;
+                Some(Self { algorithm, digest })
+            })
+            .max_by_key(|integrity| integrity.algorithm)
+    }
+
+    fn parse_shasum(shasum: &str) -> Option<Self> {
+        if shasum.len() != 40 {
+            return None;
+        }
+
+        Some(Self {
+            algorithm: Algorithm::Sha1,
+            digest: decode_hex(shasum)?,
+        })
+    }
+
+    /// Parse the hash of a Corepack `packageManager` field, e.g. `sha512.<hex digest>`
+    pub fn from_corepack(hash: &str) -> Option<Self> {
+        let (prefix, digest) = hash.split_once('.')?;
+
+        Some(Self {
+            algorithm: Algorithm::from_prefix(prefix)?,
+            digest: decode_hex(digest)?,
+        })
+    }
+
+    /// Format as the hash of a Corepack `packageManager` field, e.g. `sha512.<hex digest>`
+    pub fn to_corepack(&self) -> String {
+        self.digest
+            .iter()
+            .fold(format!("{}.", self.algorithm), |mut hash, byte| {
+                let _ = write!(hash, "{byte:02x}");
+                hash
+            })
+    }
+
+    /// Create a checker that computes the digest of everything written to it
+    pub fn checker(&self) -> IntegrityChecker {
+        match self.algorithm {
+            Algorithm::Sha1 => IntegrityChecker::Sha1(Sha1::new()),
+            Algorithm::Sha224 => IntegrityChecker::Sha224(Sha224::new()),
+            Algorithm::Sha256 => IntegrityChecker::Sha256(Sha256::new()),
+            Algorithm::Sha512 => IntegrityChecker::Sha512(Sha512::new()),
+        }
+    }
+
+    /// Compute the digest of a file, using the same algorithm as this integrity
+    pub fn digest_file(&self, file: &Path) -> io::Result<Self> {
+        let mut checker = self.checker();
+        io::copy(&mut File::open(file)?, &mut checker)?;
+        Ok(checker.finish())
+    }
+}
+
+/// Load the integrity recorded next to a cached archive, if the archive is cached and there is a
+/// valid one
+///
+/// A cached archive was verified against its published integrity when it was downloaded, so the
+/// recorded integrity can be used instead of fetching the registry metadata again.
+pub fn load_recorded(archive: &Path, file: &Path) -> Option<Integrity> {
+    if !archive.is_file() {
+        return None;
+    }
+
+    let recorded = read_to_string(file).ok()?;
+    let integrity = Integrity::parse_sri(recorded.trim());
+    if integrity.is_none() {
+        debug!(
+            "Ignoring invalid integrity recorded at '{}'",
+            file.display()
+        );
+    }
+    integrity
+}
+
+/// Record the verified integrity of an archive next to it in the inventory
+///
+/// This only saves fetching the registry metadata again, so a failure is not an error.
+pub fn save_recorded(file: &Path, integrity: &Integrity) {
+    if let Err(error) = write(file, integrity.to_string()) {
+        debug!(
+            "Could not record the archive integrity at '{}': {error}",
+            file.display()
+        );
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+impl fmt::Display for Integrity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.algorithm, STANDARD.encode(&self.digest))
+    }
+}
+
+/// A running digest of a tarball, using the algorithm of the expected integrity
+pub enum IntegrityChecker {
+    Sha1(Sha1),
+    Sha224(Sha224),
+    Sha256(Sha256),
+    Sha512(Sha512),
+}
+
+impl IntegrityChecker {
+    fn finish(self) -> Integrity {
+        let (algorithm, digest) = match self {
+            Self::Sha1(digest) => (Algorithm::Sha1, digest.finalize().to_vec()),
+            Self::Sha224(digest) => (Algorithm::Sha224, digest.finalize().to_vec()),
+            Self::Sha256(digest) => (Algorithm::Sha256, digest.finalize().to_vec()),
+            Self::Sha512(digest) => (Algorithm::Sha512, digest.finalize().to_vec()),
+        };
+
+        Integrity { algorithm, digest }
+    }
+
+    /// Compare the finished digest to the expected integrity
+    pub fn verify(self, expected: &Integrity, tool: &str, version: &str) -> Fallible<()> {
+        let actual = self.finish();
+
+        if actual == *expected {
+            debug!("Verified {tool} archive integrity ({actual})");
+            Ok(())
+        } else {
+            Err(ToolError::IntegrityMismatch {
+                tool: tool.into(),
+                version: version.into(),
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            }
+            .into())
+        }
+    }
+}
+
+impl Write for IntegrityChecker {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Sha1(digest) => digest.update(buf),
+            Self::Sha224(digest) => digest.update(buf),
+            Self::Sha256(digest) => digest.update(buf),
+            Self::Sha512(digest) => digest.update(buf),
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The running digests of a tarball as it is unpacked, for the integrity published by the
+/// registry (if any) and the Corepack hash in the `packageManager` field of the project (if any)
+pub struct ArchiveChecker<'a> {
+    registry: Option<(&'a Integrity, IntegrityChecker)>,
+    corepack: Option<(&'a Integrity, IntegrityChecker)>,
+}
+
+impl<'a> ArchiveChecker<'a> {
+    pub fn new(registry: Option<&'a Integrity>, corepack: Option<&'a Integrity>) -> Self {
+        Self {
+            registry: registry.map(|integrity| (integrity, integrity.checker())),
+            corepack: corepack.map(|hash| (hash, hash.checker())),
+        }
+    }
+
+    /// Compare the finished digests to the expected integrity and Corepack hash
+    pub fn verify(self, tool: &str, version: &str) -> Fallible<()> {
+        if let Some((expected, checker)) = self.registry {
+            checker.verify(expected, tool, version)?;
+        }
+
+        if let Some((expected, checker)) = self.corepack {
+            let actual = checker.finish();
+            if actual != *expected {
+                return Err(ToolError::CorepackHashMismatch {
+                    tool: tool.into(),
+                    version: version.into(),
+                    expected: expected.to_corepack(),
+                    actual: actual.to_corepack(),
+                }
+                .into());
+            }
+
+            debug!(
+                "Verified {tool} archive against the `packageManager` hash ({})",
+                actual.to_corepack()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Write for ArchiveChecker<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for (_, checker) in self.registry.iter_mut().chain(self.corepack.iter_mut()) {
+            checker.write_all(buf)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Hex-encoded SHA-1 digest of the string "volta"
+    const SHA1_HEX: &str = "d11c07a27a6ff38fa64da74f441ca4bcf3d784fc";
+
+    fn digest_of<D: Digest>(content: &[u8]) -> String {
+        STANDARD.encode(D::digest(content))
+    }
+
+    #[test]
+    fn test_from_dist_prefers_strongest() {
+        let sha512 = format!("sha512-{}", digest_of::<Sha512>(b"volta"));
+        let sha1 = format!("sha1-{}", digest_of::<Sha1>(b"volta"));
+
+        let integrity = Integrity::from_dist(Some(&format!("{sha1} {sha512}")), Some(SHA1_HEX));
+        assert_eq!(integrity.unwrap().to_string(), sha512);
+
+        // Unsupported algorithms are ignored
+        let integrity = Integrity::from_dist(Some(&format!("sha384-abcd {sha1}")), None);
+        assert_eq!(integrity.unwrap().to_string(), sha1);
+
+        // Options after the digest are ignored
+        let integrity = Integrity::from_dist(Some(&format!("{sha512}?opt")), None);
+        assert_eq!(integrity.unwrap().to_string(), sha512);
+    }
+
+    #[test]
+    fn test_from_dist_shasum_fallback() {
+        let sha1 = format!("sha1-{}", digest_of::<Sha1>(b"volta"));
+        let integrity = Integrity::from_dist(None, Some(SHA1_HEX)).unwrap();
+        assert_eq!(integrity.to_string(), sha1);
+
+        let integrity = Integrity::from_dist(Some("md5-abcd"), Some(SHA1_HEX)).unwrap();
+        assert_eq!(integrity.algorithm, Algorithm::Sha1);
+
+        assert_eq!(Integrity::from_dist(None, Some("not-a-shasum")), None);
+        assert_eq!(Integrity::from_dist(None, None), None);
+    }
+
+    #[test]
+    fn test_verify() {
+        for expected in [
+            format!("sha512-{}", digest_of::<Sha512>(b"volta")),
+            format!("sha1-{}", digest_of::<Sha1>(b"volta")),
+        ] {
+            let expected = Integrity::from_dist(Some(&expected), None).unwrap();
+
+            let mut checker = expected.checker();
+            checker.write_all(b"volta").unwrap();
+            assert!(checker.verify(&expected, "npm", "1.0.0").is_ok());
+
+            let mut checker = expected.checker();
+            checker.write_all(b"volt").unwrap();
+            assert!(checker.verify(&expected, "npm", "1.0.0").is_err());
+        }
+    }
+
+    #[test]
+    fn test_corepack_hash() {
+        let hash = format!("sha1.{SHA1_HEX}");
+        let integrity = Integrity::from_corepack(&hash).unwrap();
+        assert_eq!(
+            integrity,
+            Integrity::from_dist(None, Some(SHA1_HEX)).unwrap()
+        );
+        assert_eq!(integrity.to_corepack(), hash);
+
+        assert_eq!(Integrity::from_corepack("sha384.abcd"), None);
+        assert_eq!(Integrity::from_corepack("sha512.not-hex"), None);
+        assert_eq!(Integrity::from_corepack(SHA1_HEX), None);
+    }
+
+    #[test]
+    fn test_archive_checker() {
+        let registry = Integrity::from_dist(None, Some(SHA1_HEX)).unwrap();
+        let corepack = Integrity::from_corepack(&format!("sha1.{SHA1_HEX}")).unwrap();
+        let other = Integrity::from_corepack(&format!("sha1.{}", "0".repeat(40))).unwrap();
+
+        let mut checker = ArchiveChecker::new(Some(&registry), Some(&corepack));
+        checker.write_all(b"volta").unwrap();
+        assert!(checker.verify("pnpm", "9.1.0").is_ok());
+
+        let mut checker = ArchiveChecker::new(None, Some(&other));
+        checker.write_all(b"volta").unwrap();
+        assert!(checker.verify("pnpm", "9.1.0").is_err());
+
+        let mut checker = ArchiveChecker::new(None, None);
+        checker.write_all(b"volta").unwrap();
+        assert!(checker.verify("Yarn", "1.22.22").is_ok());
+    }
+
+    #[test]
+    fn test_recorded_integrity() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("npm-8.1.5.tgz");
+        let file = dir.path().join("npm-8.1.5.tgz.integrity");
+        let integrity = Integrity::from_dist(None, Some(SHA1_HEX)).unwrap();
+
+        save_recorded(&file, &integrity);
+        // Without the archive, the recorded integrity is not used
+        assert_eq!(load_recorded(&archive, &file), None);
+
+        std::fs::write(&archive, b"volta").unwrap();
+        assert_eq!(load_recorded(&archive, &file), Some(integrity));
+
+        std::fs::write(&file, "not an integrity").unwrap();
+        assert_eq!(load_recorded(&archive, &file), None);
+    }
+}
@@ -0,0 +1,223 @@
+//! Provides resolution of the ranges and tags in the `volta` key of a project into the project
+//! lockfile, and verification of fetched archives against the checksums recorded there
+
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+use std::path::PathBuf;
+
+use super::integrity::Integrity;
+use super::{Node, Npm, Pnpm, Yarn, node, npm, pnpm, yarn};
+use crate::error::{Fallible, PackageError, ToolError};
+use crate::project::{LOCKFILE_NAME, Lockfile, ManifestKey, RequestedPin};
+use crate::session::Session;
+use crate::style::{success_prefix, tool_version};
+use crate::sync::VoltaLock;
+use log::{debug, info};
+use nodejs_semver::Version;
+
+/// Resolves the ranges and tags in the `volta` key of the current project, and records the
+/// versions they resolved to in the project lockfile
+///
+/// Only the ranges and tags that aren't in the lockfile yet are resolved, unless `refresh` is
+/// set, in which case all of them are resolved again.
+///
+/// # Errors
+///
+/// Returns an error if a version cannot be resolved or the lockfile cannot be written.
+pub fn resolve_project_pins(session: &mut Session, refresh: bool) -> Fallible<()> {
+    if pending_pins(session, refresh)?.is_empty() {
+        return Ok(());
+    }
+
+    // Shims started together would otherwise all resolve the same pins over the network, so
+    // they are resolved under the lock, after reading the lockfile again in case another
+    // process locked them in the meantime
+    let _lock = VoltaLock::acquire();
+    session.reload_project();
+    let pins = pending_pins(session, refresh)?;
+    if pins.is_empty() {
+        debug!("Project pins were locked by another process");
+        return Ok(());
+    }
+
+    let mut lockfiles: BTreeMap<PathBuf, Lockfile> = BTreeMap::new();
+    for pin in &pins {
+        let Some(version) = resolve_pin(session, pin)? else {
+            continue;
+        };
+        let checksum = if session.offline() {
+            debug!(
+                "Skipping checksum of {}, it will be recorded by `volta pin --refresh`",
+                tool_version(pin.key, &version)
+            );
+            None
+        } else {
+            published_checksum(session, pin.key, &version)?
+        };
+
+        let lockfile = match lockfiles.entry(pin.lock_file.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                entry.insert(Lockfile::from_file(&pin.lock_file)?.unwrap_or_default())
+            }
+        };
+
+        info!(
+            "{} locked {} to {}",
+            success_prefix(),
+            tool_version(pin.key, &pin.requested),
+            tool_version(pin.key, &version)
+        );
+        lockfile.lock(
+            pin,
+            version,
+            checksum.map(|(filename, integrity)| (filename, integrity.to_string())),
+        );
+    }
+
+    for (file, lockfile) in &lockfiles {
+        lockfile.write(file)?;
+        debug!("Wrote project lockfile to {}", file.display());
+    }
+
+    // The project is loaded again, so that its platform has the versions in the new lockfile
+    session.reload_project();
+    Ok(())
+}
+
+/// The ranges and tags in the `volta` key of the current project that need to be resolved
+fn pending_pins(session: &Session, refresh: bool) -> Fallible<Vec<RequestedPin>> {
+    Ok(session
+        .project()?
+        .map(|project| {
+            project
+                .requested_pins()
+                .iter()
+                .filter(|pin| refresh || pin.locked.is_none())
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Resolves a range or tag to an exact version
+fn resolve_pin(session: &mut Session, pin: &RequestedPin) -> Fallible<Option<Version>> {
+    let matching = pin.matching()?;
+    match pin.key {
+        ManifestKey::Node => node::resolve(matching, session).map(Some),
+        ManifestKey::Npm => npm::resolve(matching, session),
+        ManifestKey::Pnpm => pnpm::resolve(matching, session).map(Some),
+        ManifestKey::Yarn => yarn::resolve(matching, session).map(Some),
+    }
+}
+
+/// Fetches the published checksum of the archive of a version, along with the file name of the
+/// archive, which is used as its key in the lockfile
+///
+/// Package manager versions from a `distro` hook, Node versions from a `distro` hook whose
+/// mirror doesn't host the checksums, or Yarn versions from a legacy `yarn.index` hook, have no
+/// published checksum to record.
+fn published_checksum(
+    session: &Session,
+    key: ManifestKey,
+    version: &Version,
+) -> Fallible<Option<(String, Integrity)>> {
+    let settings = session.settings()?;
+    let hooks = session.hooks()?;
+
+    let integrity = match key {
+        ManifestKey::Node => node::archive_checksum(version, settings, hooks.node())?,
+        ManifestKey::Npm => npm::archive_checksum(version, settings, hooks.npm())?,
+        ManifestKey::Pnpm => pnpm::archive_checksum(version, settings, hooks.pnpm())?,
+        ManifestKey::Yarn => yarn::archive_checksum(version, settings, hooks.yarn())?,
+    };
+
+    Ok(integrity.map(|integrity| (archive_filename(key, version), integrity)))
+}
+
+/// The file name of the archive of a version, which is the key of its checksum in the lockfile
+///
+/// Node has a different archive for each platform, so the lockfile can record a checksum for
+/// each platform that the project is used on.
+fn archive_filename(key: ManifestKey, version: &Version) -> String {
+    match key {
+        ManifestKey::Node => Node::archive_filename(version),
+        ManifestKey::Npm => Npm::archive_filename(&version.to_string()),
+        ManifestKey::Pnpm => Pnpm::archive_filename(&version.to_string()),
+        ManifestKey::Yarn => Yarn::archive_filename(&version.to_string()),
+    }
+}
+
+/// A checksum recorded in the project lockfile, which the published checksum must match
+pub(super) struct LockedChecksum {
+    integrity: Integrity,
+    file: PathBuf,
+}
+
+impl LockedChecksum {
+    /// The locked integrity, for archives that have no published checksum to compare against
+    pub(super) const fn integrity(&self) -> &Integrity {
+        &self.integrity
+    }
+
+    /// Compares the published checksum of an archive to the locked one
+    pub(super) fn check(
+        &self,
+        published: &Integrity,
+        tool: &str,
+        version: &Version,
+    ) -> Fallible<()> {
+        if *published == self.integrity {
+            debug!(
+                "Verified published checksum of {tool} v{version} against {}",
+                self.file.display()
+            );
+            Ok(())
+        } else {
+            Err(ToolError::LockChecksumMismatch {
+                tool: tool.into(),
+                version: version.to_string(),
+                file: self.file.clone(),
+                expected: self.integrity.to_string(),
+                actual: published.to_string(),
+            }
+            .into())
+        }
+    }
+}
+
+/// Finds the checksum in the project lockfile for the archive of a version, if the version is
+/// locked and a checksum was recorded for its archive on this platform
+pub(super) fn locked_checksum(
+    session: &Session,
+    key: ManifestKey,
+    version: &Version,
+) -> Fallible<Option<LockedChecksum>> {
+    let Some(project) = session.project()? else {
+        return Ok(None);
+    };
+    let Some(pin) = project.locked_pin(key, version) else {
+        return Ok(None);
+    };
+    let Some(checksum) = pin.checksums.get(&archive_filename(key, version)) else {
+        debug!(
+            "No checksum for {} in {LOCKFILE_NAME}, it will be recorded by `volta pin --refresh`",
+            tool_version(key, version)
+        );
+        return Ok(None);
+    };
+
+    Integrity::from_dist(Some(checksum), None)
+        .map(|integrity| {
+            Some(LockedChecksum {
+                integrity,
+                file: pin.lock_file.clone(),
+            })
+        })
+        .ok_or_else(|| {
+            PackageError::LockfileParse {
+                file: pin.lock_file.clone(),
+            }
+            .into()
+        })
+}
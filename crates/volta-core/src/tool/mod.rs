@@ -1,30 +1,51 @@
+use std::collections::BTreeSet;
 use std::fmt::{self, Display};
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use crate::error::{ErrorKind, Fallible, NetworkError};
+use crate::error::{
+    Context, ErrorKind, Fallible, FilesystemError, NetworkError, ToolError, VersionError,
+};
+use crate::fs::{ensure_containing_dir_exists, remove_file_if_exists, rename};
 use crate::layout::volta_home;
 use crate::session::Session;
 use crate::style::{note_prefix, success_prefix, tool_version};
 use crate::sync::VoltaLock;
-use crate::version::VersionSpec;
+use crate::version::{Tag, VersionSpec};
 use cfg_if::cfg_if;
 use log::{debug, info};
+use nodejs_semver::Version;
 
+mod cache;
+mod corepack;
+mod integrity;
+mod lockfile;
 pub mod node;
 pub mod npm;
+mod outdated;
 pub mod package;
 pub mod pnpm;
+mod prune;
 mod registry;
+mod remote;
 mod serial;
+mod uninstall;
+mod verify;
 pub mod yarn;
 
+pub use lockfile::resolve_project_pins;
 pub use node::{
     NODE_DISTRO_ARCH, NODE_DISTRO_EXTENSION, NODE_DISTRO_OS, Node, load_default_npm_version,
 };
 pub use npm::{Bundled, Npm};
+pub use outdated::{VersionCheck, check_image, check_package};
 pub use package::{BinConfig, Package, PackageConfig, PackageManifest};
 pub use pnpm::Pnpm;
+pub use prune::prune;
 pub use registry::PackageDetails;
+pub use remote::{RemoteVersion, remote_versions};
+pub use uninstall::ImageKind;
+pub use verify::{Check, CheckKind, CheckStatus, verify_project};
 pub use yarn::Yarn;
 
 fn debug_already_fetched<T: Display>(tool: T) {
@@ -226,36 +247,25 @@ impl ToolSpec {
 
     /// Uninstall a tool, removing it from the local inventory
     ///
-    /// This is implemented on Spec, instead of Resolved, because there is currently no need to
-    /// resolve the specific version before uninstalling a tool.
+    /// This is implemented on Spec, instead of Resolved, because uninstalling only ever needs the
+    /// versions that are already available locally. Unless `force` is set, versions of Node and
+    /// the package managers that are still in use will not be removed.
     ///
     /// # Errors
     ///
     /// Returns an error if the tool cannot be uninstalled.
-    pub fn uninstall(self, pnpm_enabled: bool) -> Fallible<()> {
+    pub fn uninstall(self, session: &Session, force: bool) -> Fallible<()> {
         match self {
-            Self::Node(_) => Err(ErrorKind::Unimplemented {
-                feature: "Uninstalling node".into(),
-            }
-            .into()),
-            Self::Npm(_) => Err(ErrorKind::Unimplemented {
-                feature: "Uninstalling npm".into(),
-            }
-            .into()),
-            Self::Pnpm(_) => {
-                if pnpm_enabled {
-                    Err(ErrorKind::Unimplemented {
-                        feature: "Uninstalling pnpm".into(),
-                    }
-                    .into())
+            Self::Node(version) => uninstall::uninstall(ImageKind::Node, version, session, force),
+            Self::Npm(version) => uninstall::uninstall(ImageKind::Npm, version, session, force),
+            Self::Pnpm(version) => {
+                if session.pnpm_enabled() {
+                    uninstall::uninstall(ImageKind::Pnpm, version, session, force)
                 } else {
                     package::uninstall("pnpm")
                 }
             }
-            Self::Yarn(_) => Err(ErrorKind::Unimplemented {
-                feature: "Uninstalling yarn".into(),
-            }
-            .into()),
+            Self::Yarn(version) => uninstall::uninstall(ImageKind::Yarn, version, session, force),
             Self::Package(name, _) => package::uninstall(&name),
         }
     }
@@ -265,8 +275,8 @@ impl ToolSpec {
     pub fn name(&self) -> &str {
         match self {
             Self::Node(_) => "Node",
-            Self::Npm(_) => "Npm",
-            Self::Pnpm(_) => "Pnpm",
+            Self::Npm(_) => "npm",
+            Self::Pnpm(_) => "pnpm",
             Self::Yarn(_) => "Yarn",
             Self::Package(name, _) => name,
         }
@@ -325,6 +335,135 @@ where
     }
 }
 
+/// A download of a tool archive, staged in the Volta tmp directory until it has been verified
+///
+/// The staging file is named after the archive rather than randomly, so that a download that was
+/// interrupted, e.g. by a network failure, is resumed by the next attempt to fetch the tool. Tools
+/// are fetched while holding the Volta lock, so there is only one download of an archive at a time.
+struct StagedDownload {
+    path: PathBuf,
+    /// The size of the partial download left by an earlier attempt
+    resumed: u64,
+}
+
+impl StagedDownload {
+    fn new(archive_filename: &str) -> Fallible<Self> {
+        let path = volta_home()?
+            .tmp_dir()
+            .join(format!("{archive_filename}.partial"));
+        let resumed = fs::metadata(&path).map_or(0, |metadata| metadata.len());
+        Ok(Self { path, resumed })
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Moves the verified download into the inventory
+    fn persist(self, cache_file: &Path, tool: &str) -> Fallible<()> {
+        ensure_containing_dir_exists(&cache_file).with_context(|| {
+            ErrorKind::Filesystem(FilesystemError::ContainingDir {
+                path: cache_file.to_owned(),
+            })
+        })?;
+        rename(&self.path, cache_file)
+            .with_context(|| ErrorKind::Tool(ToolError::PersistInventory { tool: tool.into() }))?;
+        remove_file_if_exists(archive::validator_file(&self.path))
+    }
+
+    /// Removes a download that failed to unpack or verify
+    ///
+    /// A download that made progress but stopped short of the size of the archive was interrupted,
+    /// so it is kept for the next attempt to resume. Otherwise, the downloaded data is invalid, so
+    /// the next attempt starts over.
+    fn discard(self, archive_size: u64) -> Fallible<()> {
+        let size = fs::metadata(&self.path).map_or(0, |metadata| metadata.len());
+        if self.resumed < size && size < archive_size {
+            debug!(
+                "Keeping the interrupted download at '{}' to resume it later",
+                self.path.display()
+            );
+            return Ok(());
+        }
+
+        debug!("Removing invalid download at '{}'", self.path.display());
+        remove_file_if_exists(&self.path)?;
+        remove_file_if_exists(archive::validator_file(&self.path))
+    }
+}
+
+/// Removes the files of a tool archive that failed to unpack or verify, so that it is downloaded
+/// again next time
+///
+/// If the archive was downloaded, only the staged download is discarded. Otherwise, the archive
+/// was loaded from the inventory, and its cached files are removed.
+fn discard_invalid_cache(
+    staging: Option<StagedDownload>,
+    archive_size: u64,
+    cached_files: &[&Path],
+) -> Fallible<()> {
+    if let Some(staging) = staging {
+        return staging.discard(archive_size);
+    }
+
+    for file in cached_files {
+        debug!("Removing invalid cached file at '{}'", file.display());
+        remove_file_if_exists(file)?;
+    }
+    Ok(())
+}
+
+/// Returns an error if a tool needs to be downloaded while in offline mode
+fn check_online<T: Display>(tool: T, session: &Session) -> Fallible<()> {
+    if session.offline() {
+        Err(ErrorKind::Network(NetworkError::Offline {
+            tool: tool.to_string(),
+        })
+        .into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Resolves a version requirement against the versions in the local inventory, for offline mode
+///
+/// Tags can't be resolved without the registry, except for `latest` (or no version at all), which
+/// resolves to the newest version that has already been fetched.
+fn resolve_offline(
+    tool: &str,
+    matching: VersionSpec,
+    versions: &BTreeSet<Version>,
+) -> Fallible<Version> {
+    let (version_opt, matching) = match matching {
+        VersionSpec::Exact(version) => return Ok(version),
+        VersionSpec::Semver(requirement) => (
+            versions
+                .iter()
+                .rev()
+                .find(|version| requirement.satisfies(version)),
+            requirement.to_string(),
+        ),
+        VersionSpec::None | VersionSpec::Tag(Tag::Latest) => {
+            (versions.last(), Tag::Latest.to_string())
+        }
+        VersionSpec::Tag(tag) => (None, tag.to_string()),
+    };
+
+    version_opt.cloned().map_or_else(
+        || {
+            Err(VersionError::NotFoundOffline {
+                tool: tool.into(),
+                matching: matching.clone(),
+            }
+            .into())
+        },
+        |version| {
+            debug!("Found {tool}@{version} matching '{matching}' in the local inventory");
+            Ok(version)
+        },
+    )
+}
+
 fn download_tool_error(tool: ToolSpec, from_url: impl AsRef<str>) -> impl FnOnce() -> ErrorKind {
     let from_url = from_url.as_ref().to_string();
     || ErrorKind::Network(NetworkError::DownloadTool { tool, from_url })
@@ -406,3 +545,75 @@ fn find_expected_shim_dir(shim_name: &str) -> Option<PathBuf> {
             .map(|install| install.root().to_owned())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nodejs_semver::Range;
+
+    fn inventory() -> BTreeSet<Version> {
+        ["8.1.5", "8.19.4", "9.0.0"]
+            .into_iter()
+            .map(|version| Version::parse(version).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_offline() {
+        let resolve = |matching| resolve_offline("npm", matching, &inventory()).ok();
+
+        assert_eq!(
+            resolve(VersionSpec::Semver(Range::parse("8").unwrap())),
+            Some(Version::parse("8.19.4").unwrap())
+        );
+        assert_eq!(
+            resolve(VersionSpec::Tag(Tag::Latest)),
+            Some(Version::parse("9.0.0").unwrap())
+        );
+        assert_eq!(
+            resolve(VersionSpec::None),
+            Some(Version::parse("9.0.0").unwrap())
+        );
+        // Exact versions are resolved even if they haven't been fetched yet
+        assert_eq!(
+            resolve(VersionSpec::Exact(Version::parse("10.0.0").unwrap())),
+            Some(Version::parse("10.0.0").unwrap())
+        );
+
+        assert_eq!(
+            resolve(VersionSpec::Semver(Range::parse("10").unwrap())),
+            None
+        );
+        assert_eq!(resolve(VersionSpec::Tag(Tag::Custom("next".into()))), None);
+        assert_eq!(
+            resolve_offline("npm", VersionSpec::None, &BTreeSet::new()).ok(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_discard_staged_download() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("node-v20.0.0.tar.gz.partial");
+        let validator = archive::validator_file(&path);
+        let staged = |resumed| StagedDownload {
+            path: path.clone(),
+            resumed,
+        };
+
+        // An interrupted download is kept to be resumed
+        fs::write(&path, b"vol").unwrap();
+        fs::write(&validator, b"\"etag\"").unwrap();
+        staged(0).discard(5).unwrap();
+        assert!(path.is_file() && validator.is_file());
+
+        // A resumed download that made no progress is removed
+        staged(3).discard(5).unwrap();
+        assert!(!path.exists() && !validator.exists());
+
+        // A complete download that failed to verify is removed
+        fs::write(&path, b"volta").unwrap();
+        staged(0).discard(5).unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,146 @@
+//! Provides the release channels of Node, which publish prerelease builds alongside the releases
+
+use std::fmt;
+
+use crate::hook::ToolHooks;
+use crate::settings::Settings;
+use crate::tool::Node;
+use crate::version::Tag;
+use nodejs_semver::{Identifier, Version};
+
+/// The root URL of the public Node server for the prerelease channels
+const PUBLIC_NODE_DOWNLOAD_ROOT: &str = "https://nodejs.org/download";
+
+/// A channel of Node builds, each published with its own index on the Node server
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NodeChannel {
+    /// The releases, published at `nodejs.org/dist`
+    Release,
+    /// Builds of the main branch, e.g. `v25.0.0-nightly20250601abcdef1234`
+    Nightly,
+    /// Release candidates, e.g. `v24.0.0-rc.1`
+    Rc,
+    /// Builds of the main branch against the newest V8, e.g. `v25.0.0-v8-canary20250601abcdef1234`
+    V8Canary,
+}
+
+impl NodeChannel {
+    /// The prerelease channels, which have their own hooks
+    pub const PRERELEASES: [Self; 3] = [Self::Nightly, Self::Rc, Self::V8Canary];
+
+    /// Finds the prerelease channel named by a tag, like `node@nightly`
+    #[must_use]
+    pub fn from_tag(tag: &Tag) -> Option<Self> {
+        match tag {
+            Tag::Custom(name) => Self::PRERELEASES
+                .into_iter()
+                .find(|channel| channel.name() == name),
+            _ => None,
+        }
+    }
+
+    /// Finds the channel which published a version, from its prerelease identifier
+    #[must_use]
+    pub fn of_version(version: &Version) -> Self {
+        match version.pre_release.first() {
+            Some(Identifier::AlphaNumeric(pre)) if pre.starts_with("nightly") => Self::Nightly,
+            Some(Identifier::AlphaNumeric(pre)) if pre.starts_with("v8-canary") => Self::V8Canary,
+            Some(Identifier::AlphaNumeric(pre)) if pre == "rc" => Self::Rc,
+            _ => Self::Release,
+        }
+    }
+
+    /// The name of the channel, as used in tags and in the public Node server
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Release => "release",
+            Self::Nightly => "nightly",
+            Self::Rc => "rc",
+            Self::V8Canary => "v8-canary",
+        }
+    }
+
+    /// Whether the builds in this channel are signed by the Node release keys
+    #[must_use]
+    pub const fn is_signed(self) -> bool {
+        matches!(self, Self::Release | Self::Rc)
+    }
+
+    /// The root URL of the server for this channel
+    ///
+    /// Only the releases use the `nodeMirror` setting, since mirrors don't generally carry the
+    /// prerelease channels. Those can be redirected with the hooks for each channel instead.
+    #[must_use]
+    pub fn server_root(self, settings: &Settings) -> String {
+        match self {
+            Self::Release => settings.node_server_root().to_owned(),
+            _ => format!("{PUBLIC_NODE_DOWNLOAD_ROOT}/{}", self.name()),
+        }
+    }
+
+    /// The hooks for this channel, if any
+    #[must_use]
+    pub fn hooks(self, hooks: Option<&ToolHooks<Node>>) -> Option<&ToolHooks<Node>> {
+        match self {
+            Self::Release => hooks,
+            channel => hooks.and_then(|hooks| hooks.channel(channel)),
+        }
+    }
+}
+
+impl fmt::Display for NodeChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(version: &str) -> NodeChannel {
+        NodeChannel::of_version(&Version::parse(version).unwrap())
+    }
+
+    #[test]
+    fn channel_of_version() {
+        assert_eq!(channel("22.16.0"), NodeChannel::Release);
+        assert_eq!(
+            channel("25.0.0-nightly20250601abcdef1234"),
+            NodeChannel::Nightly
+        );
+        assert_eq!(channel("24.0.0-rc.1"), NodeChannel::Rc);
+        assert_eq!(
+            channel("25.0.0-v8-canary20250601abcdef1234"),
+            NodeChannel::V8Canary
+        );
+        assert_eq!(channel("24.0.0-beta.1"), NodeChannel::Release);
+    }
+
+    #[test]
+    fn channel_from_tag() {
+        let channel = |tag: &str| NodeChannel::from_tag(&tag.parse().unwrap());
+
+        assert_eq!(channel("nightly"), Some(NodeChannel::Nightly));
+        assert_eq!(channel("rc"), Some(NodeChannel::Rc));
+        assert_eq!(channel("v8-canary"), Some(NodeChannel::V8Canary));
+        assert_eq!(channel("release"), None);
+        assert_eq!(channel("latest"), None);
+        assert_eq!(channel("iron"), None);
+    }
+
+    #[test]
+    fn server_roots() {
+        let settings = Settings::default();
+
+        assert_eq!(
+            NodeChannel::Release.server_root(&settings),
+            "https://nodejs.org/dist"
+        );
+        assert_eq!(
+            NodeChannel::V8Canary.server_root(&settings),
+            "https://nodejs.org/download/v8-canary"
+        );
+    }
+}
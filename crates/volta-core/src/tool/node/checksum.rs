@@ -0,0 +1,151 @@
+//! Provides verification of Node distributions against the published `SHASUMS256.txt`
+
+use std::fmt::Write;
+
+use crate::error::{Context, Fallible, NetworkError, ToolError};
+use crate::style::progress_spinner;
+use attohttpc::{Response, Session, StatusCode};
+use log::debug;
+use sha2::{Digest, Sha256};
+
+/// The name of the file listing the SHA-256 checksums of every file in a Node release
+pub const CHECKSUMS_FILENAME: &str = "SHASUMS256.txt";
+
+/// Fetch the contents of the published checksums file from the given URL
+pub fn fetch(client: &Session, url: &str) -> Fallible<String> {
+    let spinner = progress_spinner(format!("Fetching checksums: {url}"));
+    debug!("Fetching Node checksums from {url}");
+
+    let checksums = client
+        .get(url)
+        .send()
+        .and_then(Response::error_for_status)
+        .and_then(Response::text)
+        .with_context(|| checksum_fetch_error(url))?;
+
+    spinner.finish_and_clear();
+    Ok(checksums)
+}
+
+/// Fetch the checksums like `fetch`, returning `None` if the server has no file at the given URL
+pub fn fetch_if_published(client: &Session, url: &str) -> Fallible<Option<String>> {
+    let spinner = progress_spinner(format!("Fetching checksums: {url}"));
+    debug!("Fetching Node checksums from {url}");
+
+    let response = client
+        .get(url)
+        .send()
+        .with_context(|| checksum_fetch_error(url))?;
+    let checksums = if response.status() == StatusCode::NOT_FOUND {
+        None
+    } else {
+        let checksums = response
+            .error_for_status()
+            .and_then(Response::text)
+            .with_context(|| checksum_fetch_error(url))?;
+        Some(checksums)
+    };
+
+    spinner.finish_and_clear();
+    Ok(checksums)
+}
+
+fn checksum_fetch_error(url: &str) -> NetworkError {
+    NetworkError::ChecksumFetch {
+        tool: "Node".into(),
+        from_url: url.into(),
+    }
+}
+
+/// Find the expected checksum for `filename` in the checksums fetched from `url`
+pub fn expected(checksums: &str, filename: &str, url: &str) -> Fallible<String> {
+    find_checksum(checksums, filename).ok_or_else(|| {
+        ToolError::MissingChecksum {
+            file: filename.into(),
+            from_url: url.into(),
+        }
+        .into()
+    })
+}
+
+/// Find the checksum for `filename` in the contents of a `SHASUMS256.txt` file
+///
+/// Each line of the file has the format `<hex digest>  <filename>`.
+fn find_checksum(checksums: &str, filename: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let (checksum, name) = line.trim().split_once(char::is_whitespace)?;
+        // Binary-mode entries from `sha256sum` prefix the filename with a `*`
+        let name = name.trim_start();
+        let name = name.strip_prefix('*').unwrap_or(name);
+
+        (name == filename).then(|| checksum.to_ascii_lowercase())
+    })
+}
+
+/// Compare the finished digest of an archive to the expected checksum
+pub fn verify(digest: Sha256, expected: &str, version: &str) -> Fallible<()> {
+    let actual = digest
+        .finalize()
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        });
+
+    if actual == expected {
+        debug!("Verified Node archive checksum ({actual})");
+        Ok(())
+    } else {
+        Err(ToolError::ChecksumMismatch {
+            tool: "Node".into(),
+            version: version.into(),
+            expected: expected.into(),
+            actual,
+        }
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Sha256, find_checksum, verify};
+    use sha2::Digest;
+
+    const CHECKSUMS: &str = "\
+9f5ad4a5b7bc3b6e3ea8b0f8cba6b4d0ef2fb1c43cb2fa1e0d2b25e1ed9f6fd1  node-v20.11.0-darwin-arm64.tar.gz
+2bd2e2fc6df36c0b03ac8b1ea54a3c6b8df2b8b1b4d8d6b3ad7ac3b1ac1dcfe0  node-v20.11.0-linux-x64.tar.gz
+A1B2C3D4E5F60718293A4B5C6D7E8F90A1B2C3D4E5F60718293A4B5C6D7E8F90 *node-v20.11.0-win-x64.zip
+";
+
+    #[test]
+    fn test_find_checksum() {
+        assert_eq!(
+            find_checksum(CHECKSUMS, "node-v20.11.0-linux-x64.tar.gz").as_deref(),
+            Some("2bd2e2fc6df36c0b03ac8b1ea54a3c6b8df2b8b1b4d8d6b3ad7ac3b1ac1dcfe0")
+        );
+
+        // Binary-mode entries and uppercase digests are normalized
+        assert_eq!(
+            find_checksum(CHECKSUMS, "node-v20.11.0-win-x64.zip").as_deref(),
+            Some("a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90")
+        );
+
+        // Partial matches don't count
+        assert_eq!(find_checksum(CHECKSUMS, "node-v20.11.0-linux-x64"), None);
+        assert_eq!(
+            find_checksum(CHECKSUMS, "node-v18.0.0-linux-x64.tar.gz"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        // SHA-256 of the empty string
+        let empty = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        assert!(verify(Sha256::new(), empty, "1.0.0").is_ok());
+
+        let mut digest = Sha256::new();
+        digest.update(b"truncated");
+        assert!(verify(digest, empty, "1.0.0").is_err());
+    }
+}
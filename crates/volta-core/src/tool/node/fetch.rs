@@ -3,35 +3,26 @@
 use std::fs::{File, read_to_string, write};
 use std::path::{Path, PathBuf};
 
-use super::NodeVersion;
+use super::checksum::{self, CHECKSUMS_FILENAME};
+use super::signature;
+use super::{NodeChannel, NodeVersion};
 use crate::error::{Context, Fallible, FilesystemError, ToolError};
-use crate::fs::{create_staging_dir, create_staging_file, ensure_containing_dir_exists, rename};
+use crate::fs::{create_staging_dir, ensure_containing_dir_exists, rename};
 use crate::hook::ToolHooks;
+use crate::http;
 use crate::layout::volta_home;
+use crate::settings::Settings;
 use crate::style::{progress_bar, tool_version};
-use crate::tool::{self, Node, download_tool_error};
+use crate::tool::integrity::Integrity;
+use crate::tool::lockfile::LockedChecksum;
+use crate::tool::{self, Node, StagedDownload, discard_invalid_cache, download_tool_error};
 use crate::version::{VersionSpec, parse};
 use archive::{self, Archive};
-use cfg_if::cfg_if;
-use log::debug;
+use attohttpc::Session;
+use log::{debug, warn};
 use nodejs_semver::Version;
 use serde::Deserialize;
-
-cfg_if! {
-    if #[cfg(feature = "mock-network")] {
-        // TODO: We need to reconsider our mocking strategy in light of mockito deprecating the
-        // SERVER_URL constant: Since our acceptance tests run the binary in a separate process,
-        // we can't use `mockito::server_url()`, which relies on shared memory.
-        fn public_node_server_root() -> String {
-            #[allow(deprecated)]
-            mockito::SERVER_URL.to_string()
-        }
-    } else {
-        fn public_node_server_root() -> String {
-            "https://nodejs.org/dist".to_string()
-        }
-    }
-}
+use sha2::{Digest, Sha256};
 
 fn npm_manifest_path(version: &Version) -> PathBuf {
     let mut manifest = PathBuf::from(Node::archive_basename(version));
@@ -46,12 +37,50 @@ fn npm_manifest_path(version: &Version) -> PathBuf {
     manifest
 }
 
-pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Node>>) -> Fallible<NodeVersion> {
+pub fn fetch(
+    version: &Version,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Node>>,
+    locked: Option<&LockedChecksum>,
+) -> Fallible<NodeVersion> {
     let home = volta_home()?;
     let node_dir = home.node_inventory_dir();
     let cache_file = node_dir.join(Node::archive_filename(version));
+    let checksum_file = node_dir.join(Node::checksum_filename(version));
+    let client = http::client(settings)?;
+
+    // Prerelease builds are published in separate channels, each with its own hooks
+    let hooks = NodeChannel::of_version(version).hooks(hooks);
+
+    // A cached archive was verified against the published checksum when it was downloaded, so
+    // the checksum recorded next to it is used instead of fetching the checksums again
+    let cached = load_cached_distro(&cache_file).and_then(|archive| {
+        let recorded = trusted_checksum(&checksum_file, version)?;
+        Some((archive, recorded))
+    });
+    let recorded = cached.is_some();
+    let published = match &cached {
+        Some((_, recorded)) => {
+            debug!("Using checksum recorded at '{}'", checksum_file.display());
+            Some(recorded.clone())
+        }
+        None => published_checksum(&client, version, settings, hooks)?,
+    };
+
+    // Without a published checksum, the archive is verified against the locked one instead
+    let expected_checksum = match (published, locked) {
+        (Some(published), Some(locked)) => {
+            locked.check(&published.integrity, "Node", version)?;
+            Some(published)
+        }
+        (None, Some(locked)) => ArchiveChecksum::from_integrity(locked.integrity()),
+        (published, None) => published,
+    };
 
-    let (archive, staging) = if let Some(archive) = load_cached_distro(&cache_file) {
+    let (archive, staging) = if let Some(archive) = cached
+        .map(|(archive, _)| archive)
+        .or_else(|| load_cached_distro(&cache_file))
+    {
         debug!(
             "Loading {} from cached archive at '{}'",
             tool_version("node", version),
@@ -59,32 +88,208 @@ pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Node>>) -> Fallible<Nod
         );
         (archive, None)
     } else {
-        let staging = create_staging_file()?;
-        let remote_url = determine_remote_url(version, hooks)?;
-        let archive = fetch_remote_distro(version, &remote_url, staging.path())?;
+        let staging = StagedDownload::new(&Node::archive_filename(version))?;
+        let remote_url = determine_remote_url(version, settings, hooks)?;
+        let archive = fetch_remote_distro(&client, version, &remote_url, staging.path())?;
         (archive, Some(staging))
     };
 
-    let node_version = unpack_archive(archive, version)?;
+    let archive_size = archive.compressed_size();
+    let expected_hex = expected_checksum
+        .as_ref()
+        .map(|checksum| checksum.hex.as_str());
+    let node_version = match unpack_archive(archive, version, expected_hex) {
+        Ok(node_version) => node_version,
+        Err(error) => {
+            discard_invalid_cache(staging, archive_size, &[&cache_file, &checksum_file])?;
+            return Err(error);
+        }
+    };
 
-    if let Some(staging_file) = staging {
-        ensure_containing_dir_exists(&cache_file).with_context(|| {
-            FilesystemError::ContainingDir {
-                path: cache_file.clone(),
-            }
-        })?;
-        staging_file
-            .persist(cache_file)
-            .with_context(|| ToolError::PersistInventory {
-                tool: "Node".into(),
-            })?;
+    if let Some(staging) = staging {
+        staging.persist(&cache_file, "Node")?;
+    }
+
+    if let Some(checksum) = expected_checksum.filter(|_| !recorded) {
+        save_cached_checksum(&checksum_file, &checksum);
     }
 
     Ok(node_version)
 }
 
+/// Determines whether the archive of a version is cached along with its verified checksum, so
+/// that it can be installed without the network
+///
+/// # Errors
+///
+/// Returns an error if the Volta home directory cannot be determined.
+pub fn is_cached(version: &Version) -> Fallible<bool> {
+    let node_dir = volta_home()?.node_inventory_dir();
+    Ok(node_dir.join(Node::archive_filename(version)).is_file()
+        && trusted_checksum(&node_dir.join(Node::checksum_filename(version)), version).is_some())
+}
+
+/// The SHA-256 checksum of a Node archive
+#[derive(Clone)]
+struct ArchiveChecksum {
+    hex: String,
+    integrity: Integrity,
+    /// Whether the signature of the published checksums was verified
+    signed: bool,
+}
+
+impl ArchiveChecksum {
+    fn from_hex(hex: String, signed: bool) -> Option<Self> {
+        let integrity = Integrity::from_corepack(&format!("sha256.{hex}"))?;
+        Some(Self {
+            hex,
+            integrity,
+            signed,
+        })
+    }
+
+    fn from_integrity(integrity: &Integrity) -> Option<Self> {
+        let hex = integrity.to_corepack().strip_prefix("sha256.")?.to_owned();
+        Some(Self {
+            hex,
+            integrity: integrity.clone(),
+            signed: false,
+        })
+    }
+}
+
+/// Fetch the published checksum of the Node archive for the current platform, to record in the
+/// project lockfile
+pub fn archive_checksum(
+    version: &Version,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Option<Integrity>> {
+    let hooks = NodeChannel::of_version(version).hooks(hooks);
+    let published = published_checksum(&http::client(settings)?, version, settings, hooks)?;
+    Ok(published.map(|published| published.integrity))
+}
+
+/// Fetch the published checksum of the Node archive, verifying the signature of the checksums
+/// for the channels that are signed
+///
+/// A `distro` hook without a `checksum` hook may point at a mirror that
+/// doesn't host the checksums, in which case there is no published checksum.
+fn published_checksum(
+    client: &Session,
+    version: &Version,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Option<ArchiveChecksum>> {
+    let channel = NodeChannel::of_version(version);
+    let checksum_url = determine_checksum_url(version, settings, hooks)?;
+    let checksums = if has_distro_hook_only(hooks) {
+        let Some(checksums) = checksum::fetch_if_published(client, &checksum_url)? else {
+            warn!(
+                "No Node checksums found at {checksum_url}, so the archive from the node.distro hook cannot be verified.
+Add a node.checksum hook to verify it."
+            );
+            return Ok(None);
+        };
+        checksums
+    } else {
+        checksum::fetch(client, &checksum_url)?
+    };
+    let signed = signature::enabled() && channel.is_signed();
+    if signed {
+        signature::verify(client, &checksums, &checksum_url)?;
+    } else if signature::enabled() {
+        debug!("Skipping signature verification, {channel} builds of Node are not signed");
+    }
+
+    let filename = Node::archive_filename(version);
+    let checksum = checksum::expected(&checksums, &filename, &checksum_url)?;
+    let published =
+        ArchiveChecksum::from_hex(checksum, signed).ok_or(ToolError::MissingChecksum {
+            file: filename,
+            from_url: checksum_url,
+        })?;
+
+    Ok(Some(published))
+}
+
+/// Determines whether the checksums are expected next to the archive of a `distro` hook, rather
+/// than given by a `checksum` hook or the Node server
+fn has_distro_hook_only(hooks: Option<&ToolHooks<Node>>) -> bool {
+    hooks.is_some_and(|hooks| hooks.distro.is_some() && hooks.checksum.is_none())
+}
+
+/// Load the checksum recorded next to a cached archive, if there is a valid one that can be used
+/// instead of the published checksums
+///
+/// When signatures are verified, a checksum recorded without verifying the signature of the
+/// published checksums is not used, so that the signature is verified before installing.
+fn trusted_checksum(file: &Path, version: &Version) -> Option<ArchiveChecksum> {
+    let recorded = load_cached_checksum(file, version)?;
+    if !recorded.signed && signature::enabled() && NodeChannel::of_version(version).is_signed() {
+        debug!(
+            "Ignoring checksum recorded at '{}', as its signature was not verified",
+            file.display()
+        );
+        return None;
+    }
+
+    Some(recorded)
+}
+
+/// Load the checksum recorded next to a cached archive, if there is a valid one
+///
+/// The file holds the hex-encoded checksum, followed by `signed` if the signature of the
+/// published checksums was verified.
+fn load_cached_checksum(file: &Path, version: &Version) -> Option<ArchiveChecksum> {
+    let contents = read_to_string(file).ok()?;
+    let mut fields = contents.split_whitespace();
+    let recorded = fields.next().and_then(|hex| {
+        let signed = match fields.next() {
+            None => false,
+            Some("signed") => true,
+            Some(_) => return None,
+        };
+        ArchiveChecksum::from_hex(hex.to_owned(), signed)
+    });
+
+    if recorded.is_none() {
+        debug!(
+            "Ignoring invalid checksum for {} recorded at '{}'",
+            tool_version("node", version),
+            file.display()
+        );
+    }
+    recorded
+}
+
+/// Record the verified checksum of an archive next to it in the inventory
+///
+/// This only saves fetching the checksums again, so a failure is not an error.
+fn save_cached_checksum(file: &Path, checksum: &ArchiveChecksum) {
+    let contents = if checksum.signed {
+        format!("{} signed", checksum.hex)
+    } else {
+        checksum.hex.clone()
+    };
+
+    if let Err(error) = write(file, contents) {
+        debug!(
+            "Could not record the archive checksum at '{}': {error}",
+            file.display()
+        );
+    }
+}
+
 /// Unpack the node archive into the image directory so that it is ready for use
-fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<NodeVersion> {
+///
+/// The archive is verified against the expected checksum, if there is one, before it is moved
+/// into the image directory, so an invalid archive never results in an installed image.
+fn unpack_archive(
+    archive: Box<dyn Archive>,
+    version: &Version,
+    expected_checksum: Option<&str>,
+) -> Fallible<NodeVersion> {
     let temp = create_staging_dir()?;
     debug!("Unpacking node into '{}'", temp.path().display());
 
@@ -94,9 +299,10 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<Node
         archive.compressed_size(),
     );
     let version_string = version.to_string();
+    let mut digest = Sha256::new();
 
     archive
-        .unpack(temp.path(), &mut |(), read| {
+        .unpack(temp.path(), &mut digest, &mut |(), read| {
             progress.inc(read as u64);
         })
         .with_context(|| ToolError::UnpackArchive {
@@ -104,6 +310,10 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<Node
             version: version_string.clone(),
         })?;
 
+    if let Some(expected_checksum) = expected_checksum {
+        checksum::verify(digest, expected_checksum, &version_string)?;
+    }
+
     // Save the npm version number in the npm version file for this distro
     let npm_package_json = temp.path().join(npm_manifest_path(version));
     let npm = Manifest::version(&npm_package_json)?;
@@ -133,9 +343,8 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<Node
     })
 }
 
-/// Return the archive if it is valid. It may have been corrupted or interrupted in the middle of
-/// downloading.
-// ISSUE(#134) - verify checksum
+/// Return the archive if it can be loaded. It may have been corrupted or interrupted in the middle
+/// of downloading, which is detected when it is verified against its checksum during unpacking.
 fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
     if file.is_file() {
         let file = File::open(file).ok()?;
@@ -146,7 +355,11 @@ fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
 }
 
 /// Determine the remote URL to download from, using the hooks if available
-fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Node>>) -> Fallible<String> {
+fn determine_remote_url(
+    version: &Version,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<String> {
     let distro_file_name = Node::archive_filename(version);
     match hooks {
         Some(&ToolHooks {
@@ -158,21 +371,58 @@ fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Node>>) -> F
         }
         _ => Ok(format!(
             "{}/v{}/{}",
-            public_node_server_root(),
+            NodeChannel::of_version(version).server_root(settings),
             version,
             distro_file_name
         )),
     }
 }
 
+/// Determine the URL of the published checksums, using the hooks if available
+///
+/// If there is a `distro` hook but no `checksum` hook, the checksums are expected to be alongside
+/// the distro archive, matching the layout of the public Node server and its mirrors. Mirrors
+/// that don't host them are allowed, with a warning, by `published_checksum`.
+fn determine_checksum_url(
+    version: &Version,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<String> {
+    match hooks {
+        Some(&ToolHooks {
+            checksum: Some(ref hook),
+            ..
+        }) => {
+            debug!("Using node.checksum hook to determine checksums URL");
+            hook.resolve(version, CHECKSUMS_FILENAME)
+        }
+        Some(&ToolHooks {
+            distro: Some(_), ..
+        }) => {
+            let distro_url = determine_remote_url(version, settings, hooks)?;
+            let base_url = distro_url
+                .rsplit_once('/')
+                .map_or(distro_url.as_str(), |(base, _)| base);
+            Ok(format!("{base_url}/{CHECKSUMS_FILENAME}"))
+        }
+        _ => Ok(format!(
+            "{}/v{}/{}",
+            NodeChannel::of_version(version).server_root(settings),
+            version,
+            CHECKSUMS_FILENAME
+        )),
+    }
+}
+
 /// Fetch the distro archive from the internet
 fn fetch_remote_distro(
+    client: &Session,
     version: &Version,
     url: &str,
     staging_path: &Path,
 ) -> Fallible<Box<dyn Archive>> {
     debug!("Downloading {} from {}", tool_version("node", version), url);
-    archive::fetch_native(url, staging_path).with_context(download_tool_error(
+    archive::fetch_native(client, url, staging_path).with_context(download_tool_error(
         tool::ToolSpec::Node(VersionSpec::Exact(version.clone())),
         url,
     ))
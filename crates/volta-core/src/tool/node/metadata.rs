@@ -18,7 +18,8 @@ pub struct NodeIndex {
 #[derive(Debug)]
 pub struct NodeEntry {
     pub version: Version,
-    pub lts: bool,
+    /// The codename of the LTS line, such as `Hydrogen`, if this is an LTS release
+    pub lts: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -33,7 +34,7 @@ pub struct RawNodeEntry {
     npm: Option<Version>,
     files: HashSet<String>,
     #[serde(deserialize_with = "lts_version_serde")]
-    lts: bool,
+    lts: Option<String>,
 }
 
 impl From<RawNodeIndex> for NodeIndex {
@@ -78,12 +79,10 @@ impl From<RawNodeIndex> for NodeIndex {
 }
 
 #[allow(clippy::unnecessary_wraps)] // Needs to match the API expected by Serde
-fn lts_version_serde<'de, D>(deserializer: D) -> Result<bool, D::Error>
+fn lts_version_serde<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    match String::deserialize(deserializer) {
-        Ok(_) => Ok(true),
-        Err(_) => Ok(false),
-    }
+    // Non-LTS releases are marked with `false` instead of a codename
+    Ok(String::deserialize(deserializer).ok())
 }
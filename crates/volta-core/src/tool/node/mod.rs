@@ -1,11 +1,14 @@
 use std::fmt::{self, Display};
 
+use super::lockfile::locked_checksum;
 use super::{
-    FetchStatus, Fetchable, Installable, Pinnable, check_fetched, check_shim_reachable,
-    debug_already_fetched, info_fetched, info_installed, info_pinned, info_project_version,
+    FetchStatus, Fetchable, Installable, Pinnable, check_fetched, check_online,
+    check_shim_reachable, debug_already_fetched, info_fetched, info_installed, info_pinned,
+    info_project_version,
 };
 use crate::error::{ErrorKind, Fallible, PlatformError};
 use crate::inventory::node_available;
+use crate::project::ManifestKey;
 use crate::session::Session;
 use crate::style::{note_prefix, tool_version};
 use crate::sync::VoltaLock;
@@ -13,11 +16,18 @@ use cfg_if::cfg_if;
 use log::info;
 use nodejs_semver::Version;
 
+mod channel;
+mod checksum;
 mod fetch;
 mod metadata;
 mod resolve;
+mod signature;
 
+#[allow(clippy::module_name_repetitions)]
+pub use channel::NodeChannel;
+pub(crate) use fetch::archive_checksum;
 pub use fetch::load_default_npm_version;
+pub(crate) use resolve::available;
 pub use resolve::resolve;
 
 cfg_if! {
@@ -198,6 +208,12 @@ impl Node {
         )
     }
 
+    /// The name of the file in the inventory that records the verified checksum of the archive
+    #[must_use]
+    pub fn checksum_filename(version: &Version) -> String {
+        format!("{}.sha256", Self::archive_filename(version))
+    }
+
     pub(crate) fn ensure_fetched(&self, session: &Session) -> Fallible<NodeVersion> {
         match check_fetched(|| node_available(&self.version))? {
             FetchStatus::AlreadyFetched => {
@@ -209,7 +225,19 @@ impl Node {
                     npm,
                 })
             }
-            FetchStatus::FetchNeeded(_lock) => fetch::fetch(&self.version, session.hooks()?.node()),
+            FetchStatus::FetchNeeded(_lock) => {
+                // A cached archive with a recorded checksum can be installed without the network
+                if !fetch::is_cached(&self.version)? {
+                    check_online(self, session)?;
+                }
+                let locked = locked_checksum(session, ManifestKey::Node, &self.version)?;
+                fetch::fetch(
+                    &self.version,
+                    session.settings()?,
+                    session.hooks()?.node(),
+                    locked.as_ref(),
+                )
+            }
         }
     }
 }
@@ -308,7 +336,7 @@ mod tests {
     fn test_node_archive_basename() {
         assert_eq!(
             Node::archive_basename(&Version::parse("20.2.3").unwrap()),
-            format!("node-v20.2.3-{}-{}", NODE_DISTRO_OS, NODE_DISTRO_ARCH)
+            format!("node-v20.2.3-{NODE_DISTRO_OS}-{NODE_DISTRO_ARCH}")
         );
     }
 
@@ -316,10 +344,7 @@ mod tests {
     fn test_node_archive_filename() {
         assert_eq!(
             Node::archive_filename(&Version::parse("20.2.3").unwrap()),
-            format!(
-                "node-v20.2.3-{}-{}.{}",
-                NODE_DISTRO_OS, NODE_DISTRO_ARCH, NODE_DISTRO_EXTENSION
-            )
+            format!("node-v20.2.3-{NODE_DISTRO_OS}-{NODE_DISTRO_ARCH}.{NODE_DISTRO_EXTENSION}")
         );
     }
 
@@ -1,63 +1,146 @@
 //! Provides resolution of Node requirements into specific versions, using the `NodeJS` index
 
-use std::fs::File;
-use std::io::Write;
-use std::time::{Duration, SystemTime};
+use std::collections::BTreeSet;
 
-use super::super::registry_fetch_error;
+use super::super::remote::RemoteVersion;
+use super::super::{cache, resolve_offline};
+use super::NodeChannel;
 use super::metadata::{NodeEntry, NodeIndex, RawNodeIndex};
-use crate::error::{Context, Fallible, FilesystemError, NetworkError, VersionError};
-use crate::fs::{create_staging_file, ensure_containing_dir_exists, read_file};
+use crate::error::{Context, Fallible, NetworkError, VersionError};
 use crate::hook::ToolHooks;
-use crate::layout::volta_home;
+use crate::http;
+use crate::inventory::node_versions;
 use crate::session::Session;
+use crate::settings::Settings;
 use crate::style::progress_spinner;
 use crate::tool::Node;
 use crate::version::{Tag, VersionSpec};
-use attohttpc::Response;
-use attohttpc::header::HeaderMap;
-use cfg_if::cfg_if;
-use headers::{CacheControl, Expires, HeaderMapExt};
 use log::debug;
 use nodejs_semver::{Range, Version};
 
-// ISSUE (#86): Move public repository URLs to config file
-cfg_if! {
-    if #[cfg(feature = "mock-network")] {
-        // TODO: We need to reconsider our mocking strategy in light of mockito deprecating the
-        // SERVER_URL constant: Since our acceptance tests run the binary in a separate process,
-        // we can't use `mockito::server_url()`, which relies on shared memory.
-        #[allow(deprecated)]
-        const SERVER_URL: &str = mockito::SERVER_URL;
-        fn public_node_version_index() -> String {
-            format!("{}/node-dist/index.json", SERVER_URL)
-        }
-    } else {
-        /// Returns the URL of the index of available Node versions on the public Node server.
-        fn public_node_version_index() -> String {
-            "https://nodejs.org/dist/index.json".to_string()
-        }
-    }
-}
-
 /// # Errors
 ///
 /// Returns an error if the version cannot be resolved.
-pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Version> {
+pub fn resolve(matching: VersionSpec, session: &Session) -> Fallible<Version> {
+    if session.offline() {
+        let settings = session.settings()?;
+        let hooks = session.hooks()?.node();
+        return match matching {
+            VersionSpec::None | VersionSpec::Tag(Tag::Lts) => {
+                resolve_lts_offline(None, "lts", settings, hooks)
+            }
+            VersionSpec::Tag(tag) => match (NodeChannel::from_tag(&tag), LtsLine::from_tag(&tag)) {
+                (Some(channel), _) => resolve_channel_offline(channel),
+                (None, Some(line)) => {
+                    resolve_lts_offline(Some(&line), &tag.to_string(), settings, hooks)
+                }
+                (None, None) => resolve_offline("node", VersionSpec::Tag(tag), &node_versions()?),
+            },
+            matching => resolve_offline("node", matching, &node_versions()?),
+        };
+    }
+
+    let settings = session.settings()?;
     let hooks = session.hooks()?.node();
     match matching {
-        VersionSpec::Semver(requirement) => resolve_semver(&requirement, hooks),
+        VersionSpec::Semver(requirement) => resolve_semver(&requirement, settings, hooks),
         VersionSpec::Exact(version) => Ok(version),
-        VersionSpec::None | VersionSpec::Tag(Tag::Lts) => resolve_lts(hooks),
-        VersionSpec::Tag(Tag::Latest) => resolve_latest(hooks),
-        // Node doesn't have "tagged" versions (apart from 'latest' and 'lts'), so custom tags will always be an error
-        VersionSpec::Tag(Tag::Custom(tag)) => {
-            Err(VersionError::NodeNotFound { matching: tag }.into())
+        VersionSpec::None | VersionSpec::Tag(Tag::Lts) => resolve_lts(settings, hooks),
+        VersionSpec::Tag(Tag::Latest) => resolve_latest(settings, hooks),
+        // Node doesn't have "tagged" versions apart from 'latest', the prerelease channels and the
+        // LTS lines, so any other custom tag is treated as the codename of an LTS line
+        VersionSpec::Tag(tag) => match (NodeChannel::from_tag(&tag), LtsLine::from_tag(&tag)) {
+            (Some(channel), _) => resolve_channel(channel, settings, hooks),
+            (None, Some(line)) => resolve_lts_line(&line, &tag.to_string(), settings, hooks),
+            (None, None) => Err(VersionError::NodeNotFound {
+                matching: tag.to_string(),
+            }
+            .into()),
+        },
+    }
+}
+
+/// A line of LTS releases, selected by codename or by counting back from the newest line
+#[derive(Debug, PartialEq, Eq)]
+enum LtsLine<'a> {
+    Codename(&'a str),
+    Previous(usize),
+}
+
+impl<'a> LtsLine<'a> {
+    fn from_tag(tag: &'a Tag) -> Option<Self> {
+        match tag {
+            Tag::LtsCodename(codename) | Tag::Custom(codename) => Some(Self::Codename(codename)),
+            Tag::PreviousLts(count) => Some(Self::Previous(*count)),
+            Tag::Latest | Tag::Lts => None,
         }
     }
+
+    /// Finds the codename of this line, as written in the index
+    fn codename<'i>(&self, index: &'i NodeIndex) -> Option<&'i str> {
+        // The index is sorted from newest to oldest, so the lines are too
+        let mut codenames: Vec<&str> = Vec::new();
+        for codename in index
+            .entries
+            .iter()
+            .filter_map(|entry| entry.lts.as_deref())
+        {
+            if !codenames.contains(&codename) {
+                codenames.push(codename);
+            }
+        }
+
+        match self {
+            Self::Codename(name) => codenames
+                .into_iter()
+                .find(|codename| codename.eq_ignore_ascii_case(name)),
+            Self::Previous(count) => codenames.get(*count).copied(),
+        }
+    }
+
+    /// The versions in this line, from newest to oldest
+    fn versions<'i>(&self, index: &'i NodeIndex) -> impl Iterator<Item = &'i Version> {
+        let codename = self.codename(index);
+        index
+            .entries
+            .iter()
+            .filter(move |entry| codename.is_some() && entry.lts.as_deref() == codename)
+            .map(|entry| &entry.version)
+    }
 }
 
-fn resolve_latest(hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
+/// Lists every version of Node in a channel available to fetch, using the hooks if available
+///
+/// The newest version is tagged as `latest` for the releases, or with the name of a prerelease
+/// channel, matching how `node@latest` and `node@nightly` are resolved.
+pub fn available(
+    channel: NodeChannel,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Vec<RemoteVersion>> {
+    let url = index_url(channel, settings, hooks)?;
+    let index: NodeIndex = resolve_node_versions(&http::client(settings)?, &url)?.into();
+
+    let mut versions: Vec<RemoteVersion> = index
+        .entries
+        .into_iter()
+        .map(|NodeEntry { version, lts }| RemoteVersion {
+            lts,
+            ..RemoteVersion::new(version)
+        })
+        .collect();
+    versions.sort_by(|a, b| a.version.cmp(&b.version));
+    if let Some(newest) = versions.last_mut() {
+        newest.tags.push(match channel {
+            NodeChannel::Release => Tag::Latest.to_string(),
+            channel => channel.to_string(),
+        });
+    }
+
+    Ok(versions)
+}
+
+fn resolve_latest(settings: &Settings, hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
     // NOTE: This assumes the registry always produces a list in sorted order
     //       from newest to oldest. This should be specified as a requirement
     //       when we document the plugin API.
@@ -69,9 +152,9 @@ fn resolve_latest(hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
             debug!("Using node.latest hook to determine node index URL");
             hook.resolve("index.json")?
         }
-        _ => public_node_version_index(),
+        _ => settings.node_version_index(),
     };
-    let version_opt = match_node_version(&url, |_| true)?;
+    let version_opt = match_node_version(settings, &url, |_| true)?;
 
     version_opt.map_or_else(
         || {
@@ -87,18 +170,9 @@ fn resolve_latest(hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
     )
 }
 
-fn resolve_lts(hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
-    let url = match hooks {
-        Some(&ToolHooks {
-            index: Some(ref hook),
-            ..
-        }) => {
-            debug!("Using node.index hook to determine node index URL");
-            hook.resolve("index.json")?
-        }
-        _ => public_node_version_index(),
-    };
-    let version_opt = match_node_version(&url, |&NodeEntry { lts, .. }| lts)?;
+fn resolve_lts(settings: &Settings, hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
+    let url = index_url(NodeChannel::Release, settings, hooks)?;
+    let version_opt = match_node_version(settings, &url, |entry| entry.lts.is_some())?;
 
     version_opt.map_or_else(
         || {
@@ -114,18 +188,130 @@ fn resolve_lts(hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
     )
 }
 
-fn resolve_semver(matching: &Range, hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
-    let url = match hooks {
-        Some(&ToolHooks {
-            index: Some(ref hook),
-            ..
-        }) => {
-            debug!("Using node.index hook to determine node index URL");
-            hook.resolve("index.json")?
-        }
-        _ => public_node_version_index(),
+/// Resolves the newest LTS version in the local inventory, for offline mode
+///
+/// If a line is given, only versions in that line are considered. The inventory doesn't record
+/// which versions are LTS releases, so this relies on the cached Node index, even if it has
+/// expired.
+fn resolve_lts_offline(
+    line: Option<&LtsLine>,
+    matching: &str,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Version> {
+    let url = index_url(NodeChannel::Release, settings, hooks)?;
+    let index: Option<NodeIndex> = cache::read_stale(&url, None)?
+        .and_then(|cached| serde_json::de::from_str::<RawNodeIndex>(&cached).ok())
+        .map(NodeIndex::from);
+
+    let lts_versions: BTreeSet<&Version> = match (&index, line) {
+        (Some(index), Some(line)) => line.versions(index).collect(),
+        (Some(index), None) => index
+            .entries
+            .iter()
+            .filter(|entry| entry.lts.is_some())
+            .map(|entry| &entry.version)
+            .collect(),
+        (None, _) => BTreeSet::new(),
     };
-    let version_opt = match_node_version(&url, |NodeEntry { version, .. }| {
+
+    let version_opt = node_versions()?
+        .into_iter()
+        .rev()
+        .find(|version| lts_versions.contains(version));
+
+    version_opt.map_or_else(
+        || {
+            Err(VersionError::NotFoundOffline {
+                tool: "node".into(),
+                matching: matching.into(),
+            }
+            .into())
+        },
+        |version| {
+            debug!("Found node@{version} matching '{matching}' in the local inventory");
+            Ok(version)
+        },
+    )
+}
+
+/// Resolves the newest build in a prerelease channel, from the index of that channel
+fn resolve_channel(
+    channel: NodeChannel,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Version> {
+    let url = index_url(channel, settings, hooks)?;
+    let version_opt = match_node_version(settings, &url, |_| true)?;
+
+    version_opt.map_or_else(
+        || {
+            Err(VersionError::NodeNotFound {
+                matching: channel.to_string(),
+            }
+            .into())
+        },
+        |version| {
+            debug!("Found newest {channel} node version ({version}) from {url}");
+            Ok(version)
+        },
+    )
+}
+
+/// Resolves the newest build of a prerelease channel in the local inventory, for offline mode
+fn resolve_channel_offline(channel: NodeChannel) -> Fallible<Version> {
+    let version_opt = node_versions()?
+        .into_iter()
+        .rev()
+        .find(|version| NodeChannel::of_version(version) == channel);
+
+    version_opt.map_or_else(
+        || {
+            Err(VersionError::NotFoundOffline {
+                tool: "node".into(),
+                matching: channel.to_string(),
+            }
+            .into())
+        },
+        |version| {
+            debug!("Found node@{version} matching '{channel}' in the local inventory");
+            Ok(version)
+        },
+    )
+}
+
+/// Resolves the newest version in a specific line of LTS releases
+fn resolve_lts_line(
+    line: &LtsLine,
+    matching: &str,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Version> {
+    let url = index_url(NodeChannel::Release, settings, hooks)?;
+    let index: NodeIndex = resolve_node_versions(&http::client(settings)?, &url)?.into();
+    let version_opt = line.versions(&index).next().cloned();
+
+    version_opt.map_or_else(
+        || {
+            Err(VersionError::NodeNotFound {
+                matching: matching.into(),
+            }
+            .into())
+        },
+        |version| {
+            debug!("Found node@{version} matching '{matching}' from {url}");
+            Ok(version)
+        },
+    )
+}
+
+fn resolve_semver(
+    matching: &Range,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Version> {
+    let url = index_url(NodeChannel::Release, settings, hooks)?;
+    let version_opt = match_node_version(settings, &url, |NodeEntry { version, .. }| {
         matching.satisfies(version)
     })?;
 
@@ -143,128 +329,118 @@ fn resolve_semver(matching: &Range, hooks: Option<&ToolHooks<Node>>) -> Fallible
     )
 }
 
+/// Determine the URL of the Node index for a channel, using the hooks for it if available
+fn index_url(
+    channel: NodeChannel,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<String> {
+    match (channel, channel.hooks(hooks)) {
+        (
+            NodeChannel::Release,
+            Some(&ToolHooks {
+                index: Some(ref hook),
+                ..
+            }),
+        ) => {
+            debug!("Using node.index hook to determine node index URL");
+            hook.resolve("index.json")
+        }
+        (
+            channel,
+            Some(&ToolHooks {
+                index: Some(ref hook),
+                ..
+            }),
+        ) => {
+            debug!("Using node.{channel}.index hook to determine node index URL");
+            hook.resolve("index.json")
+        }
+        (NodeChannel::Release, _) => Ok(settings.node_version_index()),
+        (channel, _) => Ok(format!("{}/index.json", channel.server_root(settings))),
+    }
+}
+
 fn match_node_version(
+    settings: &Settings,
     url: &str,
     predicate: impl Fn(&NodeEntry) -> bool,
 ) -> Fallible<Option<Version>> {
-    let index: NodeIndex = resolve_node_versions(url)?.into();
+    let index: NodeIndex = resolve_node_versions(&http::client(settings)?, url)?.into();
     let mut entries = index.entries.into_iter();
     Ok(entries
         .find(predicate)
         .map(|NodeEntry { version, .. }| version))
 }
 
-/// Reads a public index from the Node cache, if it exists and hasn't expired.
-fn read_cached_opt(url: &str) -> Fallible<Option<RawNodeIndex>> {
-    let expiry_file = volta_home()?.node_index_expiry_file();
-    let expiry = read_file(expiry_file).with_context(|| FilesystemError::ReadNodeIndexExpiry {
-        file: expiry_file.to_owned(),
-    })?;
-
-    if expiry
-        .map(|date| httpdate::parse_http_date(&date))
-        .transpose()
-        .with_context(|| FilesystemError::ParseNodeIndexExpiry)?
-        .is_none_or(|expiry_date| SystemTime::now() >= expiry_date)
-    {
-        return Ok(None);
-    }
-
-    let index_file = volta_home()?.node_index_file();
-    let cached = read_file(index_file).with_context(|| FilesystemError::ReadNodeIndexCache {
-        file: index_file.to_owned(),
-    })?;
-
-    let Some(json) = cached
-        .as_ref()
-        .and_then(|content| content.strip_prefix(url))
-    else {
-        return Ok(None);
-    };
+fn resolve_node_versions(client: &attohttpc::Session, url: &str) -> Fallible<RawNodeIndex> {
+    let spinner = progress_spinner(format!("Fetching public registry: {url}"));
+    let response_text = cache::fetch(client, "Node", url, None)?;
+    spinner.finish_and_clear();
 
-    serde_json::de::from_str(json).with_context(|| FilesystemError::ParseNodeIndexCache)
+    serde_json::de::from_str(&response_text).with_context(|| NetworkError::ParseNodeIndex {
+        from_url: url.to_string(),
+    })
 }
 
-/// Get the cache max-age of an HTTP response.
-fn max_age(headers: &HeaderMap) -> Duration {
-    const FOUR_HOURS: Duration = Duration::from_secs(4 * 60 * 60);
-    headers
-        .typed_get::<CacheControl>()
-        .and_then(|cache_control| cache_control.max_age())
-        .unwrap_or(FOUR_HOURS)
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-fn resolve_node_versions(url: &str) -> Fallible<RawNodeIndex> {
-    if let Some(serial) = read_cached_opt(url)? {
-        debug!("Found valid cache of Node version index");
-        Ok(serial)
-    } else {
-        debug!("Node index cache was not found or was invalid");
-        let spinner = progress_spinner(format!("Fetching public registry: {url}"));
-
-        let (_, headers, response) = attohttpc::get(url)
-            .send()
-            .and_then(Response::error_for_status)
-            .with_context(registry_fetch_error("Node", url))?
-            .split();
-
-        let expires = headers
-            .typed_get::<Expires>()
-            .map_or_else(|| SystemTime::now() + max_age(&headers), SystemTime::from);
-
-        let response_text = response
-            .text()
-            .with_context(registry_fetch_error("Node", url))?;
-
-        let index: RawNodeIndex = serde_json::de::from_str(&response_text).with_context(|| {
-            NetworkError::ParseNodeIndex {
-                from_url: url.to_string(),
-            }
-        })?;
+    fn index() -> NodeIndex {
+        let entries = [
+            ("24.1.0", None),
+            ("22.16.0", Some("Jod")),
+            ("22.15.1", Some("Jod")),
+            ("20.19.2", Some("Iron")),
+            ("18.20.8", Some("Hydrogen")),
+        ];
 
-        let cached = create_staging_file()?;
+        NodeIndex {
+            entries: entries
+                .into_iter()
+                .map(|(version, lts)| NodeEntry {
+                    version: Version::parse(version).unwrap(),
+                    lts: lts.map(str::to_owned),
+                })
+                .collect(),
+        }
+    }
 
-        let mut cached_file: &File = cached.as_file();
-        writeln!(cached_file, "{url}")
-            .and_then(|()| cached_file.write(response_text.as_bytes()))
-            .with_context(|| FilesystemError::WriteNodeIndexCache {
-                file: cached.path().to_path_buf(),
-            })?;
+    fn newest(line: &LtsLine) -> Option<String> {
+        line.versions(&index()).next().map(Version::to_string)
+    }
 
-        let index_cache_file = volta_home()?.node_index_file();
-        ensure_containing_dir_exists(&index_cache_file).with_context(|| {
-            FilesystemError::ContainingDir {
-                path: index_cache_file.to_owned(),
-            }
-        })?;
-        cached
-            .persist(index_cache_file)
-            .with_context(|| FilesystemError::WriteNodeIndexCache {
-                file: index_cache_file.to_owned(),
-            })?;
-
-        let expiry = create_staging_file()?;
-        let mut expiry_file: &File = expiry.as_file();
-
-        write!(expiry_file, "{}", httpdate::fmt_http_date(expires)).with_context(|| {
-            FilesystemError::WriteNodeIndexExpiry {
-                file: expiry.path().to_path_buf(),
-            }
-        })?;
+    #[test]
+    fn lts_line_from_tag() {
+        assert_eq!(
+            LtsLine::from_tag(&Tag::LtsCodename("iron".into())),
+            Some(LtsLine::Codename("iron"))
+        );
+        assert_eq!(
+            LtsLine::from_tag(&Tag::Custom("hydrogen".into())),
+            Some(LtsLine::Codename("hydrogen"))
+        );
+        assert_eq!(
+            LtsLine::from_tag(&Tag::PreviousLts(1)),
+            Some(LtsLine::Previous(1))
+        );
+        assert_eq!(LtsLine::from_tag(&Tag::Lts), None);
+        assert_eq!(LtsLine::from_tag(&Tag::Latest), None);
+    }
 
-        let index_expiry_file = volta_home()?.node_index_expiry_file();
-        ensure_containing_dir_exists(&index_expiry_file).with_context(|| {
-            FilesystemError::ContainingDir {
-                path: index_expiry_file.to_owned(),
-            }
-        })?;
-        expiry.persist(index_expiry_file).with_context(|| {
-            FilesystemError::WriteNodeIndexExpiry {
-                file: index_expiry_file.to_owned(),
-            }
-        })?;
+    #[test]
+    fn lts_line_by_codename() {
+        assert_eq!(newest(&LtsLine::Codename("jod")), Some("22.16.0".into()));
+        assert_eq!(newest(&LtsLine::Codename("Iron")), Some("20.19.2".into()));
+        assert_eq!(newest(&LtsLine::Codename("gallium")), None);
+    }
 
-        spinner.finish_and_clear();
-        Ok(index)
+    #[test]
+    fn previous_lts_line() {
+        assert_eq!(newest(&LtsLine::Previous(0)), Some("22.16.0".into()));
+        assert_eq!(newest(&LtsLine::Previous(1)), Some("20.19.2".into()));
+        assert_eq!(newest(&LtsLine::Previous(2)), Some("18.20.8".into()));
+        assert_eq!(newest(&LtsLine::Previous(3)), None);
     }
 }
@@ -0,0 +1,304 @@
+//! Provides opt-in verification of the signature on the published Node checksums
+
+use std::fs::File;
+use std::io::Read;
+use std::iter;
+use std::path::{Path, PathBuf};
+
+use crate::env_flag;
+use crate::error::{Context, ErrorKind, Fallible, FilesystemError, ToolError};
+use crate::fs::{dir_entry_match, ok_if_not_found};
+use crate::layout::volta_home;
+use crate::style::progress_spinner;
+use attohttpc::{Response, Session};
+use log::debug;
+use pgp::composed::{Deserializable, DetachedSignature, SignedPublicKey};
+use pgp::types::VerifyingKey;
+
+/// Environment variable that enables signature verification, unless it is empty, `0` or `false`
+const VOLTA_VERIFY_SIGNATURES: &str = "VOLTA_VERIFY_SIGNATURES";
+
+/// The Node.js release signing keys that are trusted by default
+///
+/// Generated by `dev/unix/update-node-release-keys.sh` from <https://github.com/nodejs/release-keys>
+const BUNDLED_KEYRING: &str = include_str!("../../../keys/node-release-keys.asc");
+
+/// The location of the bundled keyring in the Volta sources, used for reporting
+const BUNDLED_KEYRING_FILE: &str = "crates/volta-core/keys/node-release-keys.asc";
+
+/// Determine whether signature verification has been enabled
+pub fn enabled() -> bool {
+    env_flag(VOLTA_VERIFY_SIGNATURES)
+}
+
+/// Verify the detached signature published alongside the checksums at `checksums_url`
+///
+/// The signature must be issued by a key in either the bundled keyring or the user's keyring
+/// directory, `VOLTA_HOME/keys/node`.
+pub fn verify(client: &Session, checksums: &str, checksums_url: &str) -> Fallible<()> {
+    let (signature, signature_url) = fetch_signature(client, checksums_url)?;
+    let keyring_dir = volta_home()?.node_keys_dir();
+    let keyring = load_keyring(keyring_dir)?;
+
+    verify_with_keyring(checksums.as_bytes(), &signature, &keyring).map_err(|error| {
+        match error {
+            SignatureError::Bad => ToolError::BadSignature {
+                from_url: signature_url,
+            },
+            SignatureError::UnknownKey(key_id) => ToolError::UnknownSigningKey {
+                key_id,
+                keyring_dir: keyring_dir.to_owned(),
+            },
+        }
+        .into()
+    })
+}
+
+/// Fetch the detached signature published for the checksums at `checksums_url`
+///
+/// The binary signature (`.sig`) is preferred, falling back to the ASCII-armored one (`.asc`) for
+/// mirrors that only publish the latter. Returns the signature along with the URL it came from.
+fn fetch_signature(client: &Session, checksums_url: &str) -> Fallible<(DetachedSignature, String)> {
+    let binary_url = format!("{checksums_url}.sig");
+    let (bytes, url) = match download_signature(client, &binary_url) {
+        Ok(bytes) => (bytes, binary_url),
+        Err(error) => {
+            debug!("{error}, trying the ASCII-armored signature");
+            let armored_url = format!("{checksums_url}.asc");
+            (download_signature(client, &armored_url)?, armored_url)
+        }
+    };
+
+    parse_signature(&bytes, &url).map(|signature| (signature, url))
+}
+
+fn download_signature(client: &Session, url: &str) -> Fallible<Vec<u8>> {
+    let spinner = progress_spinner(format!("Fetching signature: {url}"));
+    debug!("Fetching Node checksums signature from {url}");
+
+    let bytes = client
+        .get(url)
+        .send()
+        .and_then(Response::error_for_status)
+        .and_then(Response::bytes)
+        .with_context(|| ToolError::MissingSignature {
+            from_url: url.into(),
+        })?;
+
+    spinner.finish_and_clear();
+    Ok(bytes)
+}
+
+fn parse_signature(bytes: &[u8], url: &str) -> Fallible<DetachedSignature> {
+    // Signatures may be either binary (`.sig`) or ASCII-armored
+    DetachedSignature::from_reader_single(bytes)
+        .map(|(signature, _)| signature)
+        .with_context(|| ToolError::BadSignature {
+            from_url: url.into(),
+        })
+}
+
+/// Load the bundled keyring along with every key file in the user's keyring directory
+fn load_keyring(keyring_dir: &Path) -> Fallible<Vec<SignedPublicKey>> {
+    let mut keyring =
+        parse_keys(BUNDLED_KEYRING.as_bytes()).with_context(|| ToolError::InvalidSigningKey {
+            file: PathBuf::from(BUNDLED_KEYRING_FILE),
+        })?;
+
+    let files = dir_entry_match(keyring_dir, |entry| Some(entry.path()))
+        .or_else(ok_if_not_found)
+        .with_context(|| FilesystemError::ReadDir {
+            dir: keyring_dir.to_owned(),
+        })?;
+
+    for file in files {
+        debug!("Loading signing keys from '{}'", file.display());
+        let keys = File::open(&file)
+            .ok()
+            .and_then(|source| parse_keys(source).ok())
+            .ok_or_else(|| ErrorKind::Tool(ToolError::InvalidSigningKey { file: file.clone() }))?;
+        keyring.extend(keys);
+    }
+
+    Ok(keyring)
+}
+
+/// Parse all of the public keys in a keyring, which may be binary or ASCII-armored
+fn parse_keys<R: Read>(mut source: R) -> pgp::errors::Result<Vec<SignedPublicKey>> {
+    let mut contents = Vec::new();
+    source.read_to_end(&mut contents)?;
+
+    if contents.iter().all(u8::is_ascii_whitespace) {
+        return Ok(Vec::new());
+    }
+
+    let (keys, _) = SignedPublicKey::from_reader_many(&contents[..])?;
+    keys.collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum SignatureError {
+    /// The signature was made by a matching key, but does not verify the content
+    Bad,
+    /// None of the keys in the keyring issued the signature
+    UnknownKey(String),
+}
+
+fn verify_with_keyring(
+    content: &[u8],
+    signature: &DetachedSignature,
+    keyring: &[SignedPublicKey],
+) -> Result<(), SignatureError> {
+    let results = keyring
+        .iter()
+        .flat_map(|key| {
+            iter::once(verify_with_key(signature, key, content)).chain(
+                key.public_subkeys
+                    .iter()
+                    .map(|subkey| verify_with_key(signature, subkey, content)),
+            )
+        })
+        .flatten()
+        .collect::<Vec<bool>>();
+
+    if results.contains(&true) {
+        debug!("Verified Node checksums signature");
+        Ok(())
+    } else if results.is_empty() {
+        let issuers = signature.signature.issuer_key_id();
+        let fingerprints = signature.signature.issuer_fingerprint();
+        let key_id = issuers
+            .first()
+            .map(|key_id| format!("{key_id}").to_uppercase())
+            .or_else(|| fingerprints.first().map(|fpr| format!("{fpr:X}")))
+            .unwrap_or_else(|| "<unknown>".into());
+        Err(SignatureError::UnknownKey(key_id))
+    } else {
+        Err(SignatureError::Bad)
+    }
+}
+
+/// Verify the signature with `key`, returning `None` if the signature was not issued by `key`
+fn verify_with_key<K: VerifyingKey>(
+    signature: &DetachedSignature,
+    key: &K,
+    content: &[u8],
+) -> Option<bool> {
+    let issued_by_key = signature
+        .signature
+        .issuer_key_id()
+        .contains(&&key.legacy_key_id())
+        || signature
+            .signature
+            .issuer_fingerprint()
+            .contains(&&key.fingerprint());
+
+    issued_by_key.then(|| signature.verify(key, content).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pgp::types::KeyDetails;
+    use std::path::PathBuf;
+
+    fn fixture_path(name: &str) -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push("signatures");
+        path.push(name);
+        path
+    }
+
+    fn fixture_keys(name: &str) -> Vec<SignedPublicKey> {
+        parse_keys(File::open(fixture_path(name)).unwrap()).expect("Could not parse fixture keys")
+    }
+
+    fn fixture_signature(name: &str) -> DetachedSignature {
+        let file = File::open(fixture_path(name)).unwrap();
+        DetachedSignature::from_reader_single(file).unwrap().0
+    }
+
+    fn checksums() -> Vec<u8> {
+        std::fs::read(fixture_path("SHASUMS256.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_bundled_keyring_parses() {
+        assert!(parse_keys(BUNDLED_KEYRING.as_bytes()).is_ok());
+    }
+
+    #[test]
+    #[ignore = "the bundled keyring must be regenerated with dev/unix/update-node-release-keys.sh"]
+    fn test_bundled_keyring_has_release_keys() {
+        let keyring = parse_keys(BUNDLED_KEYRING.as_bytes()).unwrap();
+        assert!(!keyring.is_empty());
+
+        // Richard Lau, who has signed releases of every active Node line
+        let fingerprints: Vec<String> = keyring
+            .iter()
+            .map(|key| format!("{:X}", key.fingerprint()))
+            .collect();
+        assert!(fingerprints.contains(&"C82FA3AE1CBEDC6BE46B9360C43CEC45C17AB93C".to_owned()));
+    }
+
+    #[test]
+    fn test_verify_with_keyring() {
+        let mut keyring = fixture_keys("other.asc");
+        keyring.extend(fixture_keys("release.asc"));
+
+        // Both binary and ASCII-armored signatures are supported
+        for name in ["SHASUMS256.txt.sig", "SHASUMS256.txt.asc"] {
+            let signature = fixture_signature(name);
+            assert_eq!(
+                verify_with_keyring(&checksums(), &signature, &keyring),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_with_keyring_bad() {
+        let keyring = fixture_keys("release.asc");
+        let signature = fixture_signature("SHASUMS256.txt.sig");
+
+        let mut tampered = checksums();
+        tampered[0] = b'0';
+
+        assert_eq!(
+            verify_with_keyring(&tampered, &signature, &keyring),
+            Err(SignatureError::Bad)
+        );
+    }
+
+    #[test]
+    fn test_verify_with_keyring_unknown_key() {
+        let keyring = fixture_keys("other.asc");
+        let signature = fixture_signature("SHASUMS256.txt.sig");
+
+        assert_eq!(
+            verify_with_keyring(&checksums(), &signature, &keyring),
+            Err(SignatureError::UnknownKey("0771BB6CAEAB1EF9".into()))
+        );
+    }
+
+    #[test]
+    fn test_load_keyring_from_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::copy(fixture_path("release.asc"), dir.path().join("release.asc")).unwrap();
+
+        let keyring = load_keyring(dir.path()).unwrap();
+        let signature = fixture_signature("SHASUMS256.txt.sig");
+        assert_eq!(
+            verify_with_keyring(&checksums(), &signature, &keyring),
+            Ok(())
+        );
+
+        // A missing keyring directory only leaves the bundled keys
+        assert!(load_keyring(&dir.path().join("missing")).is_ok());
+
+        // Files that aren't keys are reported
+        std::fs::write(dir.path().join("notes.txt"), "not a key").unwrap();
+        assert!(load_keyring(dir.path()).is_err());
+    }
+}
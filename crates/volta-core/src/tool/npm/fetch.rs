@@ -3,24 +3,57 @@
 use std::fs::{File, write};
 use std::path::Path;
 
-use super::super::download_tool_error;
-use super::super::registry::public_registry_package;
+use super::super::integrity::{ArchiveChecker, Integrity, load_recorded, save_recorded};
+use super::super::lockfile::LockedChecksum;
+use super::super::registry::registry_package;
+use super::super::{StagedDownload, discard_invalid_cache, download_tool_error};
+use super::resolve::fetch_npm_index;
 use crate::error::{Context, ErrorKind, Fallible, FilesystemError, ToolError};
-use crate::fs::{
-    create_staging_dir, create_staging_file, ensure_containing_dir_exists, rename, set_executable,
-};
+use crate::fs::{create_staging_dir, ensure_containing_dir_exists, rename, set_executable};
 use crate::hook::ToolHooks;
+use crate::http;
 use crate::layout::volta_home;
+use crate::settings::Settings;
 use crate::style::{progress_bar, tool_version};
 use crate::tool::{self, Npm};
 use crate::version::VersionSpec;
 use archive::{Archive, Tarball};
+use attohttpc::Session;
 use log::debug;
 use nodejs_semver::Version;
 
-pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Npm>>) -> Fallible<()> {
+pub fn fetch(
+    version: &Version,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Npm>>,
+    corepack: Option<&Integrity>,
+    locked: Option<&LockedChecksum>,
+) -> Fallible<()> {
     let npm_dir = volta_home()?.npm_inventory_dir();
-    let cache_file = npm_dir.join(Npm::archive_filename(&version.to_string()));
+    let version_str = version.to_string();
+    let cache_file = npm_dir.join(Npm::archive_filename(&version_str));
+    let integrity_file = npm_dir.join(Npm::integrity_filename(&version_str));
+
+    // A cached archive was verified against the published integrity when it was downloaded, so
+    // the integrity recorded next to it is used instead of fetching the registry metadata again
+    let recorded = load_recorded(&cache_file, &integrity_file);
+    let published = match &recorded {
+        Some(recorded) => {
+            debug!("Using integrity recorded at '{}'", integrity_file.display());
+            Some(recorded.clone())
+        }
+        None => archive_checksum(version, settings, hooks)?,
+    };
+
+    // Without a published integrity, the archive is verified against the locked one instead
+    let integrity = match (published, locked) {
+        (Some(integrity), Some(locked)) => {
+            locked.check(&integrity, "npm", version)?;
+            Some(integrity)
+        }
+        (None, Some(locked)) => Some(locked.integrity().clone()),
+        (integrity, None) => integrity,
+    };
 
     let (archive, staging) = if let Some(archive) = load_cached_distro(&cache_file) {
         debug!(
@@ -30,30 +63,82 @@ pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Npm>>) -> Fallible<()>
         );
         (archive, None)
     } else {
-        let staging = create_staging_file()?;
-        let remote_url = determine_remote_url(version, hooks)?;
-        let archive = fetch_remote_distro(version, &remote_url, staging.path())?;
+        let staging = StagedDownload::new(&Npm::archive_filename(&version_str))?;
+        let remote_url = determine_remote_url(version, settings, hooks)?;
+        let archive = fetch_remote_distro(
+            &http::client(settings)?,
+            version,
+            &remote_url,
+            staging.path(),
+        )?;
         (archive, Some(staging))
     };
 
-    unpack_archive(archive, version)?;
+    let archive_size = archive.compressed_size();
+    if let Err(error) = unpack_archive(archive, version, integrity.as_ref(), corepack) {
+        discard_invalid_cache(staging, archive_size, &[&cache_file, &integrity_file])?;
+        return Err(error);
+    }
 
-    if let Some(staging_file) = staging {
-        ensure_containing_dir_exists(&cache_file).with_context(|| {
-            ErrorKind::Filesystem(FilesystemError::ContainingDir {
-                path: cache_file.clone(),
-            })
-        })?;
-        staging_file
-            .persist(cache_file)
-            .with_context(|| ErrorKind::Tool(ToolError::PersistInventory { tool: "npm".into() }))?;
+    if let Some(staging) = staging {
+        staging.persist(&cache_file, "npm")?;
+    }
+
+    if let Some(integrity) = integrity.filter(|_| recorded.is_none()) {
+        save_recorded(&integrity_file, &integrity);
     }
 
     Ok(())
 }
 
+/// Determines whether the archive of a version is cached along with its verified integrity, so
+/// that it can be installed without the network
+///
+/// # Errors
+///
+/// Returns an error if the Volta home directory cannot be determined.
+pub fn is_cached(version: &Version) -> Fallible<bool> {
+    let version_str = version.to_string();
+    let npm_dir = volta_home()?.npm_inventory_dir();
+    Ok(load_recorded(
+        &npm_dir.join(Npm::archive_filename(&version_str)),
+        &npm_dir.join(Npm::integrity_filename(&version_str)),
+    )
+    .is_some())
+}
+
+/// Fetch the integrity published by the registry for the npm archive
+///
+/// Archives from a `distro` hook may not be the ones published to the registry, so there is no
+/// integrity to check them against.
+pub fn archive_checksum(
+    version: &Version,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Npm>>,
+) -> Fallible<Option<Integrity>> {
+    if let Some(&ToolHooks {
+        distro: Some(_), ..
+    }) = hooks
+    {
+        debug!("Skipping integrity check, npm.distro hook archives may differ from the registry");
+        return Ok(None);
+    }
+
+    let (index_url, index) = fetch_npm_index(settings, hooks)?;
+    index.integrity("npm", version, &index_url).map(Some)
+}
+
 /// Unpack the npm archive into the image directory so that it is ready for use
-fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()> {
+///
+/// If the registry published an integrity, or the project has a Corepack hash, the archive is
+/// verified against it before it is moved into the image directory, so an invalid archive never
+/// results in an installed image.
+fn unpack_archive(
+    archive: Box<dyn Archive>,
+    version: &Version,
+    integrity: Option<&Integrity>,
+    corepack: Option<&Integrity>,
+) -> Fallible<()> {
     let temp = create_staging_dir()?;
     debug!("Unpacking npm into '{}'", temp.path().display());
 
@@ -63,9 +148,10 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
         archive.compressed_size(),
     );
     let version_string = version.to_string();
+    let mut checker = ArchiveChecker::new(integrity, corepack);
 
     archive
-        .unpack(temp.path(), &mut |(), read| {
+        .unpack(temp.path(), &mut checker, &mut |(), read| {
             progress.inc(read as u64);
         })
         .with_context(|| {
@@ -75,6 +161,8 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
             })
         })?;
 
+    checker.verify("npm", &version_string)?;
+
     let bin_path = temp.path().join("package").join("bin");
     overwrite_launcher(&bin_path, "npm")?;
     overwrite_launcher(&bin_path, "npx")?;
@@ -106,9 +194,8 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
     Ok(())
 }
 
-/// Return the archive if it is valid. It may have been corrupted or interrupted in the middle of
-/// downloading.
-/// ISSUE(#134) - verify checksum
+/// Return the archive if it can be loaded. It may have been corrupted or interrupted in the middle
+/// of downloading, which is detected when it is verified against its integrity during unpacking.
 fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
     if file.is_file() {
         let file = File::open(file).ok()?;
@@ -119,7 +206,11 @@ fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
 }
 
 /// Determine the remote URL to download from, using the hooks if avaialble
-fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Npm>>) -> Fallible<String> {
+fn determine_remote_url(
+    version: &Version,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Npm>>,
+) -> Fallible<String> {
     let version_str = version.to_string();
     match hooks {
         Some(&ToolHooks {
@@ -130,18 +221,19 @@ fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Npm>>) -> Fa
             let distro_file_name = Npm::archive_filename(&version_str);
             hook.resolve(version, &distro_file_name)
         }
-        _ => Ok(public_registry_package("npm", &version_str)),
+        _ => Ok(registry_package(settings, "npm", &version_str)),
     }
 }
 
 /// Fetch the distro archive from the internet
 fn fetch_remote_distro(
+    client: &Session,
     version: &Version,
     url: &str,
     staging_path: &Path,
 ) -> Fallible<Box<dyn Archive>> {
     debug!("Downloading {} from {}", tool_version("npm", version), url);
-    Tarball::fetch(url, staging_path).with_context(download_tool_error(
+    Tarball::fetch(client, url, staging_path).with_context(download_tool_error(
         tool::ToolSpec::Npm(VersionSpec::Exact(version.clone())),
         url,
     ))
@@ -1,12 +1,17 @@
 use std::fmt::{self, Display};
 
+use super::corepack::{corepack_hash, sync_package_manager};
+use super::lockfile::locked_checksum;
 use super::node::load_default_npm_version;
+use super::package::PackageManager;
 use super::{
-    FetchStatus, Fetchable, Installable, Pinnable, check_fetched, check_shim_reachable,
-    debug_already_fetched, info_fetched, info_installed, info_pinned, info_project_version,
+    FetchStatus, Fetchable, Installable, Pinnable, check_fetched, check_online,
+    check_shim_reachable, debug_already_fetched, info_fetched, info_installed, info_pinned,
+    info_project_version,
 };
 use crate::error::{Context, ErrorKind, Fallible, PlatformError, VersionError};
 use crate::inventory::npm_available;
+use crate::project::ManifestKey;
 use crate::session::Session;
 use crate::style::{success_prefix, tool_version};
 use crate::sync::VoltaLock;
@@ -16,6 +21,8 @@ use nodejs_semver::Version;
 mod fetch;
 mod resolve;
 
+pub(crate) use fetch::archive_checksum;
+pub(crate) use resolve::available;
 pub use resolve::resolve;
 
 /// The Tool implementation for fetching and installing npm
@@ -39,13 +46,33 @@ impl Npm {
         format!("{}.tgz", Self::archive_basename(version))
     }
 
+    /// The name of the file in the inventory that records the verified integrity of the archive
+    #[must_use]
+    pub fn integrity_filename(version: &str) -> String {
+        format!("{}.integrity", Self::archive_filename(version))
+    }
+
     pub(crate) fn ensure_fetched(&self, session: &Session) -> Fallible<()> {
         match check_fetched(|| npm_available(&self.version))? {
             FetchStatus::AlreadyFetched => {
                 debug_already_fetched(self);
                 Ok(())
             }
-            FetchStatus::FetchNeeded(_lock) => fetch::fetch(&self.version, session.hooks()?.npm()),
+            FetchStatus::FetchNeeded(_lock) => {
+                // A cached archive with a recorded integrity can be installed without the network
+                if !fetch::is_cached(&self.version)? {
+                    check_online(self, session)?;
+                }
+                let corepack = corepack_hash(session, PackageManager::Npm, &self.version)?;
+                let locked = locked_checksum(session, ManifestKey::Npm, &self.version)?;
+                fetch::fetch(
+                    &self.version,
+                    session.settings()?,
+                    session.hooks()?.npm(),
+                    corepack.as_ref(),
+                    locked.as_ref(),
+                )
+            }
         }
     }
 }
@@ -90,8 +117,8 @@ impl Pinnable for Npm {
             let project = session.project_mut()?.unwrap();
             project.pin_npm(Some(self.version.clone()))?;
 
-            info_pinned(self);
-            Ok(())
+            info_pinned(&self);
+            sync_package_manager(session, PackageManager::Npm, &self.version)
         } else {
             Err(ErrorKind::Platform(PlatformError::NotInPackage).into())
         }
@@ -1,11 +1,14 @@
 //! Provides resolution of npm Version requirements into specific versions
 
-use super::super::registry::{
-    PackageDetails, PackageIndex, fetch_npm_registry, public_registry_index,
-};
+use super::super::registry::{PackageDetails, PackageIndex, fetch_npm_registry, registry_index};
+use super::super::remote::{RemoteVersion, remote_versions_from};
+use super::super::resolve_offline;
 use crate::error::{ErrorKind, Fallible, VersionError};
 use crate::hook::ToolHooks;
+use crate::http;
+use crate::inventory::npm_versions;
 use crate::session::Session;
+use crate::settings::Settings;
 use crate::tool::Npm;
 use crate::version::{Tag, VersionSpec};
 use log::debug;
@@ -15,17 +18,30 @@ use nodejs_semver::{Range, Version};
 ///
 /// Returns an error if the version cannot be resolved.
 pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Option<Version>> {
+    if session.offline() {
+        return match matching {
+            VersionSpec::Tag(Tag::Custom(tag)) if tag == "bundled" => Ok(None),
+            matching => resolve_offline("npm", matching, &npm_versions()?).map(Some),
+        };
+    }
+
+    let settings = session.settings()?;
     let hooks = session.hooks()?.npm();
     match matching {
-        VersionSpec::Semver(requirement) => resolve_semver(&requirement, hooks).map(Some),
+        VersionSpec::Semver(requirement) => resolve_semver(&requirement, settings, hooks).map(Some),
         VersionSpec::Exact(version) => Ok(Some(version)),
-        VersionSpec::None | VersionSpec::Tag(Tag::Latest) => resolve_tag("latest", hooks).map(Some),
+        VersionSpec::None | VersionSpec::Tag(Tag::Latest) => {
+            resolve_tag("latest", settings, hooks).map(Some)
+        }
         VersionSpec::Tag(Tag::Custom(tag)) if tag == "bundled" => Ok(None),
-        VersionSpec::Tag(tag) => resolve_tag(&tag.to_string(), hooks).map(Some),
+        VersionSpec::Tag(tag) => resolve_tag(&tag.to_string(), settings, hooks).map(Some),
     }
 }
 
-fn fetch_npm_index(hooks: Option<&ToolHooks<Npm>>) -> Fallible<(String, PackageIndex)> {
+pub(super) fn fetch_npm_index(
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Npm>>,
+) -> Fallible<(String, PackageIndex)> {
     let url = match hooks {
         Some(&ToolHooks {
             index: Some(ref hook),
@@ -34,14 +50,27 @@ fn fetch_npm_index(hooks: Option<&ToolHooks<Npm>>) -> Fallible<(String, PackageI
             debug!("Using npm.index hook to determine npm index URL");
             hook.resolve("npm")?
         }
-        _ => public_registry_index("npm"),
+        _ => registry_index(settings, "npm"),
     };
 
-    fetch_npm_registry(url, "npm")
+    fetch_npm_registry(&http::client(settings)?, url, "npm")
+}
+
+/// Lists every version of npm available to fetch, using the hooks if available
+pub fn available(
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Npm>>,
+) -> Fallible<Vec<RemoteVersion>> {
+    let (_, index) = fetch_npm_index(settings, hooks)?;
+    Ok(remote_versions_from(index))
 }
 
-fn resolve_tag(tag: &str, hooks: Option<&ToolHooks<Npm>>) -> Fallible<Version> {
-    let (url, mut index) = fetch_npm_index(hooks)?;
+fn resolve_tag(
+    tag: &str,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Npm>>,
+) -> Fallible<Version> {
+    let (url, mut index) = fetch_npm_index(settings, hooks)?;
 
     index.tags.remove(tag).map_or_else(
         || {
@@ -57,8 +86,12 @@ fn resolve_tag(tag: &str, hooks: Option<&ToolHooks<Npm>>) -> Fallible<Version> {
     )
 }
 
-fn resolve_semver(matching: &Range, hooks: Option<&ToolHooks<Npm>>) -> Fallible<Version> {
-    let (url, index) = fetch_npm_index(hooks)?;
+fn resolve_semver(
+    matching: &Range,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Npm>>,
+) -> Fallible<Version> {
+    let (url, index) = fetch_npm_index(settings, hooks)?;
 
     let details_opt = index
         .entries
@@ -0,0 +1,204 @@
+//! Provides the comparison of tool and package versions with the newest versions available
+
+use super::registry::{PackageIndex, fetch_npm_registry, registry_index};
+use super::{ImageKind, PackageConfig, node, npm, pnpm, yarn};
+use crate::error::{ErrorKind, Fallible, NetworkError, VersionError};
+use crate::http;
+use crate::session::Session;
+use crate::version::{Tag, VersionSpec};
+use log::debug;
+use nodejs_semver::{Range, Version};
+
+/// The newest versions available for a tool or package, compared with the version in use
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionCheck {
+    /// The version currently in use
+    pub current: Version,
+    /// The newest version with the same major version as the current one
+    pub wanted: Version,
+    /// The newest version overall
+    pub latest: Version,
+    /// The newest LTS version, only for Node
+    pub lts: Option<Version>,
+}
+
+impl VersionCheck {
+    /// Whether a newer version is available, either within the same major version or overall
+    #[must_use]
+    pub fn is_outdated(&self) -> bool {
+        self.current < self.wanted || self.current < self.latest
+    }
+}
+
+/// Checks for newer versions of Node or a package manager
+///
+/// # Errors
+///
+/// Returns an error if in offline mode, or if the versions cannot be resolved.
+pub fn check_image(
+    kind: ImageKind,
+    current: &Version,
+    session: &mut Session,
+) -> Fallible<VersionCheck> {
+    check_online(session)?;
+    let same_major = || same_major(current).map(VersionSpec::Semver);
+    let latest = || VersionSpec::Tag(Tag::Latest);
+
+    let (wanted, latest, lts) = match kind {
+        ImageKind::Node => (
+            node::resolve(same_major()?, session)?,
+            node::resolve(latest(), session)?,
+            Some(node::resolve(VersionSpec::Tag(Tag::Lts), session)?),
+        ),
+        ImageKind::Npm => (
+            resolve_npm(same_major()?, session)?,
+            resolve_npm(latest(), session)?,
+            None,
+        ),
+        ImageKind::Pnpm => (
+            pnpm::resolve(same_major()?, session)?,
+            pnpm::resolve(latest(), session)?,
+            None,
+        ),
+        ImageKind::Yarn => (
+            yarn::resolve(same_major()?, session)?,
+            yarn::resolve(latest(), session)?,
+            None,
+        ),
+    };
+
+    Ok(VersionCheck {
+        current: current.clone(),
+        wanted,
+        latest,
+        lts,
+    })
+}
+
+/// Checks for newer versions of a globally installed package in the npm registry
+///
+/// # Errors
+///
+/// Returns an error if in offline mode, or if the registry cannot be reached.
+pub fn check_package(config: &PackageConfig, session: &Session) -> Fallible<VersionCheck> {
+    check_online(session)?;
+    let settings = session.settings()?;
+    let url = registry_index(settings, &config.name);
+    let (url, index) = fetch_npm_registry(&http::client(settings)?, url, &config.name)?;
+    debug!("Checking for newer versions of {} from {url}", config.name);
+
+    compare_package(&config.version, index)
+}
+
+/// Compares the current version of a package with those in its registry index
+///
+/// Packages which are missing from the registry, or have no published versions in the same
+/// major version, are compared against their current version.
+fn compare_package(current: &Version, mut index: PackageIndex) -> Fallible<VersionCheck> {
+    let range = same_major(current)?;
+
+    // The index entries are sorted from newest to oldest
+    let wanted = index
+        .entries
+        .iter()
+        .map(|entry| &entry.version)
+        .find(|version| range.satisfies(version))
+        .unwrap_or(current)
+        .clone();
+
+    let latest = index
+        .tags
+        .remove("latest")
+        .or_else(|| index.entries.first().map(|entry| entry.version.clone()))
+        .unwrap_or_else(|| current.clone());
+
+    Ok(VersionCheck {
+        current: current.clone(),
+        wanted,
+        latest,
+        lts: None,
+    })
+}
+
+/// Resolves an npm version, which is always available for a semver range or the `latest` tag
+fn resolve_npm(matching: VersionSpec, session: &mut Session) -> Fallible<Version> {
+    let matching_str = matching.to_string();
+    npm::resolve(matching, session)?.ok_or_else(|| {
+        ErrorKind::Version(VersionError::NpmNotFound {
+            matching: matching_str,
+        })
+        .into()
+    })
+}
+
+/// The range of all versions with the same major version as the given one
+fn same_major(version: &Version) -> Fallible<Range> {
+    let range = format!("{}.x", version.major);
+    Range::parse(&range)
+        .map_err(|_| ErrorKind::Version(VersionError::ParseFailed { version: range }).into())
+}
+
+fn check_online(session: &Session) -> Fallible<()> {
+    if session.offline() {
+        Err(ErrorKind::Network(NetworkError::OutdatedOffline).into())
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::tool::registry::PackageDetails;
+
+    fn index(latest: Option<&str>, versions: &[&str]) -> PackageIndex {
+        PackageIndex {
+            tags: latest
+                .map(|latest| HashMap::from([("latest".into(), Version::parse(latest).unwrap())]))
+                .unwrap_or_default(),
+            entries: versions
+                .iter()
+                .map(|version| PackageDetails {
+                    version: Version::parse(version).unwrap(),
+                    integrity: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn outdated_within_major_and_overall() {
+        let current = Version::parse("5.1.0").unwrap();
+        let check = compare_package(
+            &current,
+            index(Some("6.0.2"), &["6.1.0-beta.1", "6.0.2", "5.9.3", "5.1.0"]),
+        )
+        .unwrap();
+
+        assert_eq!(check.wanted, Version::parse("5.9.3").unwrap());
+        assert_eq!(check.latest, Version::parse("6.0.2").unwrap());
+        assert!(check.is_outdated());
+    }
+
+    #[test]
+    fn up_to_date() {
+        let current = Version::parse("6.0.2").unwrap();
+        let check = compare_package(&current, index(Some("6.0.2"), &["6.0.2", "5.9.3"])).unwrap();
+
+        assert_eq!(check.wanted, current);
+        assert_eq!(check.latest, current);
+        assert!(!check.is_outdated());
+    }
+
+    #[test]
+    fn missing_from_registry() {
+        let current = Version::parse("1.2.3").unwrap();
+        let check = compare_package(&current, index(None, &[])).unwrap();
+
+        assert_eq!(check.wanted, current);
+        assert_eq!(check.latest, current);
+        assert!(!check.is_outdated());
+    }
+}
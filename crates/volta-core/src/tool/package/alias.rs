@@ -0,0 +1,265 @@
+//! Provides installs of packages under aliases, which put a version of a package alongside the
+//! default one, with its executables renamed so that both versions can be used
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use super::manager::PackageManager;
+use super::metadata::{BinConfig, PackageConfig, PackageManifest};
+use super::{configure, persist_install, remove_unused_image};
+use crate::error::{BinaryError, ErrorKind, Fallible, PackageError};
+use crate::fs::remove_file_if_exists;
+use crate::layout::volta_home;
+use crate::platform::RuntimeImage;
+use crate::shim;
+use crate::tool::check_shim_reachable;
+use nodejs_semver::Version;
+
+/// An executable of a package to install under another name, given as `[<executable>=]<alias>`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BinAlias {
+    bin: Option<String>,
+    alias: String,
+}
+
+impl BinAlias {
+    /// Parses an alias, optionally preceded by the executable of the package that it runs
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias or the executable is empty or contains a path separator.
+    pub fn parse(value: &str) -> Fallible<Self> {
+        let (bin, alias) = match value.split_once('=') {
+            Some((bin, alias)) => (Some(bin), alias),
+            None => (None, value),
+        };
+
+        let is_valid = |name: &str| !name.is_empty() && !name.contains(['/', '\\']);
+        if !is_valid(alias) || !bin.is_none_or(is_valid) {
+            return Err(PackageError::AliasInvalid {
+                alias: value.into(),
+            }
+            .into());
+        }
+
+        Ok(Self {
+            bin: bin.map(str::to_owned),
+            alias: alias.into(),
+        })
+    }
+
+    /// Determines the executable of the package that this alias runs
+    ///
+    /// Without an explicit executable, this is the only executable of the package, or else the
+    /// only one whose name starts the alias, like `tsc` for `tsc4`.
+    fn resolve(&self, package: &str, bins: &[String]) -> Fallible<String> {
+        if let Some(bin) = &self.bin {
+            return if bins.contains(bin) {
+                Ok(bin.clone())
+            } else {
+                Err(PackageError::AliasBinNotFound {
+                    package: package.into(),
+                    bin: bin.clone(),
+                    bins: bins.to_vec(),
+                }
+                .into())
+            };
+        }
+
+        let mut candidates = bins
+            .iter()
+            .filter(|bin| bins.len() == 1 || self.alias.starts_with(bin.as_str()));
+        match (candidates.next(), candidates.next()) {
+            (Some(bin), None) => Ok(bin.clone()),
+            _ => Err(PackageError::AliasBinAmbiguous {
+                package: package.into(),
+                alias: self.alias.clone(),
+                bins: bins.to_vec(),
+            }
+            .into()),
+        }
+    }
+}
+
+/// Moves an installed package into the image of its version, without changing the default
+/// version, and makes its executables available under the given aliases
+///
+/// Aliases that previously ran another version of the package are moved to this one, and the
+/// image of that version is removed if it is no longer used.
+///
+/// Returns the installed aliases, mapped to the executables that they run.
+pub(super) fn install_aliased(
+    name: &str,
+    manifest: &PackageManifest,
+    staging_dir: &Path,
+    image: &RuntimeImage,
+    aliases: &[BinAlias],
+) -> Fallible<BTreeMap<String, String>> {
+    let home = volta_home()?;
+    let aliases = aliases
+        .iter()
+        .map(|alias| Ok((alias.alias.clone(), alias.resolve(name, &manifest.bin)?)))
+        .collect::<Fallible<BTreeMap<_, _>>>()?;
+
+    // Find the versions that the aliases ran before, refusing to replace any other executable
+    let mut previous_versions = BTreeSet::new();
+    for alias in aliases.keys() {
+        if let Ok(config) = BinConfig::from_file(home.default_tool_bin_config(alias)) {
+            if config.package != name || config.alias_of.is_none() {
+                return Err(ErrorKind::Binary(BinaryError::AlreadyInstalled {
+                    bin_name: alias.clone(),
+                    existing_package: config.package,
+                    new_package: name.into(),
+                })
+                .into());
+            }
+
+            if config.version != manifest.version {
+                previous_versions.insert(config.version);
+            }
+        }
+    }
+
+    let package_dir = home.package_version_image_dir(name, &manifest.version.to_string());
+    persist_install(name, &manifest.version, staging_dir, package_dir)?;
+
+    let platform = configure::platform_spec(image);
+    for (alias, bin) in &aliases {
+        shim::create(alias)?;
+        check_shim_reachable(alias);
+
+        BinConfig {
+            name: alias.clone(),
+            package: name.into(),
+            version: manifest.version.clone(),
+            platform: platform.clone(),
+            manager: PackageManager::Npm,
+            alias_of: Some(bin.clone()),
+        }
+        .write()?;
+    }
+
+    // Keep the aliases installed earlier for this version
+    let config_file = home.package_version_config_file(name, &manifest.version.to_string());
+    let mut all_aliases = PackageConfig::from_file_if_exists(&config_file)?
+        .map(|config| config.aliases)
+        .unwrap_or_default();
+    all_aliases.extend(aliases.clone());
+
+    PackageConfig {
+        name: name.into(),
+        version: manifest.version.clone(),
+        platform,
+        bins: all_aliases.keys().cloned().collect(),
+        manager: PackageManager::Npm,
+        aliases: all_aliases,
+    }
+    .write()?;
+
+    for version in previous_versions {
+        remove_aliases(name, &version, &aliases)?;
+        remove_unused_image(name, &version)?;
+    }
+
+    Ok(aliases)
+}
+
+/// Removes aliases from the config of a version of a package, which is deleted once it has none
+fn remove_aliases(
+    name: &str,
+    version: &Version,
+    aliases: &BTreeMap<String, String>,
+) -> Fallible<()> {
+    let config_file = volta_home()?.package_version_config_file(name, &version.to_string());
+    let Some(mut config) = PackageConfig::from_file_if_exists(&config_file)? else {
+        return Ok(());
+    };
+
+    config
+        .aliases
+        .retain(|alias, _| !aliases.contains_key(alias));
+    config.bins.retain(|bin| !aliases.contains_key(bin));
+
+    if config.aliases.is_empty() {
+        remove_file_if_exists(config_file)
+    } else {
+        config.write()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bins(names: &[&str]) -> Vec<String> {
+        names.iter().map(|&name| name.to_owned()).collect()
+    }
+
+    #[test]
+    fn parses_aliases() {
+        assert_eq!(
+            BinAlias::parse("tsc4").unwrap(),
+            BinAlias {
+                bin: None,
+                alias: "tsc4".into(),
+            }
+        );
+        assert_eq!(
+            BinAlias::parse("tsserver=tsserver4").unwrap(),
+            BinAlias {
+                bin: Some("tsserver".into()),
+                alias: "tsserver4".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_aliases() {
+        assert!(BinAlias::parse("").is_err());
+        assert!(BinAlias::parse("tsc=").is_err());
+        assert!(BinAlias::parse("=tsc4").is_err());
+        assert!(BinAlias::parse("bin/tsc4").is_err());
+    }
+
+    #[test]
+    fn resolves_the_only_executable() {
+        let alias = BinAlias::parse("ng16").unwrap();
+        assert_eq!(alias.resolve("@angular/cli", &bins(&["ng"])).unwrap(), "ng");
+    }
+
+    #[test]
+    fn resolves_executable_by_prefix() {
+        let alias = BinAlias::parse("tsc4").unwrap();
+        assert_eq!(
+            alias
+                .resolve("typescript", &bins(&["tsc", "tsserver"]))
+                .unwrap(),
+            "tsc"
+        );
+
+        let alias = BinAlias::parse("legacy-tsc").unwrap();
+        assert!(
+            alias
+                .resolve("typescript", &bins(&["tsc", "tsserver"]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn resolves_explicit_executable() {
+        let alias = BinAlias::parse("tsserver=tss4").unwrap();
+        assert_eq!(
+            alias
+                .resolve("typescript", &bins(&["tsc", "tsserver"]))
+                .unwrap(),
+            "tsserver"
+        );
+
+        let alias = BinAlias::parse("tsx=tsx4").unwrap();
+        assert!(
+            alias
+                .resolve("typescript", &bins(&["tsc", "tsserver"]))
+                .is_err()
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use super::manager::PackageManager;
@@ -29,12 +30,7 @@ pub(super) fn write_config_and_shims(
 ) -> Fallible<()> {
     validate_bins(name, manifest)?;
 
-    let platform = PlatformSpec {
-        node: image.node.value.clone(),
-        npm: image.npm.clone().map(|s| s.value),
-        pnpm: image.pnpm.clone().map(|s| s.value),
-        yarn: image.yarn.clone().map(|s| s.value),
-    };
+    let platform = platform_spec(image);
 
     // Generate the shims and bin configs for each bin provided by the package
     for bin_name in &manifest.bin {
@@ -47,6 +43,7 @@ pub(super) fn write_config_and_shims(
             version: manifest.version.clone(),
             platform: platform.clone(),
             manager,
+            alias_of: None,
         }
         .write()?;
     }
@@ -58,12 +55,23 @@ pub(super) fn write_config_and_shims(
         platform,
         bins: manifest.bin.clone(),
         manager,
+        aliases: BTreeMap::new(),
     }
     .write()?;
 
     Ok(())
 }
 
+/// The platform that an installed package runs with
+pub(super) fn platform_spec(image: &RuntimeImage) -> PlatformSpec {
+    PlatformSpec {
+        node: image.node.value.clone(),
+        npm: image.npm.clone().map(|s| s.value),
+        pnpm: image.pnpm.clone().map(|s| s.value),
+        yarn: image.yarn.clone().map(|s| s.value),
+    }
+}
+
 /// Validate that we aren't attempting to install a bin that is already installed by
 /// another package.
 fn validate_bins(package_name: &str, manifest: &PackageManifest) -> Fallible<()> {
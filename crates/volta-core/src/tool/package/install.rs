@@ -12,10 +12,13 @@ use log::debug;
 /// Sets the environment variable `npm_config_prefix` to redirect the install to the Volta
 /// data directory, taking advantage of the standard global install behavior with a custom
 /// location
+///
+/// In offline mode, npm is only allowed to install the package from its own cache
 pub(super) fn run_global_install(
     package: String,
     staging_dir: PathBuf,
     platform_image: &RuntimeImage,
+    offline: bool,
 ) -> Fallible<()> {
     let mut command = create_command("npm");
     command.args([
@@ -25,6 +28,9 @@ pub(super) fn run_global_install(
         "--no-update-notifier",
         "--no-audit",
     ]);
+    if offline {
+        command.arg("--offline");
+    }
     command.arg(&package);
     command.env("PATH", platform_image.path()?);
     PackageManager::Npm.setup_global_command(&mut command, staging_dir);
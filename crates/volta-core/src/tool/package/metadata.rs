@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::io;
 use std::path::Path;
@@ -15,7 +15,9 @@ use nodejs_semver::Version;
 
 /// Configuration information about an installed package
 ///
-/// Will be stored in `<VOLTA_HOME>/tools/user/packages/<package>.json`
+/// Will be stored in `<VOLTA_HOME>/tools/user/packages/<package>.json` for the default version,
+/// or in `<VOLTA_HOME>/tools/user/packages/<package>@<version>.json` for a version installed
+/// alongside it under aliases
 #[derive(serde::Serialize, serde::Deserialize, PartialOrd, Ord, PartialEq, Eq)]
 pub struct PackageConfig {
     /// The package name
@@ -30,6 +32,9 @@ pub struct PackageConfig {
     pub bins: Vec<String>,
     /// The package manager that was used to install this package
     pub manager: PackageManager,
+    /// The executables in `bins` that are aliases, mapped to the bins of the package they run
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
 }
 
 impl PackageConfig {
@@ -83,7 +88,12 @@ impl PackageConfig {
     ///
     /// Returns an error if the config file cannot be written.
     pub fn write(self) -> Fallible<()> {
-        let config_file_path = volta_home()?.default_package_config_file(&self.name);
+        let home = volta_home()?;
+        let config_file_path = if self.aliases.is_empty() {
+            home.default_package_config_file(&self.name)
+        } else {
+            home.package_version_config_file(&self.name, &self.version.to_string())
+        };
 
         ensure_containing_dir_exists(&config_file_path).with_context(|| {
             ErrorKind::Filesystem(FilesystemError::ContainingDir {
@@ -118,9 +128,18 @@ pub struct BinConfig {
     pub platform: PlatformSpec,
     /// The package manager used to install this binary
     pub manager: PackageManager,
+    /// The bin of the package that this binary runs, if it was installed under an alias
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias_of: Option<String>,
 }
 
 impl BinConfig {
+    /// The name of the executable in the package image
+    #[must_use]
+    pub fn bin_name(&self) -> &str {
+        self.alias_of.as_deref().unwrap_or(&self.name)
+    }
+
     /// Parse a `BinConfig` instance from the given config file
     ///
     /// # Errors
@@ -243,19 +262,24 @@ impl PackageManifest {
             })
         })?;
 
-        // If the bin list contains only an empty string, that means `bin` was a string value,
-        // rather than a map. In that case, to match `npm`s behavior, we use the name of the package
-        // as the bin name.
-        // Note: For a scoped package, we should remove the scope and only use the package name
-        if manifest.bin == [""] {
-            manifest.bin.pop();
-            manifest.bin.push(default_binary_name(&manifest.name));
-        }
+        resolve_bin_names(&manifest.name, &mut manifest.bin);
 
         Ok(manifest)
     }
 }
 
+/// Completes a list of bins parsed with `serde_bins`, for the package with the given name
+pub(super) fn resolve_bin_names(package_name: &str, bin: &mut Vec<String>) {
+    // If the bin list contains only an empty string, that means `bin` was a string value,
+    // rather than a map. In that case, to match `npm`s behavior, we use the name of the package
+    // as the bin name.
+    // Note: For a scoped package, we should remove the scope and only use the package name
+    if bin == &[""] {
+        bin.pop();
+        bin.push(default_binary_name(package_name));
+    }
+}
+
 #[derive(serde::Deserialize)]
 /// Struct to read the `dependencies` out of Yarn's global manifest.
 ///
@@ -266,7 +290,9 @@ pub(super) struct GlobalYarnManifest {
     pub dependencies: HashMap<String, String>,
 }
 
-mod serde_bins {
+/// Deserializes the `bin` field of a package, which is either a map of bin names to paths or a
+/// single path, into the list of bin names
+pub mod serde_bins {
     use std::fmt;
 
     use serde::de::{Deserializer, Error, MapAccess, Visitor};
@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use super::Installable;
+use super::uninstall::bin_configs;
 use crate::error::{
     Context, ErrorKind, Fallible, FilesystemError, PackageError, PlatformError, ToolError,
 };
@@ -15,18 +16,24 @@ use crate::style::{success_prefix, tool_version};
 use crate::sync::VoltaLock;
 use crate::version::VersionSpec;
 use log::info;
+use nodejs_semver::Version;
 use tempfile::{TempDir, tempdir_in};
 
+mod alias;
 mod configure;
 mod install;
 mod manager;
 mod metadata;
+mod project;
 mod uninstall;
 
+pub use alias::BinAlias;
 #[allow(clippy::module_name_repetitions)]
 pub use manager::PackageManager;
+pub(crate) use metadata::serde_bins;
 #[allow(clippy::module_name_repetitions)]
 pub use metadata::{BinConfig, PackageConfig, PackageManifest};
+pub use project::{install_project_tools, project_tool_bin};
 pub use uninstall::uninstall;
 
 /// The Tool implementation for installing 3rd-party global packages
@@ -34,6 +41,7 @@ pub struct Package {
     name: String,
     version: VersionSpec,
     staging: TempDir,
+    aliases: Vec<BinAlias>,
 }
 
 impl Package {
@@ -47,17 +55,28 @@ impl Package {
             name,
             version,
             staging,
+            aliases: Vec::new(),
         })
     }
 
+    /// Installs the package alongside the default version instead of replacing it, with its
+    /// executables available under the given aliases
+    #[must_use]
+    pub fn with_aliases(self, aliases: Vec<BinAlias>) -> Self {
+        Self { aliases, ..self }
+    }
+
+    /// Installs the package into the staging directory, using only the npm cache if `offline`
+    ///
     /// # Errors
     ///
     /// Returns an error if the install fails.
-    pub fn run_install(&self, platform_image: &RuntimeImage) -> Fallible<()> {
+    pub fn run_install(&self, platform_image: &RuntimeImage, offline: bool) -> Fallible<()> {
         install::run_global_install(
             self.to_string(),
             self.staging.path().to_owned(),
             platform_image,
+            offline,
         )
     }
 
@@ -69,9 +88,7 @@ impl Package {
         let manifest =
             configure::parse_manifest(&self.name, self.staging.path().to_owned(), manager)?;
 
-        persist_install(&self.name, &self.version, self.staging.path())?;
-        link_package_to_shared_dir(&self.name, manager)?;
-        configure::write_config_and_shims(&self.name, &manifest, image, manager)?;
+        install_default(&self.name, &manifest, self.staging.path(), image, manager)?;
 
         Ok(manifest)
     }
@@ -87,7 +104,36 @@ impl Installable for Package {
             .ok_or(ErrorKind::Platform(PlatformError::NoPlatform))?
             .checkout(session)?;
 
-        self.run_install(&default_image)?;
+        self.run_install(&default_image, session.offline())?;
+
+        if !self.aliases.is_empty() {
+            let manifest = configure::parse_manifest(
+                &self.name,
+                self.staging.path().to_owned(),
+                PackageManager::Npm,
+            )?;
+            let aliases = alias::install_aliased(
+                &self.name,
+                &manifest,
+                self.staging.path(),
+                &default_image,
+                &self.aliases,
+            )?;
+
+            let executables: Vec<String> = aliases
+                .iter()
+                .map(|(alias, bin)| format!("{alias} ({bin})"))
+                .collect();
+            info!(
+                "{} installed {} alongside the default with executables: {}",
+                success_prefix(),
+                tool_version(manifest.name, manifest.version),
+                executables.join(", ")
+            );
+
+            return Ok(());
+        }
+
         let manifest = self.complete_install(&default_image)?;
 
         let bins = manifest.bin.join(", ");
@@ -180,9 +226,7 @@ impl DirectInstall {
             .ok_or(ErrorKind::Package(PackageError::InstalledNameUnknown))?;
         let manifest = configure::parse_manifest(&name, staging.path().to_owned(), manager)?;
 
-        persist_install(&name, &manifest.version, staging.path())?;
-        link_package_to_shared_dir(&name, manager)?;
-        configure::write_config_and_shims(&name, &manifest, image, manager)
+        install_default(&name, &manifest, staging.path(), image, manager)
     }
 }
 
@@ -198,9 +242,14 @@ pub struct InPlaceUpgrade {
 impl InPlaceUpgrade {
     /// # Errors
     ///
-    /// Returns an error if the Volta home directory cannot be determined.
+    /// Returns an error if the Volta home directory or the installed package cannot be read.
     pub fn new(package: String, manager: PackageManager) -> Fallible<Self> {
-        let directory = volta_home()?.package_image_dir(&package);
+        let home = volta_home()?;
+        let directory =
+            match PackageConfig::from_file_if_exists(home.default_package_config_file(&package))? {
+                Some(config) => package_image(&package, &config.version)?,
+                None => home.package_image_dir(&package),
+            };
 
         Ok(Self {
             package,
@@ -246,9 +295,22 @@ impl InPlaceUpgrade {
     ///
     /// Returns an error if the upgrade cannot be completed.
     pub fn complete_upgrade(self, image: &RuntimeImage) -> Fallible<()> {
-        let manifest = configure::parse_manifest(&self.package, self.directory, self.manager)?;
+        let manifest =
+            configure::parse_manifest(&self.package, self.directory.clone(), self.manager)?;
+
+        // The upgraded package is moved to the image of its new version
+        let package_dir =
+            volta_home()?.package_version_image_dir(&self.package, &manifest.version.to_string());
+        if package_dir != self.directory {
+            persist_install(
+                &self.package,
+                &manifest.version,
+                &self.directory,
+                package_dir.clone(),
+            )?;
+        }
 
-        link_package_to_shared_dir(&self.package, self.manager)?;
+        link_package_to_shared_dir(&self.package, &package_dir, self.manager)?;
         configure::write_config_and_shims(&self.package, &manifest, image, self.manager)
     }
 }
@@ -270,7 +332,7 @@ impl From<bool> for NeedsScope {
 fn setup_staging_directory(manager: PackageManager, needs_scope: NeedsScope) -> Fallible<TempDir> {
     // Workaround to ensure relative symlinks continue to work.
     // The final installed location of packages is:
-    //      $VOLTA_HOME/tools/image/packages/{name}/
+    //      $VOLTA_HOME/tools/image/packages/{name}@{version}/
     // To ensure that the temp directory has the same amount of nesting, we use:
     //      $VOLTA_HOME/tmp/image/packages/{tempdir}/
     // This way any relative symlinks will have the same amount of nesting and will remain valid
@@ -307,12 +369,76 @@ fn setup_staging_directory(manager: PackageManager, needs_scope: NeedsScope) ->
     Ok(staging)
 }
 
-fn persist_install<V>(package_name: &str, package_version: V, staging_dir: &Path) -> Fallible<()>
+/// Determines the image directory of an installed version of a package
+///
+/// Packages installed before images were keyed by version are found in the image directory named
+/// after the package alone.
+///
+/// # Errors
+///
+/// Returns an error if the Volta home directory cannot be determined.
+#[allow(clippy::module_name_repetitions)]
+pub fn package_image(name: &str, version: &Version) -> Fallible<PathBuf> {
+    let home = volta_home()?;
+    let image = home.package_version_image_dir(name, &version.to_string());
+    if image.exists() {
+        Ok(image)
+    } else {
+        Ok(home.package_image_dir(name))
+    }
+}
+
+/// Moves an installed package into the image of its version and makes it the default version
+///
+/// The image of the previous default version is removed, unless it is still used by one of the
+/// installed binaries.
+fn install_default(
+    name: &str,
+    manifest: &PackageManifest,
+    staging_dir: &Path,
+    image: &RuntimeImage,
+    manager: PackageManager,
+) -> Fallible<()> {
+    let home = volta_home()?;
+    let previous = PackageConfig::from_file_if_exists(home.default_package_config_file(name))?;
+
+    let package_dir = home.package_version_image_dir(name, &manifest.version.to_string());
+    persist_install(name, &manifest.version, staging_dir, package_dir.clone())?;
+    remove_dir_if_exists(home.package_image_dir(name))?;
+
+    link_package_to_shared_dir(name, &package_dir, manager)?;
+    configure::write_config_and_shims(name, manifest, image, manager)?;
+
+    match previous {
+        Some(previous) if previous.version != manifest.version => {
+            remove_unused_image(name, &previous.version)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Removes the image of a version of a package, unless an installed binary still runs it
+fn remove_unused_image(name: &str, version: &Version) -> Fallible<()> {
+    let in_use = bin_configs()?
+        .iter()
+        .any(|bin| bin.package == name && bin.version == *version);
+
+    if !in_use {
+        remove_dir_if_exists(volta_home()?.package_version_image_dir(name, &version.to_string()))?;
+    }
+
+    Ok(())
+}
+
+fn persist_install<V>(
+    package_name: &str,
+    package_version: V,
+    staging_dir: &Path,
+    package_dir: PathBuf,
+) -> Fallible<()>
 where
     V: Display,
 {
-    let package_dir = volta_home()?.package_image_dir(package_name);
-
     remove_dir_if_exists(&package_dir)?;
 
     // Handle scoped packages (@vue/cli), which have an extra directory for the scope
@@ -333,12 +459,15 @@ where
     Ok(())
 }
 
-fn link_package_to_shared_dir(package_name: &str, manager: PackageManager) -> Fallible<()> {
-    let home = volta_home()?;
-    let mut source = manager.source_dir(home.package_image_dir(package_name));
+fn link_package_to_shared_dir(
+    package_name: &str,
+    package_dir: &Path,
+    manager: PackageManager,
+) -> Fallible<()> {
+    let mut source = manager.source_dir(package_dir.to_owned());
     source.push(package_name);
 
-    let target = home.shared_lib_dir(package_name);
+    let target = volta_home()?.shared_lib_dir(package_name);
 
     remove_dir_if_exists(&target)?;
 
@@ -0,0 +1,197 @@
+//! Provides the packages in the `volta.tools` map of a project, which are installed into package
+//! images keyed by name and version on first use, so that several projects can use different
+//! versions of a package alongside the default one
+//!
+//! A bin can only be run directly once it has a shim, so on a fresh checkout the packages are
+//! installed with `volta install` in the project, or on the first `volta run <bin>`.
+
+use std::path::PathBuf;
+
+use super::manager::PackageManager;
+use super::metadata::{PackageManifest, resolve_bin_names};
+use super::{NeedsScope, configure, install, persist_install, setup_staging_directory};
+use crate::error::{ErrorKind, Fallible, PackageError, PlatformError};
+use crate::http;
+use crate::layout::volta_home;
+use crate::platform::Platform;
+use crate::project::ProjectTool;
+use crate::session::Session;
+use crate::shim;
+use crate::style::{success_prefix, tool_version};
+use crate::sync::VoltaLock;
+use crate::tool::check_shim_reachable;
+use crate::tool::registry::{fetch_npm_metadata, registry_index};
+use log::{debug, info};
+
+/// Finds the path to a bin provided by one of the packages in `volta.tools` of the current
+/// project, if any
+///
+/// The installed packages are checked first. The others are only installed once one of their bins
+/// is needed, and only if the registry metadata of the package lists that bin.
+///
+/// # Errors
+///
+/// Returns an error if the project cannot be loaded or a package cannot be installed.
+pub fn project_tool_bin(bin: &str, session: &mut Session) -> Fallible<Option<PathBuf>> {
+    let tools: Vec<ProjectTool> = match session.project()? {
+        Some(project) => project.tools().cloned().collect(),
+        None => return Ok(None),
+    };
+
+    let mut missing = Vec::new();
+    for tool in tools {
+        match installed_manifest(&tool)? {
+            Some(manifest) if provides(&manifest.bin, bin) => {
+                return bin_path(&tool, bin).map(Some);
+            }
+            Some(_) => {}
+            None => missing.push(tool),
+        }
+    }
+
+    for tool in missing {
+        if publishes(&tool, bin, session)? {
+            let manifest = install(&tool, session)?;
+            if provides(&manifest.bin, bin) {
+                return bin_path(&tool, bin).map(Some);
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Installs the packages in `volta.tools` of the current project that aren't installed yet, and
+/// creates shims for the bins of all of them, so that they can be run directly in the project
+///
+/// # Errors
+///
+/// Returns an error if there is no project with tools, or a package cannot be installed.
+pub fn install_project_tools(session: &mut Session) -> Fallible<()> {
+    let tools: Vec<ProjectTool> = session
+        .project()?
+        .map(|project| project.tools().cloned().collect())
+        .unwrap_or_default();
+    if tools.is_empty() {
+        return Err(PackageError::NoProjectTools.into());
+    }
+
+    for tool in tools {
+        if let Some(manifest) = installed_manifest(&tool)? {
+            create_shims(&manifest)?;
+            info!(
+                "{} {} is already installed for this project",
+                success_prefix(),
+                tool_version(&tool.name, &tool.version)
+            );
+        } else {
+            install(&tool, session)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn provides(bins: &[String], bin: &str) -> bool {
+    bins.iter().any(|name| name == bin)
+}
+
+/// Determines whether the published version of a package in `volta.tools` provides a bin, from
+/// its metadata in the registry
+///
+/// If the metadata can't be fetched, the package is assumed not to provide the bin, so that the
+/// default version of the bin can still be run.
+fn publishes(tool: &ProjectTool, bin: &str, session: &Session) -> Fallible<bool> {
+    let settings = session.settings()?;
+    let url = registry_index(settings, &tool.name);
+    let metadata = match http::client(settings)
+        .and_then(|client| fetch_npm_metadata(&client, &url, &tool.name))
+    {
+        Ok(metadata) => metadata,
+        Err(error) => {
+            debug!(
+                "Could not determine the executables of {}: {error}",
+                tool_version(&tool.name, &tool.version)
+            );
+            return Ok(false);
+        }
+    };
+
+    Ok(metadata
+        .versions
+        .into_values()
+        .find(|info| info.version == tool.version)
+        .is_some_and(|mut info| {
+            resolve_bin_names(&tool.name, &mut info.bin);
+            provides(&info.bin, bin)
+        }))
+}
+
+/// The image of a package in `volta.tools`, which is kept apart from the images of global
+/// installs, so that uninstalling a global version never removes it
+fn image_dir(tool: &ProjectTool) -> Fallible<PathBuf> {
+    Ok(volta_home()?.project_tool_image_dir(&tool.name, &tool.version.to_string()))
+}
+
+fn bin_path(tool: &ProjectTool, bin: &str) -> Fallible<PathBuf> {
+    let mut path = PackageManager::Npm.binary_dir(image_dir(tool)?);
+    path.push(bin);
+    Ok(path)
+}
+
+/// Reads the manifest of a package in `volta.tools`, if its image is installed
+fn installed_manifest(tool: &ProjectTool) -> Fallible<Option<PackageManifest>> {
+    let image = image_dir(tool)?;
+    if !image.exists() {
+        return Ok(None);
+    }
+
+    configure::parse_manifest(&tool.name, image, PackageManager::Npm).map(Some)
+}
+
+/// Installs a package in `volta.tools` into its versioned image, with the project platform
+///
+/// Shims are created for its bins, so that they can be run directly, but no bin configs are
+/// written, since those would make the package the default outside of the project.
+fn install(tool: &ProjectTool, session: &mut Session) -> Fallible<PackageManifest> {
+    let _lock = VoltaLock::acquire();
+    let spec = tool_version(&tool.name, &tool.version);
+    debug!("Installing {spec} from the project tools");
+
+    let image = Platform::current(session)?
+        .ok_or(ErrorKind::Platform(PlatformError::NoPlatform))?
+        .checkout(session)?;
+
+    let staging = setup_staging_directory(
+        PackageManager::Npm,
+        NeedsScope::from(tool.name.contains('/')),
+    )?;
+    install::run_global_install(
+        spec.clone(),
+        staging.path().to_owned(),
+        &image,
+        session.offline(),
+    )?;
+
+    let manifest =
+        configure::parse_manifest(&tool.name, staging.path().to_owned(), PackageManager::Npm)?;
+    persist_install(&tool.name, &tool.version, staging.path(), image_dir(tool)?)?;
+
+    create_shims(&manifest)?;
+
+    info!(
+        "{} installed {spec} for this project with executables: {}",
+        success_prefix(),
+        manifest.bin.join(", ")
+    );
+
+    Ok(manifest)
+}
+
+fn create_shims(manifest: &PackageManifest) -> Fallible<()> {
+    for bin in &manifest.bin {
+        shim::create(bin)?;
+        check_shim_reachable(bin);
+    }
+    Ok(())
+}
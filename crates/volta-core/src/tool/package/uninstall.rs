@@ -1,8 +1,11 @@
+use std::collections::BTreeSet;
+
 use super::metadata::{BinConfig, PackageConfig};
 use crate::error::{BinaryError, Context, ErrorKind, Fallible};
 use crate::fs::{
     dir_entry_match, ok_if_not_found, read_dir_eager, remove_dir_if_exists, remove_file_if_exists,
 };
+use crate::inventory::package_configs;
 use crate::layout::volta_home;
 use crate::shim;
 use crate::style::success_prefix;
@@ -15,7 +18,8 @@ use log::{info, warn};
 ///
 /// - The JSON configuration files for both the package and its bins
 /// - The shims for the package bins
-/// - The package directory itself
+/// - The versions of the package installed alongside the default under aliases
+/// - The package directories themselves
 ///
 /// # Errors
 ///
@@ -27,16 +31,18 @@ pub fn uninstall(name: &str) -> Fallible<()> {
 
     // If the package config file exists, use that to remove any installed bins and shims
     let package_config_file = home.default_package_config_file(name);
+    let mut versions = BTreeSet::new();
 
-    let package_found = match PackageConfig::from_file_if_exists(&package_config_file)? {
+    let mut package_found = match PackageConfig::from_file_if_exists(&package_config_file)? {
         None => {
             // there is no package config - check for orphaned binaries
             let package_binary_list = binaries_from_package(name)?;
             if package_binary_list.is_empty() {
                 false
             } else {
-                for bin_name in package_binary_list {
-                    remove_config_and_shim(&bin_name, name)?;
+                for bin in package_binary_list {
+                    remove_config_and_shim(&bin.name, name)?;
+                    versions.insert(bin.version);
                 }
                 true
             }
@@ -47,15 +53,32 @@ pub fn uninstall(name: &str) -> Fallible<()> {
             }
 
             remove_file_if_exists(package_config_file)?;
+            versions.insert(package_config.version);
             true
         }
     };
 
+    // Remove the versions installed alongside the default under aliases
+    for config in package_configs()?
+        .into_iter()
+        .filter(|config| config.name == name)
+    {
+        for alias in &config.bins {
+            remove_config_and_shim(alias, name)?;
+        }
+
+        remove_file_if_exists(home.package_version_config_file(name, &config.version.to_string()))?;
+        versions.insert(config.version);
+        package_found = true;
+    }
+
     remove_shared_link_dir(name)?;
 
-    // Remove the package directory itself
-    let package_image_dir = home.package_image_dir(name);
-    remove_dir_if_exists(package_image_dir)?;
+    // Remove the package directories themselves
+    for version in versions {
+        remove_dir_if_exists(home.package_version_image_dir(name, &version.to_string()))?;
+    }
+    remove_dir_if_exists(home.package_image_dir(name))?;
 
     if package_found {
         info!("{} package '{}' uninstalled", success_prefix(), name);
@@ -75,17 +98,18 @@ fn remove_config_and_shim(bin_name: &str, pkg_name: &str) -> Fallible<()> {
     Ok(())
 }
 
-/// Reads the contents of a directory and returns a Vec containing the names of
+/// Reads the contents of a directory and returns a Vec containing the configs of
 /// all the binaries installed by the given package.
-fn binaries_from_package(package: &str) -> Fallible<Vec<String>> {
+fn binaries_from_package(package: &str) -> Fallible<Vec<BinConfig>> {
     let bin_config_dir = volta_home()?.default_bin_dir();
 
     dir_entry_match(bin_config_dir, |entry| {
         let path = entry.path();
         if let Ok(config) = BinConfig::from_file(path)
             && config.package == package
+            && config.alias_of.is_none()
         {
-            return Some(config.name);
+            return Some(config);
         }
         None
     })
@@ -3,26 +3,48 @@ use nodejs_semver::{Range, Version};
 
 use crate::error::{ErrorKind, Fallible, VersionError};
 use crate::hook::ToolHooks;
+use crate::http;
+use crate::inventory::pnpm_versions;
 use crate::session::Session;
-use crate::tool::registry::{PackageIndex, fetch_npm_registry, public_registry_index};
-use crate::tool::{PackageDetails, Pnpm};
+use crate::settings::Settings;
+use crate::tool::registry::{PackageIndex, fetch_npm_registry, registry_index};
+use crate::tool::remote::{RemoteVersion, remote_versions_from};
+use crate::tool::{PackageDetails, Pnpm, resolve_offline};
 use crate::version::{Tag, VersionSpec};
 
 /// # Errors
 ///
 /// Returns an error if the version cannot be resolved.
 pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Version> {
+    if session.offline() {
+        return resolve_offline("pnpm", matching, &pnpm_versions()?);
+    }
+
+    let settings = session.settings()?;
     let hooks = session.hooks()?.pnpm();
     match matching {
-        VersionSpec::Semver(requirement) => resolve_semver(&requirement, hooks),
+        VersionSpec::Semver(requirement) => resolve_semver(&requirement, settings, hooks),
         VersionSpec::Exact(version) => Ok(version),
-        VersionSpec::None | VersionSpec::Tag(Tag::Latest) => resolve_tag("latest", hooks),
-        VersionSpec::Tag(tag) => resolve_tag(&tag.to_string(), hooks),
+        VersionSpec::None | VersionSpec::Tag(Tag::Latest) => resolve_tag("latest", settings, hooks),
+        VersionSpec::Tag(tag) => resolve_tag(&tag.to_string(), settings, hooks),
     }
 }
 
-fn resolve_tag(tag: &str, hooks: Option<&ToolHooks<Pnpm>>) -> Fallible<Version> {
-    let (url, mut index) = fetch_pnpm_index(hooks)?;
+/// Lists every version of pnpm available to fetch, using the hooks if available
+pub fn available(
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Pnpm>>,
+) -> Fallible<Vec<RemoteVersion>> {
+    let (_, index) = fetch_pnpm_index(settings, hooks)?;
+    Ok(remote_versions_from(index))
+}
+
+fn resolve_tag(
+    tag: &str,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Pnpm>>,
+) -> Fallible<Version> {
+    let (url, mut index) = fetch_pnpm_index(settings, hooks)?;
 
     index.tags.remove(tag).map_or_else(
         || {
@@ -38,8 +60,12 @@ fn resolve_tag(tag: &str, hooks: Option<&ToolHooks<Pnpm>>) -> Fallible<Version>
     )
 }
 
-fn resolve_semver(matching: &Range, hooks: Option<&ToolHooks<Pnpm>>) -> Fallible<Version> {
-    let (url, index) = fetch_pnpm_index(hooks)?;
+fn resolve_semver(
+    matching: &Range,
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Pnpm>>,
+) -> Fallible<Version> {
+    let (url, index) = fetch_pnpm_index(settings, hooks)?;
 
     let details_opt = index
         .entries
@@ -62,7 +88,10 @@ fn resolve_semver(matching: &Range, hooks: Option<&ToolHooks<Pnpm>>) -> Fallible
 }
 
 /// Fetch the index of available pnpm versions from the npm registry
-fn fetch_pnpm_index(hooks: Option<&ToolHooks<Pnpm>>) -> Fallible<(String, PackageIndex)> {
+pub(super) fn fetch_pnpm_index(
+    settings: &Settings,
+    hooks: Option<&ToolHooks<Pnpm>>,
+) -> Fallible<(String, PackageIndex)> {
     let url = match hooks {
         Some(&ToolHooks {
             index: Some(ref hook),
@@ -71,8 +100,8 @@ fn fetch_pnpm_index(hooks: Option<&ToolHooks<Pnpm>>) -> Fallible<(String, Packag
             debug!("Using pnpm.index hook to determine pnpm index URL");
             hook.resolve("pnpm")?
         }
-        _ => public_registry_index("pnpm"),
+        _ => registry_index(settings, "pnpm"),
     };
 
-    fetch_npm_registry(url, "pnpm")
+    fetch_npm_registry(&http::client(settings)?, url, "pnpm")
 }
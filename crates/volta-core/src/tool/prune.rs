@@ -0,0 +1,221 @@
+//! Provides the garbage collector for unused Node and package manager versions
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Display};
+use std::fs::{self, DirEntry, Metadata};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::uninstall::{ImageKind, bin_configs};
+use crate::error::{Context, ErrorKind, Fallible, FilesystemError};
+use crate::fs::{
+    disk_usage, ok_if_not_found, read_dir_eager, remove_dir_if_exists, remove_file_if_exists,
+};
+use crate::inventory::package_configs;
+use crate::platform::PlatformSpec;
+use crate::project::Project;
+use crate::session::Session;
+use crate::style::{file_size, note_prefix, success_prefix, tool_version};
+use crate::sync::VoltaLock;
+use crate::usage::Index;
+use crate::version::parse;
+use log::{debug, info};
+use nodejs_semver::Version;
+
+/// A tool image or inventory archive that is not referenced by any platform
+struct Unused {
+    kind: ImageKind,
+    version: Version,
+    path: PathBuf,
+    is_image: bool,
+}
+
+impl Unused {
+    /// The last time this version was used, falling back to when the file was last modified for
+    /// versions that were never recorded in the index
+    fn last_used(&self, index: &Index) -> SystemTime {
+        index
+            .tool_last_used(self.kind.name(), &self.version)
+            .or_else(|| {
+                fs::metadata(&self.path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+            })
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+    }
+}
+
+impl Display for Unused {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            tool_version(self.kind.name(), &self.version),
+            if self.is_image { "image" } else { "archive" }
+        )
+    }
+}
+
+/// Removes every tool image and inventory archive that isn't used by the default platform, an
+/// installed package, or a recently used project.
+///
+/// If `older_than` is set, only projects used within that time count as recently used, and only
+/// images and archives which haven't been used within that time are removed. If `dry_run` is set,
+/// the unused images and archives are only reported, not removed.
+///
+/// # Errors
+///
+/// Returns an error if the platforms in use cannot be determined, or if removing fails.
+pub fn prune(session: &Session, dry_run: bool, older_than: Option<Duration>) -> Fallible<()> {
+    // Acquire a lock on the Volta directory, if possible, to prevent concurrent changes
+    let _lock = VoltaLock::acquire();
+
+    let index = Index::current()?;
+    let cutoff = older_than.and_then(|age| SystemTime::now().checked_sub(age));
+    let platforms = platforms_in_use(session, &index, cutoff, dry_run)?;
+    let mut removed = 0;
+    let mut freed = 0;
+
+    for kind in ImageKind::ALL {
+        let used: BTreeSet<&Version> = platforms
+            .iter()
+            .filter_map(|platform| kind.version_in(platform))
+            .collect();
+
+        for unused in find_unused(kind, &used)? {
+            if let Some(cutoff) = cutoff
+                && unused.last_used(&index) >= cutoff
+            {
+                debug!("Keeping {unused}, which has been used recently");
+                continue;
+            }
+
+            let size = disk_usage(&unused.path);
+
+            if dry_run {
+                info!("Would remove {unused} ({})", file_size(size));
+            } else {
+                if unused.is_image {
+                    remove_dir_if_exists(&unused.path)?;
+                    index.forget_tool(kind.name(), &unused.version)?;
+                } else {
+                    remove_file_if_exists(&unused.path)?;
+                }
+                info!("Removed {unused} ({})", file_size(size));
+            }
+
+            removed += 1;
+            freed += size;
+        }
+    }
+
+    if removed == 0 {
+        info!("No unused tool versions to prune");
+    } else if dry_run {
+        info!(
+            "{} pruning would remove {removed} images and archives, freeing {}",
+            note_prefix(),
+            file_size(freed)
+        );
+    } else {
+        info!(
+            "{} pruned {removed} images and archives, freeing {}",
+            success_prefix(),
+            file_size(freed)
+        );
+    }
+
+    Ok(())
+}
+
+/// Collects every platform that may still be used
+///
+/// Projects that haven't been used since the `cutoff` are skipped, and projects that no longer
+/// exist are forgotten, unless this is a dry run.
+fn platforms_in_use(
+    session: &Session,
+    index: &Index,
+    cutoff: Option<SystemTime>,
+    dry_run: bool,
+) -> Fallible<Vec<PlatformSpec>> {
+    let mut platforms: Vec<PlatformSpec> =
+        session.default_platform()?.into_iter().cloned().collect();
+    platforms.extend(session.project_platform()?.cloned());
+    platforms.extend(package_configs()?.into_iter().map(|config| config.platform));
+    platforms.extend(bin_configs()?.into_iter().map(|config| config.platform));
+
+    for project in index.projects()? {
+        if cutoff.is_some_and(|cutoff| project.last_used < cutoff) {
+            debug!(
+                "Project at '{}' has not been used recently, not keeping its pinned versions",
+                project.root.display()
+            );
+            continue;
+        }
+
+        match Project::for_root(&project.root) {
+            Ok(Some(found)) => platforms.extend(found.platform().cloned()),
+            Ok(None) => {
+                debug!(
+                    "Project at '{}' no longer exists, not keeping its pinned versions",
+                    project.root.display()
+                );
+                if !dry_run {
+                    project.forget()?;
+                }
+            }
+            Err(error) => debug!(
+                "Could not read project at '{}', not keeping its pinned versions: {error}",
+                project.root.display()
+            ),
+        }
+    }
+
+    Ok(platforms)
+}
+
+/// Finds the images and inventory archives for a tool whose versions aren't in use
+fn find_unused(kind: ImageKind, used: &BTreeSet<&Version>) -> Fallible<Vec<Unused>> {
+    let images = read_entries(kind.image_root_dir()?)?
+        .into_iter()
+        .filter(|(_, metadata)| metadata.is_dir())
+        .filter_map(|(entry, _)| {
+            let version = parse(entry.file_name().to_string_lossy()).ok()?;
+            Some(Unused {
+                kind,
+                version,
+                path: entry.path(),
+                is_image: true,
+            })
+        });
+
+    let archives = read_entries(kind.inventory_dir()?)?
+        .into_iter()
+        .filter(|(_, metadata)| metadata.is_file())
+        .filter_map(|(entry, _)| {
+            let version = kind.inventory_file_version(&entry.file_name().to_string_lossy())?;
+            Some(Unused {
+                kind,
+                version,
+                path: entry.path(),
+                is_image: false,
+            })
+        });
+
+    Ok(images
+        .chain(archives)
+        .filter(|unused| !used.contains(&unused.version))
+        .collect())
+}
+
+/// Reads the entries of a directory, treating a missing directory as empty
+fn read_entries(dir: &Path) -> Fallible<Vec<(DirEntry, Metadata)>> {
+    read_dir_eager(dir)
+        .map(Iterator::collect)
+        .or_else(ok_if_not_found)
+        .with_context(|| {
+            ErrorKind::Filesystem(FilesystemError::ReadDir {
+                dir: dir.to_owned(),
+            })
+        })
+}
@@ -1,14 +1,15 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use super::cache;
+use super::integrity::Integrity;
 use super::registry_fetch_error;
-use crate::error::{Context, ErrorKind, Fallible, PackageError};
+use crate::error::{Context, ErrorKind, Fallible, PackageError, ToolError};
 use crate::fs::read_dir_eager;
+use crate::settings::Settings;
 use crate::style::progress_spinner;
 use crate::version::{hashmap_version_serde, version_serde};
-use attohttpc::Response;
-use attohttpc::header::ACCEPT;
-use cfg_if::cfg_if;
+use attohttpc::Session;
 use nodejs_semver::Version;
 use serde::Deserialize;
 
@@ -17,41 +18,37 @@ use serde::Deserialize;
 pub const NPM_ABBREVIATED_ACCEPT_HEADER: &str =
     "application/vnd.npm.install-v1+json; q=1.0, application/json; q=0.8, */*";
 
-cfg_if! {
-    if #[cfg(feature = "mock-network")] {
-        // TODO: We need to reconsider our mocking strategy in light of mockito deprecating the
-        // SERVER_URL constant: Since our acceptance tests run the binary in a separate process,
-        // we can't use `mockito::server_url()`, which relies on shared memory.
-        #[allow(deprecated)]
-        const SERVER_URL: &str = mockito::SERVER_URL;
-        pub fn public_registry_index(package: &str) -> String {
-            format!("{}/{}", SERVER_URL, package)
-        }
-    } else {
-        pub fn public_registry_index(package: &str) -> String {
-            format!("https://registry.npmjs.org/{package}")
-        }
-    }
+/// Returns the URL of the index of a package in the configured npm registry
+pub fn registry_index(settings: &Settings, package: &str) -> String {
+    format!("{}/{}", settings.npm_registry(), package)
 }
 
-// fetch a registry that returns info in Npm format
-pub fn fetch_npm_registry(url: String, name: &str) -> Fallible<(String, PackageIndex)> {
+// fetch a registry that returns info in Npm format, using the metadata cache when possible
+pub fn fetch_npm_registry(
+    client: &Session,
+    url: String,
+    name: &str,
+) -> Fallible<(String, PackageIndex)> {
+    let metadata = fetch_npm_metadata(client, &url, name)?;
+    Ok((url, metadata.into()))
+}
+
+/// Fetch the abbreviated metadata of a package from a registry in npm format, using the metadata
+/// cache when possible
+pub fn fetch_npm_metadata(client: &Session, url: &str, name: &str) -> Fallible<RawPackageMetadata> {
     let spinner = progress_spinner(format!("Fetching npm registry: {url}"));
-    let metadata: RawPackageMetadata = attohttpc::get(&url)
-        .header(ACCEPT, NPM_ABBREVIATED_ACCEPT_HEADER)
-        .send()
-        .and_then(Response::error_for_status)
-        .and_then(Response::json)
-        .with_context(registry_fetch_error(name, &url))?;
+    let response_text = cache::fetch(client, name, url, Some(NPM_ABBREVIATED_ACCEPT_HEADER))?;
+    let metadata: RawPackageMetadata =
+        serde_json::de::from_str(&response_text).with_context(registry_fetch_error(name, url))?;
 
     spinner.finish_and_clear();
-    Ok((url, metadata.into()))
+    Ok(metadata)
 }
 
-pub fn public_registry_package(package: &str, version: &str) -> String {
+pub fn registry_package(settings: &Settings, package: &str, version: &str) -> String {
     format!(
         "{}/-/{}-{}.tgz",
-        public_registry_index(package),
+        registry_index(settings, package),
         package,
         version
     )
@@ -59,10 +56,15 @@ pub fn public_registry_package(package: &str, version: &str) -> String {
 
 // need package and filename for namespaced tools like @yarnpkg/cli-dist, which is located at
 //   https://registry.npmjs.org/@yarnpkg/cli-dist/-/cli-dist-1.2.3.tgz
-pub fn scoped_public_registry_package(scope: &str, package: &str, version: &str) -> String {
+pub fn scoped_registry_package(
+    settings: &Settings,
+    scope: &str,
+    package: &str,
+    version: &str,
+) -> String {
     format!(
         "{}/{}/-/{}-{}.tgz",
-        public_registry_index(scope),
+        registry_index(settings, scope),
         package,
         package,
         version
@@ -91,6 +93,7 @@ pub fn find_unpack_dir(in_dir: &Path) -> Fallible<PathBuf> {
 #[derive(Debug)]
 pub struct PackageDetails {
     pub(crate) version: Version,
+    pub(crate) integrity: Option<Integrity>,
 }
 
 /// Index of versions of a specific package from the npm Registry
@@ -99,6 +102,27 @@ pub struct PackageIndex {
     pub entries: Vec<PackageDetails>,
 }
 
+impl PackageIndex {
+    /// Find the published integrity of a specific version of the package
+    ///
+    /// The registry is expected to publish an integrity for every version, so it is an error if
+    /// the version is missing or has no supported integrity.
+    pub fn integrity(self, tool: &str, version: &Version, from_url: &str) -> Fallible<Integrity> {
+        self.entries
+            .into_iter()
+            .find(|details| details.version == *version)
+            .and_then(|details| details.integrity)
+            .ok_or_else(|| {
+                ToolError::MissingIntegrity {
+                    tool: tool.into(),
+                    version: version.to_string(),
+                    from_url: from_url.into(),
+                }
+                .into()
+            })
+    }
+}
+
 /// Package Metadata Response
 ///
 /// See npm registry API doc:
@@ -117,18 +141,19 @@ pub struct RawPackageMetadata {
 
 #[derive(Deserialize, Debug)]
 pub struct RawPackageVersionInfo {
-    // there's a lot more in there, but right now just care about the version
+    // there's a lot more in there, but right now just care about the version and dist info
     #[serde(with = "version_serde")]
     pub version: Version,
-    #[allow(dead_code)]
     pub dist: RawDistInfo,
+    /// The names of the bins of the version, see `package::serde_bins`
+    #[serde(default, deserialize_with = "super::package::serde_bins::deserialize")]
+    pub bin: Vec<String>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
-#[allow(dead_code)]
 pub struct RawDistInfo {
-    pub shasum: String,
-    pub tarball: String,
+    pub shasum: Option<String>,
+    pub integrity: Option<String>,
 }
 
 impl From<RawPackageMetadata> for PackageIndex {
@@ -137,6 +162,10 @@ impl From<RawPackageMetadata> for PackageIndex {
             .versions
             .into_values()
             .map(|version_info| PackageDetails {
+                integrity: Integrity::from_dist(
+                    version_info.dist.integrity.as_deref(),
+                    version_info.dist.shasum.as_deref(),
+                ),
                 version: version_info.version,
             })
             .collect();
@@ -149,3 +178,91 @@ impl From<RawPackageMetadata> for PackageIndex {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA: &str = r#"{
+        "name": "pnpm",
+        "dist-tags": { "latest": "8.1.0" },
+        "versions": {
+            "8.0.0": {
+                "version": "8.0.0",
+                "dist": {
+                    "shasum": "d11c07a27a6ff38fa64da74f441ca4bcf3d784fc",
+                    "tarball": "https://registry.npmjs.org/pnpm/-/pnpm-8.0.0.tgz"
+                }
+            },
+            "8.1.0": {
+                "version": "8.1.0",
+                "dist": {
+                    "shasum": "d11c07a27a6ff38fa64da74f441ca4bcf3d784fc",
+                    "integrity": "sha1-0RwHonpv84+mTadPRBykvPPXhPw=",
+                    "tarball": "https://registry.npmjs.org/pnpm/-/pnpm-8.1.0.tgz"
+                }
+            },
+            "8.2.0": {
+                "version": "8.2.0",
+                "dist": {
+                    "tarball": "https://registry.npmjs.org/pnpm/-/pnpm-8.2.0.tgz"
+                }
+            }
+        }
+    }"#;
+
+    fn index() -> PackageIndex {
+        serde_json::from_str::<RawPackageMetadata>(METADATA)
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn test_integrity() {
+        let url = "https://registry.npmjs.org/pnpm";
+        let expected = "sha1-0RwHonpv84+mTadPRBykvPPXhPw=";
+
+        // Both the SRI string and the legacy shasum are supported
+        let integrity = index().integrity("pnpm", &Version::parse("8.1.0").unwrap(), url);
+        assert_eq!(integrity.unwrap().to_string(), expected);
+        let integrity = index().integrity("pnpm", &Version::parse("8.0.0").unwrap(), url);
+        assert_eq!(integrity.unwrap().to_string(), expected);
+
+        // Versions without a published integrity, or that don't exist, can't be verified
+        assert!(
+            index()
+                .integrity("pnpm", &Version::parse("8.2.0").unwrap(), url)
+                .is_err()
+        );
+        assert!(
+            index()
+                .integrity("pnpm", &Version::parse("9.0.0").unwrap(), url)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_bins() {
+        let metadata: RawPackageMetadata = serde_json::from_str(
+            r#"{
+                "name": "typescript",
+                "dist-tags": {},
+                "versions": {
+                    "4.9.5": {
+                        "version": "4.9.5",
+                        "bin": { "tsc": "bin/tsc", "../escape": "bin/escape" },
+                        "dist": {}
+                    },
+                    "5.4.5": { "version": "5.4.5", "bin": "bin/tsc", "dist": {} },
+                    "5.5.0": { "version": "5.5.0", "dist": {} }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(metadata.versions["4.9.5"].bin, ["tsc"]);
+        // A single path is named after the package, once the name is known
+        assert_eq!(metadata.versions["5.4.5"].bin, [""]);
+        assert!(metadata.versions["5.5.0"].bin.is_empty());
+    }
+}
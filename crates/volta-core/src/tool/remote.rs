@@ -0,0 +1,284 @@
+//! Provides the listing of versions available to fetch from the remote registries
+
+use std::collections::BTreeSet;
+
+use super::node::NodeChannel;
+use super::registry::{PackageIndex, fetch_npm_registry, registry_index};
+use super::{ToolSpec, node, npm, pnpm, yarn};
+use crate::error::{ErrorKind, Fallible, NetworkError};
+use crate::http;
+use crate::inventory::{
+    node_versions, npm_versions, package_configs, pnpm_versions, yarn_versions,
+};
+use crate::session::Session;
+use crate::version::{Tag, VersionSpec};
+use nodejs_semver::Version;
+
+/// A version of a tool or package which is available to fetch
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteVersion {
+    pub version: Version,
+    /// The codename of the LTS line, only for Node
+    pub lts: Option<String>,
+    /// The tags that point at this version, such as `latest`
+    pub tags: Vec<String>,
+    /// Whether this version is already on the local machine
+    pub installed: bool,
+}
+
+impl RemoteVersion {
+    #[must_use]
+    pub const fn new(version: Version) -> Self {
+        Self {
+            version,
+            lts: None,
+            tags: Vec::new(),
+            installed: false,
+        }
+    }
+
+    fn matches(&self, matching: &VersionSpec) -> bool {
+        match matching {
+            VersionSpec::None => true,
+            VersionSpec::Semver(range) => range.satisfies(&self.version),
+            VersionSpec::Exact(version) => self.version == *version,
+            VersionSpec::Tag(Tag::Lts) if self.lts.is_some() => true,
+            VersionSpec::Tag(Tag::LtsCodename(codename) | Tag::Custom(codename))
+                if self.is_lts_line(codename) =>
+            {
+                true
+            }
+            VersionSpec::Tag(tag) => {
+                let tag = tag.to_string();
+                self.tags.contains(&tag)
+            }
+        }
+    }
+
+    fn is_lts_line(&self, codename: &str) -> bool {
+        self.lts
+            .as_deref()
+            .is_some_and(|lts| lts.eq_ignore_ascii_case(codename))
+    }
+}
+
+/// Finds the codename of an LTS line, counting back from the newest, in versions sorted from
+/// oldest to newest
+fn previous_lts(versions: &[RemoteVersion], count: usize) -> Option<&str> {
+    let mut codenames: Vec<&str> = Vec::new();
+    for codename in versions
+        .iter()
+        .rev()
+        .filter_map(|remote| remote.lts.as_deref())
+    {
+        if !codenames.contains(&codename) {
+            codenames.push(codename);
+        }
+    }
+
+    codenames.get(count).copied()
+}
+
+/// Lists the versions of a tool or package which are available to fetch, from oldest to newest
+///
+/// Only the versions matching the version of the spec are included, and each version is marked
+/// if it is already in the local inventory or, for packages, installed globally.
+///
+/// # Errors
+///
+/// Returns an error if in offline mode, or if the registry cannot be reached.
+pub fn remote_versions(spec: &ToolSpec, session: &Session) -> Fallible<Vec<RemoteVersion>> {
+    let (name, matching) = match spec {
+        ToolSpec::Node(matching) => ("node", matching),
+        ToolSpec::Npm(matching) => ("npm", matching),
+        ToolSpec::Pnpm(matching) => ("pnpm", matching),
+        ToolSpec::Yarn(matching) => ("yarn", matching),
+        ToolSpec::Package(name, matching) => (name.as_str(), matching),
+    };
+
+    if session.offline() {
+        return Err(
+            ErrorKind::Network(NetworkError::RemoteVersionsOffline { tool: name.into() }).into(),
+        );
+    }
+
+    // The prerelease channels of Node have their own indexes, which are listed in full
+    let channel = match spec {
+        ToolSpec::Node(VersionSpec::Tag(tag)) => NodeChannel::from_tag(tag),
+        _ => None,
+    };
+
+    let settings = session.settings()?;
+    let hooks = session.hooks()?;
+    let (mut versions, installed) = match spec {
+        ToolSpec::Node(_) => (
+            node::available(
+                channel.unwrap_or(NodeChannel::Release),
+                settings,
+                hooks.node(),
+            )?,
+            node_versions()?,
+        ),
+        ToolSpec::Npm(_) => (npm::available(settings, hooks.npm())?, npm_versions()?),
+        ToolSpec::Pnpm(_) => (pnpm::available(settings, hooks.pnpm())?, pnpm_versions()?),
+        ToolSpec::Yarn(_) => (yarn::available(settings, hooks.yarn())?, yarn_versions()?),
+        ToolSpec::Package(name, _) => {
+            let url = registry_index(settings, name);
+            let (_, index) = fetch_npm_registry(&http::client(settings)?, url, name)?;
+            let installed: BTreeSet<Version> = package_configs()?
+                .into_iter()
+                .filter(|config| config.name == *name)
+                .map(|config| config.version)
+                .collect();
+
+            (remote_versions_from(index), installed)
+        }
+    };
+
+    versions.sort_by(|a, b| a.version.cmp(&b.version));
+    // Every version in the index of a channel matches it
+    if channel.is_none() {
+        match matching {
+            VersionSpec::Tag(Tag::PreviousLts(count)) => {
+                let codename = previous_lts(&versions, *count).map(str::to_owned);
+                versions.retain(|remote| {
+                    codename
+                        .as_deref()
+                        .is_some_and(|line| remote.is_lts_line(line))
+                });
+            }
+            _ => versions.retain(|remote| remote.matches(matching)),
+        }
+    }
+    for remote in &mut versions {
+        remote.installed = installed.contains(&remote.version);
+    }
+
+    Ok(versions)
+}
+
+/// Lists the versions in a package index, each with the tags that point at it
+pub(super) fn remote_versions_from(index: PackageIndex) -> Vec<RemoteVersion> {
+    let PackageIndex { tags, entries } = index;
+
+    entries
+        .into_iter()
+        .map(|entry| {
+            let mut tags: Vec<String> = tags
+                .iter()
+                .filter(|(_, version)| **version == entry.version)
+                .map(|(tag, _)| tag.clone())
+                .collect();
+            tags.sort();
+
+            RemoteVersion {
+                tags,
+                ..RemoteVersion::new(entry.version)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::tool::registry::PackageDetails;
+    use crate::version::parse_requirements;
+
+    fn version(version: &str) -> Version {
+        Version::parse(version).unwrap()
+    }
+
+    fn index() -> PackageIndex {
+        PackageIndex {
+            tags: HashMap::from([
+                ("latest".into(), version("5.9.2")),
+                ("beta".into(), version("6.0.0-beta")),
+                ("next".into(), version("6.0.0-beta")),
+            ]),
+            entries: ["6.0.0-beta", "5.9.2", "5.5.4", "4.9.5"]
+                .into_iter()
+                .map(|entry| PackageDetails {
+                    version: version(entry),
+                    integrity: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn tags_from_package_index() {
+        let versions = remote_versions_from(index());
+
+        assert_eq!(versions[0].tags, vec!["beta".to_owned(), "next".to_owned()]);
+        assert_eq!(versions[1].tags, vec!["latest".to_owned()]);
+        assert!(versions[2].tags.is_empty());
+    }
+
+    #[test]
+    fn matches_version_specs() {
+        let versions = remote_versions_from(index());
+        let matching = |spec: VersionSpec| -> Vec<String> {
+            versions
+                .iter()
+                .filter(|remote| remote.matches(&spec))
+                .map(|remote| remote.version.to_string())
+                .collect()
+        };
+
+        assert_eq!(matching(VersionSpec::None).len(), 4);
+        assert_eq!(
+            matching(VersionSpec::Semver(parse_requirements("5").unwrap())),
+            vec!["5.9.2", "5.5.4"]
+        );
+        assert_eq!(
+            matching(VersionSpec::Exact(version("4.9.5"))),
+            vec!["4.9.5"]
+        );
+        assert_eq!(matching(VersionSpec::Tag(Tag::Latest)), vec!["5.9.2"]);
+        assert_eq!(
+            matching(VersionSpec::Tag(Tag::Custom("next".into()))),
+            vec!["6.0.0-beta"]
+        );
+        assert!(matching(VersionSpec::Tag(Tag::Lts)).is_empty());
+    }
+
+    #[test]
+    fn matches_node_lts() {
+        let lts = RemoteVersion {
+            lts: Some("Jod".into()),
+            ..RemoteVersion::new(version("22.16.0"))
+        };
+        let current = RemoteVersion::new(version("24.1.0"));
+
+        assert!(lts.matches(&VersionSpec::Tag(Tag::Lts)));
+        assert!(!current.matches(&VersionSpec::Tag(Tag::Lts)));
+        assert!(lts.matches(&VersionSpec::Tag(Tag::LtsCodename("jod".into()))));
+        assert!(lts.matches(&VersionSpec::Tag(Tag::Custom("Jod".into()))));
+        assert!(!lts.matches(&VersionSpec::Tag(Tag::LtsCodename("iron".into()))));
+    }
+
+    #[test]
+    fn previous_lts_lines() {
+        let versions: Vec<RemoteVersion> = [
+            ("18.20.8", Some("Hydrogen")),
+            ("20.19.2", Some("Iron")),
+            ("22.15.1", Some("Jod")),
+            ("22.16.0", Some("Jod")),
+            ("24.1.0", None),
+        ]
+        .into_iter()
+        .map(|(number, lts)| RemoteVersion {
+            lts: lts.map(str::to_owned),
+            ..RemoteVersion::new(version(number))
+        })
+        .collect();
+
+        assert_eq!(previous_lts(&versions, 0), Some("Jod"));
+        assert_eq!(previous_lts(&versions, 1), Some("Iron"));
+        assert_eq!(previous_lts(&versions, 2), Some("Hydrogen"));
+        assert_eq!(previous_lts(&versions, 3), None);
+    }
+}
@@ -163,13 +163,14 @@ impl ToolSpec {
 
 /// Determine if a given string is "version-like".
 ///
-/// This means it is either 'latest', 'lts', a Version, or a Version Range.
+/// This means it is either 'latest', 'lts', an LTS line like 'lts/iron', a Version, or a Version
+/// Range.
 fn is_version_like(value: &str) -> bool {
     matches!(
         value.parse(),
         Ok(VersionSpec::Exact(_)
             | VersionSpec::Semver(_)
-            | VersionSpec::Tag(Tag::Latest | Tag::Lts))
+            | VersionSpec::Tag(Tag::Latest | Tag::Lts | Tag::LtsCodename(_) | Tag::PreviousLts(_)))
     )
 }
 
@@ -239,6 +240,34 @@ mod tests {
             );
         }
 
+        #[test]
+        fn parses_node_with_lts_lines() {
+            assert_eq!(
+                ToolSpec::try_from_str("node@lts/*").expect("succeeds"),
+                ToolSpec::Node(VersionSpec::Tag(Tag::Lts))
+            );
+
+            assert_eq!(
+                ToolSpec::try_from_str("node@lts/Iron").expect("succeeds"),
+                ToolSpec::Node(VersionSpec::Tag(Tag::LtsCodename("iron".into())))
+            );
+
+            assert_eq!(
+                ToolSpec::try_from_str("node@lts/-1").expect("succeeds"),
+                ToolSpec::Node(VersionSpec::Tag(Tag::PreviousLts(1)))
+            );
+
+            assert_eq!(
+                ToolSpec::try_from_str("node@lts/-0").expect("succeeds"),
+                ToolSpec::Node(VersionSpec::Tag(Tag::Lts))
+            );
+
+            assert_eq!(
+                ToolSpec::try_from_str("node@hydrogen").expect("succeeds"),
+                ToolSpec::Node(VersionSpec::Tag(Tag::Custom("hydrogen".into())))
+            );
+        }
+
         #[test]
         fn parses_bare_yarn() {
             assert_eq!(
@@ -0,0 +1,387 @@
+//! Provides the uninstall logic for Node and package manager versions
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use super::{BinConfig, NODE_DISTRO_OS, Node, Npm, PackageConfig, Pnpm, Yarn};
+use crate::error::{BinaryError, Context, ErrorKind, Fallible, ToolError};
+use crate::fs::{dir_entry_match, ok_if_not_found, remove_dir_if_exists, remove_file_if_exists};
+use crate::inventory::package_configs;
+use crate::layout::volta_home;
+use crate::platform::PlatformSpec;
+use crate::session::Session;
+use crate::style::{note_prefix, success_prefix, tool_version};
+use crate::sync::VoltaLock;
+use crate::usage::Index;
+use crate::version::{VersionSpec, parse};
+use log::{info, warn};
+use nodejs_semver::Version;
+
+/// The tools which are stored as versioned images in the inventory
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageKind {
+    Node,
+    Npm,
+    Pnpm,
+    Yarn,
+}
+
+impl ImageKind {
+    pub(super) const ALL: [Self; 4] = [Self::Node, Self::Npm, Self::Pnpm, Self::Yarn];
+
+    pub(super) const fn name(self) -> &'static str {
+        match self {
+            Self::Node => "node",
+            Self::Npm => "npm",
+            Self::Pnpm => "pnpm",
+            Self::Yarn => "yarn",
+        }
+    }
+
+    /// The version of this tool used by the given platform, if any
+    pub(super) const fn version_in(self, platform: &PlatformSpec) -> Option<&Version> {
+        match self {
+            Self::Node => Some(&platform.node),
+            Self::Npm => platform.npm.as_ref(),
+            Self::Pnpm => platform.pnpm.as_ref(),
+            Self::Yarn => platform.yarn.as_ref(),
+        }
+    }
+
+    pub(super) fn image_root_dir(self) -> Fallible<&'static Path> {
+        let home = volta_home()?;
+        Ok(match self {
+            Self::Node => home.node_image_root_dir(),
+            Self::Npm => home.npm_image_root_dir(),
+            Self::Pnpm => home.pnpm_image_root_dir(),
+            Self::Yarn => home.yarn_image_root_dir(),
+        })
+    }
+
+    pub(super) fn inventory_dir(self) -> Fallible<&'static Path> {
+        let home = volta_home()?;
+        Ok(match self {
+            Self::Node => home.node_inventory_dir(),
+            Self::Npm => home.npm_inventory_dir(),
+            Self::Pnpm => home.pnpm_inventory_dir(),
+            Self::Yarn => home.yarn_inventory_dir(),
+        })
+    }
+
+    /// Determine the version of a file in the inventory directory, based on its name
+    ///
+    /// This is the inverse of the `archive_filename` of each tool, as well as the default npm
+    /// version file for Node.
+    pub(super) fn inventory_file_version(self, file_name: &str) -> Option<Version> {
+        let version = match self {
+            Self::Node => {
+                let rest = file_name.strip_prefix("node-v")?;
+                rest.strip_suffix("-npm").or_else(|| {
+                    rest.split_once(&format!("-{NODE_DISTRO_OS}-"))
+                        .map(|(version, _)| version)
+                })?
+            }
+            Self::Npm => file_name.strip_prefix("npm-")?.strip_suffix(".tgz")?,
+            Self::Pnpm => file_name.strip_prefix("pnpm-")?.strip_suffix(".tgz")?,
+            Self::Yarn => file_name.strip_prefix("yarn-v")?.strip_suffix(".tar.gz")?,
+        };
+
+        parse(version).ok()
+    }
+
+    pub(crate) fn image_dir(self, version: &str) -> Fallible<PathBuf> {
+        let home = volta_home()?;
+        Ok(match self {
+            Self::Node => home.node_image_dir(version),
+            Self::Npm => home.npm_image_dir(version),
+            Self::Pnpm => home.pnpm_image_dir(version),
+            Self::Yarn => home.yarn_image_dir(version),
+        })
+    }
+
+    /// The files kept in the inventory for a given version
+    pub(crate) fn inventory_files(self, version: &Version) -> Fallible<Vec<PathBuf>> {
+        let home = volta_home()?;
+        let version_str = version.to_string();
+        Ok(match self {
+            Self::Node => vec![
+                home.node_inventory_dir()
+                    .join(Node::archive_filename(version)),
+                home.node_inventory_dir()
+                    .join(Node::checksum_filename(version)),
+                home.node_npm_version_file(&version_str),
+            ],
+            Self::Npm => vec![
+                home.npm_inventory_dir()
+                    .join(Npm::archive_filename(&version_str)),
+                home.npm_inventory_dir()
+                    .join(Npm::integrity_filename(&version_str)),
+            ],
+            Self::Pnpm => vec![
+                home.pnpm_inventory_dir()
+                    .join(Pnpm::archive_filename(&version_str)),
+                home.pnpm_inventory_dir()
+                    .join(Pnpm::integrity_filename(&version_str)),
+            ],
+            Self::Yarn => vec![
+                home.yarn_inventory_dir()
+                    .join(Yarn::archive_filename(&version_str)),
+                home.yarn_inventory_dir()
+                    .join(Yarn::integrity_filename(&version_str)),
+            ],
+        })
+    }
+}
+
+/// Uninstalls a specific version of Node or a package manager.
+///
+/// This removes the unpacked image as well as the cached archive in the inventory. If the version
+/// is still used by the default platform or by an installed package, the uninstall is refused
+/// unless `force` is set.
+///
+/// # Errors
+///
+/// Returns an error if the version isn't exact, if it is still in use, or if the files cannot be
+/// removed.
+pub fn uninstall(
+    kind: ImageKind,
+    matching: VersionSpec,
+    session: &Session,
+    force: bool,
+) -> Fallible<()> {
+    let VersionSpec::Exact(version) = matching else {
+        return Err(ErrorKind::Tool(ToolError::UninstallExactVersion {
+            tool: kind.name().into(),
+        })
+        .into());
+    };
+
+    // Acquire a lock on the Volta directory, if possible, to prevent concurrent changes
+    let _lock = VoltaLock::acquire();
+
+    let image_dir = kind.image_dir(&version.to_string())?;
+    let inventory_files = kind.inventory_files(&version)?;
+    if !image_dir.exists() && !inventory_files.iter().any(|file| file.exists()) {
+        warn!(
+            "No {} found to uninstall",
+            tool_version(kind.name(), &version)
+        );
+        return Ok(());
+    }
+
+    let references = references(
+        kind,
+        &version,
+        session.default_platform()?,
+        &package_configs()?,
+        &bin_configs()?,
+    );
+
+    if !references.is_empty() {
+        if !force {
+            return Err(ErrorKind::Tool(ToolError::UninstallVersionInUse {
+                tool: kind.name().into(),
+                version: version.to_string(),
+                references,
+            })
+            .into());
+        }
+
+        info!(
+            "{} {} is still used by {}, it will be fetched again the next time it is needed",
+            note_prefix(),
+            tool_version(kind.name(), &version),
+            references.join(", "),
+        );
+    }
+
+    remove_dir_if_exists(image_dir)?;
+    for file in inventory_files {
+        remove_file_if_exists(file)?;
+    }
+    Index::current()?.forget_tool(kind.name(), &version)?;
+
+    info!(
+        "{} uninstalled {}",
+        success_prefix(),
+        tool_version(kind.name(), &version)
+    );
+
+    Ok(())
+}
+
+/// Describes everything that still uses the given version of a tool
+fn references(
+    kind: ImageKind,
+    version: &Version,
+    default: Option<&PlatformSpec>,
+    packages: &BTreeSet<PackageConfig>,
+    bins: &[BinConfig],
+) -> Vec<String> {
+    let uses_version = |platform: &PlatformSpec| kind.version_in(platform) == Some(version);
+
+    let mut references = Vec::new();
+    if default.is_some_and(uses_version) {
+        references.push("the default platform".to_owned());
+    }
+
+    for package in packages.iter().filter(|pkg| uses_version(&pkg.platform)) {
+        references.push(format!("package '{}'", package.name));
+    }
+
+    // Binaries normally share the platform of their package, so only report the ones whose
+    // package config is missing
+    for bin in bins.iter().filter(|bin| uses_version(&bin.platform)) {
+        if !packages.iter().any(|pkg| pkg.name == bin.package) {
+            references.push(format!("executable '{}'", bin.name));
+        }
+    }
+
+    references
+}
+
+/// Reads all of the binary configs in the default toolchain
+pub(super) fn bin_configs() -> Fallible<Vec<BinConfig>> {
+    let bin_config_dir = volta_home()?.default_bin_dir();
+
+    dir_entry_match(bin_config_dir, |entry| {
+        BinConfig::from_file(entry.path()).ok()
+    })
+    .or_else(ok_if_not_found)
+    .with_context(|| {
+        ErrorKind::Binary(BinaryError::ReadConfigDirError {
+            dir: bin_config_dir.to_owned(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool::package::PackageManager;
+    use std::collections::BTreeMap;
+
+    fn platform(node: &str, yarn: Option<&str>) -> PlatformSpec {
+        PlatformSpec {
+            node: Version::parse(node).unwrap(),
+            npm: None,
+            pnpm: None,
+            yarn: yarn.map(|version| Version::parse(version).unwrap()),
+        }
+    }
+
+    fn package(name: &str, platform: PlatformSpec) -> PackageConfig {
+        PackageConfig {
+            name: name.into(),
+            version: Version::parse("1.0.0").unwrap(),
+            platform,
+            bins: vec![name.into()],
+            manager: PackageManager::Npm,
+            aliases: BTreeMap::new(),
+        }
+    }
+
+    fn bin(name: &str, package: &str, platform: PlatformSpec) -> BinConfig {
+        BinConfig {
+            name: name.into(),
+            package: package.into(),
+            version: Version::parse("1.0.0").unwrap(),
+            platform,
+            manager: PackageManager::Npm,
+            alias_of: None,
+        }
+    }
+
+    #[test]
+    fn inventory_file_version() {
+        let version = Version::parse("16.20.2").unwrap();
+
+        assert_eq!(
+            ImageKind::Node.inventory_file_version(&Node::archive_filename(&version)),
+            Some(version.clone())
+        );
+        assert_eq!(
+            ImageKind::Node.inventory_file_version("node-v16.20.2-npm"),
+            Some(version.clone())
+        );
+        assert_eq!(
+            ImageKind::Npm.inventory_file_version(&Npm::archive_filename("16.20.2")),
+            Some(version.clone())
+        );
+        assert_eq!(
+            ImageKind::Pnpm.inventory_file_version(&Pnpm::archive_filename("16.20.2")),
+            Some(version.clone())
+        );
+        assert_eq!(
+            ImageKind::Yarn.inventory_file_version(&Yarn::archive_filename("16.20.2")),
+            Some(version)
+        );
+
+        assert_eq!(
+            ImageKind::Node.inventory_file_version("SHASUMS256.txt"),
+            None
+        );
+        assert_eq!(
+            ImageKind::Npm.inventory_file_version(&Yarn::archive_filename("1.22.22")),
+            None
+        );
+    }
+
+    #[test]
+    fn unreferenced_version() {
+        let version = Version::parse("16.20.2").unwrap();
+        let default = platform("18.20.4", None);
+        let packages = BTreeSet::from([package("cowsay", platform("18.20.4", None))]);
+
+        assert!(references(ImageKind::Node, &version, Some(&default), &packages, &[]).is_empty());
+    }
+
+    #[test]
+    fn referenced_by_default_platform() {
+        let version = Version::parse("1.22.22").unwrap();
+        let default = platform("18.20.4", Some("1.22.22"));
+
+        assert_eq!(
+            references(
+                ImageKind::Yarn,
+                &version,
+                Some(&default),
+                &BTreeSet::new(),
+                &[]
+            ),
+            vec!["the default platform".to_owned()]
+        );
+        assert!(
+            references(
+                ImageKind::Npm,
+                &version,
+                Some(&default),
+                &BTreeSet::new(),
+                &[]
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn referenced_by_packages_and_orphaned_bins() {
+        let version = Version::parse("16.20.2").unwrap();
+        let packages = BTreeSet::from([
+            package("cowsay", platform("16.20.2", None)),
+            package("typescript", platform("18.20.4", None)),
+        ]);
+        let bins = [
+            bin("cowsay", "cowsay", platform("16.20.2", None)),
+            bin("ember", "ember-cli", platform("16.20.2", None)),
+            bin("tsc", "typescript", platform("18.20.4", None)),
+        ];
+
+        assert_eq!(
+            references(ImageKind::Node, &version, None, &packages, &bins),
+            vec![
+                "package 'cowsay'".to_owned(),
+                "executable 'ember'".to_owned()
+            ]
+        );
+    }
+}
@@ -0,0 +1,377 @@
+//! Provides the checks of `volta verify`, which confirm that the toolchain of a project is
+//! consistent, available, and in use, without running any of the tools
+
+use std::fmt;
+
+use super::node::NodeChannel;
+use super::package::PackageManager;
+use super::{PATH_VAR_NAME, RemoteVersion, find_expected_shim_dir, node, npm, pnpm, yarn};
+use crate::error::{Fallible, PlatformError};
+use crate::platform::PlatformSpec;
+use crate::project::{LOCKFILE_NAME, ManifestKey, Project};
+use crate::session::Session;
+use crate::style::tool_version;
+use crate::version::parse_requirements;
+use nodejs_semver::Version;
+
+/// Whether a check passed, failed, or couldn't be made
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// The check couldn't be made, such as checking the registries in offline mode
+    Skip,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Pass => "pass",
+            Self::Fail => "fail",
+            Self::Skip => "skip",
+        })
+    }
+}
+
+/// The kinds of checks made of a project toolchain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckKind {
+    /// The `volta` key pins a Node version, which npm, pnpm and Yarn require
+    Platform,
+    /// The ranges and tags in the `volta` key are in the project lockfile
+    Lockfile,
+    /// The pinned Node version satisfies `engines.node`
+    Engines,
+    /// The pinned package manager matches the `packageManager` field
+    PackageManager,
+    /// The pinned version is published in the configured registry
+    Registry,
+    /// The tool on `PATH` is the Volta shim
+    Path,
+}
+
+impl fmt::Display for CheckKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Platform => "platform",
+            Self::Lockfile => "lockfile",
+            Self::Engines => "engines",
+            Self::PackageManager => "packageManager",
+            Self::Registry => "registry",
+            Self::Path => "path",
+        })
+    }
+}
+
+/// The outcome of a single check
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    pub kind: CheckKind,
+    /// The tool that was checked, for checks made of each tool
+    pub tool: Option<String>,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+impl Check {
+    fn new(kind: CheckKind, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            tool: None,
+            status,
+            message: message.into(),
+        }
+    }
+
+    fn with_tool(mut self, tool: &str) -> Self {
+        self.tool = Some(tool.into());
+        self
+    }
+}
+
+/// Checks the toolchain of the current project, without running any of its tools
+///
+/// The checks stop after the platform check if the project doesn't have a usable platform, since
+/// the other checks depend on its versions.
+///
+/// # Errors
+///
+/// Returns an error if not in a project, or if the Volta settings cannot be loaded.
+pub fn verify_project(session: &Session) -> Fallible<Vec<Check>> {
+    let project = match session.project() {
+        Ok(Some(project)) => project,
+        Ok(None) => return Err(PlatformError::NotInPackage.into()),
+        // A project that can't be loaded, such as one pinning Yarn without Node, fails the check
+        Err(error) => {
+            return Ok(vec![Check::new(
+                CheckKind::Platform,
+                CheckStatus::Fail,
+                first_line(&error.to_string()),
+            )]);
+        }
+    };
+
+    let mut checks = vec![check_platform(project)];
+    checks.extend(check_lockfile(project));
+
+    let Some(platform) = project.platform() else {
+        return Ok(checks);
+    };
+
+    checks.extend(check_engines(project, platform));
+    checks.extend(check_package_manager(project, platform));
+    checks.extend(check_registries(session, platform)?);
+    checks.extend(check_path(platform));
+
+    Ok(checks)
+}
+
+fn first_line(message: &str) -> String {
+    message.lines().next().unwrap_or_default().to_owned()
+}
+
+fn check_platform(project: &Project) -> Check {
+    let manifest = project.manifest_file().display();
+    if let Some(platform) = project.platform() {
+        let tools: Vec<String> = pinned_tools(platform)
+            .map(|(name, version)| tool_version(name, version))
+            .collect();
+        return Check::new(
+            CheckKind::Platform,
+            CheckStatus::Pass,
+            format!("{} pinned in {manifest}", tools.join(", ")),
+        );
+    }
+
+    let message = if project
+        .requested_pins()
+        .iter()
+        .any(|pin| pin.key == ManifestKey::Node)
+    {
+        format!("The Node version in {manifest} has not been resolved")
+    } else {
+        format!("No Node version is pinned in {manifest}")
+    };
+    Check::new(CheckKind::Platform, CheckStatus::Fail, message)
+}
+
+fn check_lockfile(project: &Project) -> impl Iterator<Item = Check> + '_ {
+    project.requested_pins().iter().map(|pin| {
+        let requested = tool_version(pin.key, &pin.requested);
+        let check = pin.locked.as_ref().map_or_else(
+            || {
+                Check::new(
+                    CheckKind::Lockfile,
+                    CheckStatus::Fail,
+                    format!("{requested} is not in {LOCKFILE_NAME}, run `volta pin --refresh`"),
+                )
+            },
+            |version| {
+                Check::new(
+                    CheckKind::Lockfile,
+                    CheckStatus::Pass,
+                    format!("{requested} is locked to {version}"),
+                )
+            },
+        );
+        check.with_tool(&pin.key.to_string())
+    })
+}
+
+fn check_engines(project: &Project, platform: &PlatformSpec) -> Option<Check> {
+    let engines = project.engines_node()?;
+    let node = tool_version("node", &platform.node);
+
+    let check = match parse_requirements(engines) {
+        Ok(range) if range.satisfies(&platform.node) => Check::new(
+            CheckKind::Engines,
+            CheckStatus::Pass,
+            format!("{node} satisfies engines.node ({engines})"),
+        ),
+        Ok(_) => Check::new(
+            CheckKind::Engines,
+            CheckStatus::Fail,
+            format!("{node} does not satisfy engines.node ({engines})"),
+        ),
+        Err(_) => Check::new(
+            CheckKind::Engines,
+            CheckStatus::Fail,
+            format!("engines.node ({engines}) is not a valid version range"),
+        ),
+    };
+    Some(check.with_tool("node"))
+}
+
+fn check_package_manager(project: &Project, platform: &PlatformSpec) -> Option<Check> {
+    let field = project.package_manager()?;
+    // A package manager that isn't pinned in the `volta` key is taken from the field itself
+    let pinned = match field.manager {
+        PackageManager::Npm => platform.npm.as_ref(),
+        PackageManager::Pnpm => platform.pnpm.as_ref(),
+        PackageManager::Yarn => platform.yarn.as_ref(),
+    }?;
+
+    let check = if *pinned == field.version {
+        Check::new(
+            CheckKind::PackageManager,
+            CheckStatus::Pass,
+            format!(
+                "{} matches the `packageManager` field",
+                tool_version(field.name(), pinned)
+            ),
+        )
+    } else {
+        Check::new(
+            CheckKind::PackageManager,
+            CheckStatus::Fail,
+            format!(
+                "{} is pinned, but the `packageManager` field asks for {}",
+                tool_version(field.name(), pinned),
+                tool_version(field.name(), &field.version)
+            ),
+        )
+    };
+    Some(check.with_tool(field.name()))
+}
+
+fn check_registries(session: &Session, platform: &PlatformSpec) -> Fallible<Vec<Check>> {
+    if session.offline() {
+        return Ok(vec![Check::new(
+            CheckKind::Registry,
+            CheckStatus::Skip,
+            "The registries can't be checked in offline mode",
+        )]);
+    }
+
+    let settings = session.settings()?;
+    let hooks = session.hooks()?;
+    let checks = pinned_tools(platform)
+        .map(|(name, version)| {
+            let available = match name {
+                "node" => node::available(NodeChannel::of_version(version), settings, hooks.node()),
+                "npm" => npm::available(settings, hooks.npm()),
+                "pnpm" => pnpm::available(settings, hooks.pnpm()),
+                _ => yarn::available(settings, hooks.yarn()),
+            };
+            registry_check(name, version, available).with_tool(name)
+        })
+        .collect();
+
+    Ok(checks)
+}
+
+fn registry_check(name: &str, version: &Version, available: Fallible<Vec<RemoteVersion>>) -> Check {
+    let tool = tool_version(name, version);
+    match available {
+        Ok(versions) if versions.iter().any(|remote| remote.version == *version) => Check::new(
+            CheckKind::Registry,
+            CheckStatus::Pass,
+            format!("{tool} is published"),
+        ),
+        Ok(_) => Check::new(
+            CheckKind::Registry,
+            CheckStatus::Fail,
+            format!("{tool} is not published in the configured registry"),
+        ),
+        Err(error) => Check::new(
+            CheckKind::Registry,
+            CheckStatus::Fail,
+            format!(
+                "Could not list the versions of {name}: {}",
+                first_line(&error.to_string())
+            ),
+        ),
+    }
+}
+
+fn check_path(platform: &PlatformSpec) -> Vec<Check> {
+    let mut shims = vec!["node", "npm", "npx"];
+    if platform.pnpm.is_some() {
+        shims.push("pnpm");
+    }
+    if platform.yarn.is_some() {
+        shims.push("yarn");
+    }
+
+    shims
+        .into_iter()
+        .filter_map(|shim| {
+            let expected_dir = find_expected_shim_dir(shim)?;
+            let check = match which::which(shim) {
+                Ok(resolved) if resolved.starts_with(&expected_dir) => Check::new(
+                    CheckKind::Path,
+                    CheckStatus::Pass,
+                    format!("{shim} resolves to {}", resolved.display()),
+                ),
+                Ok(resolved) => Check::new(
+                    CheckKind::Path,
+                    CheckStatus::Fail,
+                    format!(
+                        "{shim} resolves to {}, instead of the Volta shim in {}",
+                        resolved.display(),
+                        expected_dir.display()
+                    ),
+                ),
+                Err(_) => Check::new(
+                    CheckKind::Path,
+                    CheckStatus::Fail,
+                    format!(
+                        "{shim} is not on your {PATH_VAR_NAME}, please add {} to it",
+                        expected_dir.display()
+                    ),
+                ),
+            };
+            Some(check.with_tool(shim))
+        })
+        .collect()
+}
+
+/// The tools pinned by a platform, with npm left out if it is the version bundled with Node
+fn pinned_tools(platform: &PlatformSpec) -> impl Iterator<Item = (&'static str, &Version)> {
+    [
+        Some(("node", &platform.node)),
+        platform.npm.as_ref().map(|version| ("npm", version)),
+        platform.pnpm.as_ref().map(|version| ("pnpm", version)),
+        platform.yarn.as_ref().map(|version| ("yarn", version)),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn platform(npm: Option<&str>, yarn: Option<&str>) -> PlatformSpec {
+        PlatformSpec {
+            node: "20.19.2".parse().unwrap(),
+            npm: npm.map(|version| version.parse().unwrap()),
+            pnpm: None,
+            yarn: yarn.map(|version| version.parse().unwrap()),
+        }
+    }
+
+    #[test]
+    fn lists_pinned_tools() {
+        let tools: Vec<_> = pinned_tools(&platform(None, Some("1.22.22")))
+            .map(|(name, version)| tool_version(name, version))
+            .collect();
+        assert_eq!(tools, vec!["node@20.19.2", "yarn@1.22.22"]);
+    }
+
+    #[test]
+    fn checks_registry_listing() {
+        let version: Version = "20.19.2".parse().unwrap();
+        let listed = || Ok(vec![RemoteVersion::new("20.19.2".parse().unwrap())]);
+
+        let check = registry_check("node", &version, listed());
+        assert_eq!(check.status, CheckStatus::Pass);
+
+        let check = registry_check("node", &"20.19.9".parse().unwrap(), listed());
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert_eq!(
+            check.message,
+            "node@20.19.9 is not published in the configured registry"
+        );
+    }
+}
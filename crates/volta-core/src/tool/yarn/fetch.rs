@@ -3,26 +3,58 @@
 use std::fs::File;
 use std::path::Path;
 
-use super::super::download_tool_error;
+use super::super::integrity::{ArchiveChecker, Integrity, load_recorded, save_recorded};
+use super::super::lockfile::LockedChecksum;
 use super::super::registry::{
-    find_unpack_dir, public_registry_package, scoped_public_registry_package,
+    fetch_npm_registry, find_unpack_dir, registry_index, registry_package, scoped_registry_package,
 };
+use super::super::{StagedDownload, discard_invalid_cache, download_tool_error};
 use crate::error::{Context, ErrorKind, Fallible, FilesystemError, ToolError};
-use crate::fs::{
-    create_staging_dir, create_staging_file, ensure_containing_dir_exists, rename, set_executable,
-};
-use crate::hook::YarnHooks;
+use crate::fs::{create_staging_dir, ensure_containing_dir_exists, rename, set_executable};
+use crate::hook::{RegistryFormat, YarnHooks};
+use crate::http;
 use crate::layout::volta_home;
+use crate::settings::Settings;
 use crate::style::{progress_bar, tool_version};
 use crate::tool::{self, Yarn};
 use crate::version::VersionSpec;
 use archive::{Archive, Tarball};
+use attohttpc::Session;
 use log::debug;
 use nodejs_semver::Version;
 
-pub fn fetch(version: &Version, hooks: Option<&YarnHooks>) -> Fallible<()> {
+pub fn fetch(
+    version: &Version,
+    settings: &Settings,
+    hooks: Option<&YarnHooks>,
+    corepack: Option<&Integrity>,
+    locked: Option<&LockedChecksum>,
+) -> Fallible<()> {
     let yarn_dir = volta_home()?.yarn_inventory_dir();
-    let cache_file = yarn_dir.join(Yarn::archive_filename(&version.to_string()));
+    let version_str = version.to_string();
+    let cache_file = yarn_dir.join(Yarn::archive_filename(&version_str));
+    let integrity_file = yarn_dir.join(Yarn::integrity_filename(&version_str));
+
+    // A cached archive was verified against the published integrity when it was downloaded, so
+    // the integrity recorded next to it is used instead of fetching the registry metadata again
+    let recorded = load_recorded(&cache_file, &integrity_file);
+    let published = match &recorded {
+        Some(recorded) => {
+            debug!("Using integrity recorded at '{}'", integrity_file.display());
+            Some(recorded.clone())
+        }
+        None => archive_checksum(version, settings, hooks)?,
+    };
+
+    // Without a published integrity, the archive is verified against the locked one instead
+    let integrity = match (published, locked) {
+        (Some(integrity), Some(locked)) => {
+            locked.check(&integrity, "Yarn", version)?;
+            Some(integrity)
+        }
+        (None, Some(locked)) => Some(locked.integrity().clone()),
+        (integrity, None) => integrity,
+    };
 
     let (archive, staging) = if let Some(archive) = load_cached_distro(&cache_file) {
         debug!(
@@ -32,32 +64,61 @@ pub fn fetch(version: &Version, hooks: Option<&YarnHooks>) -> Fallible<()> {
         );
         (archive, None)
     } else {
-        let staging = create_staging_file()?;
-        let remote_url = determine_remote_url(version, hooks)?;
-        let archive = fetch_remote_distro(version, &remote_url, staging.path())?;
+        let staging = StagedDownload::new(&Yarn::archive_filename(&version_str))?;
+        let remote_url = determine_remote_url(version, settings, hooks)?;
+        let archive = fetch_remote_distro(
+            &http::client(settings)?,
+            version,
+            &remote_url,
+            staging.path(),
+        )?;
         (archive, Some(staging))
     };
 
-    unpack_archive(archive, version)?;
+    let archive_size = archive.compressed_size();
+    if let Err(error) = unpack_archive(archive, version, integrity.as_ref(), corepack) {
+        discard_invalid_cache(staging, archive_size, &[&cache_file, &integrity_file])?;
+        return Err(error);
+    }
 
-    if let Some(staging_file) = staging {
-        ensure_containing_dir_exists(&cache_file).with_context(|| {
-            ErrorKind::Filesystem(FilesystemError::ContainingDir {
-                path: cache_file.clone(),
-            })
-        })?;
-        staging_file.persist(cache_file).with_context(|| {
-            ErrorKind::Tool(ToolError::PersistInventory {
-                tool: "Yarn".into(),
-            })
-        })?;
+    if let Some(staging) = staging {
+        staging.persist(&cache_file, "Yarn")?;
+    }
+
+    if let Some(integrity) = integrity.filter(|_| recorded.is_none()) {
+        save_recorded(&integrity_file, &integrity);
     }
 
     Ok(())
 }
 
+/// Determines whether the archive of a version is cached along with its verified integrity, so
+/// that it can be installed without the network
+///
+/// # Errors
+///
+/// Returns an error if the Volta home directory cannot be determined.
+pub fn is_cached(version: &Version) -> Fallible<bool> {
+    let version_str = version.to_string();
+    let yarn_dir = volta_home()?.yarn_inventory_dir();
+    Ok(load_recorded(
+        &yarn_dir.join(Yarn::archive_filename(&version_str)),
+        &yarn_dir.join(Yarn::integrity_filename(&version_str)),
+    )
+    .is_some())
+}
+
 /// Unpack the yarn archive into the image directory so that it is ready for use
-fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()> {
+///
+/// If the registry published an integrity, or the project has a Corepack hash, the archive is
+/// verified against it before it is moved into the image directory, so an invalid archive never
+/// results in an installed image.
+fn unpack_archive(
+    archive: Box<dyn Archive>,
+    version: &Version,
+    integrity: Option<&Integrity>,
+    corepack: Option<&Integrity>,
+) -> Fallible<()> {
     let temp = create_staging_dir()?;
     debug!("Unpacking yarn into '{}'", temp.path().display());
 
@@ -67,9 +128,10 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
         archive.compressed_size(),
     );
     let version_string = version.to_string();
+    let mut checker = ArchiveChecker::new(integrity, corepack);
 
     archive
-        .unpack(temp.path(), &mut |(), read| {
+        .unpack(temp.path(), &mut checker, &mut |(), read| {
             progress.inc(read as u64);
         })
         .with_context(|| {
@@ -79,6 +141,8 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
             })
         })?;
 
+    checker.verify("Yarn", &version_string)?;
+
     let unpack_dir = find_unpack_dir(temp.path())?;
     // "bin/yarn" is not executable in the @yarnpkg/cli-dist package
     ensure_bin_is_executable(&unpack_dir, "yarn")?;
@@ -104,9 +168,8 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
     Ok(())
 }
 
-/// Return the archive if it is valid. It may have been corrupted or interrupted in the middle of
-/// downloading.
-// ISSUE(#134) - verify checksum
+/// Return the archive if it can be loaded. It may have been corrupted or interrupted in the middle
+/// of downloading, which is detected when it is verified against its integrity during unpacking.
 fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
     if file.is_file() {
         let file = File::open(file).ok()?;
@@ -116,8 +179,52 @@ fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
     }
 }
 
+/// Find the published integrity of the Yarn version, using the hooks if available
+///
+/// The legacy GitHub format for the `yarn.index` hook doesn't include any integrity information,
+/// so Yarn versions resolved through such an index can't be verified. Neither can archives from a
+/// `distro` hook, which may not be the ones published to the registry.
+pub fn archive_checksum(
+    version: &Version,
+    settings: &Settings,
+    hooks: Option<&YarnHooks>,
+) -> Fallible<Option<Integrity>> {
+    if let Some(&YarnHooks {
+        distro: Some(_), ..
+    }) = hooks
+    {
+        debug!("Skipping integrity check, yarn.distro hook archives may differ from the registry");
+        return Ok(None);
+    }
+
+    let url = match hooks {
+        Some(&YarnHooks {
+            index: Some(ref hook),
+            ..
+        }) => match hook.format {
+            RegistryFormat::Npm => {
+                debug!("Using yarn.index hook to determine yarn index URL");
+                hook.resolve("")?
+            }
+            RegistryFormat::Github => {
+                debug!("Skipping integrity check, yarn.index hook does not publish integrity");
+                return Ok(None);
+            }
+        },
+        _ if version.major >= 2 => registry_index(settings, "@yarnpkg/cli-dist"),
+        _ => registry_index(settings, "yarn"),
+    };
+
+    let (url, index) = fetch_npm_registry(&http::client(settings)?, url, "Yarn")?;
+    index.integrity("Yarn", version, &url).map(Some)
+}
+
 /// Determine the remote URL to download from, using the hooks if available
-fn determine_remote_url(version: &Version, hooks: Option<&YarnHooks>) -> Fallible<String> {
+fn determine_remote_url(
+    version: &Version,
+    settings: &Settings,
+    hooks: Option<&YarnHooks>,
+) -> Fallible<String> {
     let version_str = version.to_string();
     match hooks {
         Some(&YarnHooks {
@@ -130,13 +237,14 @@ fn determine_remote_url(version: &Version, hooks: Option<&YarnHooks>) -> Fallibl
         }
         _ => {
             if version.major >= 2 {
-                Ok(scoped_public_registry_package(
+                Ok(scoped_registry_package(
+                    settings,
                     "@yarnpkg",
                     "cli-dist",
                     &version_str,
                 ))
             } else {
-                Ok(public_registry_package("yarn", &version_str))
+                Ok(registry_package(settings, "yarn", &version_str))
             }
         }
     }
@@ -144,12 +252,13 @@ fn determine_remote_url(version: &Version, hooks: Option<&YarnHooks>) -> Fallibl
 
 /// Fetch the distro archive from the internet
 fn fetch_remote_distro(
+    client: &Session,
     version: &Version,
     url: &str,
     staging_path: &Path,
 ) -> Fallible<Box<dyn Archive>> {
     debug!("Downloading {} from {}", tool_version("yarn", version), url);
-    Tarball::fetch(url, staging_path).with_context(download_tool_error(
+    Tarball::fetch(client, url, staging_path).with_context(download_tool_error(
         tool::ToolSpec::Yarn(VersionSpec::Exact(version.clone())),
         url,
     ))
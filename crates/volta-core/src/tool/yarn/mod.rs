@@ -1,11 +1,16 @@
 use std::fmt::{self, Display};
 
+use super::corepack::{corepack_hash, sync_package_manager};
+use super::lockfile::locked_checksum;
+use super::package::PackageManager;
 use super::{
-    FetchStatus, Fetchable, Installable, Pinnable, check_fetched, check_shim_reachable,
-    debug_already_fetched, info_fetched, info_installed, info_pinned, info_project_version,
+    FetchStatus, Fetchable, Installable, Pinnable, check_fetched, check_online,
+    check_shim_reachable, debug_already_fetched, info_fetched, info_installed, info_pinned,
+    info_project_version,
 };
 use crate::error::{ErrorKind, Fallible, PlatformError};
 use crate::inventory::yarn_available;
+use crate::project::ManifestKey;
 use crate::session::Session;
 use crate::style::tool_version;
 use crate::sync::VoltaLock;
@@ -15,6 +20,8 @@ mod fetch;
 mod metadata;
 mod resolve;
 
+pub(crate) use fetch::archive_checksum;
+pub(crate) use resolve::available;
 pub use resolve::resolve;
 
 /// The Tool implementation for fetching and installing Yarn
@@ -38,13 +45,33 @@ impl Yarn {
         format!("{}.tar.gz", Self::archive_basename(version))
     }
 
+    /// The name of the file in the inventory that records the verified integrity of the archive
+    #[must_use]
+    pub fn integrity_filename(version: &str) -> String {
+        format!("{}.integrity", Self::archive_filename(version))
+    }
+
     pub(crate) fn ensure_fetched(&self, session: &Session) -> Fallible<()> {
         match check_fetched(|| yarn_available(&self.version))? {
             FetchStatus::AlreadyFetched => {
                 debug_already_fetched(self);
                 Ok(())
             }
-            FetchStatus::FetchNeeded(_lock) => fetch::fetch(&self.version, session.hooks()?.yarn()),
+            FetchStatus::FetchNeeded(_lock) => {
+                // A cached archive with a recorded integrity can be installed without the network
+                if !fetch::is_cached(&self.version)? {
+                    check_online(self, session)?;
+                }
+                let corepack = corepack_hash(session, PackageManager::Yarn, &self.version)?;
+                let locked = locked_checksum(session, ManifestKey::Yarn, &self.version)?;
+                fetch::fetch(
+                    &self.version,
+                    session.settings()?,
+                    session.hooks()?.yarn(),
+                    corepack.as_ref(),
+                    locked.as_ref(),
+                )
+            }
         }
     }
 }
@@ -89,8 +116,8 @@ impl Pinnable for Yarn {
             let project = session.project_mut()?.unwrap();
             project.pin_yarn(Some(self.version.clone()))?;
 
-            info_pinned(self);
-            Ok(())
+            info_pinned(&self);
+            sync_package_manager(session, PackageManager::Yarn, &self.version)
         } else {
             Err(ErrorKind::Platform(PlatformError::NotInPackage).into())
         }
@@ -1,13 +1,15 @@
 //! Provides resolution of Yarn requirements into specific versions
 
-use super::super::registry::{
-    PackageDetails, PackageIndex, fetch_npm_registry, public_registry_index,
-};
-use super::super::registry_fetch_error;
+use super::super::registry::{PackageDetails, PackageIndex, fetch_npm_registry, registry_index};
+use super::super::remote::{RemoteVersion, remote_versions_from};
+use super::super::{registry_fetch_error, resolve_offline};
 use super::metadata::{RawYarnIndex, YarnIndex};
 use crate::error::{Context, ErrorKind, Fallible, NetworkError, VersionError};
 use crate::hook::{RegistryFormat, YarnHooks};
+use crate::http;
+use crate::inventory::yarn_versions;
 use crate::session::Session;
+use crate::settings::Settings;
 use crate::style::progress_spinner;
 use crate::version::{Tag, VersionSpec, parse};
 use attohttpc::Response;
@@ -18,16 +20,58 @@ use nodejs_semver::{Range, Version};
 ///
 /// Returns an error if the version cannot be resolved.
 pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Version> {
+    if session.offline() {
+        return resolve_offline("Yarn", matching, &yarn_versions()?);
+    }
+
+    let settings = session.settings()?;
     let hooks = session.hooks()?.yarn();
     match matching {
-        VersionSpec::Semver(requirement) => resolve_semver(&requirement, hooks),
+        VersionSpec::Semver(requirement) => resolve_semver(&requirement, settings, hooks),
         VersionSpec::Exact(version) => Ok(version),
-        VersionSpec::None => resolve_tag(Tag::Latest, hooks),
-        VersionSpec::Tag(tag) => resolve_tag(tag, hooks),
+        VersionSpec::None => resolve_tag(Tag::Latest, settings, hooks),
+        VersionSpec::Tag(tag) => resolve_tag(tag, settings, hooks),
+    }
+}
+
+/// Lists every version of Yarn available to fetch, using the hooks if available
+///
+/// Without hooks, this combines Yarn 1 from the `yarn` package with Yarn 3+ from the
+/// `@yarnpkg/cli-dist` package. Tags from the latter take precedence, as they do when resolving.
+pub fn available(settings: &Settings, hooks: Option<&YarnHooks>) -> Fallible<Vec<RemoteVersion>> {
+    if let Some(&YarnHooks {
+        index: Some(ref hook),
+        ..
+    }) = hooks
+    {
+        debug!("Using yarn.index hook to determine yarn index URL");
+        return match hook.format {
+            RegistryFormat::Github => {
+                let index = fetch_legacy_index(settings, &hook.resolve("releases")?)?;
+                Ok(index.entries.into_iter().map(RemoteVersion::new).collect())
+            }
+            RegistryFormat::Npm => {
+                let url = hook.resolve("")?;
+                let (_, index) = fetch_npm_registry(&http::client(settings)?, url, "Yarn")?;
+                Ok(remote_versions_from(index))
+            }
+        };
+    }
+
+    let mut versions = remote_versions_from(fetch_yarn_index(settings, "yarn")?.1);
+    let mut berry = remote_versions_from(fetch_yarn_index(settings, "@yarnpkg/cli-dist")?.1);
+    berry.retain(|remote| remote.version.major >= 3);
+
+    let berry_tags: Vec<&String> = berry.iter().flat_map(|remote| &remote.tags).collect();
+    for remote in &mut versions {
+        remote.tags.retain(|tag| !berry_tags.contains(&tag));
     }
+    versions.extend(berry);
+
+    Ok(versions)
 }
 
-fn resolve_tag(tag: Tag, hooks: Option<&YarnHooks>) -> Fallible<Version> {
+fn resolve_tag(tag: Tag, settings: &Settings, hooks: Option<&YarnHooks>) -> Fallible<Version> {
     // This triage is complicated because we need to maintain the legacy behavior of hooks
     // First, if the tag is 'latest' and we have a 'latest' hook, we use the old behavior
     // Next, if the tag is 'latest' and we _do not_ have a 'latest' hook, we use the new behavior
@@ -44,20 +88,24 @@ fn resolve_tag(tag: Tag, hooks: Option<&YarnHooks>) -> Fallible<Version> {
         ) => {
             debug!("Using yarn.latest hook to determine latest-version URL");
             // does yarn3 use latest-version? no
-            resolve_latest_legacy(&hook.resolve("latest-version")?)
+            resolve_latest_legacy(settings, &hook.resolve("latest-version")?)
         }
-        (Tag::Latest, _) => resolve_custom_tag(Tag::Latest.to_string()),
+        (Tag::Latest, _) => resolve_custom_tag(settings, Tag::Latest.to_string()),
         (tag, Some(&YarnHooks { index: Some(_), .. })) => {
             Err(ErrorKind::Version(VersionError::YarnNotFound {
                 matching: tag.to_string(),
             })
             .into())
         }
-        (tag, _) => resolve_custom_tag(tag.to_string()),
+        (tag, _) => resolve_custom_tag(settings, tag.to_string()),
     }
 }
 
-fn resolve_semver(matching: &Range, hooks: Option<&YarnHooks>) -> Fallible<Version> {
+fn resolve_semver(
+    matching: &Range,
+    settings: &Settings,
+    hooks: Option<&YarnHooks>,
+) -> Fallible<Version> {
     // For semver, the triage is less complicated: The previous behavior _always_ used
     // the 'index' hook, so we can check for that to decide which behavior to use.
     //
@@ -70,22 +118,24 @@ fn resolve_semver(matching: &Range, hooks: Option<&YarnHooks>) -> Fallible<Versi
     {
         debug!("Using yarn.index hook to determine yarn index URL");
         match hook.format {
-            RegistryFormat::Github => resolve_semver_legacy(matching, &hook.resolve("releases")?),
-            RegistryFormat::Npm => resolve_semver_npm(matching, &hook.resolve("")?),
+            RegistryFormat::Github => {
+                resolve_semver_legacy(matching, settings, &hook.resolve("releases")?)
+            }
+            RegistryFormat::Npm => resolve_semver_npm(matching, settings, &hook.resolve("")?),
         }
     } else {
-        resolve_semver_from_registry(matching)
+        resolve_semver_from_registry(matching, settings)
     }
 }
 
-fn fetch_yarn_index(package: &str) -> Fallible<(String, PackageIndex)> {
-    let url = public_registry_index(package);
-    fetch_npm_registry(url, "Yarn")
+fn fetch_yarn_index(settings: &Settings, package: &str) -> Fallible<(String, PackageIndex)> {
+    let url = registry_index(settings, package);
+    fetch_npm_registry(&http::client(settings)?, url, "Yarn")
 }
 
-fn resolve_custom_tag(tag: String) -> Fallible<Version> {
+fn resolve_custom_tag(settings: &Settings, tag: String) -> Fallible<Version> {
     // first try yarn2+, which uses "@yarnpkg/cli-dist" instead of "yarn"
-    if let Ok((url, mut index)) = fetch_yarn_index("@yarnpkg/cli-dist")
+    if let Ok((url, mut index)) = fetch_yarn_index(settings, "@yarnpkg/cli-dist")
         && let Some(version) = index.tags.remove(&tag)
     {
         debug!("Found yarn@{version} matching tag '{tag}' from {url}");
@@ -96,7 +146,7 @@ fn resolve_custom_tag(tag: String) -> Fallible<Version> {
     }
     debug!("Did not find yarn matching tag '{tag}' from @yarnpkg/cli-dist");
 
-    let (url, mut index) = fetch_yarn_index("yarn")?;
+    let (url, mut index) = fetch_yarn_index(settings, "yarn")?;
     match index.tags.remove(&tag) {
         Some(version) => {
             debug!("Found yarn@{version} matching tag '{tag}' from {url}");
@@ -106,8 +156,9 @@ fn resolve_custom_tag(tag: String) -> Fallible<Version> {
     }
 }
 
-fn resolve_latest_legacy(url: &str) -> Fallible<Version> {
-    let response_text = attohttpc::get(url)
+fn resolve_latest_legacy(settings: &Settings, url: &str) -> Fallible<Version> {
+    let response_text = http::client(settings)?
+        .get(url)
         .send()
         .and_then(Response::error_for_status)
         .and_then(Response::text)
@@ -121,9 +172,9 @@ fn resolve_latest_legacy(url: &str) -> Fallible<Version> {
     parse(response_text)
 }
 
-fn resolve_semver_from_registry(matching: &Range) -> Fallible<Version> {
+fn resolve_semver_from_registry(matching: &Range, settings: &Settings) -> Fallible<Version> {
     // first try yarn2+, which uses "@yarnpkg/cli-dist" instead of "yarn"
-    if let Ok((url, index)) = fetch_yarn_index("@yarnpkg/cli-dist") {
+    if let Ok((url, index)) = fetch_yarn_index(settings, "@yarnpkg/cli-dist") {
         let matching_entries: Vec<PackageDetails> = index
             .entries
             .into_iter()
@@ -151,7 +202,7 @@ fn resolve_semver_from_registry(matching: &Range) -> Fallible<Version> {
     }
     debug!("Did not find yarn matching requirement '{matching}' for @yarnpkg/cli-dist");
 
-    let (url, index) = fetch_yarn_index("yarn")?;
+    let (url, index) = fetch_yarn_index(settings, "yarn")?;
 
     let details_opt = index
         .entries
@@ -174,16 +225,8 @@ fn resolve_semver_from_registry(matching: &Range) -> Fallible<Version> {
     }
 }
 
-fn resolve_semver_legacy(matching: &Range, url: &str) -> Fallible<Version> {
-    let spinner = progress_spinner(format!("Fetching registry: {url}"));
-    let releases: RawYarnIndex = attohttpc::get(url)
-        .send()
-        .and_then(Response::error_for_status)
-        .and_then(Response::json)
-        .with_context(registry_fetch_error("Yarn", url))?;
-    let index = YarnIndex::from(releases);
-    let releases = index.entries;
-    spinner.finish_and_clear();
+fn resolve_semver_legacy(matching: &Range, settings: &Settings, url: &str) -> Fallible<Version> {
+    let releases = fetch_legacy_index(settings, url)?.entries;
     let version_opt = releases.into_iter().rev().find(|v| matching.satisfies(v));
 
     version_opt.map_or_else(
@@ -200,8 +243,21 @@ fn resolve_semver_legacy(matching: &Range, url: &str) -> Fallible<Version> {
     )
 }
 
-fn resolve_semver_npm(matching: &Range, url: &str) -> Fallible<Version> {
-    let (url, index) = fetch_npm_registry(url.to_owned(), "Yarn")?;
+fn fetch_legacy_index(settings: &Settings, url: &str) -> Fallible<YarnIndex> {
+    let spinner = progress_spinner(format!("Fetching registry: {url}"));
+    let releases: RawYarnIndex = http::client(settings)?
+        .get(url)
+        .send()
+        .and_then(Response::error_for_status)
+        .and_then(Response::json)
+        .with_context(registry_fetch_error("Yarn", url))?;
+    spinner.finish_and_clear();
+
+    Ok(YarnIndex::from(releases))
+}
+
+fn resolve_semver_npm(matching: &Range, settings: &Settings, url: &str) -> Fallible<Version> {
+    let (url, index) = fetch_npm_registry(&http::client(settings)?, url.to_owned(), "Yarn")?;
 
     let details_opt = index
         .entries
@@ -110,7 +110,7 @@ pub mod tests {
 
     #[test]
     fn test_from_json_empty_string() {
-        let json_str = "".to_string();
+        let json_str = String::new();
         let platform = Platform::try_from(json_str).expect("could not parse JSON string");
         let expected_platform = Platform {
             node: None,
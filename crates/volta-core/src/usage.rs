@@ -0,0 +1,270 @@
+//! Tracks when tool versions and projects were last used, so that unused versions can be found
+//! and pruned from the inventory.
+//!
+//! The index is a directory of small marker files under `VOLTA_HOME/usage`, whose modification
+//! times are the last time each item was used:
+//!
+//! - `tools/<tool>/<version>` for each version of Node, npm, pnpm and Yarn
+//! - `projects/<digest>` for each project root, named after the digest of the root and containing
+//!   the root itself
+//!
+//! Recording usage happens every time a shim runs, so markers are only rewritten when they are
+//! older than `REFRESH_INTERVAL`. In the common case, recording is a single `stat` call.
+
+use std::fmt::Write as _;
+use std::fs::{self, DirEntry, File, Metadata};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::error::{Context, ErrorKind, Fallible, FilesystemError};
+use crate::fs::{
+    ensure_containing_dir_exists, ok_if_not_found, read_dir_eager, remove_file_if_exists,
+};
+use crate::layout::volta_home;
+use log::debug;
+use nodejs_semver::Version;
+use sha2::{Digest, Sha256};
+
+/// How old a marker must be before it is updated again
+const REFRESH_INTERVAL: Duration = Duration::from_hours(1);
+
+/// A project that has been used by Volta
+pub struct RecentProject {
+    /// The root directory of the project
+    pub root: PathBuf,
+    /// The last time the project was used
+    pub last_used: SystemTime,
+    marker: PathBuf,
+}
+
+impl RecentProject {
+    /// Stop tracking this project
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the marker file cannot be removed.
+    pub fn forget(self) -> Fallible<()> {
+        remove_file_if_exists(self.marker)
+    }
+}
+
+/// The last-used index in a given directory
+pub struct Index {
+    dir: PathBuf,
+}
+
+impl Index {
+    /// Creates an index stored in the given directory
+    #[must_use]
+    pub const fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Opens the index in the Volta home directory
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Volta home directory cannot be determined.
+    pub fn current() -> Fallible<Self> {
+        Ok(Self::new(volta_home()?.usage_dir().to_owned()))
+    }
+
+    /// Records that a version of a tool has been used
+    ///
+    /// Failing to record usage should never prevent a tool from running, so errors are only logged.
+    pub fn record_tool(&self, tool: &str, version: &Version) {
+        if let Err(error) = refresh_marker(&self.tool_marker(tool, version), &[]) {
+            debug!("Could not record usage of {tool}@{version}: {error}");
+        }
+    }
+
+    /// Records that the project at `root` has been used
+    ///
+    /// Failing to record usage should never prevent a tool from running, so errors are only logged.
+    pub fn record_project(&self, root: &Path) {
+        let marker = self.projects_dir().join(project_key(root));
+
+        if let Err(error) = refresh_marker(&marker, root.to_string_lossy().as_bytes()) {
+            debug!(
+                "Could not record usage of project at '{}': {error}",
+                root.display()
+            );
+        }
+    }
+
+    /// The last time a version of a tool was used, if it has been recorded
+    #[must_use]
+    pub fn tool_last_used(&self, tool: &str, version: &Version) -> Option<SystemTime> {
+        fs::metadata(self.tool_marker(tool, version))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    /// Stop tracking a version of a tool
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the marker file cannot be removed.
+    pub fn forget_tool(&self, tool: &str, version: &Version) -> Fallible<()> {
+        remove_file_if_exists(self.tool_marker(tool, version))
+    }
+
+    /// Collects all of the projects which have been recorded as used
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the usage directory cannot be read.
+    pub fn projects(&self) -> Fallible<Vec<RecentProject>> {
+        let dir = self.projects_dir();
+
+        let entries: Vec<(DirEntry, Metadata)> = read_dir_eager(&dir)
+            .map(Iterator::collect)
+            .or_else(ok_if_not_found)
+            .with_context(|| {
+                ErrorKind::Filesystem(FilesystemError::ReadDir { dir: dir.clone() })
+            })?;
+
+        Ok(entries
+            .into_iter()
+            .filter(|(_, metadata)| metadata.is_file())
+            .filter_map(|(entry, metadata)| {
+                let marker = entry.path();
+                let root = fs::read_to_string(&marker).ok()?;
+
+                Some(RecentProject {
+                    root: PathBuf::from(root),
+                    last_used: metadata.modified().ok()?,
+                    marker,
+                })
+            })
+            .collect())
+    }
+
+    fn tool_marker(&self, tool: &str, version: &Version) -> PathBuf {
+        let mut marker = self.dir.join("tools");
+        marker.push(tool);
+        marker.push(version.to_string());
+        marker
+    }
+
+    fn projects_dir(&self) -> PathBuf {
+        self.dir.join("projects")
+    }
+}
+
+/// Records that a version of a tool has been used, in the index in the Volta home directory
+pub fn record_tool(tool: &str, version: &Version) {
+    if let Ok(index) = Index::current() {
+        index.record_tool(tool, version);
+    }
+}
+
+/// Records that the project at `root` has been used, in the index in the Volta home directory
+pub fn record_project(root: &Path) {
+    if let Ok(index) = Index::current() {
+        index.record_project(root);
+    }
+}
+
+/// Updates the modification time of a marker, creating it with the given contents if needed
+///
+/// Markers that have been updated within the `REFRESH_INTERVAL` are left alone.
+fn refresh_marker(marker: &Path, contents: &[u8]) -> io::Result<()> {
+    match fs::metadata(marker).and_then(|metadata| metadata.modified()) {
+        Ok(modified) if is_fresh(modified) => Ok(()),
+        Ok(_) => File::options()
+            .write(true)
+            .open(marker)?
+            .set_modified(SystemTime::now()),
+        Err(_) => {
+            ensure_containing_dir_exists(&marker)?;
+            fs::write(marker, contents)
+        }
+    }
+}
+
+fn is_fresh(modified: SystemTime) -> bool {
+    modified
+        .elapsed()
+        .is_ok_and(|elapsed| elapsed < REFRESH_INTERVAL)
+}
+
+/// Determine the marker file name for a project, based on the SHA-256 digest of its root
+fn project_key(root: &Path) -> String {
+    Sha256::digest(root.to_string_lossy().as_bytes())
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn records_tools() {
+        let dir = tempdir().unwrap();
+        let index = Index::new(dir.path().to_owned());
+        let version = Version::parse("18.20.4").unwrap();
+
+        assert_eq!(index.tool_last_used("node", &version), None);
+
+        index.record_tool("node", &version);
+        let last_used = index.tool_last_used("node", &version).unwrap();
+        assert!(is_fresh(last_used));
+        assert_eq!(index.tool_last_used("yarn", &version), None);
+
+        index.forget_tool("node", &version).unwrap();
+        assert_eq!(index.tool_last_used("node", &version), None);
+    }
+
+    #[test]
+    fn refreshes_stale_markers() {
+        let dir = tempdir().unwrap();
+        let index = Index::new(dir.path().to_owned());
+        let version = Version::parse("1.22.22").unwrap();
+
+        index.record_tool("yarn", &version);
+        let stale = SystemTime::now() - Duration::from_hours(24 * 100);
+        File::options()
+            .write(true)
+            .open(index.tool_marker("yarn", &version))
+            .unwrap()
+            .set_modified(stale)
+            .unwrap();
+        assert_eq!(index.tool_last_used("yarn", &version), Some(stale));
+
+        index.record_tool("yarn", &version);
+        assert!(is_fresh(index.tool_last_used("yarn", &version).unwrap()));
+    }
+
+    #[test]
+    fn records_projects() {
+        let dir = tempdir().unwrap();
+        let index = Index::new(dir.path().to_owned());
+        let first = Path::new("/path/to/first");
+        let second = Path::new("/path/to/second");
+
+        index.record_project(first);
+        index.record_project(second);
+        index.record_project(first);
+
+        let mut roots: Vec<PathBuf> = index
+            .projects()
+            .unwrap()
+            .into_iter()
+            .map(|project| project.root)
+            .collect();
+        roots.sort();
+        assert_eq!(roots, vec![first.to_owned(), second.to_owned()]);
+
+        for project in index.projects().unwrap() {
+            project.forget().unwrap();
+        }
+        assert!(index.projects().unwrap().is_empty());
+    }
+}
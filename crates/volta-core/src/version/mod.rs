@@ -33,6 +33,12 @@ pub enum Tag {
     /// The 'lts' tag, a special case for Node
     Lts,
 
+    /// A line of Node LTS releases by codename, like 'lts/iron'
+    LtsCodename(String),
+
+    /// A line of Node LTS releases counting back from the newest line, like 'lts/-1'
+    PreviousLts(usize),
+
     /// An arbitrary tag version
     Custom(String),
 }
@@ -53,6 +59,8 @@ impl fmt::Display for Tag {
         match self {
             Self::Latest => write!(f, "latest"),
             Self::Lts => write!(f, "lts"),
+            Self::LtsCodename(codename) => write!(f, "lts/{codename}"),
+            Self::PreviousLts(count) => write!(f, "lts/-{count}"),
             Self::Custom(s) => s.fmt(f),
         }
     }
@@ -78,8 +86,15 @@ impl FromStr for Tag {
     fn from_str(s: &str) -> Fallible<Self> {
         if s == "latest" {
             Ok(Self::Latest)
-        } else if s == "lts" {
+        } else if s == "lts" || s == "lts/*" {
             Ok(Self::Lts)
+        } else if let Some(line) = s.strip_prefix("lts/") {
+            // The same forms as nvm, where `lts/-1` is the LTS line before the newest one
+            match line.strip_prefix('-').map(str::parse) {
+                Some(Ok(0)) => Ok(Self::Lts),
+                Some(Ok(count)) => Ok(Self::PreviousLts(count)),
+                _ => Ok(Self::LtsCodename(line.to_lowercase())),
+            }
         } else {
             Ok(Self::Custom(s.into()))
         }
@@ -7,12 +7,13 @@ pub use crate::v1::VoltaInstall;
 layout! {
     pub struct VoltaHome {
         "cache": cache_dir {
-            "node": node_cache_dir {
-                "index.json": node_index_file;
-                "index.json.expires": node_index_expiry_file;
-            }
+            "http": http_cache_dir {}
+            "fallback.json": fallback_cache_file;
         }
         "bin": shim_dir {}
+        "keys": keys_dir {
+            "node": node_keys_dir {}
+        }
         "log": log_dir {}
         "tools": tools_dir {
             "inventory": inventory_dir {
@@ -27,6 +28,7 @@ layout! {
                 "pnpm": pnpm_image_root_dir {}
                 "yarn": yarn_image_root_dir {}
                 "packages": package_image_root_dir {}
+                "project-tools": project_tool_image_root_dir {}
             }
             "shared": shared_lib_root {}
             "user": default_toolchain_dir {
@@ -36,7 +38,9 @@ layout! {
             }
         }
         "tmp": tmp_dir {}
+        "usage": usage_dir {}
         "hooks.json": default_hooks_file;
+        "settings.json": default_settings_file;
         "layout.v4": layout_file;
     }
 }
@@ -82,6 +86,22 @@ impl VoltaHome {
         path_buf!(self.package_image_root_dir.clone(), name)
     }
 
+    #[must_use]
+    pub fn package_version_image_dir(&self, name: &str, version: &str) -> PathBuf {
+        path_buf!(
+            self.package_image_root_dir.clone(),
+            format!("{}@{}", name, version)
+        )
+    }
+
+    #[must_use]
+    pub fn project_tool_image_dir(&self, name: &str, version: &str) -> PathBuf {
+        path_buf!(
+            self.project_tool_image_root_dir.clone(),
+            format!("{}@{}", name, version)
+        )
+    }
+
     #[must_use]
     pub fn default_package_config_file(&self, package_name: &str) -> PathBuf {
         path_buf!(
@@ -90,6 +110,14 @@ impl VoltaHome {
         )
     }
 
+    #[must_use]
+    pub fn package_version_config_file(&self, package_name: &str, version: &str) -> PathBuf {
+        path_buf!(
+            self.default_package_dir.clone(),
+            format!("{}@{}.json", package_name, version)
+        )
+    }
+
     #[must_use]
     pub fn default_tool_bin_config(&self, bin_name: &str) -> PathBuf {
         path_buf!(self.default_bin_dir.clone(), format!("{}.json", bin_name))
@@ -113,6 +141,11 @@ impl VoltaHome {
         path_buf!(self.shim_dir.clone(), toolname)
     }
 
+    #[must_use]
+    pub fn http_cache_file(&self, key: &str) -> PathBuf {
+        path_buf!(self.http_cache_dir.clone(), key)
+    }
+
     #[must_use]
     pub fn shared_lib_dir(&self, library: &str) -> PathBuf {
         path_buf!(self.shared_lib_root.clone(), library)
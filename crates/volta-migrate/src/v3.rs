@@ -162,7 +162,7 @@ fn migrate_single_package(config: LegacyPackageConfig, session: &mut Session) ->
     let image = platform.as_binary().checkout(session)?;
 
     // Run the global install command
-    tool.run_install(&image)?;
+    tool.run_install(&image, session.offline())?;
     // Overwrite the config files and image directory
     tool.complete_install(&image)?;
 
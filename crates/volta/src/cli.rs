@@ -40,6 +40,10 @@ pub struct Volta {
     )]
     pub(crate) quiet: bool,
 
+    /// Resolves tools from the local inventory, without network access
+    #[arg(long, global = true)]
+    pub(crate) offline: bool,
+
     /// Prints the current version of Volta
     #[arg(short, long)]
     pub(crate) version: bool,
@@ -74,6 +78,9 @@ pub enum Subcommand {
     /// Uninstalls a tool from your toolchain
     Uninstall(command::Uninstall),
 
+    /// Removes tool versions that are no longer used by any platform
+    Prune(command::Prune),
+
     /// Updates a tool in your toolchain to a newer version
     Update(command::Update),
 
@@ -84,6 +91,10 @@ pub enum Subcommand {
     #[command(alias = "ls")]
     List(command::List),
 
+    /// Lists the versions of a tool available to fetch
+    #[command(name = "list-remote", alias = "search")]
+    ListRemote(command::ListRemote),
+
     /// Generates Volta completions
     ///
     /// By default, completions will be generated for the value of your current shell,
@@ -98,6 +109,9 @@ pub enum Subcommand {
     /// Locates the actual binary that will be called by Volta
     Which(command::Which),
 
+    /// Checks that your project's toolchain is consistent and in use, without running it
+    Verify(command::Verify),
+
     #[command(long_about = crate::command::r#use::USAGE, hide = true)]
     Use(command::Use),
 
@@ -114,11 +128,14 @@ impl Subcommand {
             Self::Fetch(fetch) => fetch.run(session),
             Self::Install(install) => install.run(session),
             Self::Uninstall(uninstall) => uninstall.run(session),
+            Self::Prune(prune) => prune.run(session),
             Self::Update(update) => update.run(session),
             Self::Pin(pin) => pin.run(session),
             Self::List(list) => list.run(session),
+            Self::ListRemote(list_remote) => list_remote.run(session),
             Self::Completions(completions) => completions.run(session),
             Self::Which(which) => which.run(session),
+            Self::Verify(verify) => verify.run(session),
             Self::Use(r#use) => r#use.run(session),
             Self::Setup(setup) => setup.run(session),
             Self::Run(run) => run.run(session),
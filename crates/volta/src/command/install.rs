@@ -1,22 +1,55 @@
-use volta_core::error::{ExitCode, Fallible};
+use volta_core::error::{ExitCode, Fallible, PackageError};
 use volta_core::session::{ActivityKind, Session};
-use volta_core::tool::ToolSpec;
+use volta_core::tool::package::{BinAlias, install_project_tools};
+use volta_core::tool::{Installable, Package, ToolSpec};
 
 use crate::command::Command;
 
 #[derive(clap::Args)]
 pub struct Install {
     /// Tools to install, like `node`, `yarn@latest` or `your-package@^14.4.3`.
-    #[arg(value_name = "tool[@version]", required = true)]
+    ///
+    /// Without any tools, installs the packages in the `volta.tools` key of the current project,
+    /// so that their executables can be run directly. They are also installed the first time
+    /// they are used with `volta run`.
+    #[arg(value_name = "tool[@version]")]
     tools: Vec<String>,
+
+    /// Install a package alongside its default version, with an executable renamed to ALIAS
+    ///
+    /// The executable is chosen from the name of the alias, like `tsc` for `tsc4`, unless it is
+    /// given as `<executable>=ALIAS`.
+    #[arg(long = "as", value_name = "ALIAS")]
+    aliases: Vec<String>,
 }
 
 impl Command for Install {
     fn run(self, session: &mut Session) -> Fallible<ExitCode> {
         session.add_event_start(ActivityKind::Install);
 
-        for tool in ToolSpec::from_strings(&self.tools, "install")? {
-            tool.resolve_installable(session)?.install(session)?;
+        let tools = ToolSpec::from_strings(&self.tools, "install")?;
+
+        if tools.is_empty() {
+            if !self.aliases.is_empty() {
+                return Err(PackageError::AliasUnsupported.into());
+            }
+            install_project_tools(session)?;
+        } else if self.aliases.is_empty() {
+            for tool in tools {
+                tool.resolve_installable(session)?.install(session)?;
+            }
+        } else {
+            let aliases = self
+                .aliases
+                .iter()
+                .map(|alias| BinAlias::parse(alias))
+                .collect::<Fallible<Vec<_>>>()?;
+
+            let package = match <[ToolSpec; 1]>::try_from(tools) {
+                Ok([ToolSpec::Package(name, version)]) => Package::new(name, version)?,
+                _ => return Err(PackageError::AliasUnsupported.into()),
+            };
+            Box::new(package.with_aliases(aliases)).install(session)?;
         }
 
         session.add_event_end(ActivityKind::Install, ExitCode::Success);
@@ -1,10 +1,15 @@
 //! Define the "human" format style for list commands.
 
 use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::SystemTime;
 
-use super::{Node, Package, PackageManager, PackageManagerKind, Toolchain};
+use super::{
+    LastUsed, Node, Outdated, Package, PackageManager, PackageManagerKind, Toolchain,
+    prerelease_channel,
+};
 use textwrap::{Options, fill};
-use volta_core::style::{MAX_WIDTH, text_width, tool_version};
+use volta_core::style::{MAX_WIDTH, file_size, text_width, time_ago, tool_version};
 
 static INDENTATION: &str = "    ";
 static NO_RUNTIME: &str = "⚡️ No Node runtimes installed!
@@ -19,7 +24,7 @@ static TEXT_WIDTH: std::sync::LazyLock<usize> =
 pub(super) fn format(toolchain: &Toolchain) -> Option<String> {
     // Formatting here depends on the toolchain: we do different degrees of
     // indentation
-    Some(match toolchain {
+    let output = match toolchain {
         Toolchain::Node(runtimes) => display_node(runtimes),
         Toolchain::Active {
             runtime,
@@ -37,6 +42,17 @@ pub(super) fn format(toolchain: &Toolchain) -> Option<String> {
             name,
             host_packages,
         } => display_tool(name, host_packages),
+        Toolchain::LastUsed {
+            runtimes,
+            package_managers,
+            projects,
+        } => display_last_used(runtimes, package_managers, projects),
+        Toolchain::Outdated(outdated) => display_outdated(outdated),
+    };
+
+    Some(match toolchain.total_size() {
+        Some(total) => format!("{output}\n\nTotal disk usage: {}", file_size(total)),
+        None => output,
     })
 }
 
@@ -105,6 +121,135 @@ fn display_all(
     }
 }
 
+/// Format the output for `Toolchain::LastUsed`.
+fn display_last_used(
+    runtimes: &[LastUsed<Node>],
+    package_managers: &[LastUsed<PackageManager>],
+    projects: &[LastUsed<PathBuf>],
+) -> String {
+    let mut sections = Vec::new();
+
+    if !runtimes.is_empty() {
+        let versions = runtimes
+            .iter()
+            .map(|runtime| {
+                format!(
+                    "{}, {}",
+                    format_runtime(&runtime.item),
+                    format_last_used(runtime.last_used)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        sections.push(wrap(format!("Node runtimes:\n{}", wrap(versions))));
+    }
+
+    if !package_managers.is_empty() {
+        let mut manager_lists = BTreeMap::new();
+        for manager in package_managers {
+            manager_lists
+                .entry(manager.item.kind)
+                .or_insert_with(Vec::new)
+                .push(format!(
+                    "{}, {}",
+                    format_package_manager(&manager.item),
+                    format_last_used(manager.last_used)
+                ));
+        }
+
+        let versions = manager_lists
+            .iter()
+            .map(|(kind, list)| {
+                format!(
+                    "{}:\n{}",
+                    format_package_manager_kind(*kind),
+                    wrap(list.join("\n"))
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        sections.push(wrap(format!("Package managers:\n{}", wrap(versions))));
+    }
+
+    if !projects.is_empty() {
+        let roots = projects
+            .iter()
+            .map(|project| {
+                format!(
+                    "{}, {}",
+                    project.item.display(),
+                    format_last_used(project.last_used)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        sections.push(wrap(format!("Projects:\n{}", wrap(roots))));
+    }
+
+    if sections.is_empty() {
+        String::from("⚡️ No tool versions or projects found.")
+    } else {
+        format!(
+            "⚡️ When tools and projects were last used:\n\n{}",
+            sections.join("\n\n")
+        )
+    }
+}
+
+/// Format the output for `Toolchain::Outdated` as a table.
+fn display_outdated(outdated: &[Outdated]) -> String {
+    if outdated.is_empty() {
+        return String::from("⚡️ Your default tools and packages are up to date.");
+    }
+
+    let show_lts = outdated.iter().any(|item| item.check().lts.is_some());
+    let mut rows = vec![
+        ["Tool", "Current", "Wanted", "Latest", "LTS"]
+            .map(String::from)
+            .to_vec(),
+    ];
+    for item in outdated {
+        let check = item.check();
+        rows.push(vec![
+            item.name(),
+            check.current.to_string(),
+            check.wanted.to_string(),
+            check.latest.to_string(),
+            check
+                .lts
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+        ]);
+    }
+    if !show_lts {
+        for row in &mut rows {
+            row.pop();
+        }
+    }
+
+    let columns = rows[0].len();
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0))
+        .collect();
+
+    let table = rows
+        .iter()
+        .map(|row| {
+            let cells = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .collect::<Vec<_>>()
+                .join("    ");
+            format!("{INDENTATION}{}", cells.trim_end())
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!("⚡️ Newer versions are available:\n\n{table}")
+}
+
 /// Format a set of `Toolchain::Node`s.
 fn display_node(runtimes: &[Node]) -> String {
     if runtimes.is_empty() {
@@ -244,7 +389,14 @@ fn format_runtime_list(runtimes: &[Node]) -> String {
 
 /// format a single version of `Toolchain::Node`.
 fn format_runtime(runtime: &Node) -> String {
-    format!("v{}{}", runtime.version, runtime.source)
+    format!(
+        "v{}{}{}{}",
+        runtime.version,
+        prerelease_channel(&runtime.version)
+            .map_or_else(String::new, |channel| format!(" ({channel} prerelease)")),
+        runtime.source,
+        format_size(runtime.size)
+    )
 }
 
 /// format a list of `Toolchain::PackageManager`s in condensed form
@@ -292,7 +444,25 @@ fn format_package_manager_list_verbose(package_managers: &[PackageManager]) -> S
 
 /// format a single `Toolchain::PackageManager`.
 fn format_package_manager(package_manager: &PackageManager) -> String {
-    format!("v{}{}", package_manager.version, package_manager.source)
+    format!(
+        "v{}{}{}",
+        package_manager.version,
+        package_manager.source,
+        format_size(package_manager.size)
+    )
+}
+
+/// format the disk space used by an item, if it was determined
+fn format_size(size: Option<u64>) -> String {
+    size.map_or_else(String::new, |size| format!(", {}", file_size(size)))
+}
+
+/// format when an item was last used, if ever
+fn format_last_used(last_used: Option<SystemTime>) -> String {
+    last_used.map_or_else(
+        || String::from("never used"),
+        |time| format!("last used {}", time_ago(time)),
+    )
 }
 
 /// format the title for a kind of package manager
@@ -331,7 +501,12 @@ fn format_package(package: &Package) -> String {
                 _ => tools.join(", "),
             };
 
-            let version = format!("{}{}", details.version, list_package_source(package));
+            let version = format!(
+                "{}{}{}",
+                details.version,
+                list_package_source(package),
+                format_size(details.size)
+            );
             let binaries = wrap(format!("binary tools: {tools}"));
             let platform_detail = wrap(format!(
                 "runtime: {}\npackage manager: {}",
@@ -354,9 +529,10 @@ fn format_package(package: &Package) -> String {
         }
         Package::Fetched(details) => {
             let package_info = format!("{}@{}", details.name, details.version);
+            let header = format!("{package_info}{}", format_size(details.size));
             let footer_message =
                 format!("To make it available to execute, run `volta install {package_info}`.");
-            format!("{package_info}\n\n{footer_message}")
+            format!("{header}\n\n{footer_message}")
         }
     }
 }
@@ -392,18 +568,19 @@ where
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
+    use std::sync::LazyLock;
 
     use nodejs_semver::Version;
-    use once_cell::sync::Lazy;
 
     use super::*;
 
-    static NODE_12: Lazy<Version> = Lazy::new(|| Version::from((12, 2, 0)));
-    static NODE_11: Lazy<Version> = Lazy::new(|| Version::from((11, 9, 0)));
-    static NODE_10: Lazy<Version> = Lazy::new(|| Version::from((10, 15, 3)));
-    static YARN_VERSION: Lazy<Version> = Lazy::new(|| Version::from((1, 16, 0)));
-    static NPM_VERSION: Lazy<Version> = Lazy::new(|| Version::from((6, 13, 1)));
-    static PROJECT_PATH: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("~/path/to/project.json"));
+    static NODE_12: LazyLock<Version> = LazyLock::new(|| Version::from((12, 2, 0)));
+    static NODE_11: LazyLock<Version> = LazyLock::new(|| Version::from((11, 9, 0)));
+    static NODE_10: LazyLock<Version> = LazyLock::new(|| Version::from((10, 15, 3)));
+    static YARN_VERSION: LazyLock<Version> = LazyLock::new(|| Version::from((1, 16, 0)));
+    static NPM_VERSION: LazyLock<Version> = LazyLock::new(|| Version::from((6, 13, 1)));
+    static PROJECT_PATH: LazyLock<PathBuf> =
+        LazyLock::new(|| PathBuf::from("~/path/to/project.json"));
 
     mod active {
         use super::*;
@@ -434,6 +611,7 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Default,
                 version: NODE_12.clone(),
+                size: None,
             }));
             let package_managers = vec![];
             let packages = vec![];
@@ -456,6 +634,7 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NODE_12.clone(),
+                size: None,
             }));
             let package_managers = vec![];
             let packages = vec![];
@@ -479,11 +658,13 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Default,
                 version: NODE_12.clone(),
+                size: None,
             }));
             let package_managers = vec![PackageManager {
                 kind: PackageManagerKind::Npm,
                 source: Source::Default,
                 version: NPM_VERSION.clone(),
+                size: None,
             }];
             let packages = vec![];
 
@@ -506,11 +687,13 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Default,
                 version: NODE_12.clone(),
+                size: None,
             }));
             let package_managers = vec![PackageManager {
                 kind: PackageManagerKind::Yarn,
                 source: Source::Default,
                 version: YARN_VERSION.clone(),
+                size: None,
             }];
             let packages = vec![];
 
@@ -533,11 +716,13 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Default,
                 version: NODE_12.clone(),
+                size: None,
             }));
             let package_managers = vec![PackageManager {
                 kind: PackageManagerKind::Npm,
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NPM_VERSION.clone(),
+                size: None,
             }];
             let packages = vec![];
 
@@ -560,11 +745,13 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Default,
                 version: NODE_12.clone(),
+                size: None,
             }));
             let package_managers = vec![PackageManager {
                 kind: PackageManagerKind::Yarn,
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: YARN_VERSION.clone(),
+                size: None,
             }];
             let packages = vec![];
 
@@ -587,11 +774,13 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NODE_12.clone(),
+                size: None,
             }));
             let package_managers = vec![PackageManager {
                 kind: PackageManagerKind::Npm,
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NPM_VERSION.clone(),
+                size: None,
             }];
             let packages = vec![];
 
@@ -614,11 +803,13 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NODE_12.clone(),
+                size: None,
             }));
             let package_managers = vec![PackageManager {
                 kind: PackageManagerKind::Yarn,
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: YARN_VERSION.clone(),
+                size: None,
             }];
             let packages = vec![];
 
@@ -642,17 +833,20 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Default,
                 version: NODE_12.clone(),
+                size: None,
             }));
             let package_managers = vec![
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Default,
                     version: NPM_VERSION.clone(),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Default,
                     version: YARN_VERSION.clone(),
+                    size: None,
                 },
             ];
             let packages = vec![];
@@ -677,17 +871,20 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NODE_12.clone(),
+                size: None,
             }));
             let package_managers = vec![
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NPM_VERSION.clone(),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: YARN_VERSION.clone(),
+                    size: None,
                 },
             ];
             let packages = vec![];
@@ -712,17 +909,20 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Default,
                 version: NODE_12.clone(),
+                size: None,
             }));
             let package_managers = vec![
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NPM_VERSION.clone(),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Default,
                     version: YARN_VERSION.clone(),
+                    size: None,
                 },
             ];
             let packages = vec![];
@@ -749,17 +949,20 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NODE_12.clone(),
+                size: None,
             }));
             let package_managers = vec![
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NPM_VERSION.clone(),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: YARN_VERSION.clone(),
+                    size: None,
                 },
             ];
             let packages = vec![
@@ -767,6 +970,7 @@ See options for more detailed reports by running `volta list --help`.";
                     details: PackageDetails {
                         name: "create-react-app".to_string(),
                         version: Version::from((3, 0, 1)),
+                        size: None,
                     },
                     node: NODE_12.clone(),
                     tools: vec!["create-react-app".to_string()],
@@ -775,6 +979,7 @@ See options for more detailed reports by running `volta list --help`.";
                     details: PackageDetails {
                         name: "typescript".to_string(),
                         version: Version::from((3, 4, 3)),
+                        size: None,
                     },
                     node: NODE_12.clone(),
                     tools: vec!["tsc".to_string(), "tsserver".to_string()],
@@ -803,17 +1008,20 @@ See options for more detailed reports by running `volta list --help`.";
             let runtime = Some(Box::new(Node {
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NODE_12.clone(),
+                size: None,
             }));
             let package_managers = vec![
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NPM_VERSION.clone(),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: YARN_VERSION.clone(),
+                    size: None,
                 },
             ];
             let packages = vec![
@@ -826,6 +1034,7 @@ See options for more detailed reports by running `volta list --help`.";
                     details: PackageDetails {
                         name: "typescript".to_string(),
                         version: Version::from((3, 4, 3)),
+                        size: None,
                     },
                     node: NODE_12.clone(),
                     tools: vec!["tsc".to_string(), "tsserver".to_string()],
@@ -860,6 +1069,7 @@ See options for more detailed reports by running `volta list --help`.";
             let runtimes = [Node {
                 source: Source::Default,
                 version: NODE_10.clone(),
+                size: None,
             }];
 
             assert_eq!(display_node(&runtimes).as_str(), expected);
@@ -874,6 +1084,7 @@ See options for more detailed reports by running `volta list --help`.";
             let runtimes = [Node {
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NODE_12.clone(),
+                size: None,
             }];
 
             assert_eq!(display_node(&runtimes).as_str(), expected);
@@ -888,6 +1099,22 @@ See options for more detailed reports by running `volta list --help`.";
             let runtimes = [Node {
                 source: Source::None,
                 version: NODE_11.clone(),
+                size: None,
+            }];
+
+            assert_eq!(display_node(&runtimes).as_str(), expected);
+        }
+
+        #[test]
+        fn single_prerelease() {
+            let expected = "⚡️ Node runtimes in your toolchain:
+
+    v24.0.0-rc.1 (rc prerelease) (default)";
+
+            let runtimes = [Node {
+                source: Source::Default,
+                version: Version::parse("24.0.0-rc.1").unwrap(),
+                size: None,
             }];
 
             assert_eq!(display_node(&runtimes).as_str(), expected);
@@ -905,14 +1132,17 @@ See options for more detailed reports by running `volta list --help`.";
                 Node {
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NODE_12.clone(),
+                    size: None,
                 },
                 Node {
                     source: Source::None,
                     version: NODE_11.clone(),
+                    size: None,
                 },
                 Node {
                     source: Source::Default,
                     version: NODE_10.clone(),
+                    size: None,
                 },
             ];
 
@@ -961,6 +1191,7 @@ See `volta help install` for details and more options.";
                 kind: PackageManagerKind::Npm,
                 source: Source::Default,
                 version: NPM_VERSION.clone(),
+                size: None,
             }];
 
             assert_eq!(
@@ -979,6 +1210,7 @@ See `volta help install` for details and more options.";
                 kind: PackageManagerKind::Yarn,
                 source: Source::Default,
                 version: YARN_VERSION.clone(),
+                size: None,
             }];
 
             assert_eq!(
@@ -997,6 +1229,7 @@ See `volta help install` for details and more options.";
                 kind: PackageManagerKind::Npm,
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: NPM_VERSION.clone(),
+                size: None,
             }];
 
             assert_eq!(
@@ -1015,6 +1248,7 @@ See `volta help install` for details and more options.";
                 kind: PackageManagerKind::Yarn,
                 source: Source::Project(PROJECT_PATH.clone()),
                 version: YARN_VERSION.clone(),
+                size: None,
             }];
 
             assert_eq!(
@@ -1033,6 +1267,7 @@ See `volta help install` for details and more options.";
                 kind: PackageManagerKind::Npm,
                 source: Source::None,
                 version: NPM_VERSION.clone(),
+                size: None,
             }];
 
             assert_eq!(
@@ -1051,6 +1286,7 @@ See `volta help install` for details and more options.";
                 kind: PackageManagerKind::Yarn,
                 source: Source::None,
                 version: YARN_VERSION.clone(),
+                size: None,
             }];
 
             assert_eq!(
@@ -1072,16 +1308,19 @@ See `volta help install` for details and more options.";
                     kind: PackageManagerKind::Npm,
                     source: Source::None,
                     version: Version::from((5, 6, 0)),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Default,
                     version: NPM_VERSION.clone(),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: Version::from((6, 14, 2)),
+                    size: None,
                 },
             ];
 
@@ -1104,16 +1343,19 @@ See `volta help install` for details and more options.";
                     kind: PackageManagerKind::Yarn,
                     source: Source::None,
                     version: Version::from((1, 3, 0)),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Default,
                     version: YARN_VERSION.clone(),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: Version::from((1, 17, 0)),
+                    size: None,
                 },
             ];
 
@@ -1153,6 +1395,7 @@ See `volta help install` for details and more options.";
                 details: PackageDetails {
                     name: "ember-cli".to_string(),
                     version: Version::from((3, 10, 1)),
+                    size: None,
                 },
                 node: NODE_12.clone(),
                 tools: vec!["ember".to_string()],
@@ -1188,6 +1431,7 @@ See `volta help install` for details and more options.";
             let packages = [Package::Fetched(PackageDetails {
                 name: "ember-cli".to_string(),
                 version: Version::from((3, 10, 1)),
+                size: None,
             })];
 
             assert_eq!(display_packages(&packages), expected);
@@ -1208,10 +1452,12 @@ See `volta help install` for details and more options.";
                 Package::Fetched(PackageDetails {
                     name: "ember-cli".to_string(),
                     version: Version::from((3, 10, 1)),
+                    size: None,
                 }),
                 Package::Fetched(PackageDetails {
                     name: "ember-cli".to_string(),
                     version: Version::from((3, 8, 2)),
+                    size: None,
                 }),
             ];
 
@@ -1235,6 +1481,7 @@ See `volta help install` for details and more options.";
                     details: PackageDetails {
                         name: "ember-cli".to_string(),
                         version: Version::from((3, 10, 1)),
+                        size: None,
                     },
                     node: NODE_12.clone(),
                     tools: vec!["ember".to_string()],
@@ -1279,6 +1526,7 @@ See `volta help install` for details and more options.";
                 details: PackageDetails {
                     name: "ember-cli".to_string(),
                     version: Version::from((3, 10, 1)),
+                    size: None,
                 },
                 node: NODE_12.clone(),
                 tools: vec!["ember".to_string()],
@@ -1314,6 +1562,7 @@ See `volta help install` for details and more options.";
             let packages = [Package::Fetched(PackageDetails {
                 name: "ember-cli".to_string(),
                 version: Version::from((3, 10, 1)),
+                size: None,
             })];
 
             assert_eq!(display_tool("ember", &packages), expected);
@@ -1334,10 +1583,12 @@ See `volta help install` for details and more options.";
                 Package::Fetched(PackageDetails {
                     name: "ember-cli".to_string(),
                     version: Version::from((3, 10, 1)),
+                    size: None,
                 }),
                 Package::Fetched(PackageDetails {
                     name: "ember-cli".to_string(),
                     version: Version::from((3, 8, 2)),
+                    size: None,
                 }),
             ];
 
@@ -1361,6 +1612,7 @@ See `volta help install` for details and more options.";
                     details: PackageDetails {
                         name: "ember-cli".to_string(),
                         version: Version::from((3, 10, 1)),
+                        size: None,
                     },
                     node: NODE_12.clone(),
                     tools: vec!["ember".to_string()],
@@ -1414,14 +1666,17 @@ See `volta help install` for details and more options.";
                 Node {
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NODE_12.clone(),
+                    size: None,
                 },
                 Node {
                     source: Source::None,
                     version: NODE_11.clone(),
+                    size: None,
                 },
                 Node {
                     source: Source::Default,
                     version: NODE_10.clone(),
+                    size: None,
                 },
             ];
 
@@ -1430,16 +1685,19 @@ See `volta help install` for details and more options.";
                     kind: PackageManagerKind::Npm,
                     source: Source::Default,
                     version: NPM_VERSION.clone(),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: Version::from((6, 12, 0)),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::None,
                     version: Version::from((5, 6, 0)),
+                    size: None,
                 },
             ];
 
@@ -1472,14 +1730,17 @@ See `volta help install` for details and more options.";
                 Node {
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NODE_12.clone(),
+                    size: None,
                 },
                 Node {
                     source: Source::None,
                     version: NODE_11.clone(),
+                    size: None,
                 },
                 Node {
                     source: Source::Default,
                     version: NODE_10.clone(),
+                    size: None,
                 },
             ];
 
@@ -1488,16 +1749,19 @@ See `volta help install` for details and more options.";
                     kind: PackageManagerKind::Yarn,
                     source: Source::Default,
                     version: YARN_VERSION.clone(),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: Version::from((1, 17, 0)),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::None,
                     version: Version::from((1, 4, 0)),
+                    size: None,
                 },
             ];
 
@@ -1509,6 +1773,7 @@ See `volta help install` for details and more options.";
         }
 
         #[test]
+        #[allow(clippy::too_many_lines)]
         fn full() {
             let expected = "⚡️ User toolchain:
 
@@ -1547,14 +1812,17 @@ See `volta help install` for details and more options.";
                 Node {
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NODE_12.clone(),
+                    size: None,
                 },
                 Node {
                     source: Source::None,
                     version: NODE_11.clone(),
+                    size: None,
                 },
                 Node {
                     source: Source::Default,
                     version: NODE_10.clone(),
+                    size: None,
                 },
             ];
 
@@ -1563,31 +1831,37 @@ See `volta help install` for details and more options.";
                     kind: PackageManagerKind::Npm,
                     source: Source::Default,
                     version: NPM_VERSION.clone(),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: Version::from((6, 12, 0)),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Npm,
                     source: Source::None,
                     version: Version::from((5, 6, 0)),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Default,
                     version: YARN_VERSION.clone(),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: Version::from((1, 17, 0)),
+                    size: None,
                 },
                 PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::None,
                     version: Version::from((1, 4, 0)),
+                    size: None,
                 },
             ];
 
@@ -1596,6 +1870,7 @@ See `volta help install` for details and more options.";
                     details: PackageDetails {
                         name: "typescript".to_string(),
                         version: Version::from((3, 4, 3)),
+                        size: None,
                     },
                     node: NODE_12.clone(),
                     tools: vec!["tsc".to_string(), "tsserver".to_string()],
@@ -1614,6 +1889,7 @@ See `volta help install` for details and more options.";
                     details: PackageDetails {
                         name: "ember-cli".to_string(),
                         version: Version::from((3, 8, 2)),
+                        size: None,
                     },
                     node: NODE_12.clone(),
                     tools: vec!["ember".to_string()],
@@ -1625,4 +1901,194 @@ See `volta help install` for details and more options.";
             );
         }
     }
+
+    mod sizes {
+        use super::*;
+        use crate::command::list::{PackageDetails, PackageManagerKind, Source};
+
+        #[test]
+        fn all_with_total() {
+            let expected = "⚡️ User toolchain:
+
+    Node runtimes:
+        v12.2.0 (default), 40.00 MiB
+        v11.9.0, 38.50 MiB
+
+    Package managers:
+        Yarn:
+            v1.16.0 (default), 5.00 MiB
+
+    Packages:
+        typescript@3.4.1 (default), 20.00 MiB
+            binary tools: tsc
+            platform:
+                runtime: node@12.2.0
+                package manager: npm@built-in
+
+Total disk usage: 103.50 MiB";
+
+            let toolchain = Toolchain::All {
+                runtimes: vec![
+                    Node {
+                        source: Source::Default,
+                        version: NODE_12.clone(),
+                        size: Some(40 * 1024 * 1024),
+                    },
+                    Node {
+                        source: Source::None,
+                        version: NODE_11.clone(),
+                        size: Some(38 * 1024 * 1024 + 512 * 1024),
+                    },
+                ],
+                package_managers: vec![PackageManager {
+                    kind: PackageManagerKind::Yarn,
+                    source: Source::Default,
+                    version: YARN_VERSION.clone(),
+                    size: Some(5 * 1024 * 1024),
+                }],
+                packages: vec![Package::Default {
+                    details: PackageDetails {
+                        name: "typescript".into(),
+                        version: Version::from((3, 4, 1)),
+                        size: Some(20 * 1024 * 1024),
+                    },
+                    node: NODE_12.clone(),
+                    tools: vec!["tsc".into()],
+                }],
+            };
+
+            assert_eq!(format(&toolchain).unwrap(), expected);
+        }
+    }
+
+    mod outdated {
+        use super::*;
+        use crate::command::list::Outdated;
+        use volta_core::tool::VersionCheck;
+
+        fn check(current: &str, wanted: &str, latest: &str, lts: Option<&str>) -> VersionCheck {
+            VersionCheck {
+                current: Version::parse(current).unwrap(),
+                wanted: Version::parse(wanted).unwrap(),
+                latest: Version::parse(latest).unwrap(),
+                lts: lts.map(|lts| Version::parse(lts).unwrap()),
+            }
+        }
+
+        #[test]
+        fn up_to_date() {
+            assert_eq!(
+                display_outdated(&[]),
+                "⚡️ Your default tools and packages are up to date."
+            );
+        }
+
+        #[test]
+        fn table_with_lts() {
+            let expected = "⚡️ Newer versions are available:
+
+    Tool          Current    Wanted     Latest    LTS
+    node          18.20.4    18.20.8    24.1.0    22.16.0
+    yarn          1.22.19    1.22.22    4.9.1
+    typescript    5.5.4      5.9.2      5.9.2";
+
+            let outdated = [
+                Outdated::Runtime(check("18.20.4", "18.20.8", "24.1.0", Some("22.16.0"))),
+                Outdated::PackageManager {
+                    kind: PackageManagerKind::Yarn,
+                    check: check("1.22.19", "1.22.22", "4.9.1", None),
+                },
+                Outdated::Package {
+                    name: "typescript".into(),
+                    check: check("5.5.4", "5.9.2", "5.9.2", None),
+                },
+            ];
+
+            assert_eq!(display_outdated(&outdated), expected);
+        }
+
+        #[test]
+        fn table_without_lts() {
+            let expected = "⚡️ Newer versions are available:
+
+    Tool          Current    Wanted    Latest
+    typescript    5.5.4      5.5.4     6.0.1";
+
+            let outdated = [Outdated::Package {
+                name: "typescript".into(),
+                check: check("5.5.4", "5.5.4", "6.0.1", None),
+            }];
+
+            assert_eq!(display_outdated(&outdated), expected);
+        }
+    }
+
+    mod last_used {
+        use std::time::{Duration, SystemTime};
+
+        use super::*;
+        use crate::command::list::{LastUsed, PackageManagerKind, Source};
+
+        #[test]
+        fn empty() {
+            assert_eq!(
+                display_last_used(&[], &[], &[]),
+                "⚡️ No tool versions or projects found."
+            );
+        }
+
+        #[test]
+        fn runtimes_package_managers_and_projects() {
+            let expected = "⚡️ When tools and projects were last used:
+
+    Node runtimes:
+        v12.2.0 (default), last used 2 days ago
+        v11.9.0, never used
+
+    Package managers:
+        Yarn:
+            v1.16.0 (current @ ~/path/to/project.json), last used 3 hours ago
+
+    Projects:
+        ~/path/to, last used 3 hours ago";
+
+            let now = SystemTime::now();
+            let runtimes = [
+                LastUsed {
+                    item: Node {
+                        source: Source::Default,
+                        version: NODE_12.clone(),
+                        size: None,
+                    },
+                    last_used: Some(now - Duration::from_hours(48)),
+                },
+                LastUsed {
+                    item: Node {
+                        source: Source::None,
+                        version: NODE_11.clone(),
+                        size: None,
+                    },
+                    last_used: None,
+                },
+            ];
+            let package_managers = [LastUsed {
+                item: PackageManager {
+                    kind: PackageManagerKind::Yarn,
+                    source: Source::Project(PROJECT_PATH.clone()),
+                    version: YARN_VERSION.clone(),
+                    size: None,
+                },
+                last_used: Some(now - Duration::from_hours(3)),
+            }];
+            let projects = [LastUsed {
+                item: PathBuf::from("~/path/to"),
+                last_used: Some(now - Duration::from_hours(3)),
+            }];
+
+            assert_eq!(
+                display_last_used(&runtimes, &package_managers, &projects),
+                expected
+            );
+        }
+    }
 }
@@ -0,0 +1,516 @@
+//! Define the "json" format style for list commands.
+//!
+//! The output is a single JSON object, meant to be consumed by other tools.
+//! Its shape is versioned by `schemaVersion`, which will be incremented for
+//! any change that is not purely additive:
+//!
+//! ```json
+//! {
+//!   "schemaVersion": 1,
+//!   "runtimes": [
+//!     {
+//!       "name": "node",
+//!       "version": "18.20.4",
+//!       "source": { "type": "default" }
+//!     }
+//!   ],
+//!   "packageManagers": [
+//!     {
+//!       "name": "yarn",
+//!       "version": "1.22.22",
+//!       "source": { "type": "project", "manifest": "/path/to/package.json" }
+//!     }
+//!   ],
+//!   "packages": [
+//!     {
+//!       "name": "typescript",
+//!       "version": "5.5.4",
+//!       "source": { "type": "default" },
+//!       "bins": ["tsc", "tsserver"],
+//!       "platform": { "node": "18.20.4", "npm": "built-in" }
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! - `source.type` is `project` (with the path to the project `manifest`, or
+//!   to the fallback file such as `.nvmrc` which sets the Node version),
+//!   `default`, or `fetched` for items which are available but not in use.
+//! - Prerelease builds of Node have a `channel` of `nightly`, `rc` or
+//!   `v8-canary`, which is omitted for releases.
+//! - Packages from a project have a `version` and `platform` of `null`, since
+//!   those are determined by the project itself.
+//! - With `--size`, each runtime, package manager and installed package has a
+//!   `size` in bytes, and the object has a `totalSize`.
+//! - With `--last-used`, each runtime and package manager has a `lastUsed`
+//!   RFC 3339 timestamp (or `null` if it was never used), `packages` is empty,
+//!   and the object has a `projects` list of `{ "root", "lastUsed" }` entries.
+//! - With `--outdated`, the runtime, package manager and package lists are
+//!   empty, and the object has an `outdated` list of entries with a `name`, a
+//!   `type` (`runtime`, `packageManager` or `package`), and the `current`,
+//!   `wanted` (newest in the same major version) and `latest` versions, plus
+//!   the `lts` version for Node.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::Serialize;
+use volta_core::style::timestamp;
+
+use super::{
+    LastUsed, Node, Outdated, Package, PackageManager, Source, Toolchain, prerelease_channel,
+};
+use volta_core::tool::node::NodeChannel;
+
+/// The version of the JSON output schema
+const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Output<'a> {
+    schema_version: u32,
+    runtimes: Vec<Tool<'a>>,
+    package_managers: Vec<Tool<'a>>,
+    packages: Vec<PackageEntry<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    projects: Option<Vec<ProjectEntry<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    outdated: Option<Vec<OutdatedEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_size: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum SourceEntry<'a> {
+    Project { manifest: &'a Path },
+    Default,
+    Fetched,
+}
+
+impl<'a> From<&'a Source> for SourceEntry<'a> {
+    fn from(source: &'a Source) -> Self {
+        match source {
+            Source::Project(manifest) => Self::Project { manifest },
+            Source::Default => Self::Default,
+            Source::None => Self::Fetched,
+        }
+    }
+}
+
+/// A runtime or package manager
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Tool<'a> {
+    name: String,
+    version: String,
+    source: SourceEntry<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_used: Option<LastUsedEntry>,
+}
+
+/// When a runtime or package manager was last used, which is `null` if it
+/// has never been used
+#[derive(Serialize)]
+#[serde(untagged)]
+enum LastUsedEntry {
+    Never,
+    At(String),
+}
+
+impl<'a> Tool<'a> {
+    fn runtime(runtime: &'a Node) -> Self {
+        Tool {
+            name: String::from("node"),
+            version: runtime.version.to_string(),
+            source: (&runtime.source).into(),
+            channel: prerelease_channel(&runtime.version).map(NodeChannel::name),
+            size: runtime.size,
+            last_used: None,
+        }
+    }
+
+    fn package_manager(manager: &'a PackageManager) -> Self {
+        Tool {
+            name: manager.kind.to_string(),
+            version: manager.version.to_string(),
+            source: (&manager.source).into(),
+            channel: None,
+            size: manager.size,
+            last_used: None,
+        }
+    }
+
+    fn with_last_used(self, last_used: Option<SystemTime>) -> Self {
+        Tool {
+            last_used: Some(last_used.map_or(LastUsedEntry::Never, |time| {
+                LastUsedEntry::At(timestamp(time))
+            })),
+            ..self
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PlatformEntry {
+    node: String,
+    npm: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PackageEntry<'a> {
+    name: &'a str,
+    version: Option<String>,
+    source: SourceEntry<'a>,
+    bins: &'a [String],
+    platform: Option<PlatformEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+}
+
+impl<'a> From<&'a Package> for PackageEntry<'a> {
+    fn from(package: &'a Package) -> Self {
+        match package {
+            Package::Default {
+                details,
+                node,
+                tools,
+            } => PackageEntry {
+                name: &details.name,
+                version: Some(details.version.to_string()),
+                source: SourceEntry::Default,
+                bins: tools,
+                platform: Some(PlatformEntry {
+                    node: node.to_string(),
+                    // Should be updated when we support installing with custom package_managers,
+                    // whether Yarn or non-built-in versions of npm
+                    npm: "built-in",
+                }),
+                size: details.size,
+            },
+            Package::Project { name, tools, path } => PackageEntry {
+                name,
+                version: None,
+                source: SourceEntry::Project { manifest: path },
+                bins: tools,
+                platform: None,
+                size: None,
+            },
+            Package::Fetched(details) => PackageEntry {
+                name: &details.name,
+                version: Some(details.version.to_string()),
+                source: SourceEntry::Fetched,
+                bins: &[],
+                platform: None,
+                size: details.size,
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectEntry<'a> {
+    root: &'a Path,
+    last_used: Option<String>,
+}
+
+impl<'a> From<&'a LastUsed<PathBuf>> for ProjectEntry<'a> {
+    fn from(project: &'a LastUsed<PathBuf>) -> Self {
+        ProjectEntry {
+            root: &project.item,
+            last_used: project.last_used.map(timestamp),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OutdatedEntry {
+    name: String,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    current: String,
+    wanted: String,
+    latest: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lts: Option<String>,
+}
+
+impl From<&Outdated> for OutdatedEntry {
+    fn from(item: &Outdated) -> Self {
+        let check = item.check();
+        Self {
+            name: item.name(),
+            kind: match item {
+                Outdated::Runtime(_) => "runtime",
+                Outdated::PackageManager { .. } => "packageManager",
+                Outdated::Package { .. } => "package",
+            },
+            current: check.current.to_string(),
+            wanted: check.wanted.to_string(),
+            latest: check.latest.to_string(),
+            lts: check.lts.as_ref().map(ToString::to_string),
+        }
+    }
+}
+
+#[allow(clippy::unnecessary_wraps)] // Needs to match the API of `plain::format`
+pub(super) fn format(toolchain: &Toolchain) -> Option<String> {
+    let mut output = Output {
+        schema_version: SCHEMA_VERSION,
+        runtimes: Vec::new(),
+        package_managers: Vec::new(),
+        packages: Vec::new(),
+        projects: None,
+        outdated: None,
+        total_size: toolchain.total_size(),
+    };
+
+    match toolchain {
+        Toolchain::Node(runtimes) => {
+            output.runtimes = runtimes.iter().map(Tool::runtime).collect();
+        }
+        Toolchain::PackageManagers { managers, .. } => {
+            output.package_managers = managers.iter().map(Tool::package_manager).collect();
+        }
+        Toolchain::Packages(packages)
+        | Toolchain::Tool {
+            host_packages: packages,
+            ..
+        } => {
+            output.packages = packages.iter().map(PackageEntry::from).collect();
+        }
+        Toolchain::Active {
+            runtime,
+            package_managers,
+            packages,
+        } => {
+            output.runtimes = runtime
+                .iter()
+                .map(|runtime| Tool::runtime(runtime))
+                .collect();
+            output.package_managers = package_managers.iter().map(Tool::package_manager).collect();
+            output.packages = packages.iter().map(PackageEntry::from).collect();
+        }
+        Toolchain::All {
+            runtimes,
+            package_managers,
+            packages,
+        } => {
+            output.runtimes = runtimes.iter().map(Tool::runtime).collect();
+            output.package_managers = package_managers.iter().map(Tool::package_manager).collect();
+            output.packages = packages.iter().map(PackageEntry::from).collect();
+        }
+        Toolchain::LastUsed {
+            runtimes,
+            package_managers,
+            projects,
+        } => {
+            output.runtimes = runtimes
+                .iter()
+                .map(|runtime| Tool::runtime(&runtime.item).with_last_used(runtime.last_used))
+                .collect();
+            output.package_managers = package_managers
+                .iter()
+                .map(|manager| {
+                    Tool::package_manager(&manager.item).with_last_used(manager.last_used)
+                })
+                .collect();
+            output.projects = Some(projects.iter().map(ProjectEntry::from).collect());
+        }
+        Toolchain::Outdated(outdated) => {
+            output.outdated = Some(outdated.iter().map(OutdatedEntry::from).collect());
+        }
+    }
+
+    Some(
+        serde_json::to_string_pretty(&output)
+            .expect("serializing the list output to JSON cannot fail"),
+    )
+}
+
+// Snapshots of the output are kept in `snapshots/`, and double as
+// examples of the schema.
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::LazyLock;
+    use std::time::{Duration, SystemTime};
+
+    use insta::assert_snapshot;
+    use nodejs_semver::Version;
+
+    use super::*;
+    use crate::command::list::{PackageDetails, PackageManagerKind};
+
+    static NODE_VERSION: LazyLock<Version> = LazyLock::new(|| Version::from((12, 4, 0)));
+    static NPM_VERSION: LazyLock<Version> = LazyLock::new(|| Version::from((6, 13, 4)));
+    static YARN_VERSION: LazyLock<Version> = LazyLock::new(|| Version::from((1, 16, 0)));
+    static PROJECT_PATH: LazyLock<PathBuf> = LazyLock::new(|| PathBuf::from("/a/b/c/package.json"));
+
+    fn format(toolchain: &Toolchain) -> String {
+        super::format(toolchain).expect("`format` always returns `Some`")
+    }
+
+    #[test]
+    fn empty() {
+        let toolchain = Toolchain::Node(vec![]);
+        assert_snapshot!(format(&toolchain));
+    }
+
+    #[test]
+    fn all() {
+        let toolchain = Toolchain::All {
+            runtimes: vec![
+                Node {
+                    source: Source::Default,
+                    version: NODE_VERSION.clone(),
+                    size: None,
+                },
+                Node {
+                    source: Source::None,
+                    version: Version::from((8, 2, 4)),
+                    size: None,
+                },
+            ],
+            package_managers: vec![
+                PackageManager {
+                    kind: PackageManagerKind::Npm,
+                    source: Source::Default,
+                    version: NPM_VERSION.clone(),
+                    size: None,
+                },
+                PackageManager {
+                    kind: PackageManagerKind::Yarn,
+                    source: Source::Project(PROJECT_PATH.clone()),
+                    version: YARN_VERSION.clone(),
+                    size: None,
+                },
+            ],
+            packages: vec![
+                Package::Default {
+                    details: PackageDetails {
+                        name: "typescript".into(),
+                        version: Version::from((3, 4, 1)),
+                        size: None,
+                    },
+                    node: NODE_VERSION.clone(),
+                    tools: vec!["tsc".into(), "tsserver".into()],
+                },
+                Package::Project {
+                    name: "ember-cli".into(),
+                    tools: vec!["ember".into()],
+                    path: PROJECT_PATH.clone(),
+                },
+                Package::Fetched(PackageDetails {
+                    name: "cowsay".into(),
+                    version: Version::from((1, 4, 0)),
+                    size: None,
+                }),
+            ],
+        };
+
+        assert_snapshot!(format(&toolchain));
+    }
+
+    #[test]
+    fn active_with_sizes() {
+        let toolchain = Toolchain::Active {
+            runtime: Some(Box::new(Node {
+                source: Source::Project(PROJECT_PATH.clone()),
+                version: NODE_VERSION.clone(),
+                size: Some(41_943_040),
+            })),
+            package_managers: vec![PackageManager {
+                kind: PackageManagerKind::Yarn,
+                source: Source::Default,
+                version: YARN_VERSION.clone(),
+                size: Some(5_242_880),
+            }],
+            packages: vec![Package::Default {
+                details: PackageDetails {
+                    name: "typescript".into(),
+                    version: Version::from((3, 4, 1)),
+                    size: Some(20_971_520),
+                },
+                node: NODE_VERSION.clone(),
+                tools: vec!["tsc".into()],
+            }],
+        };
+
+        assert_snapshot!(format(&toolchain));
+    }
+
+    #[test]
+    fn last_used() {
+        let used_at = SystemTime::UNIX_EPOCH + Duration::from_mins(28_674_030);
+
+        let toolchain = Toolchain::LastUsed {
+            runtimes: vec![
+                LastUsed {
+                    item: Node {
+                        source: Source::Default,
+                        version: NODE_VERSION.clone(),
+                        size: None,
+                    },
+                    last_used: Some(used_at),
+                },
+                LastUsed {
+                    item: Node {
+                        source: Source::None,
+                        version: Version::from((8, 2, 4)),
+                        size: None,
+                    },
+                    last_used: None,
+                },
+            ],
+            package_managers: vec![LastUsed {
+                item: PackageManager {
+                    kind: PackageManagerKind::Npm,
+                    source: Source::None,
+                    version: NPM_VERSION.clone(),
+                    size: None,
+                },
+                last_used: Some(used_at),
+            }],
+            projects: vec![LastUsed {
+                item: PathBuf::from("/a/b/c"),
+                last_used: Some(used_at),
+            }],
+        };
+
+        assert_snapshot!(format(&toolchain));
+    }
+
+    #[test]
+    fn outdated() {
+        let check = |current: &str, wanted: &str, latest: &str, lts: Option<&str>| {
+            volta_core::tool::VersionCheck {
+                current: Version::parse(current).unwrap(),
+                wanted: Version::parse(wanted).unwrap(),
+                latest: Version::parse(latest).unwrap(),
+                lts: lts.map(|lts| Version::parse(lts).unwrap()),
+            }
+        };
+
+        let toolchain = Toolchain::Outdated(vec![
+            Outdated::Runtime(check("18.20.4", "18.20.8", "24.1.0", Some("22.16.0"))),
+            Outdated::PackageManager {
+                kind: PackageManagerKind::Yarn,
+                check: check("1.22.19", "1.22.22", "1.22.22", None),
+            },
+            Outdated::Package {
+                name: "typescript".into(),
+                check: check("5.5.4", "5.9.2", "5.9.2", None),
+            },
+        ]);
+
+        assert_snapshot!(format(&toolchain));
+    }
+}
@@ -1,8 +1,10 @@
 mod human;
+mod json;
 mod plain;
 mod toolchain;
 
 use std::io::IsTerminal as _;
+use std::time::SystemTime;
 use std::{fmt, path::PathBuf, str::FromStr};
 
 use nodejs_semver::Version;
@@ -13,12 +15,15 @@ use volta_core::error::{ExitCode, Fallible};
 use volta_core::inventory::package_configs;
 use volta_core::project::Project;
 use volta_core::session::{ActivityKind, Session};
-use volta_core::tool::PackageConfig;
+use volta_core::tool::node::NodeChannel;
+use volta_core::tool::{PackageConfig, VersionCheck};
 
 #[derive(clap::ValueEnum, Copy, Clone)]
 enum Format {
     Human,
     Plain,
+    /// A JSON object with a versioned schema, for use by other tools
+    Json,
 }
 
 /// The source of a given item, from the perspective of a user.
@@ -29,7 +34,8 @@ enum Format {
 #[derive(Clone, PartialEq, Debug)]
 enum Source {
     /// The item is from a project. The wrapped `PathBuf` is the path to the
-    /// project's `package.json`.
+    /// project's `package.json`, or to the fallback file (such as `.nvmrc`)
+    /// which sets its Node version.
     Project(PathBuf),
 
     /// The item is the user's default.
@@ -70,6 +76,8 @@ struct PackageDetails {
     pub name: String,
     /// The package's own version.
     pub version: Version,
+    /// The disk space used by the package image, if requested.
+    pub size: Option<u64>,
 }
 
 enum Package {
@@ -94,6 +102,7 @@ impl Package {
         let details = PackageDetails {
             name: config.name.clone(),
             version: config.version.clone(),
+            size: None,
         };
 
         match source {
@@ -123,6 +132,14 @@ impl Package {
         })
     }
 
+    /// The disk space used by the package, if it was determined.
+    const fn size(&self) -> Option<u64> {
+        match self {
+            Self::Default { details, .. } | Self::Fetched(details) => details.size,
+            Self::Project { .. } => None,
+        }
+    }
+
     fn source(name: &str, project: Option<&Project>) -> Source {
         match project {
             Some(project) if project.has_direct_dependency(name) => {
@@ -137,6 +154,16 @@ impl Package {
 struct Node {
     pub source: Source,
     pub version: Version,
+    /// The disk space used by the image and archive, if requested.
+    pub size: Option<u64>,
+}
+
+/// The prerelease channel a version of Node was published in, if it isn't a release
+fn prerelease_channel(version: &Version) -> Option<NodeChannel> {
+    match NodeChannel::of_version(version) {
+        NodeChannel::Release => None,
+        channel => Some(channel),
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -165,6 +192,47 @@ struct PackageManager {
     kind: PackageManagerKind,
     source: Source,
     version: Version,
+    /// The disk space used by the image and archive, if requested.
+    size: Option<u64>,
+}
+
+/// An item in the toolchain, along with the last time it was used, if that
+/// has been recorded.
+struct LastUsed<T> {
+    item: T,
+    last_used: Option<SystemTime>,
+}
+
+/// A tool or package in the default toolchain which has newer versions
+/// available.
+enum Outdated {
+    Runtime(VersionCheck),
+    PackageManager {
+        kind: PackageManagerKind,
+        check: VersionCheck,
+    },
+    Package {
+        name: String,
+        check: VersionCheck,
+    },
+}
+
+impl Outdated {
+    fn name(&self) -> String {
+        match self {
+            Self::Runtime(_) => String::from("node"),
+            Self::PackageManager { kind, .. } => kind.to_string(),
+            Self::Package { name, .. } => name.clone(),
+        }
+    }
+
+    const fn check(&self) -> &VersionCheck {
+        match self {
+            Self::Runtime(check)
+            | Self::PackageManager { check, .. }
+            | Self::Package { check, .. } => check,
+        }
+    }
 }
 
 /// How (if at all) should the list query be narrowed?
@@ -186,6 +254,7 @@ enum Filter {
 }
 
 #[derive(clap::Args)]
+#[allow(clippy::struct_excessive_bools)] // CLI flags are naturally bools
 pub struct List {
     /// The tool to lookup - `all`, `node`, `npm`, `yarn`, `pnpm`, or the name
     /// of a package or binary.
@@ -207,6 +276,29 @@ pub struct List {
     /// Show your default tool(s).
     #[arg(short, long, conflicts_with = "current")]
     default: bool,
+
+    /// Show when each Node and package manager version, and each project, was
+    /// last used.
+    ///
+    /// Shows every version in the toolchain when not specifying a specific tool.
+    #[arg(long)]
+    last_used: bool,
+
+    /// Show the disk space used by each tool version and package, and in
+    /// total.
+    #[arg(long)]
+    size: bool,
+
+    /// Show the default tool(s) and packages with newer versions available.
+    ///
+    /// Compares each version with the newest one in the same major version,
+    /// and with the latest (and for Node, LTS) version.
+    #[arg(long, conflicts_with_all = ["current", "last_used", "size"])]
+    outdated: bool,
+
+    /// Exit with a non-zero code if any tool is outdated, for use in CI.
+    #[arg(long, requires = "outdated")]
+    exit_code: bool,
 }
 
 /// Which tool should we look up?
@@ -266,11 +358,10 @@ impl Command for List {
     fn run(self, session: &mut Session) -> Fallible<ExitCode> {
         session.add_event_start(ActivityKind::List);
 
-        let project = session.project()?;
-        let default_platform = session.default_platform()?;
         let format = match self.output_format() {
             Format::Human => human::format,
             Format::Plain => plain::format,
+            Format::Json => json::format,
         };
 
         let filter = match (self.current, self.default) {
@@ -280,24 +371,56 @@ impl Command for List {
             _ => Filter::None,
         };
 
-        let toolchain = match self.subcommand {
-            // For no subcommand, show the user's current toolchain
-            None => Toolchain::active(project, default_platform)?,
-            Some(Subcommand::All) => Toolchain::all(project, default_platform)?,
-            Some(Subcommand::Node) => Toolchain::node(project, default_platform, &filter)?,
-            Some(Subcommand::Npm) => Toolchain::npm(project, default_platform, &filter)?,
-            Some(Subcommand::Pnpm) => Toolchain::pnpm(project, default_platform, &filter)?,
-            Some(Subcommand::Yarn) => Toolchain::yarn(project, default_platform, &filter)?,
-            Some(Subcommand::PackageOrTool { name }) => {
-                Toolchain::package_or_tool(&name, project, &filter)?
+        let toolchain = if self.outdated {
+            Toolchain::outdated(
+                self.subcommand.as_ref().unwrap_or(&Subcommand::All),
+                session,
+            )?
+        } else {
+            // Resolve the Node version from the fallback sources first, so a
+            // project which doesn't pin one still shows its current version
+            session.fallback_platform()?;
+            let project = session.project()?;
+            let default_platform = session.default_platform()?;
+
+            match self.subcommand {
+                _ if self.last_used => Toolchain::last_used(
+                    project,
+                    default_platform,
+                    self.subcommand.as_ref().unwrap_or(&Subcommand::All),
+                    &filter,
+                )?,
+                // For no subcommand, show the user's current toolchain
+                None => Toolchain::active(project, default_platform)?,
+                Some(Subcommand::All) => Toolchain::all(project, default_platform)?,
+                Some(Subcommand::Node) => Toolchain::node(project, default_platform, &filter)?,
+                Some(Subcommand::Npm) => Toolchain::npm(project, default_platform, &filter)?,
+                Some(Subcommand::Pnpm) => Toolchain::pnpm(project, default_platform, &filter)?,
+                Some(Subcommand::Yarn) => Toolchain::yarn(project, default_platform, &filter)?,
+                Some(Subcommand::PackageOrTool { name }) => {
+                    Toolchain::package_or_tool(&name, project, &filter)?
+                }
             }
         };
 
+        let toolchain = if self.size {
+            toolchain.with_sizes()?
+        } else {
+            toolchain
+        };
+
         if let Some(string) = format(&toolchain) {
             println!("{string}");
         }
 
-        session.add_event_end(ActivityKind::List, ExitCode::Success);
-        Ok(ExitCode::Success)
+        let exit_code = match toolchain {
+            Toolchain::Outdated(outdated) if self.exit_code && !outdated.is_empty() => {
+                ExitCode::Outdated
+            }
+            _ => ExitCode::Success,
+        };
+
+        session.add_event_end(ActivityKind::List, exit_code);
+        Ok(exit_code)
     }
 }
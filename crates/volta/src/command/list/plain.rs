@@ -1,10 +1,15 @@
 //! Define the "plain" format style for list commands.
 
+use std::path::PathBuf;
+use std::time::SystemTime;
+
 use nodejs_semver::Version;
 
-use volta_core::style::tool_version;
+use volta_core::style::{timestamp, tool_version};
 
-use super::{Node, Package, PackageManager, Source, Toolchain};
+use super::{
+    LastUsed, Node, Outdated, Package, PackageManager, Source, Toolchain, prerelease_channel,
+};
 
 pub(super) fn format(toolchain: &Toolchain) -> Option<String> {
     let (runtimes, package_managers, packages) = match toolchain {
@@ -37,9 +42,20 @@ pub(super) fn format(toolchain: &Toolchain) -> Option<String> {
             describe_package_managers(package_managers),
             describe_packages(packages),
         ),
+        Toolchain::LastUsed {
+            runtimes,
+            package_managers,
+            projects,
+        } => {
+            return with_total_size(
+                describe_last_used(runtimes, package_managers, projects),
+                toolchain,
+            );
+        }
+        Toolchain::Outdated(outdated) => return describe_outdated(outdated),
     };
 
-    match (runtimes, package_managers, packages) {
+    let output = match (runtimes, package_managers, packages) {
         (Some(runtimes), Some(package_managers), Some(packages)) => {
             Some(format!("{runtimes}\n{package_managers}\n{packages}"))
         }
@@ -54,6 +70,15 @@ pub(super) fn format(toolchain: &Toolchain) -> Option<String> {
         (None, Some(package_managers), None) => Some(package_managers),
         (None, None, Some(packages)) => Some(packages),
         (None, None, None) => None,
+    };
+
+    with_total_size(output, toolchain)
+}
+
+fn with_total_size(output: Option<String>, toolchain: &Toolchain) -> Option<String> {
+    match (output, toolchain.total_size()) {
+        (Some(output), Some(total)) => Some(format!("{output}\ntotal size {total}")),
+        (output, _) => output,
     }
 }
 
@@ -64,7 +89,13 @@ fn describe_runtimes(runtimes: &[Node]) -> Option<String> {
         Some(
             runtimes
                 .iter()
-                .map(|runtime| display_node(&runtime.source, &runtime.version))
+                .map(|runtime| {
+                    format!(
+                        "{}{}",
+                        display_node(&runtime.source, &runtime.version),
+                        display_size(runtime.size)
+                    )
+                })
                 .collect::<Vec<String>>()
                 .join("\n"),
         )
@@ -107,15 +138,101 @@ fn describe_tool_set(name: &str, hosts: &[Package]) -> String {
         .join("\n")
 }
 
+fn describe_last_used(
+    runtimes: &[LastUsed<Node>],
+    package_managers: &[LastUsed<PackageManager>],
+    projects: &[LastUsed<PathBuf>],
+) -> Option<String> {
+    let lines: Vec<String> = runtimes
+        .iter()
+        .map(|runtime| {
+            format!(
+                "{}{} last-used {}",
+                display_node(&runtime.item.source, &runtime.item.version),
+                display_size(runtime.item.size),
+                display_last_used(runtime.last_used)
+            )
+        })
+        .chain(package_managers.iter().map(|manager| {
+            format!(
+                "{} last-used {}",
+                display_package_manager(&manager.item),
+                display_last_used(manager.last_used)
+            )
+        }))
+        .chain(projects.iter().map(|project| {
+            format!(
+                "project {} last-used {}",
+                project.item.display(),
+                display_last_used(project.last_used)
+            )
+        }))
+        .collect();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+fn describe_outdated(outdated: &[Outdated]) -> Option<String> {
+    if outdated.is_empty() {
+        return None;
+    }
+
+    Some(
+        outdated
+            .iter()
+            .map(|item| {
+                let check = item.check();
+                let kind = match item {
+                    Outdated::Runtime(_) => "runtime",
+                    Outdated::PackageManager { .. } => "package-manager",
+                    Outdated::Package { .. } => "package",
+                };
+                let lts = check
+                    .lts
+                    .as_ref()
+                    .map(|lts| format!(" lts {lts}"))
+                    .unwrap_or_default();
+
+                format!(
+                    "{kind} {} wanted {} latest {}{lts}",
+                    tool_version(item.name(), &check.current),
+                    check.wanted,
+                    check.latest
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n"),
+    )
+}
+
+fn display_size(size: Option<u64>) -> String {
+    size.map_or_else(String::new, |size| format!(" size {size}"))
+}
+
+fn display_last_used(last_used: Option<SystemTime>) -> String {
+    last_used.map_or_else(|| String::from("never"), timestamp)
+}
+
 fn display_node(source: &Source, version: &Version) -> String {
-    format!("runtime {}{}", tool_version("node", version), source)
+    format!(
+        "runtime {}{}{}",
+        tool_version("node", version),
+        prerelease_channel(version)
+            .map_or_else(String::new, |channel| format!(" prerelease {channel}")),
+        source
+    )
 }
 
 fn display_package_manager(package_manager: &PackageManager) -> String {
     format!(
-        "package-manager {}{}",
+        "package-manager {}{}{}",
         tool_version(package_manager.kind, &package_manager.version),
-        package_manager.source
+        package_manager.source,
+        display_size(package_manager.size)
     )
 }
 
@@ -141,14 +258,15 @@ fn display_package(package: &Package) -> String {
             };
 
             format!(
-                "package {} /{}/ {} {}{}",
+                "package {} /{}/ {} {}{}{}",
                 tool_version(&details.name, &details.version),
                 tools,
                 tool_version("node", node),
                 // Should be updated when we support installing with custom package_managers,
                 // whether Yarn or non-built-in versions of npm
                 "npm@built-in",
-                package_source(package)
+                package_source(package),
+                display_size(details.size)
             )
         }
         Package::Project { name, tools, .. } => {
@@ -167,8 +285,9 @@ fn display_package(package: &Package) -> String {
             )
         }
         Package::Fetched(details) => format!(
-            "package {} (fetched)",
-            tool_version(&details.name, &details.version)
+            "package {} (fetched){}",
+            tool_version(&details.name, &details.version),
+            display_size(details.size)
         ),
     }
 }
@@ -176,12 +295,13 @@ fn display_package(package: &Package) -> String {
 fn display_tool(name: &str, host: &Package) -> Option<String> {
     match host {
         Package::Default { details, node, .. } => Some(format!(
-            "tool {} / {} / {} {}{}",
+            "tool {} / {} / {} {}{}{}",
             name,
             tool_version(&details.name, &details.version),
             tool_version("node", node),
             "npm@built-in",
-            package_source(host)
+            package_source(host),
+            display_size(details.size)
         )),
         Package::Project {
             name: host_name, ..
@@ -202,17 +322,17 @@ fn display_tool(name: &str, host: &Package) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
+    use std::sync::LazyLock;
 
     use nodejs_semver::Version;
-    use once_cell::sync::Lazy;
 
     use crate::command::list::PackageDetails;
 
-    static NODE_VERSION: Lazy<Version> = Lazy::new(|| Version::from((12, 4, 0)));
-    static TYPESCRIPT_VERSION: Lazy<Version> = Lazy::new(|| Version::from((3, 4, 1)));
-    static NPM_VERSION: Lazy<Version> = Lazy::new(|| Version::from((6, 13, 4)));
-    static YARN_VERSION: Lazy<Version> = Lazy::new(|| Version::from((1, 16, 0)));
-    static PROJECT_PATH: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("/a/b/c"));
+    static NODE_VERSION: LazyLock<Version> = LazyLock::new(|| Version::from((12, 4, 0)));
+    static TYPESCRIPT_VERSION: LazyLock<Version> = LazyLock::new(|| Version::from((3, 4, 1)));
+    static NPM_VERSION: LazyLock<Version> = LazyLock::new(|| Version::from((6, 13, 4)));
+    static YARN_VERSION: LazyLock<Version> = LazyLock::new(|| Version::from((1, 16, 0)));
+    static PROJECT_PATH: LazyLock<PathBuf> = LazyLock::new(|| PathBuf::from("/a/b/c"));
 
     mod node {
         use super::super::*;
@@ -244,6 +364,16 @@ mod tests {
                 "runtime node@12.4.0"
             );
         }
+
+        #[test]
+        fn prerelease() {
+            let source = Source::None;
+            let version = Version::parse("25.0.0-nightly20250601abcdef1234").unwrap();
+            assert_eq!(
+                display_node(&source, &version).as_str(),
+                "runtime node@25.0.0-nightly20250601abcdef1234 prerelease nightly"
+            );
+        }
     }
 
     mod npm {
@@ -258,6 +388,7 @@ mod tests {
                     kind: PackageManagerKind::Npm,
                     source: Source::Default,
                     version: NPM_VERSION.clone(),
+                    size: None,
                 })
                 .as_str(),
                 "package-manager npm@6.13.4 (default)"
@@ -271,6 +402,7 @@ mod tests {
                     kind: PackageManagerKind::Npm,
                     source: Source::Project(PROJECT_PATH.clone()),
                     version: NPM_VERSION.clone(),
+                    size: None,
                 })
                 .as_str(),
                 "package-manager npm@6.13.4 (current @ /a/b/c)"
@@ -284,6 +416,7 @@ mod tests {
                     kind: PackageManagerKind::Npm,
                     source: Source::None,
                     version: NPM_VERSION.clone(),
+                    size: None,
                 })
                 .as_str(),
                 "package-manager npm@6.13.4"
@@ -303,6 +436,7 @@ mod tests {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Default,
                     version: YARN_VERSION.clone(),
+                    size: None,
                 })
                 .as_str(),
                 "package-manager yarn@1.16.0 (default)"
@@ -315,7 +449,8 @@ mod tests {
                 display_package_manager(&PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::Project(PROJECT_PATH.clone()),
-                    version: YARN_VERSION.clone()
+                    version: YARN_VERSION.clone(),
+                    size: None,
                 })
                 .as_str(),
                 "package-manager yarn@1.16.0 (current @ /a/b/c)"
@@ -328,7 +463,8 @@ mod tests {
                 display_package_manager(&PackageManager {
                     kind: PackageManagerKind::Yarn,
                     source: Source::None,
-                    version: YARN_VERSION.clone()
+                    version: YARN_VERSION.clone(),
+                    size: None,
                 })
                 .as_str(),
                 "package-manager yarn@1.16.0"
@@ -347,6 +483,7 @@ mod tests {
                     details: PackageDetails {
                         name: "typescript".into(),
                         version: TYPESCRIPT_VERSION.clone(),
+                        size: None,
                     },
                     node: NODE_VERSION.clone(),
                     tools: vec!["tsc".into(), "tsserver".into()]
@@ -384,6 +521,7 @@ mod tests {
                         details: PackageDetails {
                             name: "ember-cli".into(),
                             version: Version::from((3, 10, 0)),
+                            size: None,
                         },
                         node: NODE_VERSION.clone(),
                         tools: vec!["ember".into()],
@@ -391,6 +529,7 @@ mod tests {
                     Package::Fetched(PackageDetails {
                         name: "create-react-app".into(),
                         version: Version::from((1, 0, 0)),
+                        size: None,
                     })
                 ])
                 .expect("Should always return a `String` if given a non-empty set")
@@ -407,6 +546,7 @@ mod tests {
                 describe_packages(&[Package::Fetched(PackageDetails {
                     name: "typescript".into(),
                     version: TYPESCRIPT_VERSION.clone(),
+                    size: None,
                 })])
                 .expect("Should always return a `String` if given a non-empty set")
                 .as_str(),
@@ -428,6 +568,7 @@ mod tests {
                         details: PackageDetails {
                             name: "typescript".into(),
                             version: TYPESCRIPT_VERSION.clone(),
+                            size: None,
                         },
                         node: NODE_VERSION.clone(),
                         tools: vec!["tsc".into(), "tsserver".into()],
@@ -463,7 +604,8 @@ mod tests {
                     "tsc",
                     &Package::Fetched(PackageDetails {
                         name: "typescript".into(),
-                        version: TYPESCRIPT_VERSION.clone()
+                        version: TYPESCRIPT_VERSION.clone(),
+                        size: None,
                     })
                 ),
                 None
@@ -475,6 +617,140 @@ mod tests {
         use super::super::*;
         use super::*;
         use crate::command::list::{Node, PackageManager, PackageManagerKind, Toolchain};
+        use std::time::Duration;
+
+        #[test]
+        fn sizes() {
+            assert_eq!(
+                format(&Toolchain::All {
+                    runtimes: vec![Node {
+                        source: Source::Default,
+                        version: NODE_VERSION.clone(),
+                        size: Some(1024),
+                    }],
+                    package_managers: vec![PackageManager {
+                        kind: PackageManagerKind::Yarn,
+                        source: Source::None,
+                        version: YARN_VERSION.clone(),
+                        size: Some(256),
+                    }],
+                    packages: vec![
+                        Package::Default {
+                            details: PackageDetails {
+                                name: "typescript".into(),
+                                version: TYPESCRIPT_VERSION.clone(),
+                                size: Some(512),
+                            },
+                            node: NODE_VERSION.clone(),
+                            tools: vec!["tsc".into()]
+                        },
+                        Package::Project {
+                            name: "ember-cli".into(),
+                            path: PROJECT_PATH.clone(),
+                            tools: vec!["ember".into()]
+                        }
+                    ]
+                })
+                .expect("`format` with a non-empty toolchain returns `Some`")
+                .as_str(),
+                "runtime node@12.4.0 (default) size 1024\n\
+                 package-manager yarn@1.16.0 size 256\n\
+                 package typescript@3.4.1 / tsc / node@12.4.0 npm@built-in (default) size 512\n\
+                 package ember-cli@project / ember / node@project npm@project (current @ /a/b/c)\n\
+                 total size 1792"
+            );
+        }
+
+        #[test]
+        fn last_used() {
+            let used_at = SystemTime::UNIX_EPOCH + Duration::from_mins(28_674_030);
+
+            assert_eq!(
+                format(&Toolchain::LastUsed {
+                    runtimes: vec![
+                        LastUsed {
+                            item: Node {
+                                source: Source::Default,
+                                version: NODE_VERSION.clone(),
+                                size: None,
+                            },
+                            last_used: Some(used_at)
+                        },
+                        LastUsed {
+                            item: Node {
+                                source: Source::None,
+                                version: Version::from((8, 2, 4)),
+                                size: None,
+                            },
+                            last_used: None
+                        }
+                    ],
+                    package_managers: vec![LastUsed {
+                        item: PackageManager {
+                            kind: PackageManagerKind::Yarn,
+                            source: Source::Project(PROJECT_PATH.clone()),
+                            version: YARN_VERSION.clone(),
+                            size: None,
+                        },
+                        last_used: Some(used_at)
+                    }],
+                    projects: vec![LastUsed {
+                        item: PROJECT_PATH.clone(),
+                        last_used: Some(used_at)
+                    }]
+                })
+                .expect("`format` with a non-empty toolchain returns `Some`")
+                .as_str(),
+                "runtime node@12.4.0 (default) last-used 2024-07-08T12:30:00Z\n\
+                 runtime node@8.2.4 last-used never\n\
+                 package-manager yarn@1.16.0 (current @ /a/b/c) last-used 2024-07-08T12:30:00Z\n\
+                 project /a/b/c last-used 2024-07-08T12:30:00Z"
+            );
+        }
+
+        #[test]
+        fn outdated() {
+            let check = |current: &str, wanted: &str, latest: &str, lts: Option<&str>| {
+                volta_core::tool::VersionCheck {
+                    current: Version::parse(current).unwrap(),
+                    wanted: Version::parse(wanted).unwrap(),
+                    latest: Version::parse(latest).unwrap(),
+                    lts: lts.map(|lts| Version::parse(lts).unwrap()),
+                }
+            };
+
+            assert_eq!(
+                format(&Toolchain::Outdated(vec![
+                    Outdated::Runtime(check("18.20.4", "18.20.8", "24.1.0", Some("22.16.0"))),
+                    Outdated::PackageManager {
+                        kind: PackageManagerKind::Npm,
+                        check: check("10.7.0", "10.9.2", "11.4.1", None)
+                    },
+                    Outdated::Package {
+                        name: "typescript".into(),
+                        check: check("5.5.4", "5.9.2", "5.9.2", None)
+                    }
+                ]))
+                .expect("`format` with outdated tools returns `Some`")
+                .as_str(),
+                "runtime node@18.20.4 wanted 18.20.8 latest 24.1.0 lts 22.16.0\n\
+                 package-manager npm@10.7.0 wanted 10.9.2 latest 11.4.1\n\
+                 package typescript@5.5.4 wanted 5.9.2 latest 5.9.2"
+            );
+            assert_eq!(format(&Toolchain::Outdated(vec![])), None);
+        }
+
+        #[test]
+        fn last_used_empty() {
+            assert_eq!(
+                format(&Toolchain::LastUsed {
+                    runtimes: vec![],
+                    package_managers: vec![],
+                    projects: vec![]
+                }),
+                None
+            );
+        }
 
         #[test]
         fn full() {
@@ -483,11 +759,13 @@ mod tests {
                     runtimes: vec![
                         Node {
                             source: Source::Default,
-                            version: NODE_VERSION.clone()
+                            version: NODE_VERSION.clone(),
+                            size: None,
                         },
                         Node {
                             source: Source::None,
-                            version: Version::from((8, 2, 4))
+                            version: Version::from((8, 2, 4)),
+                            size: None,
                         }
                     ],
                     package_managers: vec![
@@ -495,21 +773,25 @@ mod tests {
                             kind: PackageManagerKind::Npm,
                             source: Source::Project(PROJECT_PATH.clone()),
                             version: NPM_VERSION.clone(),
+                            size: None,
                         },
                         PackageManager {
                             kind: PackageManagerKind::Npm,
                             source: Source::Default,
-                            version: Version::from((5, 10, 0))
+                            version: Version::from((5, 10, 0)),
+                            size: None,
                         },
                         PackageManager {
                             kind: PackageManagerKind::Yarn,
                             source: Source::Project(PROJECT_PATH.clone()),
-                            version: YARN_VERSION.clone()
+                            version: YARN_VERSION.clone(),
+                            size: None,
                         },
                         PackageManager {
                             kind: PackageManagerKind::Yarn,
                             source: Source::Default,
-                            version: Version::from((1, 17, 0))
+                            version: Version::from((1, 17, 0)),
+                            size: None,
                         }
                     ],
                     packages: vec![
@@ -517,6 +799,7 @@ mod tests {
                             details: PackageDetails {
                                 name: "ember-cli".into(),
                                 version: Version::from((3, 10, 2)),
+                                size: None,
                             },
                             node: NODE_VERSION.clone(),
                             tools: vec!["ember".into()]
@@ -530,6 +813,7 @@ mod tests {
                             details: PackageDetails {
                                 name: "typescript".into(),
                                 version: TYPESCRIPT_VERSION.clone(),
+                                size: None,
                             },
                             node: NODE_VERSION.clone(),
                             tools: vec!["tsc".into(), "tsserver".into()]
@@ -547,7 +831,7 @@ mod tests {
                  package ember-cli@3.10.2 / ember / node@12.4.0 npm@built-in (default)\n\
                  package ember-cli@project / ember / node@project npm@project (current @ /a/b/c)\n\
                  package typescript@3.4.1 / tsc, tsserver / node@12.4.0 npm@built-in (default)"
-            )
+            );
         }
     }
 }
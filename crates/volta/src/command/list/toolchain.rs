@@ -1,13 +1,19 @@
-use super::{Filter, Node, Package, PackageManager, Source};
+use std::cmp::Reverse;
+use std::path::PathBuf;
+
+use super::{Filter, LastUsed, Node, Outdated, Package, PackageManager, Source, Subcommand};
 use crate::command::list::PackageManagerKind;
 use nodejs_semver::Version;
 use volta_core::error::Fallible;
 use volta_core::inventory::{
-    node_versions, npm_versions, package_configs, pnpm_versions, yarn_versions,
+    node_versions, npm_versions, package_configs, package_disk_usage, pnpm_versions,
+    tool_disk_usage, yarn_versions,
 };
 use volta_core::platform::PlatformSpec;
 use volta_core::project::Project;
-use volta_core::tool::PackageConfig;
+use volta_core::session::Session;
+use volta_core::tool::{ImageKind, PackageConfig, check_image, check_package};
+use volta_core::usage::Index;
 
 pub(super) enum Toolchain {
     Node(Vec<Node>),
@@ -30,9 +36,16 @@ pub(super) enum Toolchain {
         package_managers: Vec<PackageManager>,
         packages: Vec<Package>,
     },
+    LastUsed {
+        runtimes: Vec<LastUsed<Node>>,
+        package_managers: Vec<LastUsed<PackageManager>>,
+        projects: Vec<LastUsed<PathBuf>>,
+    },
+    Outdated(Vec<Outdated>),
 }
 
 /// Lightweight rule for which item to get the `Source` for.
+#[derive(Clone, Copy)]
 enum Lookup {
     /// Look up the Node runtime
     Runtime,
@@ -61,16 +74,15 @@ impl Lookup {
         version: &Version,
     ) -> Source {
         project
-            .and_then(|proj| {
-                proj.platform()
-                    .and_then(self.version_from_spec())
-                    .and_then(|project_version| {
-                        if &project_version == version {
-                            Some(Source::Project(proj.manifest_file().to_owned()))
-                        } else {
-                            None
-                        }
-                    })
+            .and_then(project_platform)
+            .and_then(|(file, platform)| {
+                self.version_from_spec()(platform).and_then(|project_version| {
+                    if &project_version == version {
+                        Some(Source::Project(file))
+                    } else {
+                        None
+                    }
+                })
             })
             .or_else(|| {
                 default_platform
@@ -93,10 +105,9 @@ impl Lookup {
         default: Option<&PlatformSpec>,
     ) -> Option<(Source, Version)> {
         project
-            .and_then(|proj| {
-                proj.platform()
-                    .and_then(self.version_from_spec())
-                    .map(|version| (Source::Project(proj.manifest_file().to_owned()), version))
+            .and_then(project_platform)
+            .and_then(|(file, platform)| {
+                self.version_from_spec()(platform).map(|version| (Source::Project(file), version))
             })
             .or_else(|| {
                 default
@@ -106,6 +117,56 @@ impl Lookup {
     }
 }
 
+/// The platform of a project, with the file it is from: either the project's
+/// `package.json` or the fallback file (such as `.nvmrc`) with its Node version.
+fn project_platform(project: &Project) -> Option<(PathBuf, &PlatformSpec)> {
+    project
+        .platform()
+        .map(|platform| (project.manifest_file().to_owned(), platform))
+        .or_else(|| {
+            project
+                .fallback()
+                .map(|fallback| (fallback.file.clone(), &fallback.platform))
+        })
+}
+
+/// Fill in the disk space used by each of the given Node versions.
+fn measure_runtimes<'a>(runtimes: impl IntoIterator<Item = &'a mut Node>) -> Fallible<()> {
+    for runtime in runtimes {
+        runtime.size = Some(tool_disk_usage(ImageKind::Node, &runtime.version)?);
+    }
+    Ok(())
+}
+
+/// Fill in the disk space used by each of the given package manager versions.
+fn measure_package_managers<'a>(
+    managers: impl IntoIterator<Item = &'a mut PackageManager>,
+) -> Fallible<()> {
+    for manager in managers {
+        let kind = match manager.kind {
+            PackageManagerKind::Npm => ImageKind::Npm,
+            PackageManagerKind::Pnpm => ImageKind::Pnpm,
+            PackageManagerKind::Yarn => ImageKind::Yarn,
+        };
+        manager.size = Some(tool_disk_usage(kind, &manager.version)?);
+    }
+    Ok(())
+}
+
+/// Fill in the disk space used by each of the given packages. Packages from
+/// a project aren't installed by Volta, so they have no size.
+fn measure_packages(packages: &mut [Package]) -> Fallible<()> {
+    for package in packages {
+        match package {
+            Package::Default { details, .. } | Package::Fetched(details) => {
+                details.size = Some(package_disk_usage(&details.name, &details.version)?);
+            }
+            Package::Project { .. } => {}
+        }
+    }
+    Ok(())
+}
+
 /// Look up the `Source` for a tool with a given name.
 fn tool_source(name: &str, project: Option<&Project>) -> Fallible<Source> {
     match project {
@@ -121,13 +182,116 @@ fn tool_source(name: &str, project: Option<&Project>) -> Fallible<Source> {
 }
 
 impl Toolchain {
+    /// Determine the disk space used by each tool version and package in the
+    /// toolchain.
+    pub(super) fn with_sizes(mut self) -> Fallible<Self> {
+        match &mut self {
+            Self::Node(runtimes) => measure_runtimes(runtimes)?,
+            Self::PackageManagers { managers, .. } => measure_package_managers(managers)?,
+            Self::Packages(packages)
+            | Self::Tool {
+                host_packages: packages,
+                ..
+            } => measure_packages(packages)?,
+            Self::Active {
+                runtime,
+                package_managers,
+                packages,
+            } => {
+                measure_runtimes(runtime.as_deref_mut())?;
+                measure_package_managers(package_managers)?;
+                measure_packages(packages)?;
+            }
+            Self::All {
+                runtimes,
+                package_managers,
+                packages,
+            } => {
+                measure_runtimes(runtimes)?;
+                measure_package_managers(package_managers)?;
+                measure_packages(packages)?;
+            }
+            Self::LastUsed {
+                runtimes,
+                package_managers,
+                ..
+            } => {
+                measure_runtimes(runtimes.iter_mut().map(|runtime| &mut runtime.item))?;
+                measure_package_managers(
+                    package_managers.iter_mut().map(|manager| &mut manager.item),
+                )?;
+            }
+            // Sizes aren't shown for outdated tools, which `clap` enforces
+            Self::Outdated(_) => {}
+        }
+
+        Ok(self)
+    }
+
+    /// The total disk space used by the toolchain, if sizes were determined.
+    pub(super) fn total_size(&self) -> Option<u64> {
+        let sizes: Vec<Option<u64>> = match self {
+            Self::Node(runtimes) => runtimes.iter().map(|runtime| runtime.size).collect(),
+            Self::PackageManagers { managers, .. } => {
+                managers.iter().map(|manager| manager.size).collect()
+            }
+            Self::Packages(packages)
+            | Self::Tool {
+                host_packages: packages,
+                ..
+            } => packages.iter().map(Package::size).collect(),
+            Self::Active {
+                runtime,
+                package_managers,
+                packages,
+            } => runtime
+                .iter()
+                .map(|runtime| runtime.size)
+                .chain(package_managers.iter().map(|manager| manager.size))
+                .chain(packages.iter().map(Package::size))
+                .collect(),
+            Self::All {
+                runtimes,
+                package_managers,
+                packages,
+            } => runtimes
+                .iter()
+                .map(|runtime| runtime.size)
+                .chain(package_managers.iter().map(|manager| manager.size))
+                .chain(packages.iter().map(Package::size))
+                .collect(),
+            Self::LastUsed {
+                runtimes,
+                package_managers,
+                ..
+            } => runtimes
+                .iter()
+                .map(|runtime| runtime.item.size)
+                .chain(package_managers.iter().map(|manager| manager.item.size))
+                .collect(),
+            Self::Outdated(_) => Vec::new(),
+        };
+
+        sizes
+            .into_iter()
+            .flatten()
+            .reduce(|total, size| total + size)
+    }
+
     pub(super) fn active(
         project: Option<&Project>,
         default_platform: Option<&PlatformSpec>,
     ) -> Fallible<Self> {
-        let runtime = Lookup::Runtime
-            .active_tool(project, default_platform)
-            .map(|(source, version)| Box::new(Node { source, version }));
+        let runtime =
+            Lookup::Runtime
+                .active_tool(project, default_platform)
+                .map(|(source, version)| {
+                    Box::new(Node {
+                        source,
+                        version,
+                        size: None,
+                    })
+                });
 
         let package_managers =
             Lookup::Npm
@@ -136,6 +300,7 @@ impl Toolchain {
                     kind: PackageManagerKind::Npm,
                     source,
                     version,
+                    size: None,
                 })
                 .into_iter()
                 .chain(Lookup::Pnpm.active_tool(project, default_platform).map(
@@ -143,6 +308,7 @@ impl Toolchain {
                         kind: PackageManagerKind::Pnpm,
                         source,
                         version,
+                        size: None,
                     },
                 ))
                 .chain(Lookup::Yarn.active_tool(project, default_platform).map(
@@ -150,6 +316,7 @@ impl Toolchain {
                         kind: PackageManagerKind::Yarn,
                         source,
                         version,
+                        size: None,
                     },
                 ))
                 .collect();
@@ -172,6 +339,7 @@ impl Toolchain {
             .map(|version| Node {
                 source: Lookup::Runtime.version_source(project, default_platform, version),
                 version: version.clone(),
+                size: None,
             })
             .collect();
 
@@ -181,16 +349,19 @@ impl Toolchain {
                 kind: PackageManagerKind::Npm,
                 source: Lookup::Npm.version_source(project, default_platform, version),
                 version: version.clone(),
+                size: None,
             })
             .chain(pnpm_versions()?.iter().map(|version| PackageManager {
                 kind: PackageManagerKind::Pnpm,
                 source: Lookup::Pnpm.version_source(project, default_platform, version),
                 version: version.clone(),
+                size: None,
             }))
             .chain(yarn_versions()?.iter().map(|version| PackageManager {
                 kind: PackageManagerKind::Yarn,
                 source: Lookup::Yarn.version_source(project, default_platform, version),
                 version: version.clone(),
+                size: None,
             }))
             .collect();
 
@@ -203,6 +374,146 @@ impl Toolchain {
         })
     }
 
+    pub(super) fn last_used(
+        project: Option<&Project>,
+        default_platform: Option<&PlatformSpec>,
+        subcommand: &Subcommand,
+        filter: &Filter,
+    ) -> Fallible<Self> {
+        let index = Index::current()?;
+
+        let runtimes = if matches!(subcommand, Subcommand::All | Subcommand::Node) {
+            node_versions()?
+                .iter()
+                .filter_map(|version| {
+                    let source = Lookup::Runtime.version_source(project, default_platform, version);
+                    source.allowed_with(filter).then(|| LastUsed {
+                        item: Node {
+                            source,
+                            version: version.clone(),
+                            size: None,
+                        },
+                        last_used: index.tool_last_used("node", version),
+                    })
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let kinds: &[PackageManagerKind] = match subcommand {
+            Subcommand::All => &[
+                PackageManagerKind::Npm,
+                PackageManagerKind::Pnpm,
+                PackageManagerKind::Yarn,
+            ],
+            Subcommand::Npm => &[PackageManagerKind::Npm],
+            Subcommand::Pnpm => &[PackageManagerKind::Pnpm],
+            Subcommand::Yarn => &[PackageManagerKind::Yarn],
+            Subcommand::Node | Subcommand::PackageOrTool { .. } => &[],
+        };
+
+        let mut package_managers = Vec::new();
+        for &kind in kinds {
+            let (versions, lookup) = match kind {
+                PackageManagerKind::Npm => (npm_versions()?, Lookup::Npm),
+                PackageManagerKind::Pnpm => (pnpm_versions()?, Lookup::Pnpm),
+                PackageManagerKind::Yarn => (yarn_versions()?, Lookup::Yarn),
+            };
+
+            package_managers.extend(versions.iter().filter_map(|version| {
+                let source = lookup.version_source(project, default_platform, version);
+                source.allowed_with(filter).then(|| LastUsed {
+                    item: PackageManager {
+                        kind,
+                        source,
+                        version: version.clone(),
+                        size: None,
+                    },
+                    last_used: index.tool_last_used(&kind.to_string(), version),
+                })
+            }));
+        }
+
+        // Projects are only tracked by when they were used, so they are only
+        // shown when listing everything
+        let projects = if matches!((subcommand, filter), (Subcommand::All, Filter::None)) {
+            let mut projects: Vec<_> = index
+                .projects()?
+                .into_iter()
+                .map(|project| LastUsed {
+                    item: project.root,
+                    last_used: Some(project.last_used),
+                })
+                .collect();
+            projects.sort_by_key(|project| Reverse(project.last_used));
+            projects
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self::LastUsed {
+            runtimes,
+            package_managers,
+            projects,
+        })
+    }
+
+    /// Check the default tools and the installed packages for newer versions,
+    /// keeping only those which are outdated.
+    pub(super) fn outdated(subcommand: &Subcommand, session: &mut Session) -> Fallible<Self> {
+        let default_platform = session.default_platform()?.cloned();
+        let mut outdated = Vec::new();
+
+        if let Some(platform) = default_platform {
+            if matches!(subcommand, Subcommand::All | Subcommand::Node) {
+                let check = check_image(ImageKind::Node, &platform.node, session)?;
+                outdated.push(Outdated::Runtime(check));
+            }
+
+            let managers = [
+                (PackageManagerKind::Npm, ImageKind::Npm, &platform.npm),
+                (PackageManagerKind::Pnpm, ImageKind::Pnpm, &platform.pnpm),
+                (PackageManagerKind::Yarn, ImageKind::Yarn, &platform.yarn),
+            ];
+            for (kind, image, version) in managers {
+                let selected = match subcommand {
+                    Subcommand::All => true,
+                    Subcommand::Npm => kind == PackageManagerKind::Npm,
+                    Subcommand::Pnpm => kind == PackageManagerKind::Pnpm,
+                    Subcommand::Yarn => kind == PackageManagerKind::Yarn,
+                    Subcommand::Node | Subcommand::PackageOrTool { .. } => false,
+                };
+
+                if let Some(version) = version.as_ref().filter(|_| selected) {
+                    let check = check_image(image, version, session)?;
+                    outdated.push(Outdated::PackageManager { kind, check });
+                }
+            }
+        }
+
+        for config in package_configs()? {
+            let selected = match subcommand {
+                Subcommand::All => true,
+                Subcommand::PackageOrTool { name } => {
+                    &config.name == name || config.bins.contains(name)
+                }
+                _ => false,
+            };
+
+            if selected {
+                let check = check_package(&config, session)?;
+                outdated.push(Outdated::Package {
+                    name: config.name,
+                    check,
+                });
+            }
+        }
+
+        outdated.retain(|item| item.check().is_outdated());
+        Ok(Self::Outdated(outdated))
+    }
+
     pub(super) fn node(
         project: Option<&Project>,
         default_platform: Option<&PlatformSpec>,
@@ -214,7 +525,11 @@ impl Toolchain {
                 let source = Lookup::Runtime.version_source(project, default_platform, version);
                 if source.allowed_with(filter) {
                     let version = version.clone();
-                    Some(Node { source, version })
+                    Some(Node {
+                        source,
+                        version,
+                        size: None,
+                    })
                 } else {
                     None
                 }
@@ -238,6 +553,7 @@ impl Toolchain {
                         kind: PackageManagerKind::Npm,
                         source,
                         version: version.clone(),
+                        size: None,
                     })
                 } else {
                     None
@@ -265,6 +581,7 @@ impl Toolchain {
                         kind: PackageManagerKind::Pnpm,
                         source,
                         version: version.clone(),
+                        size: None,
                     })
                 } else {
                     None
@@ -292,6 +609,7 @@ impl Toolchain {
                         kind: PackageManagerKind::Yarn,
                         source,
                         version: version.clone(),
+                        size: None,
                     })
                 } else {
                     None
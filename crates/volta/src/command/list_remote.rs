@@ -0,0 +1,75 @@
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::tool::{RemoteVersion, ToolSpec, remote_versions};
+
+use log::warn;
+
+use crate::command::Command;
+
+#[derive(clap::Args)]
+pub struct ListRemote {
+    /// The tool to list available versions of, like `node`, `node@lts`, `yarn@^4` or
+    /// `typescript@5`
+    ///
+    /// Versions are listed from oldest to newest, and can be filtered by a semver range or a
+    /// tag. Versions already on this machine are marked as installed.
+    #[arg(value_name = "tool[@version]")]
+    tool: String,
+}
+
+impl Command for ListRemote {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::ListRemote);
+
+        let spec = ToolSpec::try_from_str(&self.tool)?;
+        let versions = remote_versions(&spec, session)?;
+
+        if versions.is_empty() {
+            warn!("No available versions found matching '{}'", self.tool);
+        }
+        for remote in &versions {
+            println!("{}", describe(remote));
+        }
+
+        session.add_event_end(ActivityKind::ListRemote, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
+
+fn describe(remote: &RemoteVersion) -> String {
+    let notes: Vec<String> = remote
+        .lts
+        .iter()
+        .map(|codename| format!("LTS: {codename}"))
+        .chain(remote.tags.iter().cloned())
+        .chain(remote.installed.then(|| "installed".to_owned()))
+        .collect();
+
+    if notes.is_empty() {
+        remote.version.to_string()
+    } else {
+        format!("{} ({})", remote.version, notes.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nodejs_semver::Version;
+
+    #[test]
+    fn describes_versions() {
+        let version = Version::parse("22.16.0").unwrap();
+
+        assert_eq!(describe(&RemoteVersion::new(version.clone())), "22.16.0");
+        assert_eq!(
+            describe(&RemoteVersion {
+                version,
+                lts: Some("Jod".into()),
+                tags: vec!["latest".into()],
+                installed: true,
+            }),
+            "22.16.0 (LTS: Jod, latest, installed)"
+        );
+    }
+}
@@ -2,12 +2,15 @@ pub mod completions;
 pub mod fetch;
 pub mod install;
 pub mod list;
+pub mod list_remote;
 pub mod pin;
+pub mod prune;
 pub mod run;
 pub mod setup;
 pub mod uninstall;
 pub mod update;
 pub mod r#use;
+pub mod verify;
 pub mod which;
 
 pub use self::which::Which;
@@ -15,12 +18,15 @@ pub use completions::Completions;
 pub use fetch::Fetch;
 pub use install::Install;
 pub use list::List;
+pub use list_remote::ListRemote;
 pub use pin::Pin;
+pub use prune::Prune;
 pub use run::Run;
 pub use setup::Setup;
 pub use uninstall::Uninstall;
 pub use update::Update;
 pub use r#use::Use;
+pub use verify::Verify;
 
 use volta_core::error::{ExitCode, Fallible};
 use volta_core::session::Session;
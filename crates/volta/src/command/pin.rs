@@ -1,20 +1,32 @@
-use volta_core::error::{ExitCode, Fallible};
+use volta_core::error::{ExitCode, Fallible, PlatformError};
 use volta_core::session::{ActivityKind, Session};
-use volta_core::tool::ToolSpec;
+use volta_core::tool::{ToolSpec, resolve_project_pins};
 
 use crate::command::Command;
 
 #[derive(clap::Args)]
 pub struct Pin {
-    /// Tools to pin, like `node@lts` or `yarn@^1.14`.
-    #[arg(value_name = "tool[@version]", required = true)]
+    /// Tools to pin, like `node@lts`, `node@lts/iron` or `yarn@^1.14`.
+    #[arg(value_name = "tool[@version]", required_unless_present = "refresh")]
     tools: Vec<String>,
+
+    /// Resolve the ranges and tags in the `volta` key again, and record the
+    /// new versions and checksums in `volta.lock`
+    #[arg(long, conflicts_with = "tools")]
+    refresh: bool,
 }
 
 impl Command for Pin {
     fn run(self, session: &mut Session) -> Fallible<ExitCode> {
         session.add_event_start(ActivityKind::Pin);
 
+        if self.refresh {
+            if session.project()?.is_none() {
+                return Err(PlatformError::NotInPackage.into());
+            }
+            resolve_project_pins(session, true)?;
+        }
+
         for tool in ToolSpec::from_strings(&self.tools, "pin")? {
             tool.resolve_pinnable(session)?.pin(session)?;
         }
@@ -0,0 +1,88 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::tool;
+
+use crate::command::Command;
+
+#[derive(clap::Args)]
+pub struct Prune {
+    /// Show what would be removed, without removing anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Only remove versions that haven't been used in this long, like `90d` or `12h`
+    ///
+    /// Projects that haven't been used in this long also no longer keep the versions they pin.
+    #[arg(long, value_name = "age")]
+    older_than: Option<Age>,
+}
+
+impl Command for Prune {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Prune);
+
+        tool::prune(
+            session,
+            self.dry_run,
+            self.older_than.map(|Age(duration)| duration),
+        )?;
+
+        session.add_event_end(ActivityKind::Prune, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
+
+/// A length of time, written as a number followed by a unit: `s`, `m`, `h`, `d` or `w`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Age(Duration);
+
+impl FromStr for Age {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid age `{s}`, expected a number and a unit like `90d`");
+
+        let split = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+        let (amount, unit) = s.split_at(split);
+        let amount: u64 = amount.parse().map_err(|_| invalid())?;
+
+        let unit_secs = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            "w" => 7 * 24 * 60 * 60,
+            _ => return Err(invalid()),
+        };
+        let secs = amount.checked_mul(unit_secs).ok_or_else(invalid)?;
+
+        Ok(Self(Duration::from_secs(secs)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ages() {
+        assert_eq!("45s".parse(), Ok(Age(Duration::from_secs(45))));
+        assert_eq!("30m".parse(), Ok(Age(Duration::from_mins(30))));
+        assert_eq!("12h".parse(), Ok(Age(Duration::from_hours(12))));
+        assert_eq!("90d".parse(), Ok(Age(Duration::from_hours(90 * 24))));
+        assert_eq!("2w".parse(), Ok(Age(Duration::from_hours(14 * 24))));
+    }
+
+    #[test]
+    fn rejects_invalid_ages() {
+        assert!("90".parse::<Age>().is_err());
+        assert!("d".parse::<Age>().is_err());
+        assert!("90 days".parse::<Age>().is_err());
+        assert!("-1d".parse::<Age>().is_err());
+        assert!("1.5h".parse::<Age>().is_err());
+        assert!(format!("{}w", u64::MAX).parse::<Age>().is_err());
+    }
+}
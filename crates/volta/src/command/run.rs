@@ -58,7 +58,11 @@ impl Command for Run {
     fn run(self, session: &mut Session) -> Fallible<ExitCode> {
         session.add_event_start(ActivityKind::Run);
 
-        let envs = self.parse_envs();
+        let mut envs = self.parse_envs();
+        if session.offline() {
+            // Keep any shims invoked by the command in offline mode as well
+            envs.insert("VOLTA_OFFLINE", "1");
+        }
         let platform = self.parse_platform(session)?;
 
         // Safety: At least one value is required for `command_and_args`, so there must be at
@@ -7,8 +7,12 @@ use crate::command::Command;
 
 #[derive(clap::Args)]
 pub struct Uninstall {
-    /// The tool to uninstall, like `ember-cli-update`, `typescript`, or <package>
+    /// The tool to uninstall, like `ember-cli-update`, `typescript`, `node@16.20.2`, or <package>
     tool: String,
+
+    /// Uninstall a Node or package manager version even if it is still in use
+    #[arg(long)]
+    force: bool,
 }
 
 impl Command for Uninstall {
@@ -18,9 +22,8 @@ impl Command for Uninstall {
         let tool = tool::ToolSpec::try_from_str(&self.tool)?;
 
         // For packages, specifically report that we do not support uninstalling
-        // specific versions. For runtimes and package managers, we currently
-        // *intentionally* let this fall through to inform the user that we do
-        // not support uninstalling those *at all*.
+        // specific versions. Runtimes and package managers are uninstalled one
+        // version at a time.
         if let tool::ToolSpec::Package(_name, version) = &tool {
             let VersionSpec::None = version else {
                 return Err(ErrorKind::Unimplemented {
@@ -30,7 +33,7 @@ impl Command for Uninstall {
             };
         }
 
-        tool.uninstall(session.pnpm_enabled())?;
+        tool.uninstall(session, self.force)?;
 
         session.add_event_end(ActivityKind::Uninstall, ExitCode::Success);
         Ok(ExitCode::Success)
@@ -81,7 +81,9 @@ impl Update {
 
             match scope {
                 Scope::Global => {
-                    tool_with_version.resolve_installable(session)?.install(session)?;
+                    tool_with_version
+                        .resolve_installable(session)?
+                        .install(session)?;
                 }
                 Scope::Project => {
                     tool_with_version.resolve_pinnable(session)?.pin(session)?;
@@ -274,7 +276,10 @@ fn get_current_version(tool: &ToolSpec, scope: &Scope, session: &Session) -> Fal
 
 /// Check if a tool is pinned in the project.
 #[must_use]
-#[allow(clippy::missing_const_for_fn, reason = "intentionally non-const for future flexibility if PlatformSpec changes")]
+#[allow(
+    clippy::missing_const_for_fn,
+    reason = "intentionally non-const for future flexibility if PlatformSpec changes"
+)]
 fn is_tool_pinned(tool: &ToolSpec, project_platform: Option<&PlatformSpec>) -> bool {
     let Some(platform) = project_platform else {
         return false;
@@ -0,0 +1,144 @@
+//! Checks the toolchain of the current project without running it, for use in CI.
+//!
+//! With `--format json`, the report is a single JSON object, whose shape is
+//! versioned by `schemaVersion` in the same way as `volta list`:
+//!
+//! ```json
+//! {
+//!   "schemaVersion": 1,
+//!   "passed": false,
+//!   "checks": [
+//!     {
+//!       "check": "engines",
+//!       "tool": "node",
+//!       "status": "fail",
+//!       "message": "node@20.19.2 does not satisfy engines.node (>=22)"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! - `check` is `platform`, `lockfile`, `engines`, `packageManager`,
+//!   `registry` or `path`.
+//! - `tool` is omitted for checks of the whole project.
+//! - `status` is `pass`, `fail`, or `skip` for checks that could not be made,
+//!   such as the registries in offline mode.
+
+use serde::Serialize;
+
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::tool::{self, Check, CheckStatus};
+
+use crate::command::Command;
+
+/// The version of the JSON output schema
+const SCHEMA_VERSION: u32 = 1;
+
+#[derive(clap::ValueEnum, Copy, Clone)]
+enum Format {
+    Human,
+    /// A JSON object with a versioned schema, for use by other tools
+    Json,
+}
+
+#[derive(clap::Args)]
+pub struct Verify {
+    /// Specify the output format.
+    #[arg(long, default_value = "human")]
+    format: Format,
+}
+
+impl Command for Verify {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Verify);
+
+        let checks = tool::verify_project(session)?;
+        let passed = checks.iter().all(|check| check.status != CheckStatus::Fail);
+
+        println!(
+            "{}",
+            match self.format {
+                Format::Human => human(&checks),
+                Format::Json => json(&checks, passed),
+            }
+        );
+
+        let exit_code = if passed {
+            ExitCode::Success
+        } else {
+            ExitCode::Unverified
+        };
+        session.add_event_end(ActivityKind::Verify, exit_code);
+        Ok(exit_code)
+    }
+}
+
+fn human(checks: &[Check]) -> String {
+    let width = checks
+        .iter()
+        .map(|check| check.kind.to_string().len())
+        .max()
+        .unwrap_or_default();
+
+    let mut lines: Vec<String> = checks
+        .iter()
+        .map(|check| {
+            format!(
+                "{}  {:width$}  {}",
+                check.status,
+                check.kind.to_string(),
+                check.message
+            )
+        })
+        .collect();
+
+    let failed = checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Fail)
+        .count();
+    lines.push(String::new());
+    lines.push(if failed == 0 {
+        String::from("All checks passed")
+    } else {
+        format!("{failed} of {} checks failed", checks.len())
+    });
+
+    lines.join("\n")
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Output<'a> {
+    schema_version: u32,
+    passed: bool,
+    checks: Vec<CheckEntry<'a>>,
+}
+
+#[derive(Serialize)]
+struct CheckEntry<'a> {
+    check: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool: Option<&'a str>,
+    status: String,
+    message: &'a str,
+}
+
+fn json(checks: &[Check], passed: bool) -> String {
+    let output = Output {
+        schema_version: SCHEMA_VERSION,
+        passed,
+        checks: checks
+            .iter()
+            .map(|check| CheckEntry {
+                check: check.kind.to_string(),
+                tool: check.tool.as_deref(),
+                status: check.status.to_string(),
+                message: &check.message,
+            })
+            .collect(),
+    };
+
+    serde_json::to_string_pretty(&output)
+        .expect("serializing the verify report to JSON cannot fail")
+}
@@ -12,6 +12,10 @@ mod common;
 use common::{Error, ensure_layout};
 
 /// The entry point for the `volta` CLI.
+///
+/// # Panics
+///
+/// Panics if a logger has already been initialized.
 pub fn main() {
     let volta = cli::Volta::parse();
     let verbosity = match (&volta.verbose, &volta.quiet) {
@@ -32,6 +36,9 @@ pub fn main() {
     log::trace!("log level: {verbosity:?}");
 
     let mut session = Session::init();
+    if volta.offline {
+        session.enable_offline();
+    }
     session.add_event_start(ActivityKind::Volta);
 
     let result = ensure_layout().and_then(|()| volta.run(&mut session).map_err(Error::Volta));
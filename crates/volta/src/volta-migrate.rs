@@ -3,6 +3,11 @@ use volta_core::layout::volta_home;
 use volta_core::log::{Context, Logger, Verbosity};
 use volta_migrate::run_migration;
 
+/// The entry point for the `volta-migrate` executable.
+///
+/// # Panics
+///
+/// Panics if a logger has already been initialized.
 pub fn main() {
     Logger::init(Context::Migration, Verbosity::Default)
         .expect("Only a single Logger should be initialized");
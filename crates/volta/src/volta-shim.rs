@@ -7,6 +7,11 @@ use volta_core::run::execute_shim;
 use volta_core::session::{ActivityKind, Session};
 use volta_core::signal::setup_signal_handler;
 
+/// The entry point for the Volta shim executable.
+///
+/// # Panics
+///
+/// Panics if a logger has already been initialized.
 pub fn main() {
     Logger::init(Context::Shim, Verbosity::Default)
         .expect("Only a single Logger should be initialized");
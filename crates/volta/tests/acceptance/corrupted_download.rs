@@ -1,4 +1,8 @@
-use crate::support::sandbox::{DistroMetadata, NodeFixture, PnpmFixture, Yarn1Fixture, sandbox};
+use std::fs;
+
+use crate::support::sandbox::{
+    DistroFixture, DistroMetadata, NodeFixture, PnpmFixture, Yarn1Fixture, sandbox,
+};
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
 use nodejs_semver::Version;
@@ -30,8 +34,8 @@ const PNPM_VERSION_INFO: &str = r#"
     "name":"pnpm",
     "dist-tags": { "latest":"7.7.1" },
     "versions": {
-        "0.0.1": { "version":"0.0.1", "dist": { "shasum":"", "tarball":"" }},
-        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"", "tarball":"" }}
+        "0.0.1": { "version":"0.0.1", "dist": { "shasum":"58f67f3039445f9b802b1a891545f894d4578851", "tarball":"" }},
+        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"1a873cc397b01b1a39ed04288bcc85ba2aefda28", "tarball":"" }}
     }
 }
 "#;
@@ -53,8 +57,8 @@ const YARN_1_VERSION_INFO: &str = r#"{
     "name":"yarn",
     "dist-tags": { "latest": "1.2.42" },
     "versions": {
-        "0.0.1": { "version":"0.0.1", "dist": { "shasum":"", "tarball":"" }},
-        "1.2.42": { "version":"1.2.42", "dist": { "shasum:"", "tarball":"" }}
+        "0.0.1": { "version":"0.0.1", "dist": { "shasum":"58f67f3039445f9b802b1a891545f894d4578851", "tarball":"" }},
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"a04472b4febb093141993a289b7253bfffba11db", "tarball":"" }}
     }
 }"#;
 
@@ -101,6 +105,86 @@ fn install_valid_node_saves_to_inventory() {
     assert!(s.node_inventory_archive_exists(&Version::parse("10.99.1040").unwrap()));
 }
 
+#[test]
+fn install_node_with_checksum_mismatch_leaves_inventory_unchanged() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .fixture_mock(
+            "/v10.99.1040/SHASUMS256.txt",
+            "tests/fixtures/node-v10.99.1040-SHASUMS256.mismatch.txt",
+        )
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::UnknownError as i32)
+            .with_stderr_contains("[..]Checksum verification failed for Node v10.99.1040")
+    );
+
+    assert!(!s.node_inventory_archive_exists(&Version::parse("10.99.1040").unwrap()));
+}
+
+#[test]
+fn install_node_removes_corrupted_cached_archive() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .build();
+
+    // A different archive stands in for a cached archive that was corrupted after downloading
+    let version = Version::parse("10.99.1040").unwrap();
+    let other = NodeFixture::from(DistroMetadata {
+        version: "6.19.62",
+        compressed_size: 0,
+        uncompressed_size: None,
+    });
+    let archive = s.node_inventory_archive(&version);
+    fs::create_dir_all(archive.parent().unwrap()).unwrap();
+    fs::copy(other.fixture_path(), &archive).unwrap();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::UnknownError as i32)
+            .with_stderr_contains("[..]Checksum verification failed for Node v10.99.1040")
+    );
+    assert!(!s.node_inventory_archive_exists(&version));
+
+    // The next install downloads the archive again
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+    assert!(s.node_inventory_archive_exists(&version));
+}
+
+#[test]
+fn install_cached_node_offline_uses_recorded_checksum() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    // Without the image, the cached archive is unpacked again without fetching the checksums
+    s.remove_node_image("10.99.1040");
+    assert_that!(
+        s.volta("install node@10.99.1040")
+            .env("VOLTA_OFFLINE", "1")
+            .env("VOLTA_LOGLEVEL", "debug"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Using checksum recorded at [..]")
+            .with_stderr_does_not_contain("[..]Fetching Node checksums[..]")
+    );
+}
+
 #[test]
 fn install_corrupted_pnpm_leaves_inventory_unchanged() {
     let s = sandbox()
@@ -138,6 +222,34 @@ fn install_valid_pnpm_saves_to_inventory() {
     assert!(s.pnpm_inventory_archive_exists("7.7.1"));
 }
 
+#[test]
+fn install_cached_pnpm_offline_uses_recorded_integrity() {
+    let s = sandbox()
+        .platform(r#"{ "node": { "runtime": "1.2.3", "npm": null }, "yarn": null }"#)
+        .node_available_versions(NODE_VERSION_INFO)
+        .pnpm_available_versions(PNPM_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .distro_mocks::<PnpmFixture>(&PNPM_VERSION_FIXTURES)
+        .env("VOLTA_FEATURE_PNPM", "1")
+        .build();
+
+    assert_that!(
+        s.volta("install pnpm@7.7.1"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    // Without the image, the cached archive is unpacked again without fetching the registry
+    s.remove_pnpm_image("7.7.1");
+    assert_that!(
+        s.volta("install pnpm@7.7.1")
+            .env("VOLTA_OFFLINE", "1")
+            .env("VOLTA_LOGLEVEL", "debug"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Using integrity recorded at [..]")
+    );
+}
+
 #[test]
 fn install_corrupted_yarn_leaves_inventory_unchanged() {
     let s = sandbox()
@@ -172,3 +284,30 @@ fn install_valid_yarn_saves_to_inventory() {
 
     assert!(s.yarn_inventory_archive_exists("1.2.42"));
 }
+
+#[test]
+fn install_cached_yarn_offline_uses_recorded_integrity() {
+    let s = sandbox()
+        .platform(r#"{ "node": { "runtime": "1.2.3", "npm": null }, "yarn": null }"#)
+        .node_available_versions(NODE_VERSION_INFO)
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("install yarn@1.2.42"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    // Without the image, the cached archive is unpacked again without fetching the registry
+    s.remove_yarn_image("1.2.42");
+    assert_that!(
+        s.volta("install yarn@1.2.42")
+            .env("VOLTA_OFFLINE", "1")
+            .env("VOLTA_LOGLEVEL", "debug"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Using integrity recorded at [..]")
+    );
+}
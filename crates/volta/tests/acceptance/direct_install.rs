@@ -114,13 +114,16 @@ cfg_if::cfg_if! {
     }
 }
 
-const YARN_1_VERSION_INFO: &str = r#"[
-{"tag_name":"v1.2.42","assets":[{"name":"yarn-v1.2.42.tar.gz"}]},
-{"tag_name":"v1.3.1","assets":[{"name":"yarn-v1.3.1.msi"}]},
-{"tag_name":"v1.4.159","assets":[{"name":"yarn-v1.4.159.tar.gz"}]},
-{"tag_name":"v1.7.71","assets":[{"name":"yarn-v1.7.71.tar.gz"}]},
-{"tag_name":"v1.12.99","assets":[{"name":"yarn-v1.12.99.tar.gz"}]}
-]"#;
+const YARN_1_VERSION_INFO: &str = r#"{
+    "name":"yarn",
+    "dist-tags": { "latest":"1.12.99" },
+    "versions": {
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"a04472b4febb093141993a289b7253bfffba11db", "tarball":"" }},
+        "1.4.159": { "version":"1.4.159", "dist": { "shasum":"1b211aa31eaf27ab4c9a2c68c52d8d5f6bb9d48c", "tarball":"" }},
+        "1.7.71": { "version":"1.7.71", "dist": { "shasum":"090cc91fdeb93933518daf741acfb505106dc711", "tarball":"" }},
+        "1.12.99": { "version":"1.12.99", "dist": { "shasum":"cc1a5558d673700822f42bcadcc50b8898b3e26f", "tarball":"" }}
+    }
+}"#;
 
 const YARN_1_VERSION_FIXTURES: [DistroMetadata; 4] = [
     DistroMetadata {
@@ -150,9 +153,9 @@ const NPM_VERSION_INFO: &str = r#"
     "name":"npm",
     "dist-tags": { "latest":"8.1.5" },
     "versions": {
-        "1.2.3": { "version":"1.2.3", "dist": { "shasum":"", "tarball":"" }},
-        "4.5.6": { "version":"4.5.6", "dist": { "shasum":"", "tarball":"" }},
-        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"", "tarball":"" }}
+        "1.2.3": { "version":"1.2.3", "dist": { "shasum":"10d224f266a6fda6cc0acfc60a7ee41e68c70f64", "tarball":"" }},
+        "4.5.6": { "version":"4.5.6", "dist": { "shasum":"459724a7681468c76bb3e45cb1ee3deb30462f6d", "tarball":"" }},
+        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"90d46be79e04806f4ed7a7a4b8599ea02f2a20eb", "tarball":"" }}
     }
 }
 "#;
@@ -399,6 +402,7 @@ fn yarn_global_with_override_does_not_intercept() {
     let s = sandbox()
         .platform(&platform_with_node_yarn("10.99.1040", "1.12.99"))
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
         .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "info")
         .env("VOLTA_UNSAFE_GLOBAL", "1")
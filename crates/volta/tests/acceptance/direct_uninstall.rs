@@ -116,6 +116,14 @@ const YARN_1_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
     uncompressed_size: Some(0x0028_0000),
 }];
 
+const YARN_1_VERSION_INFO: &str = r#"{
+    "name":"yarn",
+    "dist-tags": { "latest":"1.2.42" },
+    "versions": {
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"a04472b4febb093141993a289b7253bfffba11db", "tarball":"" }}
+    }
+}"#;
+
 #[test]
 fn npm_uninstall_uses_volta_logic() {
     let s = sandbox()
@@ -299,6 +307,7 @@ fn yarn_remove_without_packages_skips_volta_logic() {
     let s = sandbox()
         .platform(&platform_with_node_yarn("10.99.1040", "1.2.42"))
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
         .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "info")
         .build();
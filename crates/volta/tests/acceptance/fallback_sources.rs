@@ -0,0 +1,275 @@
+//! Tests for the fallback sources of a Node version (`.nvmrc`, `.node-version` and
+//! `engines.node`) in projects that don't pin one, configured in `settings.json`.
+
+use crate::support::sandbox::{DistroMetadata, NodeFixture, sandbox};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PACKAGE_JSON: &str = r#"{
+    "name": "unpinned"
+}"#;
+
+const PACKAGE_JSON_WITH_ENGINES: &str = r#"{
+    "name": "with-engines",
+    "engines": {
+        "node": ">=10"
+    }
+}"#;
+
+const PACKAGE_JSON_PINNED: &str = r#"{
+    "name": "pinned",
+    "volta": {
+        "node": "9.27.6"
+    }
+}"#;
+
+const PLATFORM_NODE_ONLY: &str = r#"{
+    "node":{
+        "runtime":"9.27.6",
+        "npm":null
+    }
+}"#;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]},
+{"version":"v9.27.6","npm":"5.6.17","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]}
+]
+"#;
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "macos")] {
+        const NODE_VERSION_FIXTURES: [DistroMetadata; 2] = [
+            DistroMetadata {
+                version: "10.99.1040",
+                compressed_size: 273,
+                uncompressed_size: Some(0x0028_0000),
+            },
+            DistroMetadata {
+                version: "9.27.6",
+                compressed_size: 272,
+                uncompressed_size: Some(0x0028_0000),
+            },
+        ];
+    } else if #[cfg(target_os = "linux")] {
+        const NODE_VERSION_FIXTURES: [DistroMetadata; 2] = [
+            DistroMetadata {
+                version: "10.99.1040",
+                compressed_size: 273,
+                uncompressed_size: Some(0x0028_0000),
+            },
+            DistroMetadata {
+                version: "9.27.6",
+                compressed_size: 272,
+                uncompressed_size: Some(0x0028_0000),
+            },
+        ];
+    } else if #[cfg(target_os = "windows")] {
+        const NODE_VERSION_FIXTURES: [DistroMetadata; 2] = [
+            DistroMetadata {
+                version: "10.99.1040",
+                compressed_size: 1096,
+                uncompressed_size: None,
+            },
+            DistroMetadata {
+                version: "9.27.6",
+                compressed_size: 1068,
+                uncompressed_size: None,
+            },
+        ];
+    } else {
+        compile_error!("Unsupported target_os for tests (expected 'macos', 'linux', or 'windows').");
+    }
+}
+
+/// The settings for the mock server, along with the given fallback sources
+fn settings_json(server_url: &str, sources: &[&str]) -> String {
+    let sources: Vec<String> = sources
+        .iter()
+        .map(|source| format!("\"{source}\""))
+        .collect();
+    format!(
+        r#"{{
+    "nodeMirror": "{server_url}",
+    "npmRegistry": "{server_url}",
+    "fallbackSources": [{}]
+}}"#,
+        sources.join(", ")
+    )
+}
+
+#[test]
+fn uses_version_from_nvmrc() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let s = s
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON)
+        .project_file(".nvmrc", "v10.99.1040\n")
+        .default_settings(&settings_json(&server_url, &[".nvmrc"]))
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 10.99.1040 from .nvmrc configuration")
+    );
+}
+
+#[test]
+fn resolves_range_from_node_version() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let s = s
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON)
+        .project_file(".node-version", "10\n")
+        .default_settings(&settings_json(&server_url, &[".node-version"]))
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 10.99.1040 from .node-version configuration")
+    );
+
+    // The resolution of the range is reused, without reading the index again
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Using the recent resolution of Node [..] to 10.99.1040")
+            .with_stderr_contains("[..]Node: 10.99.1040 from .node-version configuration")
+    );
+}
+
+#[test]
+fn resolves_range_from_engines() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let s = s
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_WITH_ENGINES)
+        .default_settings(&settings_json(&server_url, &["engines.node"]))
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 10.99.1040 from engines.node configuration")
+    );
+}
+
+#[test]
+fn uses_first_source_with_version() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let s = s
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_WITH_ENGINES)
+        .project_file(".nvmrc", "9.27.6\n")
+        .default_settings(&settings_json(
+            &server_url,
+            &[".node-version", ".nvmrc", "engines.node"],
+        ))
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 9.27.6 from .nvmrc configuration")
+    );
+}
+
+#[test]
+fn ignores_fallback_sources_by_default() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON)
+        .project_file(".nvmrc", "10.99.1040\n")
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 9.27.6 from default configuration")
+    );
+}
+
+#[test]
+fn pinned_version_takes_precedence() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let s = s
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_PINNED)
+        .project_file(".nvmrc", "10.99.1040\n")
+        .default_settings(&settings_json(&server_url, &[".nvmrc"]))
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 9.27.6 from project configuration")
+    );
+}
+
+#[test]
+fn unsupported_version() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let s = s
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON)
+        .project_file(".nvmrc", "iojs\n")
+        .default_settings(&settings_json(&server_url, &[".nvmrc"]))
+        .build();
+
+    assert_that!(
+        s.npm("--version"),
+        execs()
+            .with_status(ExitCode::ExecutionFailure as i32)
+            .with_stderr_contains("[..]Could not parse Node version 'iojs'")
+    );
+}
+
+#[test]
+fn list_shows_fallback_file() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let s = s
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON)
+        .project_file(".nvmrc", "10.99.1040\n")
+        .default_settings(&settings_json(&server_url, &[".nvmrc"]))
+        .build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("runtime node@10.99.1040 (current @ [..].nvmrc)")
+    );
+}
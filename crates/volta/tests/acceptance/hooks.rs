@@ -4,12 +4,14 @@ use std::{thread, time};
 use crate::support::events_helpers::{
     assert_events, match_args, match_end, match_error, match_start,
 };
-use crate::support::sandbox::sandbox;
+use crate::support::sandbox::{DistroMetadata, NodeFixture, sandbox};
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
-use mockito::mock;
+use mockito::Matcher;
+use nodejs_semver::Version;
 use test_support::matchers::execs;
 use volta_core::error::ExitCode;
+use volta_core::tool::Node;
 
 const WORKSPACE_PACKAGE_JSON: &str = r#"
 {
@@ -25,6 +27,17 @@ const PROJECT_PACKAGE_JSON: &str = r#"
     }
 }"#;
 
+/// Published Node checksums for the given versions, so that the distro download is attempted
+fn node_checksums(versions: &[&str]) -> String {
+    versions
+        .iter()
+        .map(|version| {
+            let filename = Node::archive_filename(&Version::parse(version).unwrap());
+            format!("{}  {}\n", "0".repeat(64), filename)
+        })
+        .collect()
+}
+
 // scripts that write events to file 'events.json'
 cfg_if::cfg_if! {
     if #[cfg(windows)] {
@@ -57,7 +70,7 @@ done
     }
 }
 
-fn default_hooks_json() -> String {
+fn default_hooks_json(server_url: &str) -> String {
     format!(
         r#"
 {{
@@ -82,12 +95,25 @@ fn default_hooks_json() -> String {
         }}
     }}
 }}"#,
-        mockito::server_url(),
-        SCRIPT_FILENAME
+        server_url, SCRIPT_FILENAME
     )
 }
 
-fn project_hooks_json() -> String {
+fn events_url_hooks_json(url: &str) -> String {
+    format!(
+        r#"
+{{
+    "events": {{
+        "publish": {{
+            "url": "{}"
+        }}
+    }}
+}}"#,
+        url
+    )
+}
+
+fn project_hooks_json(server_url: &str) -> String {
     format!(
         r#"
 {{
@@ -97,11 +123,11 @@ fn project_hooks_json() -> String {
         }}
     }}
 }}"#,
-        mockito::server_url()
+        server_url
     )
 }
 
-fn workspace_hooks_json() -> String {
+fn workspace_hooks_json(server_url: &str) -> String {
     format!(
         r#"
 {{
@@ -116,11 +142,11 @@ fn workspace_hooks_json() -> String {
         }}
     }}
 }}"#,
-        mockito::server_url()
+        server_url
     )
 }
 
-fn pnpm_hooks_json() -> String {
+fn pnpm_hooks_json(server_url: &str) -> String {
     format!(
         r#"
 {{
@@ -133,11 +159,11 @@ fn pnpm_hooks_json() -> String {
         }}
     }}
 }}"#,
-        mockito::server_url()
+        server_url
     )
 }
 
-fn yarn_hooks_json() -> String {
+fn yarn_hooks_json(server_url: &str) -> String {
     format!(
         r#"
 {{
@@ -150,11 +176,11 @@ fn yarn_hooks_json() -> String {
         }}
     }}
 }}"#,
-        mockito::server_url()
+        server_url
     )
 }
 
-fn yarn_hooks_format_json(format: &str) -> String {
+fn yarn_hooks_format_json(server_url: &str, format: &str) -> String {
     format!(
         r#"
 {{
@@ -168,18 +194,23 @@ fn yarn_hooks_format_json(format: &str) -> String {
         }}
     }}
 }}"#,
-        mockito::server_url(),
-        format
+        server_url, format
     )
 }
 
 #[test]
 fn redirects_download() {
-    let s = sandbox()
-        .default_hooks(&default_hooks_json())
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
+        .default_hooks(&default_hooks_json(&server_url))
         .env("VOLTA_WRITE_EVENTS_FILE", "true")
         .executable_file(SCRIPT_FILENAME, EVENTS_EXECUTABLE)
         .build();
+    let _checksums = s
+        .mock("GET", "/hook/default/node/SHASUMS256.txt")
+        .with_body(node_checksums(&["1.2.3"]))
+        .create();
 
     assert_that!(
         s.volta("install node@1.2.3"),
@@ -208,13 +239,22 @@ fn redirects_download() {
 #[test]
 fn merges_project_and_default_hooks() {
     let local_hooks: PathBuf = [".volta", "hooks.json"].iter().collect();
-    let s = sandbox()
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
         .package_json("{}")
-        .default_hooks(&default_hooks_json())
-        .project_file(&local_hooks.to_string_lossy(), &project_hooks_json())
+        .default_hooks(&default_hooks_json(&server_url))
+        .project_file(
+            &local_hooks.to_string_lossy(),
+            &project_hooks_json(&server_url),
+        )
         .env("VOLTA_WRITE_EVENTS_FILE", "true")
         .executable_file(SCRIPT_FILENAME, EVENTS_EXECUTABLE)
         .build();
+    let _checksums = s
+        .mock("GET", "/hook/default/node/SHASUMS256.txt")
+        .with_body(node_checksums(&["10.12.1"]))
+        .create();
 
     // Project defines yarn hooks, so those should be used
     assert_that!(
@@ -268,18 +308,30 @@ fn merges_workspace_hooks() {
     let workspace_hooks: PathBuf = ["workspace", ".volta", "hooks.json"].iter().collect();
     let workspace_package_json: PathBuf = ["workspace", "package.json"].iter().collect();
     let project_hooks: PathBuf = [".volta", "hooks.json"].iter().collect();
-    let s = sandbox()
-        .default_hooks(&default_hooks_json())
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
+        .default_hooks(&default_hooks_json(&server_url))
         .package_json(PROJECT_PACKAGE_JSON)
-        .project_file(&project_hooks.to_string_lossy(), &project_hooks_json())
+        .project_file(
+            &project_hooks.to_string_lossy(),
+            &project_hooks_json(&server_url),
+        )
         .project_file(
             &workspace_package_json.to_string_lossy(),
             WORKSPACE_PACKAGE_JSON,
         )
-        .project_file(&workspace_hooks.to_string_lossy(), &workspace_hooks_json())
+        .project_file(
+            &workspace_hooks.to_string_lossy(),
+            &workspace_hooks_json(&server_url),
+        )
         .env("VOLTA_WRITE_EVENTS_FILE", "true")
         .executable_file(SCRIPT_FILENAME, EVENTS_EXECUTABLE)
         .build();
+    let _checksums = s
+        .mock("GET", "/hook/default/node/SHASUMS256.txt")
+        .with_body(node_checksums(&["11.11.2"]))
+        .create();
 
     // Project defines yarn hooks, so those should be used
     assert_that!(
@@ -339,12 +391,14 @@ fn merges_workspace_hooks() {
 
 #[test]
 fn pnpm_latest_with_hook_reads_index() {
-    let s = sandbox()
-        .default_hooks(&pnpm_hooks_json())
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
+        .default_hooks(&pnpm_hooks_json(&server_url))
         .env("VOLTA_LOGLEVEL", "debug")
         .env("VOLTA_FEATURE_PNPM", "1")
         .build();
-    let _mock = mock("GET", "/pnpm/index")
+    let _mock = s.mock("GET", "/pnpm/index")
         .with_status(200)
         .with_header("Content-Type", "application/json")
         .with_body(
@@ -353,9 +407,9 @@ fn pnpm_latest_with_hook_reads_index() {
     "name":"pnpm",
     "dist-tags": { "latest":"7.7.1" },
     "versions": {
-        "0.0.1": { "version":"0.0.1", "dist": { "shasum":"", "tarball":"" }},
-        "6.34.0": { "version":"6.34.0", "dist": { "shasum":"", "tarball":"" }},
-        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"", "tarball":"" }}
+        "0.0.1": { "version":"0.0.1", "dist": { "shasum":"58f67f3039445f9b802b1a891545f894d4578851", "tarball":"" }},
+        "6.34.0": { "version":"6.34.0", "dist": { "shasum":"6e6523071c0d6884c6c98d5156dd8c1fb06d3b1a", "tarball":"" }},
+        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"1a873cc397b01b1a39ed04288bcc85ba2aefda28", "tarball":"" }}
     }
 }"#,
         )
@@ -372,14 +426,84 @@ fn pnpm_latest_with_hook_reads_index() {
     );
 }
 
+#[test]
+fn pnpm_distro_hook_skips_registry_integrity() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let s = s
+        .default_hooks(&format!(
+            r#"{{ "pnpm": {{ "distro": {{ "template": "{server_url}/pnpm/{{{{version}}}}" }} }} }}"#
+        ))
+        .platform(r#"{ "node": { "runtime": "1.2.3", "npm": null }, "yarn": null }"#)
+        // The registry integrity doesn't match the archive served by the hook
+        .pnpm_available_versions(
+            r#"{
+    "name":"pnpm",
+    "dist-tags": { "latest":"7.7.1" },
+    "versions": {
+        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"da39a3ee5e6b4b0d3255bfef95601890afd80709", "tarball":"" }}
+    }
+}"#,
+        )
+        .fixture_mock("/pnpm/7.7.1", "tests/fixtures/pnpm-7.7.1.tgz")
+        .env("VOLTA_LOGLEVEL", "debug")
+        .env("VOLTA_FEATURE_PNPM", "1")
+        .build();
+
+    assert_that!(
+        s.volta("install pnpm@7.7.1"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains(
+                "[..]Skipping integrity check, pnpm.distro hook archives may differ from the registry"
+            )
+            .with_stderr_contains("[..]Downloading pnpm@7.7.1 from[..]/pnpm/7.7.1[..]")
+    );
+}
+
+#[test]
+fn node_distro_hook_without_checksums_installs_with_warning() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
+        .default_hooks(&format!(
+            r#"{{ "node": {{ "distro": {{ "template": "{server_url}/v{{{{version}}}}/{{{{filename}}}}" }} }} }}"#
+        ))
+        .distro_mocks::<NodeFixture>(&[DistroMetadata {
+            version: "10.99.1040",
+            compressed_size: 273,
+            uncompressed_size: Some(0x0028_0000),
+        }])
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+    // The mirror only hosts the archives
+    let _checksums = s
+        .mock("GET", "/v10.99.1040/SHASUMS256.txt")
+        .with_status(404)
+        .create();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains(
+                "[..]No Node checksums found at [..]/v10.99.1040/SHASUMS256.txt, so the archive from the node.distro hook cannot be verified."
+            )
+    );
+
+    assert!(s.node_inventory_archive_exists(&Version::parse("10.99.1040").unwrap()));
+}
+
 #[test]
 fn pnpm_no_version_with_hook_reads_index() {
-    let s = sandbox()
-        .default_hooks(&pnpm_hooks_json())
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
+        .default_hooks(&pnpm_hooks_json(&server_url))
         .env("VOLTA_LOGLEVEL", "debug")
         .env("VOLTA_FEATURE_PNPM", "1")
         .build();
-    let _mock = mock("GET", "/pnpm/index")
+    let _mock = s.mock("GET", "/pnpm/index")
         .with_status(200)
         .with_header("Content-Type", "application/json")
         .with_body(
@@ -388,9 +512,9 @@ fn pnpm_no_version_with_hook_reads_index() {
     "name":"pnpm",
     "dist-tags": { "latest":"7.7.1" },
     "versions": {
-        "0.0.1": { "version":"0.0.1", "dist": { "shasum":"", "tarball":"" }},
-        "6.34.0": { "version":"6.34.0", "dist": { "shasum":"", "tarball":"" }},
-        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"", "tarball":"" }}
+        "0.0.1": { "version":"0.0.1", "dist": { "shasum":"58f67f3039445f9b802b1a891545f894d4578851", "tarball":"" }},
+        "6.34.0": { "version":"6.34.0", "dist": { "shasum":"6e6523071c0d6884c6c98d5156dd8c1fb06d3b1a", "tarball":"" }},
+        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"1a873cc397b01b1a39ed04288bcc85ba2aefda28", "tarball":"" }}
     }
 }"#,
         )
@@ -409,11 +533,14 @@ fn pnpm_no_version_with_hook_reads_index() {
 
 #[test]
 fn yarn_latest_with_hook_reads_latest() {
-    let s = sandbox()
-        .default_hooks(&yarn_hooks_json())
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
+        .default_hooks(&yarn_hooks_json(&server_url))
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
-    let _mock = mock("GET", "/yarn-old/latest")
+    let _mock = s
+        .mock("GET", "/yarn-old/latest")
         .with_status(200)
         .with_body("4.2.9")
         .create();
@@ -430,11 +557,14 @@ fn yarn_latest_with_hook_reads_latest() {
 
 #[test]
 fn yarn_no_version_with_hook_reads_latest() {
-    let s = sandbox()
-        .default_hooks(&yarn_hooks_json())
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
+        .default_hooks(&yarn_hooks_json(&server_url))
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
-    let _mock = mock("GET", "/yarn-old/latest")
+    let _mock = s
+        .mock("GET", "/yarn-old/latest")
         .with_status(200)
         .with_body("4.2.9")
         .create();
@@ -451,11 +581,14 @@ fn yarn_no_version_with_hook_reads_latest() {
 
 #[test]
 fn yarn_semver_with_hook_uses_old_format() {
-    let s = sandbox()
-        .default_hooks(&yarn_hooks_json())
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
+        .default_hooks(&yarn_hooks_json(&server_url))
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
-    let _mock = mock("GET", "/yarn-old/index")
+    let _mock = s
+        .mock("GET", "/yarn-old/index")
         .with_status(200)
         .with_header("Content-Type", "application/json")
         .with_body(
@@ -481,11 +614,13 @@ fn yarn_semver_with_hook_uses_old_format() {
 
 #[test]
 fn yarn_semver_with_hook_uses_configured_format() {
-    let s = sandbox()
-        .default_hooks(&yarn_hooks_format_json("npm"))
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
+        .default_hooks(&yarn_hooks_format_json(&server_url, "npm"))
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
-    let _mock = mock("GET", "/yarn-new/index")
+    let _mock = s.mock("GET", "/yarn-new/index")
         .with_status(200)
         .with_header("Content-Type", "application/json")
         .with_body(
@@ -494,10 +629,10 @@ fn yarn_semver_with_hook_uses_configured_format() {
     "name":"@yarnpkg/cli-dist",
     "dist-tags": { "latest":"3.12.99" },
     "versions": {
-        "2.4.159": { "version":"2.4.159", "dist": { "shasum":"", "tarball":"" }},
-        "3.2.42": { "version":"3.2.42", "dist": { "shasum":"", "tarball":"" }},
-        "3.7.71": { "version":"3.7.71", "dist": { "shasum":"", "tarball":"" }},
-        "3.12.99": { "version":"3.12.99", "dist": { "shasum":"", "tarball":"" }}
+        "2.4.159": { "version":"2.4.159", "dist": { "shasum":"1b211aa31eaf27ab4c9a2c68c52d8d5f6bb9d48c", "tarball":"" }},
+        "3.2.42": { "version":"3.2.42", "dist": { "shasum":"a04472b4febb093141993a289b7253bfffba11db", "tarball":"" }},
+        "3.7.71": { "version":"3.7.71", "dist": { "shasum":"090cc91fdeb93933518daf741acfb505106dc711", "tarball":"" }},
+        "3.12.99": { "version":"3.12.99", "dist": { "shasum":"cc1a5558d673700822f42bcadcc50b8898b3e26f", "tarball":"" }}
     }
 }"#,
         )
@@ -512,3 +647,47 @@ fn yarn_semver_with_hook_uses_configured_format() {
             .with_stderr_contains("[..]Could not download yarn@3.12.99")
     );
 }
+
+#[test]
+fn publishes_events_to_url() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
+        .default_hooks(&events_url_hooks_json(&format!("{server_url}/events")))
+        .build();
+    let events_mock = s
+        .mock("POST", "/events")
+        .match_header("content-type", Matcher::Regex("application/json".into()))
+        .match_body(Matcher::AllOf(vec![
+            Matcher::Regex(r#""name":"install","event":"start""#.into()),
+            Matcher::Regex(r#""name":"volta","event":\{"end":\{"exit_code":5\}\}"#.into()),
+        ]))
+        .with_status(200)
+        .create();
+    let _checksums = s
+        .mock("GET", "/v1.2.3/SHASUMS256.txt")
+        .with_body(node_checksums(&["1.2.3"]))
+        .create();
+
+    assert_that!(
+        s.volta("install node@1.2.3"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download node@1.2.3")
+    );
+
+    events_mock.assert();
+}
+
+#[test]
+fn unreachable_events_url_does_not_fail_command() {
+    let s = sandbox()
+        // Nothing is listening on port 9 (discard), so publishing will fail to connect
+        .default_hooks(&events_url_hooks_json("http://127.0.0.1:9/events"))
+        .build();
+
+    assert_that!(
+        s.volta("--version"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+}
@@ -1,3 +1,13 @@
+// Each sandbox holds on to its mock server until the end of the test, by design
+#![allow(clippy::significant_drop_tightening)]
+// The sandbox support code predates the pedantic lints and is only used by the tests
+#![allow(
+    clippy::doc_markdown,
+    clippy::module_name_repetitions,
+    clippy::needless_pass_by_value,
+    clippy::unused_self
+)]
+
 use cfg_if::cfg_if;
 
 cfg_if! {
@@ -9,15 +19,30 @@ cfg_if! {
         mod direct_install;
         mod direct_uninstall;
         mod execute_binary;
+        mod fallback_sources;
         mod hooks;
         mod merged_platform;
+        mod metadata_cache;
         mod migrations;
+        mod node_channels;
+        mod node_signatures;
+        mod offline;
+        mod package_aliases;
+        mod package_manager_field;
+        mod project_lockfile;
+        mod project_tools;
         mod run_shim_directly;
+        mod settings;
         mod verbose_errors;
         mod volta_bypass;
         mod volta_install;
+        mod volta_list;
+        mod volta_list_remote;
         mod volta_pin;
+        mod volta_prune;
         mod volta_run;
         mod volta_uninstall;
+        mod volta_verify;
+        mod workspaces;
     }
 }
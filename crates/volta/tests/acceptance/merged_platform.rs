@@ -195,12 +195,46 @@ const YARN_1_VERSION_FIXTURES: [DistroMetadata; 2] = [
     },
 ];
 
+const NPM_VERSION_INFO: &str = r#"
+{
+    "name":"npm",
+    "dist-tags": { "latest":"4.5.6" },
+    "versions": {
+        "1.2.3": { "version":"1.2.3", "dist": { "shasum":"10d224f266a6fda6cc0acfc60a7ee41e68c70f64", "tarball":"" }},
+        "4.5.6": { "version":"4.5.6", "dist": { "shasum":"459724a7681468c76bb3e45cb1ee3deb30462f6d", "tarball":"" }}
+    }
+}
+"#;
+
+const PNPM_VERSION_INFO: &str = r#"
+{
+    "name":"pnpm",
+    "dist-tags": { "latest":"7.7.1" },
+    "versions": {
+        "6.34.0": { "version":"6.34.0", "dist": { "shasum":"6e6523071c0d6884c6c98d5156dd8c1fb06d3b1a", "tarball":"" }},
+        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"1a873cc397b01b1a39ed04288bcc85ba2aefda28", "tarball":"" }}
+    }
+}
+"#;
+
+const YARN_1_VERSION_INFO: &str = r#"
+{
+    "name":"yarn",
+    "dist-tags": { "latest":"1.12.99" },
+    "versions": {
+        "1.7.71": { "version":"1.7.71", "dist": { "shasum":"090cc91fdeb93933518daf741acfb505106dc711", "tarball":"" }},
+        "1.12.99": { "version":"1.12.99", "dist": { "shasum":"cc1a5558d673700822f42bcadcc50b8898b3e26f", "tarball":"" }}
+    }
+}
+"#;
+
 #[test]
 fn uses_project_npm_if_available() {
     let s = sandbox()
         .platform(PLATFORM_WITH_NPM)
         .package_json(PACKAGE_JSON_WITH_NPM)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .npm_available_versions(NPM_VERSION_INFO)
         .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
@@ -220,6 +254,7 @@ fn uses_bundled_npm_in_project_without_npm() {
         .platform(PLATFORM_WITH_NPM)
         .package_json(PACKAGE_JSON_NODE_ONLY)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .npm_available_versions(NPM_VERSION_INFO)
         .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
@@ -238,6 +273,7 @@ fn uses_default_npm_outside_project() {
     let s = sandbox()
         .platform(PLATFORM_WITH_NPM)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .npm_available_versions(NPM_VERSION_INFO)
         .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
@@ -256,6 +292,7 @@ fn uses_bundled_npm_outside_project() {
     let s = sandbox()
         .platform(PLATFORM_NODE_ONLY)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .npm_available_versions(NPM_VERSION_INFO)
         .distro_mocks::<NpmFixture>(&NPM_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
@@ -275,6 +312,7 @@ fn uses_project_yarn_if_available() {
         .platform(PLATFORM_WITH_YARN)
         .package_json(PACKAGE_JSON_WITH_YARN)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
         .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .env("VOLTA_WRITE_EVENTS_FILE", "true")
@@ -312,6 +350,7 @@ fn uses_default_yarn_in_project_without_yarn() {
         .platform(PLATFORM_WITH_YARN)
         .package_json(PACKAGE_JSON_NODE_ONLY)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
         .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
@@ -331,6 +370,7 @@ fn uses_default_yarn_outside_project() {
     let s = sandbox()
         .platform(PLATFORM_WITH_YARN)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
         .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .build();
@@ -378,6 +418,7 @@ fn uses_project_pnpm_if_available() {
         .platform(PLATFORM_WITH_PNPM)
         .package_json(PACKAGE_JSON_WITH_PNPM)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .pnpm_available_versions(PNPM_VERSION_INFO)
         .distro_mocks::<PnpmFixture>(&PNPM_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .env("VOLTA_WRITE_EVENTS_FILE", "true")
@@ -416,6 +457,7 @@ fn uses_default_pnpm_in_project_without_pnpm() {
         .platform(PLATFORM_WITH_PNPM)
         .package_json(PACKAGE_JSON_NODE_ONLY)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .pnpm_available_versions(PNPM_VERSION_INFO)
         .distro_mocks::<PnpmFixture>(&PNPM_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .env("VOLTA_FEATURE_PNPM", "1")
@@ -436,6 +478,7 @@ fn uses_default_pnpm_outside_project() {
     let s = sandbox()
         .platform(PLATFORM_WITH_PNPM)
         .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .pnpm_available_versions(PNPM_VERSION_INFO)
         .distro_mocks::<PnpmFixture>(&PNPM_VERSION_FIXTURES)
         .env("VOLTA_LOGLEVEL", "debug")
         .env("VOLTA_FEATURE_PNPM", "1")
@@ -0,0 +1,122 @@
+//! Tests for the HTTP cache of registry metadata.
+
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use mockito::Matcher;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]}
+]
+"#;
+
+const NPM_VERSION_INFO: &str = r#"
+{
+    "name":"npm",
+    "dist-tags": { "latest":"8.1.5" },
+    "versions": {
+        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"90d46be79e04806f4ed7a7a4b8599ea02f2a20eb", "tarball":"" }}
+    }
+}
+"#;
+
+#[test]
+fn npm_registry_is_cached() {
+    let mut s = sandbox().build();
+    let registry = s
+        .mock("GET", "/npm")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("cache-control", "max-age=300")
+        .with_body(NPM_VERSION_INFO)
+        .expect(1)
+        .create();
+
+    for _ in 0..2 {
+        assert_that!(
+            s.volta("install npm@9"),
+            execs().with_status(ExitCode::NoVersionMatch as i32)
+        );
+    }
+
+    registry.assert();
+}
+
+#[test]
+fn node_index_is_cached() {
+    let mut s = sandbox().build();
+    let index = s
+        .mock("GET", "/index.json")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(NODE_VERSION_INFO)
+        .expect(1)
+        .create();
+
+    for _ in 0..2 {
+        assert_that!(
+            s.volta("install node@11"),
+            execs().with_status(ExitCode::NoVersionMatch as i32)
+        );
+    }
+
+    index.assert();
+}
+
+#[test]
+fn expired_cache_is_revalidated_with_etag() {
+    let mut s = sandbox().build();
+    let registry = s
+        .mock("GET", "/npm")
+        .match_header("if-none-match", Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("cache-control", "no-cache")
+        .with_header("etag", "\"npm-etag\"")
+        .with_body(NPM_VERSION_INFO)
+        .expect(1)
+        .create();
+    let revalidated = s
+        .mock("GET", "/npm")
+        .match_header("if-none-match", "\"npm-etag\"")
+        .with_status(304)
+        .expect(1)
+        .create();
+
+    for _ in 0..2 {
+        assert_that!(
+            s.volta("install npm@9"),
+            execs()
+                .with_status(ExitCode::NoVersionMatch as i32)
+                .with_stderr_contains("[..]Could not find npm version matching[..]")
+        );
+    }
+
+    registry.assert();
+    revalidated.assert();
+}
+
+#[test]
+fn uncacheable_response_is_not_stored() {
+    let mut s = sandbox().build();
+    let registry = s
+        .mock("GET", "/npm")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("cache-control", "no-store")
+        .with_body(NPM_VERSION_INFO)
+        .expect(2)
+        .create();
+
+    for _ in 0..2 {
+        assert_that!(
+            s.volta("install npm@9"),
+            execs().with_status(ExitCode::NoVersionMatch as i32)
+        );
+    }
+
+    registry.assert();
+}
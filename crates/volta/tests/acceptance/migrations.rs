@@ -49,7 +49,7 @@ fn legacy_v0_volta_home_is_upgraded() {
 
     // directories that are already created by the test framework
     assert!(Sandbox::path_exists(".volta"));
-    assert!(Sandbox::path_exists(".volta/cache/node"));
+    assert!(Sandbox::path_exists(".volta/cache/http"));
     assert!(Sandbox::path_exists(".volta/tmp"));
     assert!(Sandbox::path_exists(".volta/tools/inventory/node"));
     assert!(Sandbox::path_exists(".volta/tools/inventory/packages"));
@@ -65,7 +65,7 @@ fn legacy_v0_volta_home_is_upgraded() {
 
     // Layout should be updated to the most recent
     assert!(Sandbox::path_exists(".volta"));
-    assert!(Sandbox::path_exists(".volta/cache/node"));
+    assert!(Sandbox::path_exists(".volta/cache/http"));
     assert!(Sandbox::path_exists(".volta/tmp"));
     assert!(Sandbox::path_exists(".volta/tools/inventory/node"));
     assert!(!Sandbox::path_exists(".volta/tools/inventory/packages"));
@@ -215,7 +215,7 @@ fn current_v4_volta_home_is_unchanged() {
     // directories that are already created by the test framework
     assert!(Sandbox::path_exists(".volta"));
     assert!(Sandbox::path_exists(".volta/layout.v4"));
-    assert!(Sandbox::path_exists(".volta/cache/node"));
+    assert!(Sandbox::path_exists(".volta/cache/http"));
     assert!(Sandbox::path_exists(".volta/tmp"));
     assert!(Sandbox::path_exists(".volta/tools/inventory/node"));
     assert!(Sandbox::path_exists(".volta/tools/inventory/yarn"));
@@ -226,7 +226,7 @@ fn current_v4_volta_home_is_unchanged() {
     // everything should be the same as before running the command
     assert!(Sandbox::path_exists(".volta"));
     assert!(Sandbox::path_exists(".volta/layout.v4"));
-    assert!(Sandbox::path_exists(".volta/cache/node"));
+    assert!(Sandbox::path_exists(".volta/cache/http"));
     assert!(Sandbox::path_exists(".volta/tmp"));
     assert!(Sandbox::path_exists(".volta/tools/inventory/node"));
     assert!(Sandbox::path_exists(".volta/tools/inventory/yarn"));
@@ -0,0 +1,140 @@
+//! Tests for the prerelease channels of Node, such as `node@nightly`, which resolve from the
+//! index of each channel.
+
+use crate::support::sandbox::sandbox;
+use cfg_if::cfg_if;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+use volta_core::error::ExitCode;
+
+const NIGHTLY_VERSION: &str = "25.0.0-nightly20250601abcdef1234";
+
+const NIGHTLY_VERSION_INFO: &str = r#"[
+{"version":"v25.0.0-nightly20250601abcdef1234","npm":"11.4.1","lts":false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]},
+{"version":"v25.0.0-nightly20250531fedcba4321","npm":"11.4.1","lts":false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]}
+]
+"#;
+
+fn nightly_hooks_json(server_url: &str) -> String {
+    format!(
+        r#"
+{{
+    "node": {{
+        "nightly": {{
+            "index": {{
+                "prefix": "{server_url}/nightly/"
+            }}
+        }}
+    }}
+}}"#
+    )
+}
+
+fn node_bin(version: &str) -> String {
+    cfg_if! {
+        if #[cfg(target_os = "windows")] {
+            format!("@echo off\necho node version {version}\n")
+        } else {
+            format!("#!/bin/sh\necho \"node version {version}\"\n")
+        }
+    }
+}
+
+#[test]
+fn run_node_nightly() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
+        .default_hooks(&nightly_hooks_json(&server_url))
+        .setup_node_binary(NIGHTLY_VERSION, "11.4.1", &node_bin(NIGHTLY_VERSION))
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+    let _mock = s
+        .mock("GET", "/nightly/index.json")
+        .with_status(200)
+        .with_header("Content-Type", "application/json")
+        .with_body(NIGHTLY_VERSION_INFO)
+        .create();
+
+    assert_that!(
+        s.volta("run --node nightly node"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Using node.nightly.index hook to determine node index URL")
+            .with_stderr_contains(&format!(
+                "[..]Found newest nightly node version ({NIGHTLY_VERSION})[..]"
+            ))
+            .with_stdout_contains(&format!("node version {NIGHTLY_VERSION}"))
+    );
+}
+
+#[test]
+fn list_remote_node_nightly() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s.default_hooks(&nightly_hooks_json(&server_url)).build();
+    let _mock = s
+        .mock("GET", "/nightly/index.json")
+        .with_status(200)
+        .with_header("Content-Type", "application/json")
+        .with_body(NIGHTLY_VERSION_INFO)
+        .create();
+
+    assert_that!(
+        s.volta("list-remote node@nightly"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(
+                "25.0.0-nightly20250531fedcba4321\n25.0.0-nightly20250601abcdef1234 (nightly)"
+            )
+    );
+}
+
+#[test]
+fn nightly_offline_uses_inventory() {
+    let s = sandbox()
+        .setup_node_binary("22.16.0", "10.9.2", &node_bin("22.16.0"))
+        .setup_node_binary(NIGHTLY_VERSION, "11.4.1", &node_bin(NIGHTLY_VERSION))
+        .env("VOLTA_OFFLINE", "1")
+        .build();
+
+    assert_that!(
+        s.volta("run --node nightly node"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(&format!("node version {NIGHTLY_VERSION}"))
+    );
+
+    assert_that!(
+        s.volta("run --node rc node"),
+        execs()
+            .with_status(ExitCode::NoVersionMatch as i32)
+            .with_stderr_contains("[..]rc[..]")
+    );
+}
+
+#[test]
+fn list_marks_prereleases() {
+    let s = sandbox()
+        .setup_node_binary("22.16.0", "10.9.2", "node 22")
+        .setup_node_binary(NIGHTLY_VERSION, "11.4.1", "node nightly")
+        .build();
+
+    assert_that!(
+        s.volta("list node --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("runtime node@22.16.0")
+            .with_stdout_contains(&format!(
+                "runtime node@{NIGHTLY_VERSION} prerelease nightly"
+            ))
+    );
+
+    assert_that!(
+        s.volta("list node --format json"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(r#"[..]"channel": "nightly"[..]"#)
+    );
+}
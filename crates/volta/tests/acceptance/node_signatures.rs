@@ -0,0 +1,160 @@
+//! Tests for `VOLTA_VERIFY_SIGNATURES`, which requires the published Node checksums to be signed
+//! by a trusted key. The fixtures are signed by a test key, which is added to the user's keyring.
+
+use crate::support::sandbox::{DistroMetadata, NodeFixture, SandboxBuilder, sandbox};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use nodejs_semver::Version;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+const NODE_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "10.99.1040",
+    compressed_size: 273,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
+const CHECKSUMS_PATH: &str = "/v10.99.1040/SHASUMS256.txt";
+const CHECKSUMS_FIXTURE: &str = "tests/fixtures/node-v10.99.1040-SHASUMS256.txt";
+
+/// A sandbox serving the signed checksums for every platform, without any signature
+fn signed_checksums() -> SandboxBuilder {
+    sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .fixture_mock(CHECKSUMS_PATH, CHECKSUMS_FIXTURE)
+        .file(
+            ".volta/keys/node/release.asc",
+            include_str!("../fixtures/node-release-test-key.asc"),
+        )
+        .env("VOLTA_VERIFY_SIGNATURES", "1")
+        .env("VOLTA_LOGLEVEL", "debug")
+}
+
+#[test]
+fn install_node_with_valid_signature() {
+    let s = signed_checksums()
+        .fixture_mock(
+            "/v10.99.1040/SHASUMS256.txt.sig",
+            "tests/fixtures/node-v10.99.1040-SHASUMS256.txt.sig",
+        )
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains(
+                "[..]Fetching Node checksums signature from [..]SHASUMS256.txt.sig"
+            )
+    );
+
+    assert!(s.node_inventory_archive_exists(&Version::parse("10.99.1040").unwrap()));
+}
+
+#[test]
+fn install_node_with_armored_signature() {
+    let s = signed_checksums()
+        .fixture_mock(
+            "/v10.99.1040/SHASUMS256.txt.asc",
+            "tests/fixtures/node-v10.99.1040-SHASUMS256.txt.asc",
+        )
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains(
+                "[..]Fetching Node checksums signature from [..]SHASUMS256.txt.asc"
+            )
+    );
+
+    assert!(s.node_inventory_archive_exists(&Version::parse("10.99.1040").unwrap()));
+}
+
+#[test]
+fn install_node_with_bad_signature() {
+    // A signature by the trusted key, but over different checksums
+    let s = signed_checksums()
+        .fixture_mock(
+            "/v10.99.1040/SHASUMS256.txt.sig",
+            "tests/fixtures/node-v10.99.1040-SHASUMS256.txt.tampered.sig",
+        )
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::UnknownError as i32)
+            .with_stderr_contains("[..]Signature verification failed for the Node checksums")
+    );
+
+    assert!(!s.node_inventory_archive_exists(&Version::parse("10.99.1040").unwrap()));
+}
+
+#[test]
+fn install_node_without_signature() {
+    let s = signed_checksums().build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download the signature for the Node checksums")
+    );
+
+    assert!(!s.node_inventory_archive_exists(&Version::parse("10.99.1040").unwrap()));
+}
+
+#[test]
+fn install_cached_node_uses_signed_recorded_checksum() {
+    let s = signed_checksums()
+        .fixture_mock(
+            "/v10.99.1040/SHASUMS256.txt.sig",
+            "tests/fixtures/node-v10.99.1040-SHASUMS256.txt.sig",
+        )
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    // The checksum was recorded after verifying its signature, so it is used as is
+    s.remove_node_image("10.99.1040");
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Using checksum recorded at [..]")
+            .with_stderr_does_not_contain("[..]Fetching Node checksums signature[..]")
+    );
+}
+
+#[test]
+fn install_cached_node_verifies_signature_of_unsigned_recorded_checksum() {
+    let s = signed_checksums().build();
+
+    // Without signature verification, the checksum is recorded without it
+    assert_that!(
+        s.volta("install node@10.99.1040")
+            .env("VOLTA_VERIFY_SIGNATURES", "0"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    s.remove_node_image("10.99.1040");
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]as its signature was not verified")
+            .with_stderr_contains("[..]Could not download the signature for the Node checksums")
+    );
+}
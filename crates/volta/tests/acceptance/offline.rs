@@ -0,0 +1,212 @@
+//! Tests for offline mode, which resolves tools from the local inventory.
+//!
+//! None of these tests create any mocks, so any network access would fail.
+
+use crate::support::sandbox::sandbox;
+use cfg_if::cfg_if;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PLATFORM_NODE: &str = r#"{
+    "node":{
+        "runtime":"11.10.1",
+        "npm":null
+    }
+}"#;
+
+const NODE_INDEX: &str = r#"[
+{"version":"v11.10.1","npm":"6.7.0","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]},
+{"version":"v10.99.1040","npm":"6.7.0","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]}
+]
+"#;
+
+fn tool_bin(tool: &str, version: &str) -> String {
+    cfg_if! {
+        if #[cfg(target_os = "windows")] {
+            format!("@echo off\necho {tool} version {version}\n")
+        } else {
+            format!("#!/bin/sh\necho \"{tool} version {version}\"\n")
+        }
+    }
+}
+
+#[test]
+fn install_node_semver_from_inventory() {
+    let s = sandbox()
+        .env("VOLTA_OFFLINE", "1")
+        .env("VOLTA_LOGLEVEL", "info")
+        .setup_node_binary("10.99.1040", "6.7.0", &tool_bin("node", "10.99.1040"))
+        .setup_node_binary("11.10.1", "6.7.0", &tool_bin("node", "11.10.1"))
+        .build();
+
+    assert_that!(
+        s.volta("install node@10"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]installed and set node@10.99.1040[..]")
+    );
+}
+
+#[test]
+fn install_node_latest_with_offline_flag() {
+    let s = sandbox()
+        .env("VOLTA_LOGLEVEL", "info")
+        .setup_node_binary("10.99.1040", "6.7.0", &tool_bin("node", "10.99.1040"))
+        .setup_node_binary("11.10.1", "6.7.0", &tool_bin("node", "11.10.1"))
+        .build();
+
+    assert_that!(
+        s.volta("install --offline node@latest"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]installed and set node@11.10.1[..]")
+    );
+}
+
+#[test]
+fn install_node_lts_from_expired_cache() {
+    let s = sandbox()
+        .env("VOLTA_OFFLINE", "1")
+        .env("VOLTA_LOGLEVEL", "info")
+        .node_cache(NODE_INDEX, true)
+        .setup_node_binary("10.99.1040", "6.7.0", &tool_bin("node", "10.99.1040"))
+        .setup_node_binary("11.10.1", "6.7.0", &tool_bin("node", "11.10.1"))
+        .build();
+
+    assert_that!(
+        s.volta("install node"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]installed and set node@10.99.1040[..]")
+    );
+}
+
+#[test]
+fn install_node_lts_line_from_expired_cache() {
+    let s = sandbox()
+        .env("VOLTA_OFFLINE", "1")
+        .env("VOLTA_LOGLEVEL", "info")
+        .node_cache(NODE_INDEX, true)
+        .setup_node_binary("10.99.1040", "6.7.0", &tool_bin("node", "10.99.1040"))
+        .setup_node_binary("11.10.1", "6.7.0", &tool_bin("node", "11.10.1"))
+        .build();
+
+    assert_that!(
+        s.volta("install node@lts/dubnium"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]installed and set node@10.99.1040[..]")
+    );
+
+    assert_that!(
+        s.volta("install node@lts/-1"),
+        execs()
+            .with_status(ExitCode::NoVersionMatch as i32)
+            .with_stderr_contains(
+                "[..]Could not find node version matching \"lts/-1\" in the local inventory."
+            )
+    );
+}
+
+#[test]
+fn install_node_lts_without_cache() {
+    let s = sandbox()
+        .env("VOLTA_OFFLINE", "1")
+        .setup_node_binary("10.99.1040", "6.7.0", &tool_bin("node", "10.99.1040"))
+        .build();
+
+    assert_that!(
+        s.volta("install node@lts"),
+        execs()
+            .with_status(ExitCode::NoVersionMatch as i32)
+            .with_stderr_contains(
+                "[..]Could not find node version matching \"lts\" in the local inventory."
+            )
+    );
+}
+
+#[test]
+fn install_node_semver_not_in_inventory() {
+    let s = sandbox()
+        .env("VOLTA_OFFLINE", "1")
+        .setup_node_binary("10.99.1040", "6.7.0", &tool_bin("node", "10.99.1040"))
+        .build();
+
+    assert_that!(
+        s.volta("install node@12"),
+        execs()
+            .with_status(ExitCode::NoVersionMatch as i32)
+            .with_stderr_contains(
+                "[..]Could not find node version matching \">=12.0.0 <13.0.0-0\" in the local inventory."
+            )
+    );
+}
+
+#[test]
+fn install_node_exact_not_in_inventory() {
+    let s = sandbox().env("VOLTA_OFFLINE", "1").build();
+
+    assert_that!(
+        s.volta("install node@12.0.0"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download node@12.0.0 while in offline mode.")
+    );
+}
+
+#[test]
+fn install_npm_from_inventory() {
+    let s = sandbox()
+        .env("VOLTA_OFFLINE", "1")
+        .env("VOLTA_LOGLEVEL", "info")
+        .platform(PLATFORM_NODE)
+        .setup_node_binary("11.10.1", "6.7.0", &tool_bin("node", "11.10.1"))
+        .setup_npm_binary("8.1.5", &tool_bin("npm", "8.1.5"))
+        .setup_npm_binary("9.0.0", &tool_bin("npm", "9.0.0"))
+        .build();
+
+    assert_that!(
+        s.volta("install npm@8"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]installed and set npm@8.1.5 as default")
+    );
+}
+
+#[test]
+fn install_npm_tag_offline() {
+    let s = sandbox()
+        .env("VOLTA_OFFLINE", "1")
+        .platform(PLATFORM_NODE)
+        .setup_node_binary("11.10.1", "6.7.0", &tool_bin("node", "11.10.1"))
+        .setup_npm_binary("8.1.5", &tool_bin("npm", "8.1.5"))
+        .build();
+
+    assert_that!(
+        s.volta("install npm@next"),
+        execs()
+            .with_status(ExitCode::NoVersionMatch as i32)
+            .with_stderr_contains(
+                "[..]Could not find npm version matching \"next\" in the local inventory."
+            )
+    );
+}
+
+#[test]
+fn volta_offline_disabled_by_zero() {
+    let s = sandbox()
+        .env("VOLTA_OFFLINE", "0")
+        .node_available_versions(NODE_INDEX)
+        .build();
+
+    // The versions are resolved from the registry rather than the local inventory
+    assert_that!(
+        s.volta("install node@12"),
+        execs()
+            .with_status(ExitCode::NoVersionMatch as i32)
+            .with_stderr_does_not_contain("[..]in the local inventory[..]")
+    );
+}
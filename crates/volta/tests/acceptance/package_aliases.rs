@@ -0,0 +1,287 @@
+//! Tests for versions of global packages installed alongside the default version with
+//! `volta install --as`, which run from package images keyed by name and version.
+
+use std::path::PathBuf;
+
+use crate::support::sandbox::{PackageBinInfo, Sandbox, SandboxBuilder, sandbox};
+use cfg_if::cfg_if;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PLATFORM_NODE_NPM: &str = r#"{
+    "node":{
+        "runtime":"11.10.1",
+        "npm":"6.7.0"
+    }
+}"#;
+
+const PKG_CONFIG_TYPESCRIPT: &str = r#"{
+  "name": "typescript",
+  "version": "5.4.5",
+  "platform": {
+    "node": "11.10.1",
+    "npm": "6.7.0",
+    "yarn": null
+  },
+  "bins": [
+    "tsc"
+  ],
+  "manager": "Npm"
+}"#;
+
+const PKG_CONFIG_TYPESCRIPT_ALIASED: &str = r#"{
+  "name": "typescript",
+  "version": "4.9.5",
+  "platform": {
+    "node": "11.10.1",
+    "npm": "6.7.0",
+    "yarn": null
+  },
+  "bins": [
+    "tsc4"
+  ],
+  "manager": "Npm",
+  "aliases": {
+    "tsc4": "tsc"
+  }
+}"#;
+
+const BIN_CONFIG_TSC: &str = r#"{
+  "name": "tsc",
+  "package": "typescript",
+  "version": "5.4.5",
+  "platform": {
+    "node": "11.10.1",
+    "npm": "6.7.0",
+    "yarn": null
+  },
+  "manager": "Npm"
+}"#;
+
+const BIN_CONFIG_TSC4: &str = r#"{
+  "name": "tsc4",
+  "package": "typescript",
+  "version": "4.9.5",
+  "platform": {
+    "node": "11.10.1",
+    "npm": "6.7.0",
+    "yarn": null
+  },
+  "manager": "Npm",
+  "alias_of": "tsc"
+}"#;
+
+fn script(name: &str, version: &str) -> String {
+    cfg_if! {
+        if #[cfg(target_os = "windows")] {
+            format!(
+                r#"@echo off
+echo {name} version {version}
+echo {name} args: %*
+"#
+            )
+        } else {
+            format!(
+                r#"#!/bin/sh
+echo "{name} version {version}"
+echo "{name} args: $@"
+"#
+            )
+        }
+    }
+}
+
+fn tsc_bin_info(version: &str) -> Vec<PackageBinInfo> {
+    vec![PackageBinInfo {
+        name: "tsc".to_string(),
+        contents: script("tsc", version),
+    }]
+}
+
+fn with_default_typescript() -> SandboxBuilder {
+    sandbox()
+        .platform(PLATFORM_NODE_NPM)
+        .package_config("typescript", PKG_CONFIG_TYPESCRIPT)
+        .binary_config("tsc", BIN_CONFIG_TSC)
+        .shim("tsc")
+        .package_image("typescript", "5.4.5", Some(tsc_bin_info("5.4.5")))
+        .setup_node_binary("11.10.1", "6.5.0", &script("Node", "11.10.1"))
+        .setup_npm_binary("6.7.0", &script("Npm", "6.7.0"))
+        .add_dir_to_path(PathBuf::from("/bin"))
+}
+
+fn with_aliased_typescript() -> SandboxBuilder {
+    with_default_typescript()
+        .package_config("typescript@4.9.5", PKG_CONFIG_TYPESCRIPT_ALIASED)
+        .binary_config("tsc4", BIN_CONFIG_TSC4)
+        .shim("tsc4")
+        .package_version_image("typescript", "4.9.5", tsc_bin_info("4.9.5"))
+}
+
+#[test]
+fn runs_aliased_version() {
+    let s = with_aliased_typescript().build();
+
+    assert_that!(
+        s.exec_shim("tsc4", "--noEmit"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("tsc version 4.9.5")
+            .with_stdout_contains("tsc args: --noEmit")
+    );
+
+    assert_that!(
+        s.exec_shim("tsc", "--noEmit"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("tsc version 5.4.5")
+    );
+}
+
+#[test]
+fn lists_all_installed_versions() {
+    let s = with_aliased_typescript().build();
+
+    assert_that!(
+        s.volta("list typescript --format plain"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("package typescript@5.4.5 / tsc / [..]")
+            .with_stdout_contains("package typescript@4.9.5 / tsc4 / [..]")
+    );
+}
+
+#[test]
+fn uninstall_removes_aliased_versions() {
+    let s = with_aliased_typescript()
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall typescript"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("Removed executable 'tsc' installed by 'typescript'")
+            .with_stdout_contains("Removed executable 'tsc4' installed by 'typescript'")
+            .with_stdout_contains("[..]package 'typescript' uninstalled")
+    );
+
+    assert!(!Sandbox::package_config_exists("typescript"));
+    assert!(!Sandbox::package_config_exists("typescript@4.9.5"));
+    assert!(!Sandbox::bin_config_exists("tsc4"));
+    assert!(!Sandbox::shim_exists("tsc4"));
+    assert!(!Sandbox::package_image_exists("typescript"));
+    assert!(!Sandbox::package_version_image_exists(
+        "typescript",
+        "4.9.5"
+    ));
+}
+
+#[test]
+fn rejects_aliases_for_several_tools() {
+    let s = with_default_typescript().build();
+
+    assert_that!(
+        s.volta("install typescript@4.9.5 eslint --as tsc4"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Only a single global package can be installed with `--as`")
+    );
+}
+
+// The stand-in for npm below writes the installed package with a shell script
+#[cfg(unix)]
+const NPM_INSTALLING_TYPESCRIPT: &str = r#"#!/bin/sh
+echo "npm args: $@"
+package="$npm_config_prefix/lib/node_modules/typescript"
+mkdir -p "$package" "$npm_config_prefix/bin"
+echo '{"name":"typescript","version":"4.9.5","bin":{"tsc":"bin/tsc"}}' > "$package/package.json"
+printf '#!/bin/sh\necho "tsc version 4.9.5"\n' > "$npm_config_prefix/bin/tsc"
+chmod +x "$npm_config_prefix/bin/tsc"
+"#;
+
+#[cfg(unix)]
+#[test]
+fn installs_version_alongside_default() {
+    let s = with_default_typescript()
+        .setup_npm_binary("6.7.0", NPM_INSTALLING_TYPESCRIPT)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("install typescript@4.9.5 --as tsc4"),
+        execs().with_status(0).with_stdout_contains(
+            "[..]installed typescript@4.9.5 alongside the default with executables: tsc4 (tsc)"
+        )
+    );
+
+    assert!(Sandbox::package_config_exists("typescript@4.9.5"));
+    assert!(Sandbox::bin_config_exists("tsc4"));
+    assert!(Sandbox::package_version_image_exists("typescript", "4.9.5"));
+
+    assert_that!(
+        s.exec_shim("tsc4", ""),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("tsc version 4.9.5")
+    );
+    assert_that!(
+        s.exec_shim("tsc", ""),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("tsc version 5.4.5")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn rejects_alias_of_default_executable() {
+    let s = with_default_typescript()
+        .setup_npm_binary("6.7.0", NPM_INSTALLING_TYPESCRIPT)
+        .build();
+
+    assert_that!(
+        s.volta("install typescript@4.9.5 --as tsc=tsc"),
+        execs()
+            .with_status(ExitCode::FileSystemError as i32)
+            .with_stderr_contains("[..]Executable 'tsc' is already installed by typescript")
+    );
+
+    // The default version is kept
+    assert_that!(
+        s.exec_shim("tsc", ""),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("tsc version 5.4.5")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn replacing_default_keeps_aliased_versions() {
+    let s = with_aliased_typescript()
+        .setup_npm_binary("6.7.0", NPM_INSTALLING_TYPESCRIPT)
+        .build();
+
+    assert_that!(s.volta("install typescript@4.9.5"), execs().with_status(0));
+
+    // The default version now runs from the image of its version, shared with the alias
+    assert!(!Sandbox::package_image_exists("typescript"));
+    assert!(Sandbox::package_version_image_exists("typescript", "4.9.5"));
+
+    assert_that!(
+        s.exec_shim("tsc", ""),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("tsc version 4.9.5")
+    );
+    assert_that!(
+        s.exec_shim("tsc4", ""),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("tsc version 4.9.5")
+    );
+}
@@ -0,0 +1,229 @@
+//! Tests for the Corepack `packageManager` field, as a source of the package manager version
+//! and of a hash to verify its archive against.
+
+use crate::support::sandbox::{DistroMetadata, NodeFixture, Yarn1Fixture, sandbox};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+// The SHA-512 digest of `tests/fixtures/yarn-1.2.42.tgz`
+const YARN_SHA512: &str = "174b0f06c64e225d812f26d0097938a94c8b3d99b963a5ea5eea0cf29b6a66650625445fb1c7b3f897e625acdcdc977a1ba57120a5331c72226ee83e9a64c507";
+
+const PLATFORM_NODE_ONLY: &str = r#"{
+    "node":{
+        "runtime":"9.27.6",
+        "npm":null
+    }
+}"#;
+
+const YARN_1_VERSION_INFO: &str = r#"{
+    "name":"yarn",
+    "dist-tags": { "latest": "1.2.42" },
+    "versions": {
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"a04472b4febb093141993a289b7253bfffba11db", "tarball":"" }}
+    }
+}"#;
+
+const YARN_1_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "1.2.42",
+    compressed_size: 174,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "macos")] {
+        const NODE_VERSION_FIXTURES: [DistroMetadata; 2] = [
+            DistroMetadata {
+                version: "10.99.1040",
+                compressed_size: 273,
+                uncompressed_size: Some(0x0028_0000),
+            },
+            DistroMetadata {
+                version: "9.27.6",
+                compressed_size: 272,
+                uncompressed_size: Some(0x0028_0000),
+            },
+        ];
+    } else if #[cfg(target_os = "linux")] {
+        const NODE_VERSION_FIXTURES: [DistroMetadata; 2] = [
+            DistroMetadata {
+                version: "10.99.1040",
+                compressed_size: 273,
+                uncompressed_size: Some(0x0028_0000),
+            },
+            DistroMetadata {
+                version: "9.27.6",
+                compressed_size: 272,
+                uncompressed_size: Some(0x0028_0000),
+            },
+        ];
+    } else if #[cfg(target_os = "windows")] {
+        const NODE_VERSION_FIXTURES: [DistroMetadata; 2] = [
+            DistroMetadata {
+                version: "10.99.1040",
+                compressed_size: 1096,
+                uncompressed_size: None,
+            },
+            DistroMetadata {
+                version: "9.27.6",
+                compressed_size: 1068,
+                uncompressed_size: None,
+            },
+        ];
+    } else {
+        compile_error!("Unsupported target_os for tests (expected 'macos', 'linux', or 'windows').");
+    }
+}
+
+/// A package.json with the given `packageManager` field, and the given `volta` key if any
+fn package_json(package_manager: &str, volta: Option<&str>) -> String {
+    let volta = volta.map_or_else(String::new, |volta| format!(",\n    \"volta\": {volta}"));
+    format!(
+        r#"{{
+    "name": "corepack",
+    "packageManager": "{package_manager}"{volta}
+}}"#
+    )
+}
+
+#[test]
+fn uses_yarn_from_package_manager_field() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(&package_json(
+            "yarn@1.2.42",
+            Some(r#"{ "node": "10.99.1040" }"#),
+        ))
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
+        .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.yarn("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 10.99.1040 from project configuration")
+            .with_stderr_contains("[..]Yarn: 1.2.42 from project configuration")
+    );
+}
+
+#[test]
+fn uses_yarn_from_package_manager_field_without_volta_key() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(&package_json("yarn@1.2.42", None))
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
+        .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.yarn("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 9.27.6 from default configuration")
+            .with_stderr_contains("[..]Yarn: 1.2.42 from project configuration")
+    );
+}
+
+#[test]
+fn volta_key_takes_precedence_with_warning() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(&package_json(
+            "yarn@1.7.71",
+            Some(r#"{ "node": "10.99.1040", "yarn": "1.2.42" }"#),
+        ))
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
+        .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.yarn("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains(
+                "[..]The `packageManager` field in [..] asks for yarn@1.7.71, but the `volta` key pins yarn@1.2.42, which takes precedence."
+            )
+            .with_stderr_contains("[..]Yarn: 1.2.42 from project configuration")
+    );
+}
+
+#[test]
+fn verifies_matching_hash() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(&package_json(
+            &format!("yarn@1.2.42+sha512.{YARN_SHA512}"),
+            Some(r#"{ "node": "10.99.1040" }"#),
+        ))
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
+        .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.yarn("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains(
+                "[..]Verified Yarn archive against the `packageManager` hash (sha512.174b0f[..])"
+            )
+    );
+    assert!(s.yarn_inventory_archive_exists("1.2.42"));
+}
+
+#[test]
+fn rejects_mismatched_hash() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(&package_json(
+            &format!("yarn@1.2.42+sha1.{}", "0".repeat(40)),
+            Some(r#"{ "node": "10.99.1040" }"#),
+        ))
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
+        .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.yarn("--version"),
+        execs()
+            .with_status(ExitCode::ExecutionFailure as i32)
+            .with_stderr_contains(
+                "[..]Yarn v1.2.42 does not match the hash in the `packageManager` field of package.json"
+            )
+            .with_stderr_contains("Expected: sha1.0000000000000000000000000000000000000000")
+            .with_stderr_contains("Actual:   sha1.a04472b4febb093141993a289b7253bfffba11db")
+    );
+    assert!(!s.yarn_inventory_archive_exists("1.2.42"));
+}
+
+#[test]
+fn pin_leaves_field_when_not_interactive() {
+    let s = sandbox()
+        .package_json(&package_json(
+            "yarn@1.7.71",
+            Some(r#"{ "node": "10.99.1040" }"#),
+        ))
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
+        .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin yarn@1.2.42"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    let manifest = s.read_package_json();
+    assert!(manifest.contains(r#""packageManager": "yarn@1.7.71""#));
+    assert!(manifest.contains(r#""yarn": "1.2.42""#));
+}
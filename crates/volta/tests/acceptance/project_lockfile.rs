@@ -0,0 +1,189 @@
+//! Tests for ranges and tags in the `volta` key, which are resolved once and recorded in the
+//! project lockfile, `volta.lock`, along with the checksums of their archives.
+
+use std::fs;
+
+use crate::support::sandbox::{DistroMetadata, NodeFixture, Yarn1Fixture, sandbox};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+// The Subresource Integrity form of the `shasum` published for Yarn 1.2.42
+const YARN_INTEGRITY: &str = "sha1-oERytP67CTFBmToom3JTv/+6Eds=";
+
+const PLATFORM_NODE_ONLY: &str = r#"{
+    "node":{
+        "runtime":"9.27.6",
+        "npm":null
+    }
+}"#;
+
+const PACKAGE_JSON_WITH_YARN_RANGE: &str = r#"{
+    "name": "locked",
+    "volta": {
+        "node": "10.99.1040",
+        "yarn": "^1.2"
+    }
+}"#;
+
+const YARN_1_VERSION_INFO: &str = r#"{
+    "name":"yarn",
+    "dist-tags": { "latest": "1.2.42" },
+    "versions": {
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"a04472b4febb093141993a289b7253bfffba11db", "tarball":"" }}
+    }
+}"#;
+
+const YARN_1_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "1.2.42",
+    compressed_size: 174,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "macos")] {
+        const NODE_VERSION_FIXTURES: [DistroMetadata; 1] = [
+            DistroMetadata {
+                version: "10.99.1040",
+                compressed_size: 273,
+                uncompressed_size: Some(0x0028_0000),
+            },
+        ];
+    } else if #[cfg(target_os = "linux")] {
+        const NODE_VERSION_FIXTURES: [DistroMetadata; 1] = [
+            DistroMetadata {
+                version: "10.99.1040",
+                compressed_size: 273,
+                uncompressed_size: Some(0x0028_0000),
+            },
+        ];
+    } else if #[cfg(target_os = "windows")] {
+        const NODE_VERSION_FIXTURES: [DistroMetadata; 1] = [
+            DistroMetadata {
+                version: "10.99.1040",
+                compressed_size: 1096,
+                uncompressed_size: None,
+            },
+        ];
+    } else {
+        compile_error!("Unsupported target_os for tests (expected 'macos', 'linux', or 'windows').");
+    }
+}
+
+/// A lockfile which records the given checksum for Yarn 1.2.42
+fn yarn_lockfile(integrity: &str) -> String {
+    format!(
+        r#"{{
+  "yarn": {{
+    "requested": "^1.2",
+    "version": "1.2.42",
+    "checksums": {{
+      "yarn-v1.2.42.tar.gz": "{integrity}"
+    }}
+  }}
+}}
+"#
+    )
+}
+
+#[test]
+fn resolves_range_into_lockfile() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_WITH_YARN_RANGE)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
+        .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.yarn("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Wrote project lockfile to [..]volta.lock")
+            .with_stderr_contains("[..]Yarn: 1.2.42 from project configuration")
+    );
+
+    assert_eq!(
+        fs::read_to_string(s.root().join("volta.lock")).unwrap(),
+        yarn_lockfile(YARN_INTEGRITY)
+    );
+}
+
+#[test]
+fn uses_locked_version() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_WITH_YARN_RANGE)
+        .project_file("volta.lock", &yarn_lockfile(YARN_INTEGRITY))
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
+        .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.yarn("--version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Verified published checksum of Yarn v1.2.42 against [..]")
+            .with_stderr_contains("[..]Yarn: 1.2.42 from project configuration")
+            .with_stderr_does_not_contain("[..]Wrote project lockfile[..]")
+    );
+    assert!(s.yarn_inventory_archive_exists("1.2.42"));
+}
+
+#[test]
+fn rejects_mismatched_checksum() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_json(PACKAGE_JSON_WITH_YARN_RANGE)
+        .project_file(
+            "volta.lock",
+            &yarn_lockfile("sha1-AAAAAAAAAAAAAAAAAAAAAAAAAAA="),
+        )
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
+        .distro_mocks::<Yarn1Fixture>(&YARN_1_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.yarn("--version"),
+        execs()
+            .with_status(ExitCode::ExecutionFailure as i32)
+            .with_stderr_contains(
+                "[..]The published checksum of Yarn v1.2.42 does not match the one in [..]volta.lock"
+            )
+            .with_stderr_contains("Expected: sha1-AAAAAAAAAAAAAAAAAAAAAAAAAAA=")
+            .with_stderr_contains(&format!("Actual:   {YARN_INTEGRITY}"))
+    );
+    assert!(!s.yarn_inventory_archive_exists("1.2.42"));
+}
+
+#[test]
+fn pin_refresh_records_current_checksums() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_WITH_YARN_RANGE)
+        .project_file(
+            "volta.lock",
+            &yarn_lockfile("sha1-AAAAAAAAAAAAAAAAAAAAAAAAAAA="),
+        )
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("pin --refresh"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]locked yarn@^1.2 to yarn@1.2.42")
+    );
+
+    assert_eq!(
+        fs::read_to_string(s.root().join("volta.lock")).unwrap(),
+        yarn_lockfile(YARN_INTEGRITY)
+    );
+}
@@ -0,0 +1,354 @@
+//! Tests for the packages in the `volta.tools` map of a project, which are installed into
+//! versioned package images and take the place of the default version inside the project.
+
+use std::path::PathBuf;
+
+use crate::support::sandbox::{PackageBinInfo, Sandbox, SandboxBuilder, sandbox};
+use cfg_if::cfg_if;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PLATFORM_NODE_NPM: &str = r#"{
+    "node":{
+        "runtime":"11.10.1",
+        "npm":"6.7.0"
+    }
+}"#;
+
+const PKG_CONFIG_TYPESCRIPT: &str = r#"{
+  "name": "typescript",
+  "version": "5.4.5",
+  "platform": {
+    "node": "11.10.1",
+    "npm": "6.7.0",
+    "yarn": null
+  },
+  "bins": [
+    "tsc"
+  ],
+  "manager": "Npm"
+}"#;
+
+const BIN_CONFIG_TSC: &str = r#"{
+  "name": "tsc",
+  "package": "typescript",
+  "version": "5.4.5",
+  "platform": {
+    "node": "11.10.1",
+    "npm": "6.7.0",
+    "yarn": null
+  },
+  "manager": "Npm"
+}"#;
+
+const PKG_CONFIG_TYPESCRIPT_ALIASED: &str = r#"{
+  "name": "typescript",
+  "version": "4.9.5",
+  "platform": {
+    "node": "11.10.1",
+    "npm": "6.7.0",
+    "yarn": null
+  },
+  "bins": [
+    "tsc4"
+  ],
+  "manager": "Npm",
+  "aliases": {
+    "tsc4": "tsc"
+  }
+}"#;
+
+const BIN_CONFIG_TSC4: &str = r#"{
+  "name": "tsc4",
+  "package": "typescript",
+  "version": "4.9.5",
+  "platform": {
+    "node": "11.10.1",
+    "npm": "6.7.0",
+    "yarn": null
+  },
+  "manager": "Npm",
+  "alias_of": "tsc"
+}"#;
+
+const PACKAGE_JSON_WITH_TOOLS: &str = r#"{
+    "name": "legacy-service",
+    "volta": {
+        "node": "10.99.1040",
+        "npm": "6.7.0",
+        "tools": {
+            "tsc-tool": "typescript@4.9.5"
+        }
+    }
+}"#;
+
+const PACKAGE_JSON_WITH_INVALID_TOOLS: &str = r#"{
+    "name": "legacy-service",
+    "volta": {
+        "node": "10.99.1040",
+        "tools": {
+            "tsc-tool": "typescript@^4"
+        }
+    }
+}"#;
+
+const TYPESCRIPT_METADATA: &str = r#"{
+    "name": "typescript",
+    "dist-tags": { "latest": "5.4.5" },
+    "versions": {
+        "4.9.5": {
+            "version": "4.9.5",
+            "bin": { "tsc": "bin/tsc", "tsserver": "bin/tsserver" },
+            "dist": { "shasum": "", "tarball": "" }
+        },
+        "5.4.5": {
+            "version": "5.4.5",
+            "bin": { "tsc": "bin/tsc", "tsserver": "bin/tsserver" },
+            "dist": { "shasum": "", "tarball": "" }
+        }
+    }
+}"#;
+
+// A version that doesn't provide `tsc`, to check that it isn't installed to look for it
+const TYPESCRIPT_METADATA_WITHOUT_TSC: &str = r#"{
+    "name": "typescript",
+    "dist-tags": { "latest": "5.4.5" },
+    "versions": {
+        "4.9.5": {
+            "version": "4.9.5",
+            "bin": { "tsserver": "bin/tsserver" },
+            "dist": { "shasum": "", "tarball": "" }
+        }
+    }
+}"#;
+
+fn script(name: &str, version: &str) -> String {
+    cfg_if! {
+        if #[cfg(target_os = "windows")] {
+            format!(
+                r#"@echo off
+echo {name} version {version}
+echo {name} args: %*
+"#
+            )
+        } else {
+            format!(
+                r#"#!/bin/sh
+echo "{name} version {version}"
+echo "{name} args: $@"
+"#
+            )
+        }
+    }
+}
+
+fn tsc_bin_info(version: &str) -> Vec<PackageBinInfo> {
+    vec![PackageBinInfo {
+        name: "tsc".to_string(),
+        contents: script("tsc", version),
+    }]
+}
+
+fn with_default_typescript() -> SandboxBuilder {
+    sandbox()
+        .platform(PLATFORM_NODE_NPM)
+        .package_config("typescript", PKG_CONFIG_TYPESCRIPT)
+        .binary_config("tsc", BIN_CONFIG_TSC)
+        .shim("tsc")
+        .package_image("typescript", "5.4.5", Some(tsc_bin_info("5.4.5")))
+        .setup_node_binary("11.10.1", "6.7.0", &script("Node", "11.10.1"))
+        .setup_node_binary("10.99.1040", "6.7.0", &script("Node", "10.99.1040"))
+        .setup_npm_binary("6.7.0", &script("Npm", "6.7.0"))
+        .add_dir_to_path(PathBuf::from("/bin"))
+}
+
+#[test]
+fn runs_project_tool_version() {
+    let s = with_default_typescript()
+        .package_json(PACKAGE_JSON_WITH_TOOLS)
+        .project_tool_image("typescript", "4.9.5", tsc_bin_info("4.9.5"))
+        .build();
+
+    assert_that!(
+        s.exec_shim("tsc", "--noEmit"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("tsc version 4.9.5")
+            .with_stdout_contains("tsc args: --noEmit")
+    );
+}
+
+#[test]
+fn runs_default_version_outside_project() {
+    let s = with_default_typescript()
+        .project_tool_image("typescript", "4.9.5", tsc_bin_info("4.9.5"))
+        .build();
+
+    assert_that!(
+        s.exec_shim("tsc", "--noEmit"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("tsc version 5.4.5")
+    );
+}
+
+#[test]
+fn uninstall_keeps_project_tool_image() {
+    // The same version is installed globally under an alias and as a project tool
+    let s = with_default_typescript()
+        .package_json(PACKAGE_JSON_WITH_TOOLS)
+        .package_config("typescript@4.9.5", PKG_CONFIG_TYPESCRIPT_ALIASED)
+        .binary_config("tsc4", BIN_CONFIG_TSC4)
+        .shim("tsc4")
+        .package_version_image("typescript", "4.9.5", tsc_bin_info("4.9.5"))
+        .project_tool_image("typescript", "4.9.5", tsc_bin_info("4.9.5"))
+        .build();
+
+    assert_that!(s.volta("uninstall typescript"), execs().with_status(0));
+    assert!(!Sandbox::package_version_image_exists(
+        "typescript",
+        "4.9.5"
+    ));
+    assert!(Sandbox::project_tool_image_exists("typescript", "4.9.5"));
+
+    // The shim of the default version is removed, but the project tool still runs
+    assert_that!(
+        s.volta("run tsc --noEmit"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("tsc version 4.9.5")
+    );
+}
+
+#[test]
+fn rejects_invalid_tools() {
+    let s = with_default_typescript()
+        .package_json(PACKAGE_JSON_WITH_INVALID_TOOLS)
+        .build();
+
+    assert_that!(
+        s.exec_shim("tsc", "--noEmit"),
+        execs()
+            .with_status(ExitCode::ExecutionFailure as i32)
+            .with_stderr_contains(
+                "[..]Invalid version of tool 'tsc-tool' in project manifest: 'typescript@^4'"
+            )
+    );
+}
+
+// The stand-in for npm below writes the installed package with a shell script
+#[cfg(unix)]
+const NPM_INSTALLING_TYPESCRIPT: &str = r#"#!/bin/sh
+echo "npm args: $@"
+package="$npm_config_prefix/lib/node_modules/typescript"
+mkdir -p "$package" "$npm_config_prefix/bin"
+echo '{"name":"typescript","version":"4.9.5","bin":{"tsc":"bin/tsc"}}' > "$package/package.json"
+printf '#!/bin/sh\necho "tsc version 4.9.5"\n' > "$npm_config_prefix/bin/tsc"
+chmod +x "$npm_config_prefix/bin/tsc"
+"#;
+
+#[cfg(unix)]
+#[test]
+fn installs_project_tool_on_first_use() {
+    let s = with_default_typescript()
+        .package_json(PACKAGE_JSON_WITH_TOOLS)
+        .package_available_versions("typescript", TYPESCRIPT_METADATA)
+        .setup_npm_binary("6.7.0", NPM_INSTALLING_TYPESCRIPT)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    assert_that!(
+        s.exec_shim("tsc", ""),
+        execs()
+            .with_status(0)
+            .with_stderr_contains("[..]Installing typescript@4.9.5 from the project tools")
+            .with_stdout_contains("tsc version 4.9.5")
+    );
+
+    // The second run uses the installed image
+    assert_that!(
+        s.exec_shim("tsc", ""),
+        execs()
+            .with_status(0)
+            .with_stderr_does_not_contain("[..]Installing typescript@4.9.5[..]")
+            .with_stdout_contains("tsc version 4.9.5")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn skips_project_tool_without_bin() {
+    let s = with_default_typescript()
+        .package_json(PACKAGE_JSON_WITH_TOOLS)
+        .package_available_versions("typescript", TYPESCRIPT_METADATA_WITHOUT_TSC)
+        .setup_npm_binary("6.7.0", NPM_INSTALLING_TYPESCRIPT)
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+
+    // The registry metadata shows that the project tool doesn't provide the bin, so the default
+    // version runs without installing it
+    assert_that!(
+        s.exec_shim("tsc", ""),
+        execs()
+            .with_status(0)
+            .with_stderr_does_not_contain("[..]Installing typescript@4.9.5[..]")
+            .with_stdout_contains("tsc version 5.4.5")
+    );
+    assert!(!Sandbox::project_tool_image_exists("typescript", "4.9.5"));
+}
+
+#[cfg(unix)]
+#[test]
+fn install_creates_shims_for_project_tools() {
+    // Without a default install of the package, there is no shim for its bin yet
+    let s = sandbox()
+        .platform(PLATFORM_NODE_NPM)
+        .package_json(PACKAGE_JSON_WITH_TOOLS)
+        .setup_node_binary("11.10.1", "6.7.0", &script("Node", "11.10.1"))
+        .setup_node_binary("10.99.1040", "6.7.0", &script("Node", "10.99.1040"))
+        .setup_npm_binary("6.7.0", NPM_INSTALLING_TYPESCRIPT)
+        .add_dir_to_path(PathBuf::from("/bin"))
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+    assert!(!Sandbox::shim_exists("tsc"));
+
+    assert_that!(
+        s.volta("install"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(
+                "[..]installed typescript@4.9.5 for this project with executables: tsc"
+            )
+    );
+    assert!(Sandbox::shim_exists("tsc"));
+
+    assert_that!(
+        s.exec_shim("tsc", ""),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("tsc version 4.9.5")
+    );
+
+    // Running it again keeps the installed version
+    assert_that!(
+        s.volta("install"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]typescript@4.9.5 is already installed for this project")
+    );
+}
+
+#[test]
+fn install_without_tools_outside_project() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("install"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]No tools to install.")
+    );
+}
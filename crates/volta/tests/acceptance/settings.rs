@@ -0,0 +1,121 @@
+//! Tests for the Node mirror and npm registry configured in `settings.json`.
+
+use std::path::PathBuf;
+
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use mockito::Matcher;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]}
+]
+"#;
+
+fn settings_json(node_mirror: &str, npm_registry: &str) -> String {
+    format!(
+        r#"{{
+    "nodeMirror": "{node_mirror}",
+    "npmRegistry": "{npm_registry}"
+}}"#
+    )
+}
+
+#[test]
+fn uses_node_mirror_from_default_settings() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
+        .default_settings(&settings_json(
+            &format!("{server_url}/mirror/"),
+            &server_url,
+        ))
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+    let _index = s
+        .mock("GET", "/mirror/index.json")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(NODE_VERSION_INFO)
+        .create();
+
+    assert_that!(
+        s.volta("install node@10"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains(
+                "[..]Found node@10.99.1040 matching requirement[..]/mirror/index.json"
+            )
+            .with_stderr_contains("[..]/mirror/v10.99.1040/SHASUMS256.txt")
+    );
+}
+
+#[test]
+fn project_settings_override_default_settings() {
+    let project_settings: PathBuf = [".volta", "settings.json"].iter().collect();
+    let s = sandbox();
+    let server_url = s.server_url();
+    let s = s
+        .package_json("{}")
+        .project_file(
+            &project_settings.to_string_lossy(),
+            &settings_json(&server_url, &format!("{server_url}/project-registry")),
+        )
+        .build();
+
+    assert_that!(
+        s.volta("install npm@8.1.5"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download npm version registry")
+            .with_stderr_contains("[..]/project-registry/npm")
+    );
+}
+
+#[test]
+fn uses_http_proxy_from_settings() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
+        .default_settings(&format!(
+            r#"{{
+    "npmRegistry": "http://registry.test",
+    "httpProxy": "{server_url}"
+}}"#
+        ))
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+    // The registry host does not resolve, so the request must go through the proxy
+    let registry = s
+        .mock("GET", Matcher::Regex("/npm$".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"name":"npm","dist-tags":{"latest":"8.1.5"},"versions":{}}"#)
+        .create();
+
+    assert_that!(
+        s.volta("install npm@9"),
+        execs()
+            .with_status(ExitCode::NoVersionMatch as i32)
+            .with_stderr_contains(&format!("[..]Using proxy {server_url}/"))
+    );
+
+    registry.assert();
+}
+
+#[test]
+fn rejects_invalid_proxy_in_settings() {
+    let s = sandbox()
+        .default_settings(r#"{ "httpsProxy": "socks5://proxy.example.com" }"#)
+        .build();
+
+    assert_that!(
+        s.volta("install npm@9"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]Invalid proxy URL: socks5://proxy.example.com")
+    );
+}
@@ -4,7 +4,7 @@ use crate::support::sandbox::Sandbox;
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
 
-use volta_core::event::{Event, EventKind};
+use volta_core::event::{Event, Kind as EventKind};
 
 pub enum EventKindMatcher<'a> {
     Start,
@@ -18,7 +18,7 @@ pub fn match_start() -> EventKindMatcher<'static> {
     EventKindMatcher::Start
 }
 
-pub fn match_error(exit_code: i32, error: &str) -> EventKindMatcher {
+pub fn match_error(exit_code: i32, error: &str) -> EventKindMatcher<'_> {
     EventKindMatcher::Error { exit_code, error }
 }
 
@@ -30,7 +30,7 @@ pub fn match_tool_end(exit_code: i32) -> EventKindMatcher<'static> {
     EventKindMatcher::ToolEnd { exit_code }
 }
 
-pub fn match_args(argv: &str) -> EventKindMatcher {
+pub fn match_args(argv: &str) -> EventKindMatcher<'_> {
     EventKindMatcher::Args { argv }
 }
 
@@ -7,42 +7,34 @@ use std::time::{Duration, SystemTime};
 
 use cfg_if::cfg_if;
 use headers::{Expires, Header};
-use mockito::{self, Matcher, mock};
+use mockito::{self, Matcher, Mock, Server, ServerGuard};
 use nodejs_semver::Version;
-use test_support::{self, ok_or_panic, paths, paths::PathExt, process::ProcessBuilder};
+use sha2::{Digest, Sha256};
+use test_support::{self, ProcessBuilder, ok_or_panic, paths, paths::PathExt};
 use volta_core::fs::{set_executable, symlink_file};
 use volta_core::tool::{Node, Pnpm, Yarn};
 
-// version cache for node and yarn
+// registry metadata cache entry, keyed by URL
 #[derive(PartialEq, Clone)]
 struct CacheBuilder {
-    path: PathBuf,
-    expiry_path: PathBuf,
+    url: String,
     contents: String,
     expired: bool,
 }
 
 impl CacheBuilder {
-    #[allow(dead_code)]
-    pub fn new(path: PathBuf, expiry_path: PathBuf, contents: &str, expired: bool) -> CacheBuilder {
+    pub fn new(url: String, contents: &str, expired: bool) -> CacheBuilder {
         CacheBuilder {
-            path,
-            expiry_path,
+            url,
             contents: contents.to_string(),
             expired,
         }
     }
 
     fn build(&self) {
-        self.dirname().mkdir_p();
-
-        // write cache file
-        let mut cache_file = File::create(&self.path).unwrap_or_else(|e| {
-            panic!("could not create cache file {}: {}", self.path.display(), e)
-        });
-        ok_or_panic! { cache_file.write_all(self.contents.as_bytes()) };
+        let path = http_cache_file(&self.url);
+        path.parent().unwrap().mkdir_p();
 
-        // write expiry file
         let one_day = Duration::from_secs(24 * 60 * 60);
         let expiry_date = Expires::from(if self.expired {
             SystemTime::now() - one_day
@@ -54,20 +46,19 @@ impl CacheBuilder {
         expiry_date.encode(&mut header_values);
         // Since we just `.encode()`d into `header_values, it is guaranteed to
         // have a `.first()`.
-        let encoded_expiry_date = header_values.first().unwrap();
-
-        let mut expiry_file = File::create(&self.expiry_path).unwrap_or_else(|e| {
-            panic!(
-                "could not create cache expiry file {}: {}",
-                self.expiry_path.display(),
-                e
+        let encoded_expiry_date = header_values.first().unwrap().to_str().unwrap();
+
+        // the cache entry is a line of metadata, followed by the response body
+        let mut cache_file = File::create(&path)
+            .unwrap_or_else(|e| panic!("could not create cache file {}: {}", path.display(), e));
+        ok_or_panic! {
+            writeln!(
+                cache_file,
+                r#"{{"url":"{}","expires":"{}"}}"#,
+                self.url, encoded_expiry_date
             )
-        });
-        ok_or_panic! { expiry_file.write_all(encoded_expiry_date.as_bytes()) };
-    }
-
-    fn dirname(&self) -> &Path {
-        self.path.parent().unwrap()
+        };
+        ok_or_panic! { cache_file.write_all(self.contents.as_bytes()) };
     }
 }
 
@@ -159,6 +150,11 @@ pub trait DistroFixture: From<DistroMetadata> {
     fn server_path(&self) -> String;
     fn fixture_path(&self) -> String;
     fn metadata(&self) -> &DistroMetadata;
+
+    /// The server path of the published checksums for this distro, if the tool has them
+    fn checksums_path(&self) -> Option<String> {
+        None
+    }
 }
 
 #[derive(Clone)]
@@ -234,6 +230,10 @@ impl DistroFixture for NodeFixture {
     fn metadata(&self) -> &DistroMetadata {
         &self.metadata
     }
+
+    fn checksums_path(&self) -> Option<String> {
+        Some(format!("/v{}/SHASUMS256.txt", self.metadata.version))
+    }
 }
 
 impl DistroFixture for NpmFixture {
@@ -301,16 +301,32 @@ impl SandboxBuilder {
         self.root.root()
     }
 
+    /// URL of the mock server for the sandbox, ex: `http://127.0.0.1:1234`
+    pub fn server_url(&self) -> String {
+        self.root.server_url()
+    }
+
     pub fn new(root: PathBuf) -> SandboxBuilder {
+        let server = Server::new();
+        // Point Volta at the mock server for the Node distribution and npm registry
+        let settings = FileBuilder::new(
+            default_settings_file(),
+            &format!(
+                r#"{{"nodeMirror":"{0}","npmRegistry":"{0}"}}"#,
+                server.url()
+            ),
+        );
+
         SandboxBuilder {
             root: Sandbox {
                 root,
+                server,
                 mocks: vec![],
                 env_vars: vec![],
                 env_vars_remove: vec![],
                 path: OsString::new(),
             },
-            files: vec![],
+            files: vec![settings],
             caches: vec![],
             path_dirs: vec![volta_bin_dir()],
             shims: vec![
@@ -322,15 +338,10 @@ impl SandboxBuilder {
         }
     }
 
-    #[allow(dead_code)]
-    /// Set the Node cache for the sandbox (chainable)
+    /// Set the cached Node index for the sandbox (chainable)
     pub fn node_cache(mut self, cache: &str, expired: bool) -> Self {
-        self.caches.push(CacheBuilder::new(
-            node_index_file(),
-            node_index_expiry_file(),
-            cache,
-            expired,
-        ));
+        let url = format!("{}/index.json", self.server_url());
+        self.caches.push(CacheBuilder::new(url, cache, expired));
         self
     }
 
@@ -355,6 +366,13 @@ impl SandboxBuilder {
         self
     }
 
+    /// Set the settings.json for the sandbox, replacing the default mock server settings
+    pub fn default_settings(mut self, contents: &str) -> Self {
+        self.files
+            .push(FileBuilder::new(default_settings_file(), contents));
+        self
+    }
+
     /// Set a layout version file for the sandbox (chainable)
     pub fn layout_file(mut self, version: &str) -> Self {
         self.files.push(FileBuilder::new(layout_file(version), ""));
@@ -368,7 +386,10 @@ impl SandboxBuilder {
 
     /// Setup mock to return the available node versions (chainable)
     pub fn node_available_versions(mut self, body: &str) -> Self {
-        let mock = mock("GET", "/node-dist/index.json")
+        let mock = self
+            .root
+            .server
+            .mock("GET", "/index.json")
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(body)
@@ -380,7 +401,10 @@ impl SandboxBuilder {
 
     /// Setup mock to return the available Yarn@1 versions (chainable)
     pub fn yarn_1_available_versions(mut self, body: &str) -> Self {
-        let mock = mock("GET", "/yarn")
+        let mock = self
+            .root
+            .server
+            .mock("GET", "/yarn")
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(body)
@@ -391,7 +415,10 @@ impl SandboxBuilder {
 
     /// Setup mock to return the available Yarn@2+ versions (chainable)
     pub fn yarn_berry_available_versions(mut self, body: &str) -> Self {
-        let mock = mock("GET", "/@yarnpkg/cli-dist")
+        let mock = self
+            .root
+            .server
+            .mock("GET", "/@yarnpkg/cli-dist")
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(body)
@@ -402,7 +429,10 @@ impl SandboxBuilder {
 
     /// Setup mock to return the available npm versions (chainable)
     pub fn npm_available_versions(mut self, body: &str) -> Self {
-        let mock = mock("GET", "/npm")
+        let mock = self
+            .root
+            .server
+            .mock("GET", "/npm")
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(body)
@@ -414,7 +444,25 @@ impl SandboxBuilder {
 
     /// Setup mock to return the available pnpm versions (chainable)
     pub fn pnpm_available_versions(mut self, body: &str) -> Self {
-        let mock = mock("GET", "/pnpm")
+        let mock = self
+            .root
+            .server
+            .mock("GET", "/pnpm")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create();
+        self.root.mocks.push(mock);
+
+        self
+    }
+
+    /// Setup mock to return the available versions of a package in the npm registry (chainable)
+    pub fn package_available_versions(mut self, name: &str, body: &str) -> Self {
+        let mock = self
+            .root
+            .server
+            .mock("GET", &format!("/{name}")[..])
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(body)
@@ -428,14 +476,17 @@ impl SandboxBuilder {
     /// Note: Mocks are matched in reverse order, so any created _after_ this will work
     ///       While those created before will not
     pub fn mock_not_found(mut self) -> Self {
-        let mock = mock("GET", Matcher::Any).with_status(404).create();
+        let mock = self
+            .root
+            .server
+            .mock("GET", Matcher::Any)
+            .with_status(404)
+            .create();
         self.root.mocks.push(mock);
         self
     }
 
     fn distro_mock<T: DistroFixture>(mut self, fx: &T) -> Self {
-        // ISSUE(#145): this should actually use a real http server instead of these mocks
-
         let server_path = fx.server_path();
         let fixture_path = fx.fixture_path();
 
@@ -450,20 +501,45 @@ impl SandboxBuilder {
                 (uncompressed_size & 0x0000_00ff) as u8,
             ];
 
-            let range_mock = mock("GET", &server_path[..])
+            let range_mock = self
+                .root
+                .server
+                .mock("GET", &server_path[..])
                 .match_header("Range", Matcher::Any)
                 .with_body(uncompressed_size_bytes)
                 .create();
             self.root.mocks.push(range_mock);
         }
 
-        let file_mock = mock("GET", &server_path[..])
+        let file_mock = self
+            .root
+            .server
+            .mock("GET", &server_path[..])
             .match_header("Range", Matcher::Missing)
             .with_header("Accept-Ranges", "bytes")
-            .with_body_from_file(fixture_path)
+            .with_body_from_file(&fixture_path)
             .create();
         self.root.mocks.push(file_mock);
 
+        if let Some(checksums_path) = fx.checksums_path() {
+            let contents = fs::read(&fixture_path).unwrap();
+            let checksum = Sha256::digest(contents)
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<String>();
+            let filename = server_path.rsplit('/').next().unwrap();
+
+            // Expecting no hits lets a later `fixture_mock` for the same path take precedence
+            let checksums_mock = self
+                .root
+                .server
+                .mock("GET", &checksums_path[..])
+                .with_body(format!("{checksum}  {filename}\n"))
+                .expect_at_least(0)
+                .create();
+            self.root.mocks.push(checksums_mock);
+        }
+
         self
     }
 
@@ -475,6 +551,18 @@ impl SandboxBuilder {
         this
     }
 
+    /// Setup mock to serve a fixture file, replacing any earlier mock for the path (chainable)
+    pub fn fixture_mock(mut self, server_path: &str, fixture_path: &str) -> Self {
+        let mock = self
+            .root
+            .server
+            .mock("GET", server_path)
+            .with_body_from_file(fixture_path)
+            .create();
+        self.root.mocks.push(mock);
+        self
+    }
+
     /// Add an arbitrary file to the sandbox (chainable)
     pub fn file(mut self, path: &str, contents: &str) -> Self {
         let file_name = sandbox_path(path);
@@ -567,6 +655,64 @@ impl SandboxBuilder {
         self
     }
 
+    /// Set an installed package, in an image keyed by name and version (chainable)
+    pub fn package_version_image(
+        self,
+        name: &str,
+        version: &str,
+        bins: Vec<PackageBinInfo>,
+    ) -> Self {
+        let image_dir = package_version_image_dir(name, version);
+        self.versioned_image(image_dir, name, version, bins)
+    }
+
+    /// Set an installed package from the `volta.tools` of a project (chainable)
+    pub fn project_tool_image(self, name: &str, version: &str, bins: Vec<PackageBinInfo>) -> Self {
+        let image_dir = project_tool_image_dir(name, version);
+        self.versioned_image(image_dir, name, version, bins)
+    }
+
+    fn versioned_image(
+        mut self,
+        image_dir: PathBuf,
+        name: &str,
+        version: &str,
+        bins: Vec<PackageBinInfo>,
+    ) -> Self {
+        let bin_entries: Vec<String> = bins
+            .iter()
+            .map(|bin_info| format!(r#""{}":"bin/{}.js""#, bin_info.name, bin_info.name))
+            .collect();
+        cfg_if! {
+            if #[cfg(target_os = "windows")] {
+                let package_dir = image_dir.join("node_modules").join(name);
+            } else {
+                let package_dir = image_dir.join("lib").join("node_modules").join(name);
+            }
+        }
+        self.files.push(FileBuilder::new(
+            package_dir.join("package.json"),
+            &format!(
+                r#"{{"name":"{}","version":"{}","bin":{{{}}}}}"#,
+                name,
+                version,
+                bin_entries.join(",")
+            ),
+        ));
+        for bin_info in bins.iter() {
+            cfg_if! {
+                if #[cfg(target_os = "windows")] {
+                    let bin_path = image_dir.join(format!("{}.cmd", &bin_info.name));
+                } else {
+                    let bin_path = image_dir.join("bin").join(&bin_info.name);
+                }
+            }
+            self.files
+                .push(FileBuilder::new(bin_path, &bin_info.contents).make_executable());
+        }
+        self
+    }
+
     /// Write executable project binaries into node_modules/.bin/ (chainable)
     pub fn project_bins(mut self, bins: Vec<PackageBinInfo>) -> Self {
         let project_bin_dir = self.root().join("node_modules").join(".bin");
@@ -691,14 +837,14 @@ impl SandboxBuilder {
 
         // make sure these directories exist
         ok_or_panic! { fs::create_dir_all(volta_bin_dir()) };
-        ok_or_panic! { fs::create_dir_all(node_cache_dir()) };
+        ok_or_panic! { fs::create_dir_all(http_cache_dir()) };
         ok_or_panic! { fs::create_dir_all(node_inventory_dir()) };
         ok_or_panic! { fs::create_dir_all(package_inventory_dir()) };
         ok_or_panic! { fs::create_dir_all(pnpm_inventory_dir()) };
         ok_or_panic! { fs::create_dir_all(yarn_inventory_dir()) };
         ok_or_panic! { fs::create_dir_all(volta_tmp_dir()) };
 
-        // write node and yarn caches
+        // write registry metadata caches
         for cache in self.caches.iter() {
             cache.build();
         }
@@ -772,16 +918,15 @@ fn package_inventory_dir() -> PathBuf {
 fn cache_dir() -> PathBuf {
     volta_home().join("cache")
 }
-fn node_cache_dir() -> PathBuf {
-    cache_dir().join("node")
-}
-#[allow(dead_code)]
-fn node_index_file() -> PathBuf {
-    node_cache_dir().join("index.json")
+fn http_cache_dir() -> PathBuf {
+    cache_dir().join("http")
 }
-#[allow(dead_code)]
-fn node_index_expiry_file() -> PathBuf {
-    node_cache_dir().join("index.json.expires")
+fn http_cache_file(url: &str) -> PathBuf {
+    let key: String = Sha256::digest(url.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    http_cache_dir().join(key)
 }
 fn package_json_file(mut root: PathBuf) -> PathBuf {
     root.push("package.json");
@@ -799,6 +944,16 @@ fn shim_file(name: &str) -> PathBuf {
 fn package_image_dir(name: &str) -> PathBuf {
     image_dir().join("packages").join(name)
 }
+fn package_version_image_dir(name: &str, version: &str) -> PathBuf {
+    image_dir()
+        .join("packages")
+        .join(format!("{}@{}", name, version))
+}
+fn project_tool_image_dir(name: &str, version: &str) -> PathBuf {
+    image_dir()
+        .join("project-tools")
+        .join(format!("{}@{}", name, version))
+}
 fn node_image_dir(version: &str) -> PathBuf {
     image_dir().join("node").join(version)
 }
@@ -817,6 +972,9 @@ fn default_platform_file() -> PathBuf {
 fn default_hooks_file() -> PathBuf {
     volta_home().join("hooks.json")
 }
+fn default_settings_file() -> PathBuf {
+    volta_home().join("settings.json")
+}
 fn layout_file(version: &str) -> PathBuf {
     volta_home().join(format!("layout.{}", version))
 }
@@ -830,7 +988,8 @@ fn sandbox_path(path: &str) -> PathBuf {
 
 pub struct Sandbox {
     root: PathBuf,
-    mocks: Vec<mockito::Mock>,
+    server: ServerGuard,
+    mocks: Vec<Mock>,
     env_vars: Vec<EnvVar>,
     env_vars_remove: Vec<String>,
     path: OsString,
@@ -842,6 +1001,16 @@ impl Sandbox {
         self.root.clone()
     }
 
+    /// URL of the mock server for the sandbox, ex: `http://127.0.0.1:1234`
+    pub fn server_url(&self) -> String {
+        self.server.url()
+    }
+
+    /// Create a mock on the sandbox's mock server, which must be `.create()`d to take effect
+    pub fn mock<P: Into<Matcher>>(&mut self, method: &str, path: P) -> Mock {
+        self.server.mock(method, path)
+    }
+
     /// Create a `ProcessBuilder` to run a program in the project.
     /// Example:
     ///         assert_that(
@@ -857,6 +1026,8 @@ impl Sandbox {
             .env("PATH", &self.path)
             .env("VOLTA_POSTSCRIPT", volta_postscript())
             .env_remove("VOLTA_SHELL")
+            // assume a local environment unless a test mocks CI
+            .env_remove("CI")
             .env_remove("MSYSTEM"); // assume cmd.exe everywhere on windows
 
         // overrides for env vars
@@ -928,12 +1099,26 @@ impl Sandbox {
         volta_home().rm_rf();
     }
 
+    pub fn remove_node_image(&self, version: &str) {
+        node_image_dir(version).rm_rf();
+    }
+
+    pub fn remove_pnpm_image(&self, version: &str) {
+        pnpm_image_dir(version).rm_rf();
+    }
+
+    pub fn remove_yarn_image(&self, version: &str) {
+        yarn_image_dir(version).rm_rf();
+    }
+
     // check that files in the sandbox exist
 
     pub fn node_inventory_archive_exists(&self, version: &Version) -> bool {
-        node_inventory_dir()
-            .join(Node::archive_filename(version))
-            .exists()
+        self.node_inventory_archive(version).exists()
+    }
+
+    pub fn node_inventory_archive(&self, version: &Version) -> PathBuf {
+        node_inventory_dir().join(Node::archive_filename(version))
     }
 
     pub fn pnpm_inventory_archive_exists(&self, version: &str) -> bool {
@@ -960,10 +1145,20 @@ impl Sandbox {
     pub fn path_exists(path: &str) -> bool {
         sandbox_path(path).exists()
     }
+    pub fn set_modified(path: &str, time: SystemTime) {
+        let file = ok_or_panic! { File::options().write(true).open(sandbox_path(path)) };
+        ok_or_panic! { file.set_modified(time) };
+    }
     pub fn package_image_exists(name: &str) -> bool {
         let package_img_dir = package_image_dir(name);
         package_img_dir.join("package.json").exists()
     }
+    pub fn package_version_image_exists(name: &str, version: &str) -> bool {
+        package_version_image_dir(name, version).exists()
+    }
+    pub fn project_tool_image_exists(name: &str, version: &str) -> bool {
+        project_tool_image_dir(name, version).exists()
+    }
     pub fn read_default_platform() -> String {
         read_file_to_string(default_platform_file())
     }
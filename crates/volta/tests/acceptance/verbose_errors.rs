@@ -1,5 +1,4 @@
 use crate::support::sandbox::sandbox;
-use ci_info::types::{CiInfo, Vendor};
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
 use test_support::matchers::execs;
@@ -18,9 +17,6 @@ const NODE_VERSION_INFO: &str = r#"[
 fn no_cause_shown_if_no_verbose_flag() {
     let s = sandbox().node_available_versions(NODE_VERSION_INFO).build();
 
-    // Mock `is_ci` to false so that this works even when running in Volta's CI Test Suite
-    ci_info::mock_ci(&CiInfo::new());
-
     assert_that!(
         s.volta("install node@10"),
         execs()
@@ -33,9 +29,6 @@ fn no_cause_shown_if_no_verbose_flag() {
 fn cause_shown_if_verbose_flag() {
     let s = sandbox().node_available_versions(NODE_VERSION_INFO).build();
 
-    // Mock `is_ci` to false so that this correctly tests the verbose flag
-    ci_info::mock_ci(&CiInfo::new());
-
     assert_that!(
         s.volta("install node@10 --verbose"),
         execs()
@@ -60,9 +53,6 @@ fn no_cause_if_no_underlying_error() {
 fn error_log_if_underlying_cause() {
     let s = sandbox().node_available_versions(NODE_VERSION_INFO).build();
 
-    // Mock `is_ci` to false so that this works even when running Volta's CI Test Suite
-    ci_info::mock_ci(&CiInfo::new());
-
     assert_that!(
         s.volta("install node@10"),
         execs()
@@ -96,14 +86,10 @@ fn cause_shown_in_ci() {
     let s = sandbox()
         .node_available_versions(NODE_VERSION_INFO)
         .env("VOLTA_LOGLEVEL", "error")
+        // Mock a CI environment so this works even when running locally
+        .env("CI", "true")
         .build();
 
-    // Mock a CI environment so this works even when running locally
-    let mut ci_mock = CiInfo::new();
-    ci_mock.vendor = Some(Vendor::GitHubActions);
-    ci_mock.ci = true;
-    ci_info::mock_ci(&ci_mock);
-
     assert_that!(
         s.volta("install node@10"),
         execs()
@@ -114,13 +100,11 @@ fn cause_shown_in_ci() {
 
 #[test]
 fn no_error_log_in_ci() {
-    let s = sandbox().node_available_versions(NODE_VERSION_INFO).build();
-
-    // Mock a CI environment so this works even when running locally
-    let mut ci_mock = CiInfo::new();
-    ci_mock.vendor = Some(Vendor::GitHubActions);
-    ci_mock.ci = true;
-    ci_info::mock_ci(&ci_mock);
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        // Mock a CI environment so this works even when running locally
+        .env("CI", "true")
+        .build();
 
     assert_that!(
         s.volta("install node@10"),
@@ -0,0 +1,232 @@
+//! Tests for `volta list`.
+
+use crate::support::sandbox::sandbox;
+use cfg_if::cfg_if;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use serde_json::{Value, json};
+use test_support::matchers::execs;
+use volta_core::error::ExitCode;
+
+const PLATFORM_NODE: &str = r#"{
+    "node":{
+        "runtime":"18.20.4",
+        "npm":null
+    }
+}"#;
+
+const PKG_CONFIG_TYPESCRIPT: &str = r#"{
+  "name": "typescript",
+  "version": "5.5.4",
+  "platform": {
+    "node": "18.20.4",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [
+    "tsc",
+    "tsserver"
+  ],
+  "manager": "Npm"
+}"#;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v24.1.0","npm":"11.3.0","lts":false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]},
+{"version":"v22.16.0","npm":"10.9.2","lts":"Jod","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]},
+{"version":"v18.20.8","npm":"10.8.2","lts":"Hydrogen","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]},
+{"version":"v18.20.4","npm":"10.7.0","lts":"Hydrogen","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]}
+]
+"#;
+
+const TYPESCRIPT_VERSION_INFO: &str = r#"{
+    "name":"typescript",
+    "dist-tags": { "latest":"5.9.2" },
+    "versions": {
+        "5.5.4": { "version":"5.5.4", "dist": { "shasum":"", "tarball":"" }},
+        "5.9.2": { "version":"5.9.2", "dist": { "shasum":"", "tarball":"" }}
+    }
+}"#;
+
+const TYPESCRIPT_UP_TO_DATE_INFO: &str = r#"{
+    "name":"typescript",
+    "dist-tags": { "latest":"5.5.4" },
+    "versions": {
+        "5.5.4": { "version":"5.5.4", "dist": { "shasum":"", "tarball":"" }}
+    }
+}"#;
+
+fn node_bin(version: &str) -> String {
+    cfg_if! {
+        if #[cfg(target_os = "windows")] {
+            format!("@echo off\necho node version {version}\n")
+        } else {
+            format!("#!/bin/sh\necho \"node version {version}\"\n")
+        }
+    }
+}
+
+#[test]
+fn list_last_used() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE)
+        .setup_node_binary("16.20.2", "8.19.4", "node 16")
+        .setup_node_binary("18.20.4", "10.7.0", &node_bin("18.20.4"))
+        .build();
+
+    assert_that!(
+        s.volta("list --last-used --format plain"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("runtime node@16.20.2 last-used never")
+            .with_stdout_contains("runtime node@18.20.4 (default) last-used never")
+    );
+
+    // Running a tool records that its version has been used
+    assert_that!(
+        s.volta("run node"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("node version 18.20.4")
+    );
+
+    assert_that!(
+        s.volta("list node --last-used --format plain"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("runtime node@16.20.2 last-used never")
+            .with_stdout_contains("runtime node@18.20.4 (default) last-used [..]-[..]-[..]T[..]Z")
+    );
+}
+
+#[test]
+fn list_size() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE)
+        .setup_node_binary("16.20.2", "8.19.4", "node 16")
+        .setup_node_binary("18.20.4", "10.7.0", "node 18")
+        .setup_yarn_binary("1.22.22", "yarn 1.22.22")
+        .build();
+
+    assert_that!(
+        s.volta("list all --format plain"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("runtime node@16.20.2")
+            .with_stdout_does_not_contain("[..]size[..]")
+    );
+
+    assert_that!(
+        s.volta("list all --size --format plain"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("runtime node@16.20.2 size [..]")
+            .with_stdout_contains("runtime node@18.20.4 (default) size [..]")
+            .with_stdout_contains("package-manager yarn@1.22.22 size [..]")
+            .with_stdout_contains("total size [..]")
+    );
+
+    assert_that!(
+        s.volta("list all --size --format human"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("        v16.20.2, [..] B")
+            .with_stdout_contains("Total disk usage: [..] B")
+    );
+}
+
+#[test]
+fn list_json() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE)
+        .package_config("typescript", PKG_CONFIG_TYPESCRIPT)
+        .setup_node_binary("16.20.2", "8.19.4", "node 16")
+        .setup_node_binary("18.20.4", "10.7.0", "node 18")
+        .setup_yarn_binary("1.22.22", "yarn 1.22.22")
+        .build();
+
+    let output = s
+        .volta("list all --format json")
+        .exec_with_output()
+        .unwrap();
+    assert!(output.status.success());
+    let list: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(
+        list,
+        json!({
+            "schemaVersion": 1,
+            "runtimes": [
+                { "name": "node", "version": "16.20.2", "source": { "type": "fetched" } },
+                { "name": "node", "version": "18.20.4", "source": { "type": "default" } }
+            ],
+            "packageManagers": [
+                { "name": "yarn", "version": "1.22.22", "source": { "type": "fetched" } }
+            ],
+            "packages": [
+                {
+                    "name": "typescript",
+                    "version": "5.5.4",
+                    "source": { "type": "default" },
+                    "bins": ["tsc", "tsserver"],
+                    "platform": { "node": "18.20.4", "npm": "built-in" }
+                }
+            ]
+        })
+    );
+}
+
+#[test]
+fn list_outdated() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE)
+        .package_config("typescript", PKG_CONFIG_TYPESCRIPT)
+        .node_available_versions(NODE_VERSION_INFO)
+        .package_available_versions("typescript", TYPESCRIPT_VERSION_INFO)
+        .build();
+
+    assert_that!(
+        s.volta("list --outdated --format plain"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("runtime node@18.20.4 wanted 18.20.8 latest 24.1.0 lts 22.16.0")
+            .with_stdout_contains("package typescript@5.5.4 wanted 5.9.2 latest 5.9.2")
+    );
+
+    assert_that!(
+        s.volta("list typescript --outdated --exit-code --format plain"),
+        execs()
+            .with_status(ExitCode::Outdated as i32)
+            .with_stdout_contains("package typescript@5.5.4 wanted 5.9.2 latest 5.9.2")
+            .with_stdout_does_not_contain("[..]node[..]")
+    );
+}
+
+#[test]
+fn list_outdated_up_to_date() {
+    let s = sandbox()
+        .package_config("typescript", PKG_CONFIG_TYPESCRIPT)
+        .package_available_versions("typescript", TYPESCRIPT_UP_TO_DATE_INFO)
+        .build();
+
+    assert_that!(
+        s.volta("list --outdated --exit-code --format human"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("⚡️ Your default tools and packages are up to date.")
+    );
+}
+
+#[test]
+fn list_outdated_offline() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE)
+        .env("VOLTA_OFFLINE", "1")
+        .build();
+
+    assert_that!(
+        s.volta("list --outdated"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not check for newer versions while in offline mode.")
+    );
+}
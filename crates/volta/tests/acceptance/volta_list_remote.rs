@@ -0,0 +1,219 @@
+//! Tests for `volta list-remote`.
+
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+use volta_core::error::ExitCode;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v24.1.0","npm":"11.3.0","lts":false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]},
+{"version":"v22.16.0","npm":"10.9.2","lts":"Jod","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]},
+{"version":"v22.15.1","npm":"10.9.2","lts":"Jod","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]},
+{"version":"v18.20.8","npm":"10.8.2","lts":"Hydrogen","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip","linux-arm64"]}
+]
+"#;
+
+const TYPESCRIPT_VERSION_INFO: &str = r#"{
+    "name":"typescript",
+    "dist-tags": { "latest":"5.9.2", "beta":"6.0.0-beta" },
+    "versions": {
+        "4.9.5": { "version":"4.9.5", "dist": { "shasum":"", "tarball":"" }},
+        "5.5.4": { "version":"5.5.4", "dist": { "shasum":"", "tarball":"" }},
+        "5.9.2": { "version":"5.9.2", "dist": { "shasum":"", "tarball":"" }},
+        "6.0.0-beta": { "version":"6.0.0-beta", "dist": { "shasum":"", "tarball":"" }}
+    }
+}"#;
+
+const PKG_CONFIG_TYPESCRIPT: &str = r#"{
+  "name": "typescript",
+  "version": "5.5.4",
+  "platform": {
+    "node": "22.16.0",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [
+    "tsc",
+    "tsserver"
+  ],
+  "manager": "Npm"
+}"#;
+
+const YARN_1_VERSION_INFO: &str = r#"{
+    "name":"yarn",
+    "dist-tags": { "latest":"1.22.22" },
+    "versions": {
+        "1.22.19": { "version":"1.22.19", "dist": { "shasum":"", "tarball":"" }},
+        "1.22.22": { "version":"1.22.22", "dist": { "shasum":"", "tarball":"" }}
+    }
+}"#;
+
+const YARN_BERRY_VERSION_INFO: &str = r#"{
+    "name":"@yarnpkg/cli-dist",
+    "dist-tags": { "latest":"4.9.1" },
+    "versions": {
+        "2.4.3": { "version":"2.4.3", "dist": { "shasum":"", "tarball":"" }},
+        "4.9.1": { "version":"4.9.1", "dist": { "shasum":"", "tarball":"" }}
+    }
+}"#;
+
+fn pnpm_hooks_json(server_url: &str) -> String {
+    format!(
+        r#"
+{{
+    "pnpm": {{
+        "index": {{
+            "template": "{server_url}/pnpm/index"
+        }}
+    }}
+}}"#
+    )
+}
+
+#[test]
+fn list_remote_node() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .setup_node_binary("22.16.0", "10.9.2", "node 22")
+        .build();
+
+    assert_that!(
+        s.volta("list-remote node"),
+        execs().with_status(0).with_stdout_contains(
+            "18.20.8 (LTS: Hydrogen)\n\
+             22.15.1 (LTS: Jod)\n\
+             22.16.0 (LTS: Jod, installed)\n\
+             24.1.0 (latest)"
+        )
+    );
+
+    assert_that!(
+        s.volta("list-remote node@22"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("22.15.1 (LTS: Jod)\n22.16.0 (LTS: Jod, installed)")
+            .with_stdout_does_not_contain("[..]18.20.8[..]")
+            .with_stdout_does_not_contain("[..]24.1.0[..]")
+    );
+
+    assert_that!(
+        s.volta("list-remote node@lts"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("18.20.8 (LTS: Hydrogen)")
+            .with_stdout_does_not_contain("[..]24.1.0[..]")
+    );
+}
+
+#[test]
+fn list_remote_package() {
+    let s = sandbox()
+        .package_config("typescript", PKG_CONFIG_TYPESCRIPT)
+        .package_available_versions("typescript", TYPESCRIPT_VERSION_INFO)
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("search typescript@5"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("5.5.4 (installed)\n5.9.2 (latest)")
+            .with_stdout_does_not_contain("[..]4.9.5[..]")
+            .with_stdout_does_not_contain("[..]6.0.0-beta[..]")
+    );
+
+    assert_that!(
+        s.volta("list-remote typescript@beta"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("6.0.0-beta (beta)")
+            .with_stdout_does_not_contain("[..]5.9.2[..]")
+    );
+
+    assert_that!(
+        s.volta("list-remote typescript@7"),
+        execs()
+            .with_status(0)
+            .with_stderr_contains("[..]No available versions found matching 'typescript@7'")
+    );
+}
+
+#[test]
+fn list_remote_yarn() {
+    let s = sandbox()
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
+        .yarn_berry_available_versions(YARN_BERRY_VERSION_INFO)
+        .build();
+
+    // Yarn 2 isn't supported, and the tags of Yarn 3+ take precedence
+    assert_that!(
+        s.volta("list-remote yarn"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("1.22.19\n1.22.22\n4.9.1 (latest)")
+            .with_stdout_does_not_contain("[..]2.4.3[..]")
+    );
+}
+
+#[test]
+fn list_remote_yarn_without_berry_index() {
+    let s = sandbox()
+        .yarn_1_available_versions(YARN_1_VERSION_INFO)
+        .build();
+
+    assert_that!(
+        s.volta("list-remote yarn"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download Yarn version registry[..]")
+            .with_stdout_does_not_contain("[..]1.22.22[..]")
+    );
+}
+
+#[test]
+fn list_remote_with_hook() {
+    let s = sandbox();
+    let server_url = s.server_url();
+    let mut s = s
+        .default_hooks(&pnpm_hooks_json(&server_url))
+        .env("VOLTA_LOGLEVEL", "debug")
+        .build();
+    let _mock = s
+        .mock("GET", "/pnpm/index")
+        .with_status(200)
+        .with_header("Content-Type", "application/json")
+        .with_body(
+            r#"{
+    "name":"pnpm",
+    "dist-tags": { "latest":"9.15.0" },
+    "versions": {
+        "8.15.9": { "version":"8.15.9", "dist": { "shasum":"", "tarball":"" }},
+        "9.15.0": { "version":"9.15.0", "dist": { "shasum":"", "tarball":"" }}
+    }
+}"#,
+        )
+        .create();
+
+    assert_that!(
+        s.volta("list-remote pnpm"),
+        execs()
+            .with_status(0)
+            .with_stderr_contains("[..]Using pnpm.index hook to determine pnpm index URL")
+            .with_stdout_contains("8.15.9\n9.15.0 (latest)")
+    );
+}
+
+#[test]
+fn list_remote_offline() {
+    let s = sandbox().env("VOLTA_OFFLINE", "1").build();
+
+    assert_that!(
+        s.volta("list-remote node"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains(
+                "[..]Could not list the available versions of node while in offline mode."
+            )
+    );
+}
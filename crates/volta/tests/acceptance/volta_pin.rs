@@ -117,6 +117,15 @@ const NODE_VERSION_INFO: &str = r#"[
 ]
 "#;
 
+// The same versions as above, with the older ones in LTS lines as well
+const NODE_LTS_VERSION_INFO: &str = r#"[
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v9.27.6","npm":"5.6.17","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v8.9.10","npm":"5.6.7","lts": "Carbon","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v6.19.62","npm":"3.10.1066","lts": "Boron","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
 cfg_if::cfg_if! {
     if #[cfg(target_os = "macos")] {
         const NODE_VERSION_FIXTURES: [DistroMetadata; 4] = [
@@ -196,10 +205,10 @@ const YARN_1_VERSION_INFO: &str = r#"{
     "name":"yarn",
     "dist-tags": { "latest":"1.12.99" },
     "versions": {
-        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"", "tarball":"" }},
-        "1.4.159": { "version":"1.4.159", "dist": { "shasum":"", "tarball":"" }},
-        "1.7.71": { "version":"1.7.71", "dist": { "shasum":"", "tarball":"" }},
-        "1.12.99": { "version":"1.12.99", "dist": { "shasum":"", "tarball":"" }}
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"a04472b4febb093141993a289b7253bfffba11db", "tarball":"" }},
+        "1.4.159": { "version":"1.4.159", "dist": { "shasum":"1b211aa31eaf27ab4c9a2c68c52d8d5f6bb9d48c", "tarball":"" }},
+        "1.7.71": { "version":"1.7.71", "dist": { "shasum":"090cc91fdeb93933518daf741acfb505106dc711", "tarball":"" }},
+        "1.12.99": { "version":"1.12.99", "dist": { "shasum":"cc1a5558d673700822f42bcadcc50b8898b3e26f", "tarball":"" }}
     }
 }"#;
 
@@ -207,10 +216,10 @@ const YARN_BERRY_VERSION_INFO: &str = r#"{
     "name":"@yarnpkg/cli-dist",
     "dist-tags": { "latest":"3.12.99" },
     "versions": {
-        "2.4.159": { "version":"2.4.159", "dist": { "shasum":"", "tarball":"" }},
-        "3.2.42": { "version":"3.2.42", "dist": { "shasum":"", "tarball":"" }},
-        "3.7.71": { "version":"3.7.71", "dist": { "shasum":"", "tarball":"" }},
-        "3.12.99": { "version":"3.12.99", "dist": { "shasum":"", "tarball":"" }}
+        "2.4.159": { "version":"2.4.159", "dist": { "shasum":"1b211aa31eaf27ab4c9a2c68c52d8d5f6bb9d48c", "tarball":"" }},
+        "3.2.42": { "version":"3.2.42", "dist": { "shasum":"a04472b4febb093141993a289b7253bfffba11db", "tarball":"" }},
+        "3.7.71": { "version":"3.7.71", "dist": { "shasum":"090cc91fdeb93933518daf741acfb505106dc711", "tarball":"" }},
+        "3.12.99": { "version":"3.12.99", "dist": { "shasum":"cc1a5558d673700822f42bcadcc50b8898b3e26f", "tarball":"" }}
     }
 }"#;
 
@@ -265,9 +274,9 @@ const PNPM_VERSION_INFO: &str = r#"
     "name":"pnpm",
     "dist-tags": { "latest":"7.7.1" },
     "versions": {
-        "0.0.1": { "version":"0.0.1", "dist": { "shasum":"", "tarball":"" }},
-        "6.34.0": { "version":"6.34.0", "dist": { "shasum":"", "tarball":"" }},
-        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"", "tarball":"" }}
+        "0.0.1": { "version":"0.0.1", "dist": { "shasum":"58f67f3039445f9b802b1a891545f894d4578851", "tarball":"" }},
+        "6.34.0": { "version":"6.34.0", "dist": { "shasum":"6e6523071c0d6884c6c98d5156dd8c1fb06d3b1a", "tarball":"" }},
+        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"1a873cc397b01b1a39ed04288bcc85ba2aefda28", "tarball":"" }}
     }
 }
 "#;
@@ -313,9 +322,9 @@ const NPM_VERSION_INFO: &str = r#"
     "name":"npm",
     "dist-tags": { "latest":"8.1.5" },
     "versions": {
-        "1.2.3": { "version":"1.2.3", "dist": { "shasum":"", "tarball":"" }},
-        "4.5.6": { "version":"4.5.6", "dist": { "shasum":"", "tarball":"" }},
-        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"", "tarball":"" }}
+        "1.2.3": { "version":"1.2.3", "dist": { "shasum":"10d224f266a6fda6cc0acfc60a7ee41e68c70f64", "tarball":"" }},
+        "4.5.6": { "version":"4.5.6", "dist": { "shasum":"459724a7681468c76bb3e45cb1ee3deb30462f6d", "tarball":"" }},
+        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"90d46be79e04806f4ed7a7a4b8599ea02f2a20eb", "tarball":"" }}
     }
 }
 "#;
@@ -358,6 +367,25 @@ fn pin_node_reports_info() {
     );
 }
 
+#[test]
+fn pin_node_lts_line() {
+    let s = sandbox()
+        .package_json(BASIC_PACKAGE_JSON)
+        .node_available_versions(NODE_LTS_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin node@lts/-1"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        package_json_with_pinned_node("8.9.10"),
+    )
+}
+
 #[test]
 fn pin_node_latest() {
     let s = sandbox()
@@ -631,7 +659,7 @@ fn pin_yarn_1_missing_release() {
         s.volta("pin yarn@1.3.1"),
         execs()
             .with_status(ExitCode::NetworkError as i32)
-            .with_stderr_contains("[..]Could not download yarn@1.3.1")
+            .with_stderr_contains("[..]Could not download Yarn version registry")
     );
 
     assert_eq!(
@@ -671,7 +699,7 @@ fn pin_yarn_3_missing_release() {
         s.volta("pin yarn@3.3.1"),
         execs()
             .with_status(ExitCode::NetworkError as i32)
-            .with_stderr_contains("[..]Could not download yarn@3.3.1")
+            .with_stderr_contains("[..]Could not download Yarn version registry")
     );
 
     assert_eq!(
@@ -825,7 +853,7 @@ fn pin_npm_missing_release() {
         s.volta("pin npm@8.1.5"),
         execs()
             .with_status(ExitCode::NetworkError as i32)
-            .with_stderr_contains("[..]Could not download npm@8.1.5")
+            .with_stderr_contains("[..]Could not download npm version registry")
     );
 
     assert_eq!(
@@ -1060,7 +1088,7 @@ fn pin_pnpm_missing_release() {
         s.volta("pin pnpm@3.3.1"),
         execs()
             .with_status(ExitCode::NetworkError as i32)
-            .with_stderr_contains("[..]Could not download pnpm@3.3.1")
+            .with_stderr_contains("[..]Could not download pnpm version registry")
     );
 
     assert_eq!(
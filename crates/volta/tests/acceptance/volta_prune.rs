@@ -0,0 +1,236 @@
+//! Tests for `volta prune`.
+
+use std::fs;
+use std::time::{Duration, SystemTime};
+
+use crate::support::sandbox::{Sandbox, sandbox};
+use cfg_if::cfg_if;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use nodejs_semver::Version;
+use test_support::matchers::execs;
+use volta_core::tool::{Node, Yarn};
+
+const PLATFORM_NODE_YARN: &str = r#"{
+    "node":{
+        "runtime":"18.20.4",
+        "npm":null
+    },
+    "yarn":"1.22.22"
+}"#;
+
+const PLATFORM_NODE: &str = r#"{
+    "node":{
+        "runtime":"18.20.4",
+        "npm":null
+    }
+}"#;
+
+const PKG_CONFIG_NODE_16: &str = r#"{
+  "name": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": "16.20.2",
+    "npm": "8.19.4",
+    "yarn": null
+  },
+  "bins": [
+    "cowsay"
+  ],
+  "manager": "Npm"
+}"#;
+
+const PACKAGE_JSON_NODE_20: &str = r#"{
+  "name": "test-package",
+  "volta": {
+    "node": "20.17.0"
+  }
+}"#;
+
+fn node_archive(version: &str) -> String {
+    format!(
+        ".volta/tools/inventory/node/{}",
+        Node::archive_filename(&Version::parse(version).unwrap())
+    )
+}
+
+fn node_bin(version: &str) -> String {
+    cfg_if! {
+        if #[cfg(target_os = "windows")] {
+            format!("@echo off\necho node version {version}\n")
+        } else {
+            format!("#!/bin/sh\necho \"node version {version}\"\n")
+        }
+    }
+}
+
+fn yarn_archive(version: &str) -> String {
+    format!(
+        ".volta/tools/inventory/yarn/{}",
+        Yarn::archive_filename(version)
+    )
+}
+
+#[test]
+fn prune_nothing_to_remove() {
+    let s = sandbox().env("VOLTA_LOGLEVEL", "info").build();
+
+    assert_that!(
+        s.volta("prune"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("No unused tool versions to prune")
+    );
+}
+
+#[test]
+fn prune_removes_unused_images_and_archives() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_YARN)
+        .package_config("cowsay", PKG_CONFIG_NODE_16)
+        .setup_node_binary("14.21.3", "6.14.18", "node 14")
+        .setup_node_binary("16.20.2", "8.19.4", "node 16")
+        .setup_node_binary("18.20.4", "10.7.0", "node 18")
+        .setup_npm_binary("8.19.4", "npm 8")
+        .setup_npm_binary("9.9.3", "npm 9")
+        .setup_yarn_binary("1.22.19", "yarn 1.22.19")
+        .setup_yarn_binary("1.22.22", "yarn 1.22.22")
+        .file(&node_archive("14.21.3"), "archive")
+        .file(&node_archive("18.20.4"), "archive")
+        .file(&yarn_archive("1.22.19"), "archive")
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("prune"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("Removed node@14.21.3 image ([..])")
+            .with_stdout_contains("Removed node@14.21.3 archive ([..])")
+            .with_stdout_contains("Removed npm@9.9.3 image ([..])")
+            .with_stdout_contains("Removed yarn@1.22.19 image ([..])")
+            .with_stdout_contains("Removed yarn@1.22.19 archive ([..])")
+            .with_stdout_contains("[..]pruned 6 images and archives, freeing [..]")
+    );
+
+    // Unused versions are removed, including the default npm marker for Node
+    assert!(!Sandbox::path_exists(".volta/tools/image/node/14.21.3"));
+    assert!(!Sandbox::path_exists(&node_archive("14.21.3")));
+    assert!(!Sandbox::path_exists(
+        ".volta/tools/inventory/node/node-v14.21.3-npm"
+    ));
+    assert!(!Sandbox::path_exists(".volta/tools/image/npm/9.9.3"));
+    assert!(!Sandbox::path_exists(".volta/tools/image/yarn/1.22.19"));
+    assert!(!Sandbox::path_exists(&yarn_archive("1.22.19")));
+
+    // Versions used by the default platform or by packages are kept
+    assert!(Sandbox::path_exists(".volta/tools/image/node/16.20.2"));
+    assert!(Sandbox::path_exists(".volta/tools/image/node/18.20.4"));
+    assert!(Sandbox::path_exists(&node_archive("18.20.4")));
+    assert!(Sandbox::path_exists(".volta/tools/image/npm/8.19.4"));
+    assert!(Sandbox::path_exists(".volta/tools/image/yarn/1.22.22"));
+}
+
+#[test]
+fn prune_dry_run_removes_nothing() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_YARN)
+        .setup_node_binary("14.21.3", "6.14.18", "node 14")
+        .setup_node_binary("18.20.4", "10.7.0", "node 18")
+        .file(&node_archive("14.21.3"), "archive")
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("prune --dry-run"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("Would remove node@14.21.3 image ([..])")
+            .with_stdout_contains("Would remove node@14.21.3 archive ([..])")
+            .with_stdout_contains("[..]pruning would remove 3 images and archives, freeing [..]")
+            .with_stdout_does_not_contain("[..]18.20.4[..]")
+    );
+
+    assert!(Sandbox::path_exists(".volta/tools/image/node/14.21.3"));
+    assert!(Sandbox::path_exists(&node_archive("14.21.3")));
+}
+
+#[test]
+fn prune_keeps_versions_pinned_by_recent_projects() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE)
+        .package_json(PACKAGE_JSON_NODE_20)
+        .setup_node_binary("18.20.4", "10.7.0", "node 18")
+        .setup_node_binary("20.17.0", "10.8.2", &node_bin("20.17.0"))
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    // Running a tool with the project platform records the project as recently used
+    assert_that!(
+        s.volta("run node"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("node version 20.17.0")
+    );
+
+    assert_that!(
+        s.volta("prune"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("No unused tool versions to prune")
+    );
+    assert!(Sandbox::path_exists(".volta/tools/image/node/20.17.0"));
+
+    // Once the project is gone, its pinned versions are no longer kept
+    fs::remove_file(s.root().join("package.json")).unwrap();
+
+    assert_that!(
+        s.volta("prune"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("Removed node@20.17.0 image ([..])")
+    );
+    assert!(!Sandbox::path_exists(".volta/tools/image/node/20.17.0"));
+    assert!(Sandbox::path_exists(".volta/tools/image/node/18.20.4"));
+}
+
+#[test]
+fn prune_older_than_keeps_recently_used_versions() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE)
+        .setup_node_binary("14.21.3", "6.14.18", "node 14")
+        .setup_node_binary("16.20.2", "8.19.4", "node 16")
+        .setup_node_binary("18.20.4", "10.7.0", "node 18")
+        .file(".volta/usage/tools/node/16.20.2", "")
+        .file(".volta/usage/tools/node/14.21.3", "")
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    let long_ago = SystemTime::now() - Duration::from_hours(120 * 24);
+    Sandbox::set_modified(".volta/usage/tools/node/16.20.2", long_ago);
+
+    assert_that!(
+        s.volta("prune --older-than 90d"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("Removed node@16.20.2 image ([..])")
+            .with_stdout_does_not_contain("[..]14.21.3[..]")
+    );
+
+    assert!(!Sandbox::path_exists(".volta/tools/image/node/16.20.2"));
+    assert!(!Sandbox::path_exists(".volta/usage/tools/node/16.20.2"));
+    assert!(Sandbox::path_exists(".volta/tools/image/node/14.21.3"));
+    assert!(Sandbox::path_exists(".volta/tools/image/node/18.20.4"));
+}
+
+#[test]
+fn prune_older_than_rejects_invalid_age() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("prune --older-than 90"),
+        execs()
+            .with_status(2)
+            .with_stderr_contains("[..]invalid age `90`[..]")
+    );
+}
@@ -146,8 +146,8 @@ const PNPM_VERSION_INFO: &str = r#"
     "name":"pnpm",
     "dist-tags": { "latest":"7.7.1" },
     "versions": {
-        "6.34.0": { "version":"6.34.0", "dist": { "shasum":"", "tarball":"" }},
-        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"", "tarball":"" }}
+        "6.34.0": { "version":"6.34.0", "dist": { "shasum":"6e6523071c0d6884c6c98d5156dd8c1fb06d3b1a", "tarball":"" }},
+        "7.7.1": { "version":"7.7.1", "dist": { "shasum":"1a873cc397b01b1a39ed04288bcc85ba2aefda28", "tarball":"" }}
     }
 }
 "#;
@@ -169,10 +169,10 @@ const YARN_1_VERSION_INFO: &str = r#"{
     "name":"yarn",
     "dist-tags": { "latest":"1.12.99" },
     "versions": {
-        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"", "tarball":"" }},
-        "1.4.159": { "version":"1.4.159", "dist": { "shasum":"", "tarball":"" }},
-        "1.7.71": { "version":"1.7.71", "dist": { "shasum":"", "tarball":"" }},
-        "1.12.99": { "version":"1.12.99", "dist": { "shasum":"", "tarball":"" }}
+        "1.2.42": { "version":"1.2.42", "dist": { "shasum":"a04472b4febb093141993a289b7253bfffba11db", "tarball":"" }},
+        "1.4.159": { "version":"1.4.159", "dist": { "shasum":"1b211aa31eaf27ab4c9a2c68c52d8d5f6bb9d48c", "tarball":"" }},
+        "1.7.71": { "version":"1.7.71", "dist": { "shasum":"090cc91fdeb93933518daf741acfb505106dc711", "tarball":"" }},
+        "1.12.99": { "version":"1.12.99", "dist": { "shasum":"cc1a5558d673700822f42bcadcc50b8898b3e26f", "tarball":"" }}
     }
 }"#;
 
@@ -203,10 +203,10 @@ const YARN_BERRY_VERSION_INFO: &str = r#"{
     "name":"@yarnpkg/cli-dist",
     "dist-tags": { "latest":"3.12.99" },
     "versions": {
-        "2.4.159": { "version":"2.4.159", "dist": { "shasum":"", "tarball":"" }},
-        "3.2.42": { "version":"3.2.42", "dist": { "shasum":"", "tarball":"" }},
-        "3.7.71": { "version":"3.7.71", "dist": { "shasum":"", "tarball":"" }},
-        "3.12.99": { "version":"3.12.99", "dist": { "shasum":"", "tarball":"" }}
+        "2.4.159": { "version":"2.4.159", "dist": { "shasum":"1b211aa31eaf27ab4c9a2c68c52d8d5f6bb9d48c", "tarball":"" }},
+        "3.2.42": { "version":"3.2.42", "dist": { "shasum":"a04472b4febb093141993a289b7253bfffba11db", "tarball":"" }},
+        "3.7.71": { "version":"3.7.71", "dist": { "shasum":"090cc91fdeb93933518daf741acfb505106dc711", "tarball":"" }},
+        "3.12.99": { "version":"3.12.99", "dist": { "shasum":"cc1a5558d673700822f42bcadcc50b8898b3e26f", "tarball":"" }}
     }
 }"#;
 
@@ -256,9 +256,9 @@ const NPM_VERSION_INFO: &str = r#"
     "name":"npm",
     "dist-tags": { "latest":"8.1.5" },
     "versions": {
-        "1.2.3": { "version":"1.2.3", "dist": { "shasum":"", "tarball":"" }},
-        "4.5.6": { "version":"4.5.6", "dist": { "shasum":"", "tarball":"" }},
-        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"", "tarball":"" }}
+        "1.2.3": { "version":"1.2.3", "dist": { "shasum":"10d224f266a6fda6cc0acfc60a7ee41e68c70f64", "tarball":"" }},
+        "4.5.6": { "version":"4.5.6", "dist": { "shasum":"459724a7681468c76bb3e45cb1ee3deb30462f6d", "tarball":"" }},
+        "8.1.5": { "version":"8.1.5", "dist": { "shasum":"90d46be79e04806f4ed7a7a4b8599ea02f2a20eb", "tarball":"" }}
     }
 }
 "#;
@@ -281,6 +281,22 @@ fn command_line_node() {
     );
 }
 
+#[test]
+fn command_line_node_lts_line() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .env(VOLTA_LOGLEVEL, "debug")
+        .build();
+
+    assert_that!(
+        s.volta("run --node lts/dubnium node --version"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_contains("[..]Node: 10.99.1040 from command-line configuration")
+    );
+}
+
 #[test]
 fn inherited_node() {
     let s = sandbox()
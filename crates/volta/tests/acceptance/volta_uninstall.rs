@@ -3,7 +3,10 @@
 use crate::support::sandbox::{Sandbox, sandbox};
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
+use nodejs_semver::Version;
 use test_support::matchers::execs;
+use volta_core::error::ExitCode;
+use volta_core::tool::{Node, Yarn};
 
 const PKG_CONFIG_BASIC: &str = r#"{
   "name": "cowsay",
@@ -206,12 +209,189 @@ fn uninstall_package_orphaned_bins() {
 }
 
 #[test]
-fn uninstall_runtime() {
+fn uninstall_runtime_without_version() {
     let s = sandbox().build();
     assert_that!(
         s.volta("uninstall node"),
         execs()
-            .with_status(1)
-            .with_stderr_contains("[..]error: Uninstalling node is not supported yet.")
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Could not determine which version of node to uninstall")
     )
 }
+
+const PLATFORM_NODE_18: &str = r#"{
+    "node":{
+        "runtime":"18.20.4",
+        "npm":null
+    }
+}"#;
+
+const PLATFORM_NODE_16_YARN: &str = r#"{
+    "node":{
+        "runtime":"16.20.2",
+        "npm":null
+    },
+    "yarn":"1.22.22"
+}"#;
+
+const PKG_CONFIG_NODE_16: &str = r#"{
+  "name": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": "16.20.2",
+    "npm": null,
+    "yarn": null
+  },
+  "bins": [
+    "cowsay"
+  ],
+  "manager": "Npm"
+}"#;
+
+fn node_archive(version: &str) -> String {
+    format!(
+        ".volta/tools/inventory/node/{}",
+        Node::archive_filename(&Version::parse(version).unwrap())
+    )
+}
+
+#[test]
+fn uninstall_node_version() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_18)
+        .setup_node_binary("16.20.2", "8.19.4", "node 16")
+        .setup_node_binary("18.20.4", "10.7.0", "node 18")
+        .file(&node_archive("16.20.2"), "archive")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@16.20.2"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("[..]uninstalled node@16.20.2")
+    );
+
+    assert!(!Sandbox::path_exists(".volta/tools/image/node/16.20.2"));
+    assert!(!Sandbox::path_exists(&node_archive("16.20.2")));
+    assert!(!Sandbox::path_exists(
+        ".volta/tools/inventory/node/node-v16.20.2-npm"
+    ));
+    assert!(Sandbox::path_exists(".volta/tools/image/node/18.20.4"));
+
+    assert_that!(
+        s.volta("list node --format plain"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("runtime node@18.20.4 (default)")
+            .with_stdout_does_not_contain("[..]16.20.2[..]")
+    );
+}
+
+#[test]
+fn uninstall_node_version_not_found() {
+    let s = sandbox().env(VOLTA_LOGLEVEL, "info").build();
+
+    assert_that!(
+        s.volta("uninstall node@16.20.2"),
+        execs()
+            .with_status(0)
+            .with_stderr_contains("[..]No node@16.20.2 found to uninstall")
+    );
+}
+
+#[test]
+fn uninstall_default_versions_refused() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_16_YARN)
+        .setup_node_binary("16.20.2", "8.19.4", "node 16")
+        .setup_yarn_binary("1.22.22", "yarn")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@16.20.2"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains(
+                "[..]Could not uninstall node v16.20.2 because it is still used by:"
+            )
+            .with_stderr_contains("    the default platform")
+    );
+    assert_that!(
+        s.volta("uninstall yarn@1.22.22"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains(
+                "[..]Could not uninstall yarn v1.22.22 because it is still used by:"
+            )
+    );
+
+    assert!(Sandbox::path_exists(".volta/tools/image/node/16.20.2"));
+    assert!(Sandbox::path_exists(".volta/tools/image/yarn/1.22.22"));
+}
+
+#[test]
+fn uninstall_package_platform_refused_without_force() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_18)
+        .package_config("cowsay", PKG_CONFIG_NODE_16)
+        .setup_node_binary("16.20.2", "8.19.4", "node 16")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@16.20.2"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("    package 'cowsay'")
+    );
+    assert!(Sandbox::path_exists(".volta/tools/image/node/16.20.2"));
+
+    assert_that!(
+        s.volta("uninstall --force node@16.20.2"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("[..]node@16.20.2 is still used by package 'cowsay'[..]")
+            .with_stdout_contains("[..]uninstalled node@16.20.2")
+    );
+    assert!(!Sandbox::path_exists(".volta/tools/image/node/16.20.2"));
+    assert!(Sandbox::package_config_exists("cowsay"));
+}
+
+#[test]
+fn uninstall_package_manager_version() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_18)
+        .setup_npm_binary("8.19.4", "npm")
+        .setup_yarn_binary("1.22.22", "yarn")
+        .file(".volta/tools/inventory/npm/npm-8.19.4.tgz", "archive")
+        .file(
+            &format!(
+                ".volta/tools/inventory/yarn/{}",
+                Yarn::archive_filename("1.22.22")
+            ),
+            "archive",
+        )
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall npm@8.19.4"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("[..]uninstalled npm@8.19.4")
+    );
+    assert_that!(
+        s.volta("uninstall yarn@1.22.22"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("[..]uninstalled yarn@1.22.22")
+    );
+
+    assert!(!Sandbox::path_exists(".volta/tools/image/npm/8.19.4"));
+    assert!(!Sandbox::path_exists(
+        ".volta/tools/inventory/npm/npm-8.19.4.tgz"
+    ));
+    assert!(!Sandbox::path_exists(".volta/tools/image/yarn/1.22.22"));
+    assert!(!s.yarn_inventory_archive_exists("1.22.22"));
+}
@@ -0,0 +1,138 @@
+//! Tests for `volta verify`, which checks the project toolchain without running it.
+
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PACKAGE_JSON_WITH_ENGINES: &str = r#"{
+    "name": "verified",
+    "engines": {
+        "node": ">=10"
+    },
+    "volta": {
+        "node": "10.99.1040"
+    }
+}"#;
+
+const PACKAGE_JSON_WITH_NEWER_ENGINES: &str = r#"{
+    "name": "unverified",
+    "engines": {
+        "node": ">=22"
+    },
+    "volta": {
+        "node": "10.99.1040"
+    }
+}"#;
+
+const PACKAGE_JSON_WITH_UNLOCKED_RANGE: &str = r#"{
+    "name": "unlocked",
+    "volta": {
+        "node": "10.99.1040",
+        "yarn": "^1.2"
+    }
+}"#;
+
+const PACKAGE_JSON_WITHOUT_NODE: &str = r#"{
+    "name": "unpinned",
+    "volta": {
+        "yarn": "1.2.42"
+    }
+}"#;
+
+#[test]
+fn checks_engines_and_skips_registries_offline() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_WITH_ENGINES)
+        .env("VOLTA_OFFLINE", "1")
+        .build();
+
+    assert_that!(
+        s.volta("verify"),
+        execs()
+            .with_stdout_contains("pass  platform [..]node@10.99.1040 pinned in [..]package.json")
+            .with_stdout_contains("pass  engines [..]node@10.99.1040 satisfies engines.node (>=10)")
+            .with_stdout_contains("skip  registry [..]offline mode")
+    );
+}
+
+#[test]
+fn fails_on_engines_mismatch() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_WITH_NEWER_ENGINES)
+        .env("VOLTA_OFFLINE", "1")
+        .build();
+
+    assert_that!(
+        s.volta("verify"),
+        execs()
+            .with_status(ExitCode::Unverified as i32)
+            .with_stdout_contains(
+                "fail  engines [..]node@10.99.1040 does not satisfy engines.node (>=22)"
+            )
+            .with_stdout_contains("[..] checks failed")
+    );
+}
+
+#[test]
+fn reports_json() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_WITH_NEWER_ENGINES)
+        .env("VOLTA_OFFLINE", "1")
+        .build();
+
+    assert_that!(
+        s.volta("verify --format json"),
+        execs()
+            .with_status(ExitCode::Unverified as i32)
+            .with_stdout_contains("  \"schemaVersion\": 1,")
+            .with_stdout_contains("  \"passed\": false,")
+            .with_stdout_contains("      \"check\": \"engines\",")
+            .with_stdout_contains("      \"tool\": \"node\",")
+            .with_stdout_contains("      \"status\": \"fail\",")
+    );
+}
+
+#[test]
+fn fails_on_unlocked_range() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_WITH_UNLOCKED_RANGE)
+        .env("VOLTA_OFFLINE", "1")
+        .build();
+
+    assert_that!(
+        s.volta("verify"),
+        execs()
+            .with_status(ExitCode::Unverified as i32)
+            .with_stdout_contains(
+                "fail  lockfile [..]yarn@^1.2 is not in volta.lock, run `volta pin --refresh`"
+            )
+    );
+}
+
+#[test]
+fn fails_without_node() {
+    let s = sandbox().package_json(PACKAGE_JSON_WITHOUT_NODE).build();
+
+    assert_that!(
+        s.volta("verify"),
+        execs()
+            .with_status(ExitCode::Unverified as i32)
+            .with_stdout_contains("fail  platform [..]")
+            .with_stdout_contains("1 of 1 checks failed")
+    );
+}
+
+#[test]
+fn requires_a_project() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("verify"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]Not in a node package[..]")
+    );
+}
@@ -0,0 +1,79 @@
+//! Tests for workspace-aware project discovery, where member packages without a `volta` key
+//! inherit the pins of the workspace root.
+
+use crate::support::sandbox::sandbox;
+use cfg_if::cfg_if;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+use volta_core::error::ExitCode;
+
+const PLATFORM_NODE: &str = r#"{
+    "node":{
+        "runtime":"18.20.4",
+        "npm":null
+    }
+}"#;
+
+const WORKSPACE_ROOT: &str = r#"{
+  "name": "monorepo",
+  "private": true,
+  "workspaces": [
+    "packages/*"
+  ],
+  "volta": {
+    "node": "22.16.0"
+  }
+}"#;
+
+const MEMBER: &str = r#"{
+  "name": "app"
+}
+"#;
+
+fn node_bin(version: &str) -> String {
+    cfg_if! {
+        if #[cfg(target_os = "windows")] {
+            format!("@echo off\necho node version {version}\n")
+        } else {
+            format!("#!/bin/sh\necho \"node version {version}\"\n")
+        }
+    }
+}
+
+#[test]
+fn member_uses_workspace_root_node() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE)
+        .package_json(WORKSPACE_ROOT)
+        .project_file("packages/app/package.json", MEMBER)
+        .setup_node_binary("18.20.4", "10.7.0", &node_bin("18.20.4"))
+        .setup_node_binary("22.16.0", "10.9.2", &node_bin("22.16.0"))
+        .build();
+
+    let mut run = s.volta("run node");
+    run.cwd(s.root().join("packages").join("app"));
+    assert_that!(
+        run,
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("node version 22.16.0")
+    );
+}
+
+#[test]
+fn pin_in_member_extends_workspace_root() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE)
+        .package_json(WORKSPACE_ROOT)
+        .project_file("packages/app/package.json", MEMBER)
+        .setup_node_binary("22.16.0", "10.9.2", &node_bin("22.16.0"))
+        .build();
+
+    let mut pin = s.volta("pin npm@bundled");
+    pin.cwd(s.root().join("packages").join("app"));
+    assert_that!(pin, execs().with_status(ExitCode::Success as i32));
+
+    let member = std::fs::read_to_string(s.root().join("packages/app/package.json")).unwrap();
+    assert!(member.contains(r#""extends": "../../package.json""#));
+}
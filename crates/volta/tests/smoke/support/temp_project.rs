@@ -111,7 +111,7 @@ impl TempProjectBuilder {
         self.root.root().mkdir_p();
 
         // make sure these directories exist and are empty
-        node_cache_dir(self.root()).ensure_empty();
+        http_cache_dir(self.root()).ensure_empty();
         volta_bin_dir(self.root()).ensure_empty();
         node_inventory_dir(self.root()).ensure_empty();
         yarn_inventory_dir(self.root()).ensure_empty();
@@ -232,8 +232,8 @@ fn package_inventory_dir(root: PathBuf) -> PathBuf {
 fn cache_dir(root: PathBuf) -> PathBuf {
     volta_home(root).join("cache")
 }
-fn node_cache_dir(root: PathBuf) -> PathBuf {
-    cache_dir(root).join("node")
+fn http_cache_dir(root: PathBuf) -> PathBuf {
+    cache_dir(root).join("http")
 }
 fn package_json_file(mut root: PathBuf) -> PathBuf {
     root.push("package.json");
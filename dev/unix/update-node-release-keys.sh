@@ -0,0 +1,23 @@
+#!/usr/bin/env bash
+
+# Script to regenerate the Node.js release signing keyring bundled with volta-core.
+# This should be run from the top-level directory.
+
+set -euo pipefail
+
+RELEASE_KEYS="https://raw.githubusercontent.com/nodejs/release-keys/HEAD"
+KEYRING="crates/volta-core/keys/node-release-keys.asc"
+
+fingerprints="$(curl --fail --silent --show-error --location "$RELEASE_KEYS/keys.list")"
+
+tmp_keyring="$(mktemp)"
+trap 'rm -f "$tmp_keyring"' EXIT
+
+for fingerprint in $fingerprints; do
+  echo "Fetching key $fingerprint" >&2
+  curl --fail --silent --show-error --location "$RELEASE_KEYS/keys/$fingerprint.asc" >> "$tmp_keyring"
+done
+
+mv "$tmp_keyring" "$KEYRING"
+trap - EXIT
+echo "Updated $KEYRING" >&2